@@ -0,0 +1,78 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+#[macro_use]
+extern crate criterion;
+use criterion::Criterion;
+
+use std::sync::Arc;
+
+extern crate arrow;
+
+use arrow::array::ArrayRef;
+use arrow::datatypes::{Int32Type, Int64Type, UInt64Type};
+use arrow::row::{RowConverter, SortField};
+use arrow::util::bench_util::{
+    create_primitive_array, create_string_array_with_len, create_string_dict_array,
+};
+
+fn do_bench(c: &mut Criterion, name: &str, cols: Vec<ArrayRef>) {
+    let fields: Vec<_> = cols
+        .iter()
+        .map(|x| SortField::new(x.data_type().clone()))
+        .collect();
+
+    let converter = RowConverter::try_new(fields).unwrap();
+
+    c.bench_function(&format!("convert_columns {}", name), |b| {
+        b.iter(|| criterion::black_box(converter.convert_columns(&cols).unwrap()));
+    });
+
+    let rows = converter.convert_columns(&cols).unwrap();
+    c.bench_function(&format!("convert_rows {}", name), |b| {
+        b.iter(|| criterion::black_box(converter.convert_rows(rows.iter()).unwrap()));
+    });
+}
+
+fn row_bench(c: &mut Criterion) {
+    let cols = vec![Arc::new(create_primitive_array::<UInt64Type>(4096, 0.)) as ArrayRef];
+    do_bench(c, "4096 u64(0)", cols);
+
+    let cols = vec![Arc::new(create_primitive_array::<Int64Type>(4096, 0.)) as ArrayRef];
+    do_bench(c, "4096 i64(0)", cols);
+
+    let cols =
+        vec![Arc::new(create_string_array_with_len::<i32>(4096, 0., 10)) as ArrayRef];
+    do_bench(c, "4096 string(10, 0)", cols);
+
+    let cols =
+        vec![Arc::new(create_string_array_with_len::<i32>(4096, 0.5, 100)) as ArrayRef];
+    do_bench(c, "4096 string(100, 0.5)", cols);
+
+    let cols =
+        vec![Arc::new(create_string_dict_array::<Int32Type>(4096, 0.5)) as ArrayRef];
+    do_bench(c, "4096 dictionary(0.5)", cols);
+
+    let cols = vec![
+        Arc::new(create_string_array_with_len::<i32>(4096, 0.5, 20)) as ArrayRef,
+        Arc::new(create_primitive_array::<Int64Type>(4096, 0.1)) as ArrayRef,
+        Arc::new(create_primitive_array::<UInt64Type>(4096, 0.)) as ArrayRef,
+    ];
+    do_bench(c, "4096 string(20, 0.5), i64(0.1), u64(0)", cols);
+}
+
+criterion_group!(benches, row_bench);
+criterion_main!(benches);
@@ -570,6 +570,7 @@ pub use self::equal_json::JsonEqual;
 
 // --------------------- Array's values comparison ---------------------
 
+pub(crate) use self::ord::build_compare_with_options;
 pub use self::ord::{build_compare, DynComparator};
 
 // --------------------- Array downcast helper functions ---------------------
@@ -18,8 +18,10 @@
 //! Contains functions and function factories to compare arrays.
 
 use std::cmp::Ordering;
+use std::ops::Range;
 
 use crate::array::*;
+use crate::compute::SortOptions;
 use crate::datatypes::TimeUnit;
 use crate::datatypes::*;
 use crate::error::{ArrowError, Result};
@@ -74,13 +76,49 @@ fn compare_string<T>(left: &dyn Array, right: &dyn Array) -> DynComparator
 where
     T: OffsetSizeTrait,
 {
-    let left: StringArray = StringArray::from(left.data().clone());
-    let right: StringArray = StringArray::from(right.data().clone());
+    let left = GenericStringArray::<T>::from(left.data().clone());
+    let right = GenericStringArray::<T>::from(right.data().clone());
 
     Box::new(move |i, j| left.value(i).cmp(right.value(j)))
 }
 
-fn compare_dict_string<T>(left: &dyn Array, right: &dyn Array) -> DynComparator
+fn compare_binary<T>(left: &dyn Array, right: &dyn Array) -> DynComparator
+where
+    T: OffsetSizeTrait,
+{
+    let left = GenericBinaryArray::<T>::from(left.data().clone());
+    let right = GenericBinaryArray::<T>::from(right.data().clone());
+
+    Box::new(move |i, j| left.value(i).cmp(right.value(j)))
+}
+
+fn compare_fixed_size_binary(left: &dyn Array, right: &dyn Array) -> DynComparator {
+    let left = FixedSizeBinaryArray::from(left.data().clone());
+    let right = FixedSizeBinaryArray::from(right.data().clone());
+
+    Box::new(move |i, j| left.value(i).cmp(right.value(j)))
+}
+
+fn compare_f16(left: &dyn Array, right: &dyn Array) -> DynComparator {
+    let left = Float16Array::from(left.data().clone());
+    let right = Float16Array::from(right.data().clone());
+
+    Box::new(move |i, j| {
+        let (a, b) = (left.value(i), right.value(j));
+        match (a.is_nan(), b.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            _ => a.partial_cmp(&b).unwrap(),
+        }
+    })
+}
+
+fn compare_dict<T>(
+    left: &dyn Array,
+    right: &dyn Array,
+    nulls_first: bool,
+) -> Result<DynComparator>
 where
     T: ArrowDictionaryKeyType,
 {
@@ -89,21 +127,194 @@ where
 
     let left_keys: PrimitiveArray<T> = PrimitiveArray::from(left.keys().data().clone());
     let right_keys: PrimitiveArray<T> = PrimitiveArray::from(right.keys().data().clone());
-    let left_values = StringArray::from(left.values().data().clone());
-    let right_values = StringArray::from(right.values().data().clone());
+    let values =
+        compare_nullable(left.values().as_ref(), right.values().as_ref(), nulls_first)?;
 
-    Box::new(move |i: usize, j: usize| {
+    Ok(Box::new(move |i: usize, j: usize| {
         let key_left = left_keys.value(i).to_usize().unwrap();
         let key_right = right_keys.value(j).to_usize().unwrap();
-        let left = left_values.value(key_left);
-        let right = right_values.value(key_right);
-        left.cmp(right)
-    })
+        values(key_left, key_right)
+    }))
+}
+
+/// Compares the elements `left` and `right` of two lists with `compare`, a list that
+/// is a prefix of another list is ordered first
+fn compare_elements(
+    left: Range<usize>,
+    right: Range<usize>,
+    compare: &DynComparator,
+) -> Ordering {
+    left.clone()
+        .zip(right.clone())
+        .map(|(i, j)| compare(i, j))
+        .find(|ordering| *ordering != Ordering::Equal)
+        .unwrap_or_else(|| left.len().cmp(&right.len()))
+}
+
+fn compare_list<T>(
+    left: &dyn Array,
+    right: &dyn Array,
+    nulls_first: bool,
+) -> Result<DynComparator>
+where
+    T: OffsetSizeTrait,
+{
+    let left = GenericListArray::<T>::from(left.data().clone());
+    let right = GenericListArray::<T>::from(right.data().clone());
+    let values =
+        compare_nullable(left.values().as_ref(), right.values().as_ref(), nulls_first)?;
+
+    Ok(Box::new(move |i, j| {
+        let range = |offsets: &[T], i: usize| {
+            offsets[i].to_usize().unwrap()..offsets[i + 1].to_usize().unwrap()
+        };
+        compare_elements(
+            range(left.value_offsets(), i),
+            range(right.value_offsets(), j),
+            &values,
+        )
+    }))
+}
+
+fn compare_fixed_size_list(
+    left: &dyn Array,
+    right: &dyn Array,
+    nulls_first: bool,
+) -> Result<DynComparator> {
+    let left = FixedSizeListArray::from(left.data().clone());
+    let right = FixedSizeListArray::from(right.data().clone());
+    let values =
+        compare_nullable(left.values().as_ref(), right.values().as_ref(), nulls_first)?;
+
+    Ok(Box::new(move |i, j| {
+        let range = |list: &FixedSizeListArray, i: usize| {
+            let start = list.value_offset(i) as usize;
+            start..start + list.value_length() as usize
+        };
+        compare_elements(range(&left, i), range(&right, j), &values)
+    }))
+}
+
+fn compare_map(
+    left: &dyn Array,
+    right: &dyn Array,
+    nulls_first: bool,
+) -> Result<DynComparator> {
+    let left = MapArray::from(left.data().clone());
+    let right = MapArray::from(right.data().clone());
+    let entries = compare_nullable(
+        make_array(left.data().child_data()[0].clone()).as_ref(),
+        make_array(right.data().child_data()[0].clone()).as_ref(),
+        nulls_first,
+    )?;
+
+    Ok(Box::new(move |i, j| {
+        let range =
+            |offsets: &[i32], i: usize| offsets[i] as usize..offsets[i + 1] as usize;
+        compare_elements(
+            range(left.value_offsets(), i),
+            range(right.value_offsets(), j),
+            &entries,
+        )
+    }))
+}
+
+fn compare_struct(
+    left: &dyn Array,
+    right: &dyn Array,
+    nulls_first: bool,
+) -> Result<DynComparator> {
+    let left = StructArray::from(left.data().clone());
+    let right = StructArray::from(right.data().clone());
+    let fields = left
+        .columns()
+        .into_iter()
+        .zip(right.columns())
+        .map(|(l, r)| compare_nullable(l.as_ref(), r.as_ref(), nulls_first))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Box::new(move |i, j| {
+        fields
+            .iter()
+            .map(|compare| compare(i, j))
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }))
+}
+
+fn compare_union(
+    left: &dyn Array,
+    right: &dyn Array,
+    type_ids: &[i8],
+    nulls_first: bool,
+) -> Result<DynComparator> {
+    let left = UnionArray::from(left.data().clone());
+    let right = UnionArray::from(right.data().clone());
+    let children = (0..type_ids.len() as i8)
+        .map(|type_id| {
+            compare_nullable(
+                left.child(type_id).as_ref(),
+                right.child(type_id).as_ref(),
+                nulls_first,
+            )
+        })
+        .collect::<Result<Vec<_>>>()?;
+    // values are ordered by the position of their type id in the data type first
+    let positions = type_ids.to_vec();
+
+    Ok(Box::new(move |i, j| {
+        let (i, j) = (left.offset() + i, right.offset() + j);
+        let (type_left, type_right) = (left.type_id(i), right.type_id(j));
+        let position = |type_id| positions.iter().position(|t| *t == type_id);
+        position(type_left)
+            .cmp(&position(type_right))
+            .then_with(|| {
+                children[type_left as usize](
+                    left.value_offset(i) as usize,
+                    right.value_offset(j) as usize,
+                )
+            })
+    }))
+}
+
+/// Returns a comparator like [`build_compare`] that also compares null values, which
+/// are ordered before other values if `nulls_first`
+fn compare_nullable(
+    left: &dyn Array,
+    right: &dyn Array,
+    nulls_first: bool,
+) -> Result<DynComparator> {
+    let values = build_compare_impl(left, right, nulls_first)?;
+    if left.null_count() == 0 && right.null_count() == 0 {
+        return Ok(values);
+    }
+    let null = if nulls_first {
+        Ordering::Less
+    } else {
+        Ordering::Greater
+    };
+    let left = left.data().clone();
+    let right = right.data().clone();
+
+    Ok(Box::new(move |i, j| {
+        match (left.is_valid(i), right.is_valid(j)) {
+            (true, true) => values(i, j),
+            (false, true) => null,
+            (true, false) => null.reverse(),
+            (false, false) => Ordering::Equal,
+        }
+    }))
 }
 
 /// returns a comparison function that compares two values at two different positions
 /// between the two arrays.
 /// The arrays' types must be equal.
+///
+/// The comparison function doesn't check if the compared values are null, but nulls
+/// nested in the values, such as null fields of a struct or null elements of a list,
+/// are ordered before other values. Lists are compared element by element, structs
+/// field by field, unions by type id and then by value, and dictionaries by their
+/// values.
 /// # Example
 /// ```
 /// use arrow::array::{build_compare, Int32Array};
@@ -122,6 +333,29 @@ where
 // This is a factory of comparisons.
 // The lifetime 'a enforces that we cannot use the closure beyond any of the array's lifetime.
 pub fn build_compare(left: &dyn Array, right: &dyn Array) -> Result<DynComparator> {
+    build_compare_impl(left, right, true)
+}
+
+/// Returns a comparator for sorting the values of `left` and `right` with `options`.
+///
+/// Like [`build_compare`], the comparator doesn't check if the compared values are
+/// null, and it orders values ascending, with callers reversing the result if
+/// `options.descending`. Nested nulls are ordered such that they respect
+/// `options.nulls_first` after this reversal.
+pub(crate) fn build_compare_with_options(
+    left: &dyn Array,
+    right: &dyn Array,
+    options: &SortOptions,
+) -> Result<DynComparator> {
+    build_compare_impl(left, right, options.nulls_first != options.descending)
+}
+
+/// Returns a comparator ordering nested nulls first if `nulls_first`
+fn build_compare_impl(
+    left: &dyn Array,
+    right: &dyn Array,
+    nulls_first: bool,
+) -> Result<DynComparator> {
     use DataType::*;
     use IntervalUnit::*;
     use TimeUnit::*;
@@ -191,32 +425,21 @@ pub fn build_compare(left: &dyn Array, right: &dyn Array) -> Result<DynComparato
         }
         (Utf8, Utf8) => compare_string::<i32>(left, right),
         (LargeUtf8, LargeUtf8) => compare_string::<i64>(left, right),
-        (
-            Dictionary(key_type_lhs, value_type_lhs),
-            Dictionary(key_type_rhs, value_type_rhs),
-        ) => {
-            if value_type_lhs.as_ref() != &DataType::Utf8
-                || value_type_rhs.as_ref() != &DataType::Utf8
-            {
-                return Err(ArrowError::InvalidArgumentError(
-                    "Arrow still does not support comparisons of non-string dictionary arrays"
-                        .to_string(),
-                ));
-            }
+        (Dictionary(key_type_lhs, _), Dictionary(key_type_rhs, _)) => {
             match (key_type_lhs.as_ref(), key_type_rhs.as_ref()) {
                 (a, b) if a != b => {
                     return Err(ArrowError::InvalidArgumentError(
                         "Can't compare arrays of different types".to_string(),
                     ));
                 }
-                (UInt8, UInt8) => compare_dict_string::<UInt8Type>(left, right),
-                (UInt16, UInt16) => compare_dict_string::<UInt16Type>(left, right),
-                (UInt32, UInt32) => compare_dict_string::<UInt32Type>(left, right),
-                (UInt64, UInt64) => compare_dict_string::<UInt64Type>(left, right),
-                (Int8, Int8) => compare_dict_string::<Int8Type>(left, right),
-                (Int16, Int16) => compare_dict_string::<Int16Type>(left, right),
-                (Int32, Int32) => compare_dict_string::<Int32Type>(left, right),
-                (Int64, Int64) => compare_dict_string::<Int64Type>(left, right),
+                (UInt8, UInt8) => compare_dict::<UInt8Type>(left, right, nulls_first)?,
+                (UInt16, UInt16) => compare_dict::<UInt16Type>(left, right, nulls_first)?,
+                (UInt32, UInt32) => compare_dict::<UInt32Type>(left, right, nulls_first)?,
+                (UInt64, UInt64) => compare_dict::<UInt64Type>(left, right, nulls_first)?,
+                (Int8, Int8) => compare_dict::<Int8Type>(left, right, nulls_first)?,
+                (Int16, Int16) => compare_dict::<Int16Type>(left, right, nulls_first)?,
+                (Int32, Int32) => compare_dict::<Int32Type>(left, right, nulls_first)?,
+                (Int64, Int64) => compare_dict::<Int64Type>(left, right, nulls_first)?,
                 (lhs, _) => {
                     return Err(ArrowError::InvalidArgumentError(format!(
                         "Dictionaries do not support keys of type {:?}",
@@ -230,6 +453,23 @@ pub fn build_compare(left: &dyn Array, right: &dyn Array) -> Result<DynComparato
             let right: DecimalArray = DecimalArray::from(right.data().clone());
             Box::new(move |i, j| left.value(i).cmp(&right.value(j)))
         }
+        (Null, Null) => Box::new(|_, _| Ordering::Equal),
+        (Float16, Float16) => compare_f16(left, right),
+        (Binary, Binary) => compare_binary::<i32>(left, right),
+        (LargeBinary, LargeBinary) => compare_binary::<i64>(left, right),
+        (FixedSizeBinary(_), FixedSizeBinary(_)) => {
+            compare_fixed_size_binary(left, right)
+        }
+        (List(_), List(_)) => compare_list::<i32>(left, right, nulls_first)?,
+        (LargeList(_), LargeList(_)) => compare_list::<i64>(left, right, nulls_first)?,
+        (FixedSizeList(_, _), FixedSizeList(_, _)) => {
+            compare_fixed_size_list(left, right, nulls_first)?
+        }
+        (Map(_, _), Map(_, _)) => compare_map(left, right, nulls_first)?,
+        (Struct(_), Struct(_)) => compare_struct(left, right, nulls_first)?,
+        (Union(_, type_ids, _), Union(_, _, _)) => {
+            compare_union(left, right, type_ids, nulls_first)?
+        }
         (lhs, _) => {
             return Err(ArrowError::InvalidArgumentError(format!(
                 "The data type type {:?} has no natural order",
@@ -245,6 +485,8 @@ pub mod tests {
     use crate::array::{Float64Array, Int32Array};
     use crate::error::Result;
     use std::cmp::Ordering;
+    use std::convert::TryFrom;
+    use std::sync::Arc;
 
     #[test]
     fn test_i32() -> Result<()> {
@@ -339,4 +581,106 @@ pub mod tests {
         assert_eq!(Ordering::Greater, (cmp)(1, 3));
         Ok(())
     }
+
+    #[test]
+    fn test_large_string() -> Result<()> {
+        let array = LargeStringArray::from(vec!["b", "a", "bc"]);
+
+        let cmp = build_compare(&array, &array)?;
+
+        assert_eq!(Ordering::Greater, (cmp)(0, 1));
+        assert_eq!(Ordering::Less, (cmp)(0, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn test_dict_primitive_values() -> Result<()> {
+        let values = Int32Array::from(vec![Some(3), None, Some(1)]);
+        let keys = Int8Array::from(vec![0, 1, 2, 0]);
+        let array = DictionaryArray::<Int8Type>::try_new(&keys, &values)?;
+
+        let cmp = build_compare(&array, &array)?;
+
+        assert_eq!(Ordering::Greater, (cmp)(0, 2));
+        assert_eq!(Ordering::Less, (cmp)(1, 2));
+        assert_eq!(Ordering::Equal, (cmp)(0, 3));
+        Ok(())
+    }
+
+    #[test]
+    fn test_list() -> Result<()> {
+        let array = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), Some(2)]),
+            Some(vec![Some(1)]),
+            Some(vec![Some(1), None]),
+            Some(vec![Some(0), Some(5), Some(5)]),
+            Some(vec![Some(1), Some(2)]),
+        ]);
+
+        let cmp = build_compare(&array, &array)?;
+
+        assert_eq!(Ordering::Greater, (cmp)(0, 1));
+        assert_eq!(Ordering::Greater, (cmp)(0, 2));
+        assert_eq!(Ordering::Less, (cmp)(1, 2));
+        assert_eq!(Ordering::Less, (cmp)(3, 1));
+        assert_eq!(Ordering::Equal, (cmp)(0, 4));
+
+        let sliced = array.slice(1, 3);
+        let cmp = build_compare(sliced.as_ref(), sliced.as_ref())?;
+        assert_eq!(Ordering::Greater, (cmp)(0, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn test_struct() -> Result<()> {
+        let a: ArrayRef =
+            Arc::new(Int32Array::from(vec![Some(1), Some(1), None, Some(1)]));
+        let b: ArrayRef = Arc::new(StringArray::from(vec!["b", "a", "a", "b"]));
+        let array = StructArray::try_from(vec![("a", a), ("b", b)])?;
+
+        let cmp = build_compare(&array, &array)?;
+
+        assert_eq!(Ordering::Greater, (cmp)(0, 1));
+        assert_eq!(Ordering::Greater, (cmp)(1, 2));
+        assert_eq!(Ordering::Equal, (cmp)(0, 3));
+
+        let cmp = build_compare_with_options(
+            &array,
+            &array,
+            &SortOptions {
+                descending: false,
+                nulls_first: false,
+            },
+        )?;
+        assert_eq!(Ordering::Less, (cmp)(1, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn test_union() -> Result<()> {
+        let mut builder = UnionBuilder::new_sparse(4);
+        builder.append::<Int32Type>("a", 2)?;
+        builder.append::<Float64Type>("b", 1.5)?;
+        builder.append::<Int32Type>("a", 1)?;
+        builder.append::<Float64Type>("b", 0.5)?;
+        let array = builder.build()?;
+
+        let cmp = build_compare(&array, &array)?;
+
+        assert_eq!(Ordering::Less, (cmp)(0, 1));
+        assert_eq!(Ordering::Greater, (cmp)(0, 2));
+        assert_eq!(Ordering::Greater, (cmp)(1, 3));
+        Ok(())
+    }
+
+    #[test]
+    fn test_binary() -> Result<()> {
+        let array = BinaryArray::from(vec![&b"b"[..], b"a", b"ba"]);
+
+        let cmp = build_compare(&array, &array)?;
+
+        assert_eq!(Ordering::Greater, (cmp)(0, 1));
+        assert_eq!(Ordering::Less, (cmp)(0, 2));
+        Ok(())
+    }
 }
@@ -402,6 +402,10 @@ impl<'a> MutableArrayData<'a> {
                 array_capacity = *capacity;
                 new_buffers(data_type, *capacity)
             }
+            (DataType::Dictionary(_, _), Capacities::Dictionary(capacity, _)) => {
+                array_capacity = *capacity;
+                new_buffers(data_type, *capacity)
+            }
             _ => panic!("Capacities: {:?} not yet supported", capacities),
         };
 
@@ -524,9 +528,6 @@ impl<'a> MutableArrayData<'a> {
                 match dict_concat {
                     false => (Some(arrays[0].child_data()[0].clone()), false),
                     true => {
-                        if let Capacities::Dictionary(_, _) = capacities {
-                            panic!("dictionary capacity not yet supported")
-                        }
                         let dictionaries: Vec<_> =
                             arrays.iter().map(|array| &array.child_data()[0]).collect();
                         let lengths: Vec<_> = dictionaries
@@ -535,8 +536,16 @@ impl<'a> MutableArrayData<'a> {
                             .collect();
                         let capacity = lengths.iter().sum();
 
-                        let mut mutable =
-                            MutableArrayData::new(dictionaries, false, capacity);
+                        let mut mutable = match &capacities {
+                            Capacities::Dictionary(_, Some(values)) => {
+                                MutableArrayData::with_capacities(
+                                    dictionaries,
+                                    false,
+                                    values.as_ref().clone(),
+                                )
+                            }
+                            _ => MutableArrayData::new(dictionaries, false, capacity),
+                        };
 
                         for (i, len) in lengths.iter().enumerate() {
                             mutable.extend(i, 0, *len)
@@ -1000,6 +1009,25 @@ mod tests {
         assert_eq!(result.keys(), &expected);
     }
 
+    #[test]
+    fn test_dictionary_capacities() {
+        let a = create_dictionary_array(&["a", "b"], &[Some("a"), Some("b")]);
+        let b = create_dictionary_array(&["c"], &[None, Some("c")]);
+        let capacities =
+            Capacities::Dictionary(4, Some(Box::new(Capacities::Binary(3, Some(3)))));
+
+        let mut mutable =
+            MutableArrayData::with_capacities(vec![&a, &b], false, capacities);
+        mutable.extend(0, 0, 2);
+        mutable.extend(1, 0, 2);
+
+        let result = DictionaryArray::<Int16Type>::from(mutable.freeze());
+        let expected = Int16Array::from(vec![Some(0), Some(1), None, Some(2)]);
+        assert_eq!(result.keys(), &expected);
+        let expected = StringArray::from(vec!["a", "b", "c"]);
+        assert_eq!(result.values().as_ref(), &expected as &dyn Array);
+    }
+
     #[test]
     fn test_struct() {
         let strings: ArrayRef = Arc::new(StringArray::from(vec![
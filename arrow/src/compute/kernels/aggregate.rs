@@ -18,17 +18,20 @@
 //! Defines aggregations over Arrow arrays.
 
 use multiversion::multiversion;
+use num::ToPrimitive;
 use std::ops::Add;
 
 use crate::array::{
     Array, BooleanArray, GenericBinaryArray, GenericStringArray, OffsetSizeTrait,
     PrimitiveArray,
 };
+use crate::compute::kernels::sketch::{check_quantile, HyperLogLog, TDigest};
 use crate::datatypes::{ArrowNativeType, ArrowNumericType};
+use crate::error::Result;
 
 /// Generic test for NaN, the optimizer should be able to remove this for integer types.
 #[inline]
-fn is_nan<T: ArrowNativeType + PartialOrd + Copy>(a: T) -> bool {
+pub(crate) fn is_nan<T: ArrowNativeType + PartialOrd + Copy>(a: T) -> bool {
     #[allow(clippy::eq_op)]
     !(a == a)
 }
@@ -620,11 +623,230 @@ where
     simd::simd_aggregation::<T, MaxAggregate<T>>(&array)
 }
 
+/// A mergeable partial state for computing the mean, variance and standard
+/// deviation of values, using [Welford's online algorithm] to update the state
+/// with new values, and the parallel algorithm of Chan et al. to merge states
+///
+/// Both are numerically stable, unlike computing the variance from the sum of the
+/// values and the sum of their squares.
+///
+/// [Welford's online algorithm]: https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance#Welford's_online_algorithm
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct VarianceState {
+    count: u64,
+    mean: f64,
+    /// The sum of squared differences from the mean
+    m2: f64,
+}
+
+impl VarianceState {
+    /// Create a new, empty [`VarianceState`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the state with the non-null values of `array`
+    pub fn update<T>(&mut self, array: &PrimitiveArray<T>)
+    where
+        T: ArrowNumericType,
+        T::Native: ToPrimitive,
+    {
+        for value in array.iter().flatten() {
+            // Native numeric types can always be represented as f64, albeit lossily
+            self.update_value(value.to_f64().unwrap());
+        }
+    }
+
+    /// Updates the state with a single value
+    pub fn update_value(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    /// Merges the values of `other` into this state
+    pub fn merge(&mut self, other: &VarianceState) {
+        if other.count == 0 {
+            return;
+        }
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        let other_weight = other.count as f64 / count as f64;
+        self.mean += delta * other_weight;
+        self.m2 += other.m2 + delta * delta * self.count as f64 * other_weight;
+        self.count = count;
+    }
+
+    /// Returns the number of values
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the mean of the values, or `None` if there are none
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.mean)
+    }
+
+    /// Returns the population variance of the values, or `None` if there are none
+    pub fn variance_pop(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.m2 / self.count as f64)
+    }
+
+    /// Returns the sample variance of the values, or `None` if there are fewer than two
+    pub fn variance_sample(&self) -> Option<f64> {
+        (self.count > 1).then(|| self.m2 / (self.count - 1) as f64)
+    }
+
+    /// Returns the population standard deviation of the values, or `None` if there
+    /// are none
+    pub fn stddev_pop(&self) -> Option<f64> {
+        self.variance_pop().map(f64::sqrt)
+    }
+
+    /// Returns the sample standard deviation of the values, or `None` if there are
+    /// fewer than two
+    pub fn stddev_sample(&self) -> Option<f64> {
+        self.variance_sample().map(f64::sqrt)
+    }
+}
+
+fn variance_state<T>(array: &PrimitiveArray<T>) -> VarianceState
+where
+    T: ArrowNumericType,
+    T::Native: ToPrimitive,
+{
+    let mut state = VarianceState::new();
+    state.update(array);
+    state
+}
+
+/// Returns the arithmetic mean of the values in the array as a `f64`.
+///
+/// Returns `None` if the array is empty or only contains null values.
+pub fn mean<T>(array: &PrimitiveArray<T>) -> Option<f64>
+where
+    T: ArrowNumericType,
+    T::Native: ToPrimitive,
+{
+    variance_state(array).mean()
+}
+
+/// Returns the population variance of the values in the array.
+///
+/// Returns `None` if the array is empty or only contains null values.
+/// See [`VarianceState`] to compute the variance across multiple arrays.
+pub fn variance_pop<T>(array: &PrimitiveArray<T>) -> Option<f64>
+where
+    T: ArrowNumericType,
+    T::Native: ToPrimitive,
+{
+    variance_state(array).variance_pop()
+}
+
+/// Returns the sample variance of the values in the array.
+///
+/// Returns `None` if the array contains fewer than two non-null values.
+/// See [`VarianceState`] to compute the variance across multiple arrays.
+pub fn variance_sample<T>(array: &PrimitiveArray<T>) -> Option<f64>
+where
+    T: ArrowNumericType,
+    T::Native: ToPrimitive,
+{
+    variance_state(array).variance_sample()
+}
+
+/// Returns the population standard deviation of the values in the array.
+///
+/// Returns `None` if the array is empty or only contains null values.
+pub fn stddev_pop<T>(array: &PrimitiveArray<T>) -> Option<f64>
+where
+    T: ArrowNumericType,
+    T::Native: ToPrimitive,
+{
+    variance_state(array).stddev_pop()
+}
+
+/// Returns the sample standard deviation of the values in the array.
+///
+/// Returns `None` if the array contains fewer than two non-null values.
+pub fn stddev_sample<T>(array: &PrimitiveArray<T>) -> Option<f64>
+where
+    T: ArrowNumericType,
+    T::Native: ToPrimitive,
+{
+    variance_state(array).stddev_sample()
+}
+
+/// Returns the exact quantile `q` of the values in the array, interpolating
+/// linearly between the two closest values. NaN values are ignored.
+///
+/// Returns `None` if the array is empty or only contains null values, and an
+/// error if `q` is not between 0 and 1.
+pub fn quantile<T>(array: &PrimitiveArray<T>, q: f64) -> Result<Option<f64>>
+where
+    T: ArrowNumericType,
+    T::Native: ToPrimitive,
+{
+    check_quantile(q)?;
+    let mut values: Vec<f64> = array
+        .iter()
+        .flatten()
+        .map(|v| v.to_f64().unwrap())
+        .filter(|v| !v.is_nan())
+        .collect();
+    if values.is_empty() {
+        return Ok(None);
+    }
+
+    let cmp = |a: &f64, b: &f64| a.partial_cmp(b).unwrap();
+    let position = q * (values.len() - 1) as f64;
+    let index = position.floor() as usize;
+    let (_, lower, upper) = values.select_nth_unstable_by(index, cmp);
+    let lower = *lower;
+    let fraction = position - index as f64;
+    if fraction == 0. {
+        return Ok(Some(lower));
+    }
+    let upper = upper.iter().copied().min_by(cmp).unwrap();
+    Ok(Some(lower + (upper - lower) * fraction))
+}
+
+/// Returns an estimate of the quantile `q` of the values in the array, using a
+/// [`TDigest`] with the default compression. NaN values are ignored.
+///
+/// Returns `None` if the array is empty or only contains null values, and an
+/// error if `q` is not between 0 and 1. See [`TDigest`] to estimate quantiles
+/// across multiple arrays.
+pub fn approx_quantile<T>(array: &PrimitiveArray<T>, q: f64) -> Result<Option<f64>>
+where
+    T: ArrowNumericType,
+    T::Native: ToPrimitive,
+{
+    let mut digest = TDigest::default();
+    digest.update(array);
+    digest.quantile(q)
+}
+
+/// Returns an estimate of the number of distinct non-null values in the array,
+/// using a [`HyperLogLog`] with the default precision.
+///
+/// Supports all arrays that can be hashed by
+/// [`hash_array`](crate::compute::kernels::hash::hash_array), including primitive,
+/// string and dictionary arrays. See [`HyperLogLog`] to count distinct values
+/// across multiple arrays.
+pub fn approx_distinct(array: &dyn Array) -> Result<u64> {
+    let mut hll = HyperLogLog::default();
+    hll.update(array)?;
+    Ok(hll.count())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::array::*;
     use crate::compute::add;
+    use crate::datatypes::{Int16Type, Int32Type};
 
     #[test]
     fn test_primitive_array_sum() {
@@ -1003,4 +1225,79 @@ mod tests {
         assert_eq!(Some(true), min_boolean(&a));
         assert_eq!(Some(true), max_boolean(&a));
     }
+
+    #[test]
+    fn test_mean_variance() {
+        let a = Int32Array::from(vec![Some(2), None, Some(4), Some(4), Some(4)]);
+        let a = a.slice(0, 5);
+        let a = as_primitive_array::<Int32Type>(&a);
+        assert_eq!(Some(3.5), mean(a));
+        assert_eq!(Some(0.75), variance_pop(a));
+        assert_eq!(Some(1.0), variance_sample(a));
+        assert_eq!(Some(0.75_f64.sqrt()), stddev_pop(a));
+        assert_eq!(Some(1.0), stddev_sample(a));
+
+        let a = Float64Array::from(vec![Some(1.5), None]);
+        assert_eq!(Some(1.5), mean(&a));
+        assert_eq!(Some(0.), variance_pop(&a));
+        assert_eq!(None, variance_sample(&a));
+
+        let a = Float64Array::from(vec![None, None]);
+        assert_eq!(None, mean(&a));
+        assert_eq!(None, variance_pop(&a));
+        assert_eq!(None, stddev_sample(&a));
+    }
+
+    #[test]
+    fn test_variance_state_merge() {
+        // a large offset would lose precision with the naive sum of squares
+        let values: Vec<f64> = (0..1000).map(|i| 1e9 + (i % 10) as f64).collect();
+        let mut state = VarianceState::new();
+        for chunk in values.chunks(300) {
+            let mut partial = VarianceState::new();
+            partial.update(&Float64Array::from(chunk.to_vec()));
+            state.merge(&partial);
+        }
+        state.merge(&VarianceState::new());
+
+        let expected = variance_state(&Float64Array::from(values));
+        assert_eq!(state.count(), 1000);
+        assert!((state.mean().unwrap() - expected.mean().unwrap()).abs() < 1e-6);
+        assert!((state.variance_pop().unwrap() - 8.25).abs() < 1e-6);
+        assert!((expected.variance_pop().unwrap() - 8.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_quantile() {
+        let a = Int64Array::from(vec![Some(5), None, Some(1), Some(3), Some(2), Some(4)]);
+        assert_eq!(Some(1.), quantile(&a, 0.).unwrap());
+        assert_eq!(Some(3.), quantile(&a, 0.5).unwrap());
+        assert_eq!(Some(4.6), quantile(&a, 0.9).unwrap());
+        assert_eq!(Some(5.), quantile(&a, 1.).unwrap());
+        assert_eq!(Some(3.), approx_quantile(&a, 0.5).unwrap());
+
+        let a = Float32Array::from(vec![Some(f32::NAN), Some(1.), Some(2.)]);
+        assert_eq!(Some(1.5), quantile(&a, 0.5).unwrap());
+
+        let a = Int64Array::from(vec![None]);
+        assert_eq!(None, quantile(&a, 0.5).unwrap());
+        assert_eq!(None, approx_quantile(&a, 0.5).unwrap());
+
+        assert!(quantile(&a, -0.1).is_err());
+        assert!(approx_quantile(&a, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_approx_distinct() {
+        let a = StringArray::from(vec![Some("a"), None, Some("b"), Some("a")]);
+        assert_eq!(2, approx_distinct(&a).unwrap());
+
+        let a: DictionaryArray<Int16Type> =
+            vec!["x", "y", "x", "z"].into_iter().collect();
+        assert_eq!(3, approx_distinct(&a).unwrap());
+
+        let a = Int32Array::from_iter_values(0..50_000);
+        let estimate = approx_distinct(&a).unwrap() as f64;
+        assert!((estimate - 50_000.).abs() / 50_000. < 0.03);
+    }
 }
@@ -23,17 +23,35 @@
 //! [here](https://doc.rust-lang.org/stable/core/arch/) for more information.
 
 use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::sync::Arc;
 
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
 use num::{One, Zero};
 
 use crate::buffer::Buffer;
 #[cfg(feature = "simd")]
 use crate::buffer::MutableBuffer;
-use crate::compute::kernels::arity::unary;
+use crate::compute::kernels::arity::{try_unary, unary};
+use crate::compute::kernels::cast::cast;
+use crate::compute::kernels::temporal::{
+    datetime_to_nanos, make_temporal_array, nanos_to_datetime, time_unit_nanos,
+    TemporalTz, NANOS_PER_DAY,
+};
 use crate::compute::util::combine_option_bitmap;
 use crate::datatypes;
-use crate::datatypes::ArrowNumericType;
+use crate::datatypes::{
+    ArrowNativeTypeOp, ArrowNumericType, ArrowPrimitiveType, DataType,
+    DurationMicrosecondType, DurationMillisecondType, DurationNanosecondType,
+    DurationSecondType, Float16Type, Float32Type, Float64Type, Int16Type, Int32Type,
+    Int64Type, Int8Type, IntervalDayTimeType, IntervalMonthDayNanoType, IntervalUnit,
+    IntervalYearMonthType, TimeUnit, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+    DECIMAL_MAX_PRECISION, MAX_DECIMAL_FOR_EACH_PRECISION,
+    MIN_DECIMAL_FOR_EACH_PRECISION,
+};
 use crate::error::{ArrowError, Result};
+use crate::util::decimal::{
+    decimal_div_rescale, decimal_mul_rescale, decimal_rescale, Decimal128,
+};
 use crate::{array::*, util::bit_util};
 use num::traits::Pow;
 #[cfg(feature = "simd")]
@@ -54,7 +72,7 @@ pub fn math_op<T, F>(
     op: F,
 ) -> Result<PrimitiveArray<T>>
 where
-    T: ArrowNumericType,
+    T: ArrowPrimitiveType,
     F: Fn(T::Native, T::Native) -> T::Native,
 {
     if left.len() != right.len() {
@@ -92,23 +110,21 @@ where
     Ok(PrimitiveArray::<T>::from(data))
 }
 
-/// Helper function for operations where a valid `0` on the right array should
-/// result in an [ArrowError::DivideByZero], namely the division and modulo operations
+/// Helper function to perform a fallible math lambda function on values from two arrays,
+/// the function is only evaluated where both the left and the right value are valid.
+/// If either left or right value is null then the output value is also null.
 ///
 /// # Errors
 ///
-/// This function errors if:
-/// * the arrays have different lengths
-/// * there is an element where both left and right values are valid and the right value is `0`
-fn math_checked_divide_op<T, F>(
+/// This function errors if the arrays have different lengths, or `op` returns an error
+fn math_checked_op<T, F>(
     left: &PrimitiveArray<T>,
     right: &PrimitiveArray<T>,
     op: F,
 ) -> Result<PrimitiveArray<T>>
 where
-    T: ArrowNumericType,
-    T::Native: One + Zero,
-    F: Fn(T::Native, T::Native) -> T::Native,
+    T: ArrowPrimitiveType,
+    F: Fn(T::Native, T::Native) -> Result<T::Native>,
 {
     if left.len() != right.len() {
         return Err(ArrowError::ComputeError(
@@ -124,11 +140,7 @@ where
             |(i, (left, right))| {
                 let is_valid = unsafe { bit_util::get_bit_raw(b.as_ptr(), i) };
                 if is_valid {
-                    if right.is_zero() {
-                        Err(ArrowError::DivideByZero)
-                    } else {
-                        Ok(op(*left, *right))
-                    }
+                    op(*left, *right)
                 } else {
                     Ok(T::default_value())
                 }
@@ -142,13 +154,7 @@ where
             .values()
             .iter()
             .zip(right.values())
-            .map(|(left, right)| {
-                if right.is_zero() {
-                    Err(ArrowError::DivideByZero)
-                } else {
-                    Ok(op(*left, *right))
-                }
-            });
+            .map(|(left, right)| op(*left, *right));
         // Safety: Iterator comes from a PrimitiveArray which reports its size correctly
         unsafe { Buffer::try_from_trusted_len_iter(values) }
     }?;
@@ -167,6 +173,33 @@ where
     Ok(PrimitiveArray::<T>::from(data))
 }
 
+/// Helper function for operations where a valid `0` on the right array should
+/// result in an [ArrowError::DivideByZero], namely the division and modulo operations
+///
+/// # Errors
+///
+/// This function errors if:
+/// * the arrays have different lengths
+/// * there is an element where both left and right values are valid and the right value is `0`
+fn math_checked_divide_op<T, F>(
+    left: &PrimitiveArray<T>,
+    right: &PrimitiveArray<T>,
+    op: F,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: One + Zero,
+    F: Fn(T::Native, T::Native) -> T::Native,
+{
+    math_checked_op(left, right, |left, right| {
+        if right.is_zero() {
+            Err(ArrowError::DivideByZero)
+        } else {
+            Ok(op(left, right))
+        }
+    })
+}
+
 /// Calculates the modulus operation `left % right` on two SIMD inputs.
 /// The lower-most bits of `valid_mask` specify which vector lanes are considered as valid.
 ///
@@ -617,127 +650,1023 @@ where
     Ok(unary(array, |a| a / divisor))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::array::Int32Array;
-
-    #[test]
-    fn test_primitive_array_add() {
-        let a = Int32Array::from(vec![5, 6, 7, 8, 9]);
-        let b = Int32Array::from(vec![6, 7, 8, 9, 8]);
-        let c = add(&a, &b).unwrap();
-        assert_eq!(11, c.value(0));
-        assert_eq!(13, c.value(1));
-        assert_eq!(15, c.value(2));
-        assert_eq!(17, c.value(3));
-        assert_eq!(17, c.value(4));
-    }
+/// Perform `left + right` operation on two arrays. If either left or right value is null
+/// then the result is also null.
+///
+/// This detects overflow and returns an `Err` for that. For an non-overflow-checking
+/// variant, use [`add`] instead.
+pub fn add_checked<T>(
+    left: &PrimitiveArray<T>,
+    right: &PrimitiveArray<T>,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: ArrowNativeTypeOp,
+{
+    math_checked_op(left, right, |a, b| a.add_checked(b))
+}
 
-    #[test]
-    fn test_primitive_array_add_sliced() {
-        let a = Int32Array::from(vec![0, 0, 0, 5, 6, 7, 8, 9, 0]);
-        let b = Int32Array::from(vec![0, 0, 0, 6, 7, 8, 9, 8, 0]);
-        let a = a.slice(3, 5);
-        let b = b.slice(3, 5);
-        let a = a.as_any().downcast_ref::<Int32Array>().unwrap();
-        let b = b.as_any().downcast_ref::<Int32Array>().unwrap();
+/// Add every value in an array by a scalar. If any value in the array is null then the
+/// result is also null.
+///
+/// This detects overflow and returns an `Err` for that. For an non-overflow-checking
+/// variant, use [`add_scalar`] instead.
+pub fn add_scalar_checked<T>(
+    array: &PrimitiveArray<T>,
+    scalar: T::Native,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: ArrowNativeTypeOp,
+{
+    try_unary(array, |value| value.add_checked(scalar))
+}
 
-        assert_eq!(5, a.value(0));
-        assert_eq!(6, b.value(0));
+/// Perform `left - right` operation on two arrays. If either left or right value is null
+/// then the result is also null.
+///
+/// This detects overflow and returns an `Err` for that. For an non-overflow-checking
+/// variant, use [`subtract`] instead.
+pub fn subtract_checked<T>(
+    left: &PrimitiveArray<T>,
+    right: &PrimitiveArray<T>,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: ArrowNativeTypeOp,
+{
+    math_checked_op(left, right, |a, b| a.sub_checked(b))
+}
 
-        let c = add(a, b).unwrap();
-        assert_eq!(5, c.len());
-        assert_eq!(11, c.value(0));
-        assert_eq!(13, c.value(1));
-        assert_eq!(15, c.value(2));
-        assert_eq!(17, c.value(3));
-        assert_eq!(17, c.value(4));
-    }
+/// Subtract every value in an array by a scalar. If any value in the array is null then the
+/// result is also null.
+///
+/// This detects overflow and returns an `Err` for that. For an non-overflow-checking
+/// variant, use [`subtract_scalar`] instead.
+pub fn subtract_scalar_checked<T>(
+    array: &PrimitiveArray<T>,
+    scalar: T::Native,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: ArrowNativeTypeOp,
+{
+    try_unary(array, |value| value.sub_checked(scalar))
+}
 
-    #[test]
-    fn test_primitive_array_add_mismatched_length() {
-        let a = Int32Array::from(vec![5, 6, 7, 8, 9]);
-        let b = Int32Array::from(vec![6, 7, 8]);
-        let e = add(&a, &b)
-            .err()
-            .expect("should have failed due to different lengths");
-        assert_eq!(
-            "ComputeError(\"Cannot perform math operation on arrays of different length\")",
-            format!("{:?}", e)
-        );
-    }
+/// Perform `left * right` operation on two arrays. If either left or right value is null
+/// then the result is also null.
+///
+/// This detects overflow and returns an `Err` for that. For an non-overflow-checking
+/// variant, use [`multiply`] instead.
+pub fn multiply_checked<T>(
+    left: &PrimitiveArray<T>,
+    right: &PrimitiveArray<T>,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: ArrowNativeTypeOp,
+{
+    math_checked_op(left, right, |a, b| a.mul_checked(b))
+}
 
-    #[test]
-    fn test_primitive_array_add_scalar() {
-        let a = Int32Array::from(vec![15, 14, 9, 8, 1]);
-        let b = 3;
-        let c = add_scalar(&a, b).unwrap();
-        let expected = Int32Array::from(vec![18, 17, 12, 11, 4]);
-        assert_eq!(c, expected);
-    }
+/// Multiply every value in an array by a scalar. If any value in the array is null then the
+/// result is also null.
+///
+/// This detects overflow and returns an `Err` for that. For an non-overflow-checking
+/// variant, use [`multiply_scalar`] instead.
+pub fn multiply_scalar_checked<T>(
+    array: &PrimitiveArray<T>,
+    scalar: T::Native,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: ArrowNativeTypeOp,
+{
+    try_unary(array, |value| value.mul_checked(scalar))
+}
 
-    #[test]
-    fn test_primitive_array_add_scalar_sliced() {
-        let a = Int32Array::from(vec![Some(15), None, Some(9), Some(8), None]);
-        let a = a.slice(1, 4);
-        let a = as_primitive_array(&a);
-        let actual = add_scalar(a, 3).unwrap();
-        let expected = Int32Array::from(vec![None, Some(12), Some(11), None]);
-        assert_eq!(actual, expected);
-    }
+/// Perform `left / right` operation on two arrays. If either left or right value is null
+/// then the result is also null. If any right hand value is zero then the result of this
+/// operation will be `Err(ArrowError::DivideByZero)`.
+///
+/// This detects overflow, such as `i32::MIN / -1`, and returns an `Err` for that.
+pub fn divide_checked<T>(
+    left: &PrimitiveArray<T>,
+    right: &PrimitiveArray<T>,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: ArrowNativeTypeOp,
+{
+    math_checked_op(left, right, |a, b| a.div_checked(b))
+}
 
-    #[test]
-    fn test_primitive_array_subtract() {
-        let a = Int32Array::from(vec![1, 2, 3, 4, 5]);
-        let b = Int32Array::from(vec![5, 4, 3, 2, 1]);
-        let c = subtract(&a, &b).unwrap();
-        assert_eq!(-4, c.value(0));
-        assert_eq!(-2, c.value(1));
-        assert_eq!(0, c.value(2));
-        assert_eq!(2, c.value(3));
-        assert_eq!(4, c.value(4));
+/// Divide every value in an array by a scalar. If any value in the array is null then the
+/// result is also null. If the scalar is zero then the result of this operation will be
+/// `Err(ArrowError::DivideByZero)`.
+///
+/// This detects overflow, such as `i32::MIN / -1`, and returns an `Err` for that.
+pub fn divide_scalar_checked<T>(
+    array: &PrimitiveArray<T>,
+    divisor: T::Native,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: ArrowNativeTypeOp,
+{
+    if divisor.is_zero() {
+        return Err(ArrowError::DivideByZero);
     }
+    try_unary(array, |value| value.div_checked(divisor))
+}
 
-    #[test]
-    fn test_primitive_array_subtract_scalar() {
-        let a = Int32Array::from(vec![15, 14, 9, 8, 1]);
-        let b = 3;
-        let c = subtract_scalar(&a, b).unwrap();
-        let expected = Int32Array::from(vec![12, 11, 6, 5, -2]);
-        assert_eq!(c, expected);
-    }
+/// Perform `left % right` operation on two arrays. If either left or right value is null
+/// then the result is also null. If any right hand value is zero then the result of this
+/// operation will be `Err(ArrowError::DivideByZero)`.
+///
+/// This detects overflow, such as `i32::MIN % -1`, and returns an `Err` for that.
+pub fn modulus_checked<T>(
+    left: &PrimitiveArray<T>,
+    right: &PrimitiveArray<T>,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: ArrowNativeTypeOp,
+{
+    math_checked_op(left, right, |a, b| a.mod_checked(b))
+}
 
-    #[test]
-    fn test_primitive_array_subtract_scalar_sliced() {
-        let a = Int32Array::from(vec![Some(15), None, Some(9), Some(8), None]);
-        let a = a.slice(1, 4);
-        let a = as_primitive_array(&a);
-        let actual = subtract_scalar(a, 3).unwrap();
-        let expected = Int32Array::from(vec![None, Some(6), Some(5), None]);
-        assert_eq!(actual, expected);
+/// Modulus every value in an array by a scalar. If any value in the array is null then the
+/// result is also null. If the scalar is zero then the result of this operation will be
+/// `Err(ArrowError::DivideByZero)`.
+///
+/// This detects overflow, such as `i32::MIN % -1`, and returns an `Err` for that.
+pub fn modulus_scalar_checked<T>(
+    array: &PrimitiveArray<T>,
+    modulo: T::Native,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: ArrowNativeTypeOp,
+{
+    if modulo.is_zero() {
+        return Err(ArrowError::DivideByZero);
     }
+    try_unary(array, |value| value.mod_checked(modulo))
+}
 
-    #[test]
-    fn test_primitive_array_multiply() {
-        let a = Int32Array::from(vec![5, 6, 7, 8, 9]);
-        let b = Int32Array::from(vec![6, 7, 8, 9, 8]);
-        let c = multiply(&a, &b).unwrap();
-        assert_eq!(30, c.value(0));
-        assert_eq!(42, c.value(1));
-        assert_eq!(56, c.value(2));
-        assert_eq!(72, c.value(3));
-        assert_eq!(72, c.value(4));
-    }
+/// Perform `-` operation on an array. If value is null then the result is also null.
+///
+/// This detects overflow, such as `-i32::MIN`, and returns an `Err` for that.
+pub fn negate_checked<T>(array: &PrimitiveArray<T>) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: ArrowNativeTypeOp,
+{
+    try_unary(array, |value| value.neg_checked())
+}
 
-    #[test]
-    fn test_primitive_array_multiply_scalar() {
-        let a = Int32Array::from(vec![15, 14, 9, 8, 1]);
-        let b = 3;
-        let c = multiply_scalar(&a, b).unwrap();
-        let expected = Int32Array::from(vec![45, 42, 27, 24, 3]);
-        assert_eq!(c, expected);
-    }
+/// The arithmetic operations supported by the dynamically typed kernels
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum MathOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulus,
+}
+
+/// Perform `left + right` operation on two arrays of any numeric type. If either left
+/// or right value is null then the result is also null.
+///
+/// Dictionary arrays are unpacked, and the result is of the dictionary value type.
+/// Integer overflow wraps around, use [`add_dyn_checked`] to detect overflow.
+///
+/// Both arrays must have the same data type, one of the numeric types, a `Duration` or
+/// an `Interval(YearMonth)`, or a dictionary of these. Alternatively both arrays may be
+/// decimals of any precision and scale, see [`add_decimal`], in which case overflow
+/// is always checked.
+///
+/// An `Interval` or `Duration` array may be added to a `Timestamp`, `Date32` or `Date64`
+/// array, or subtracted from it with [`subtract_dyn`], returning an array of the
+/// temporal type. Months and days are added in calendar terms, in the local time of a
+/// timestamp's timezone if any, so that adding a day across a daylight saving time
+/// transition is not the same as adding 24 hours. A month added to the 31st of January
+/// is clamped to the last day of February. The remaining parts of the interval and
+/// durations are exact, and the result is truncated to the precision of the temporal
+/// type. Subtracting two `Timestamp` arrays of the same unit returns a `Duration`.
+/// Overflow of temporal arithmetic is always checked.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use arrow::array::{Array, ArrayRef, DictionaryArray, Int32Array};
+/// # use arrow::compute::kernels::arithmetic::add_dyn;
+/// # use arrow::datatypes::Int8Type;
+/// let a = Int32Array::from(vec![Some(1), None, Some(i32::MAX)]);
+/// let b: DictionaryArray<Int8Type> =
+///     DictionaryArray::try_new(&vec![0, 0, 1].into(), &Int32Array::from(vec![2, 5])).unwrap();
+/// let c = add_dyn(&a, &b).unwrap();
+/// assert_eq!(
+///     c.as_any().downcast_ref::<Int32Array>().unwrap(),
+///     &Int32Array::from(vec![Some(3), None, Some(i32::MIN + 4)])
+/// );
+/// ```
+pub fn add_dyn(left: &dyn Array, right: &dyn Array) -> Result<ArrayRef> {
+    math_op_dyn(left, right, MathOp::Add, false)
+}
+
+/// Perform `left + right` operation on two arrays of any numeric type, see [`add_dyn`].
+///
+/// This detects overflow and returns an `Err` for that.
+pub fn add_dyn_checked(left: &dyn Array, right: &dyn Array) -> Result<ArrayRef> {
+    math_op_dyn(left, right, MathOp::Add, true)
+}
+
+/// Perform `left - right` operation on two arrays of any numeric type, see [`add_dyn`].
+///
+/// Integer overflow wraps around, use [`subtract_dyn_checked`] to detect overflow.
+pub fn subtract_dyn(left: &dyn Array, right: &dyn Array) -> Result<ArrayRef> {
+    math_op_dyn(left, right, MathOp::Subtract, false)
+}
+
+/// Perform `left - right` operation on two arrays of any numeric type, see [`add_dyn`].
+///
+/// This detects overflow and returns an `Err` for that.
+pub fn subtract_dyn_checked(left: &dyn Array, right: &dyn Array) -> Result<ArrayRef> {
+    math_op_dyn(left, right, MathOp::Subtract, true)
+}
+
+/// Perform `left * right` operation on two arrays of any numeric type, see [`add_dyn`].
+///
+/// Integer overflow wraps around, use [`multiply_dyn_checked`] to detect overflow.
+/// `Duration` and `Interval` arrays are not supported.
+pub fn multiply_dyn(left: &dyn Array, right: &dyn Array) -> Result<ArrayRef> {
+    math_op_dyn(left, right, MathOp::Multiply, false)
+}
+
+/// Perform `left * right` operation on two arrays of any numeric type, see [`multiply_dyn`].
+///
+/// This detects overflow and returns an `Err` for that.
+pub fn multiply_dyn_checked(left: &dyn Array, right: &dyn Array) -> Result<ArrayRef> {
+    math_op_dyn(left, right, MathOp::Multiply, true)
+}
+
+/// Perform `left / right` operation on two arrays of any numeric type, see [`add_dyn`].
+/// If any right hand value is zero then the result of this operation will be
+/// `Err(ArrowError::DivideByZero)`.
+///
+/// Integer overflow wraps around, use [`divide_dyn_checked`] to detect overflow.
+/// `Duration` and `Interval` arrays are not supported.
+pub fn divide_dyn(left: &dyn Array, right: &dyn Array) -> Result<ArrayRef> {
+    math_op_dyn(left, right, MathOp::Divide, false)
+}
+
+/// Perform `left / right` operation on two arrays of any numeric type, see [`divide_dyn`].
+///
+/// This detects overflow and returns an `Err` for that.
+pub fn divide_dyn_checked(left: &dyn Array, right: &dyn Array) -> Result<ArrayRef> {
+    math_op_dyn(left, right, MathOp::Divide, true)
+}
+
+/// Perform `left % right` operation on two arrays of any numeric type, see [`add_dyn`].
+/// If any right hand value is zero then the result of this operation will be
+/// `Err(ArrowError::DivideByZero)`.
+///
+/// Integer overflow wraps around, so that `i32::MIN % -1` is `0`, use
+/// [`modulus_dyn_checked`] to detect overflow. `Duration` and `Interval` arrays are
+/// not supported.
+pub fn modulus_dyn(left: &dyn Array, right: &dyn Array) -> Result<ArrayRef> {
+    math_op_dyn(left, right, MathOp::Modulus, false)
+}
+
+/// Perform `left % right` operation on two arrays of any numeric type, see [`modulus_dyn`].
+///
+/// This detects overflow and returns an `Err` for that.
+pub fn modulus_dyn_checked(left: &dyn Array, right: &dyn Array) -> Result<ArrayRef> {
+    math_op_dyn(left, right, MathOp::Modulus, true)
+}
+
+fn math_op_dyn(
+    left: &dyn Array,
+    right: &dyn Array,
+    op: MathOp,
+    checked: bool,
+) -> Result<ArrayRef> {
+    use DataType::*;
+    let additive = matches!(op, MathOp::Add | MathOp::Subtract);
+
+    match (left.data_type(), right.data_type()) {
+        (Dictionary(_, value_type), _) => {
+            let left = cast(&make_array(left.data().clone()), value_type)?;
+            math_op_dyn(left.as_ref(), right, op, checked)
+        }
+        (_, Dictionary(_, value_type)) => {
+            let right = cast(&make_array(right.data().clone()), value_type)?;
+            math_op_dyn(left, right.as_ref(), op, checked)
+        }
+        (Decimal(_, _), Decimal(_, _)) => Ok(Arc::new(decimal_op(
+            as_decimal_array(left),
+            as_decimal_array(right),
+            op,
+        )?)),
+        (
+            Timestamp(_, _) | Date32 | Date64,
+            Interval(_) | Duration(_),
+        ) if additive => temporal_interval_op(left, right, op),
+        (
+            Interval(_) | Duration(_),
+            Timestamp(_, _) | Date32 | Date64,
+        ) if op == MathOp::Add => temporal_interval_op(right, left, op),
+        (Timestamp(_, _), Timestamp(_, _)) if op == MathOp::Subtract => {
+            timestamp_difference(left, right)
+        }
+        (l, r) if l != r => Err(ArrowError::ComputeError(format!(
+            "Cannot perform arithmetic operation {:?} on arrays of different types {:?} and {:?}",
+            op, l, r
+        ))),
+        (Int8, _) => typed_math_op::<Int8Type>(left, right, op, checked),
+        (Int16, _) => typed_math_op::<Int16Type>(left, right, op, checked),
+        (Int32, _) => typed_math_op::<Int32Type>(left, right, op, checked),
+        (Int64, _) => typed_math_op::<Int64Type>(left, right, op, checked),
+        (UInt8, _) => typed_math_op::<UInt8Type>(left, right, op, checked),
+        (UInt16, _) => typed_math_op::<UInt16Type>(left, right, op, checked),
+        (UInt32, _) => typed_math_op::<UInt32Type>(left, right, op, checked),
+        (UInt64, _) => typed_math_op::<UInt64Type>(left, right, op, checked),
+        (Float16, _) => typed_math_op::<Float16Type>(left, right, op, checked),
+        (Float32, _) => typed_math_op::<Float32Type>(left, right, op, checked),
+        (Float64, _) => typed_math_op::<Float64Type>(left, right, op, checked),
+        (Duration(TimeUnit::Second), _) if additive => {
+            typed_math_op::<DurationSecondType>(left, right, op, checked)
+        }
+        (Duration(TimeUnit::Millisecond), _) if additive => {
+            typed_math_op::<DurationMillisecondType>(left, right, op, checked)
+        }
+        (Duration(TimeUnit::Microsecond), _) if additive => {
+            typed_math_op::<DurationMicrosecondType>(left, right, op, checked)
+        }
+        (Duration(TimeUnit::Nanosecond), _) if additive => {
+            typed_math_op::<DurationNanosecondType>(left, right, op, checked)
+        }
+        (Interval(IntervalUnit::YearMonth), _) if additive => {
+            typed_math_op::<IntervalYearMonthType>(left, right, op, checked)
+        }
+        (t, _) => Err(ArrowError::ComputeError(format!(
+            "Arithmetic operation {:?} not supported for data type {:?}",
+            op, t
+        ))),
+    }
+}
+
+fn typed_math_op<T>(
+    left: &dyn Array,
+    right: &dyn Array,
+    op: MathOp,
+    checked: bool,
+) -> Result<ArrayRef>
+where
+    T: ArrowPrimitiveType,
+    T::Native: ArrowNativeTypeOp,
+{
+    let left = as_primitive_array::<T>(left);
+    let right = as_primitive_array::<T>(right);
+    let array = match (op, checked) {
+        (MathOp::Add, false) => math_op(left, right, |a, b| a.add_wrapping(b)),
+        (MathOp::Add, true) => math_checked_op(left, right, |a, b| a.add_checked(b)),
+        (MathOp::Subtract, false) => math_op(left, right, |a, b| a.sub_wrapping(b)),
+        (MathOp::Subtract, true) => math_checked_op(left, right, |a, b| a.sub_checked(b)),
+        (MathOp::Multiply, false) => math_op(left, right, |a, b| a.mul_wrapping(b)),
+        (MathOp::Multiply, true) => math_checked_op(left, right, |a, b| a.mul_checked(b)),
+        (MathOp::Divide, false) => math_checked_op(left, right, |a, b| {
+            if b.is_zero() {
+                Err(ArrowError::DivideByZero)
+            } else {
+                Ok(a.div_wrapping(b))
+            }
+        }),
+        (MathOp::Divide, true) => math_checked_op(left, right, |a, b| a.div_checked(b)),
+        (MathOp::Modulus, false) => math_checked_op(left, right, |a, b| {
+            if b.is_zero() {
+                Err(ArrowError::DivideByZero)
+            } else {
+                Ok(a.mod_wrapping(b))
+            }
+        }),
+        (MathOp::Modulus, true) => math_checked_op(left, right, |a, b| a.mod_checked(b)),
+    }?;
+    Ok(Arc::new(array))
+}
+
+/// The minimum scale of the result of a decimal operation, when reducing the scale
+/// of the result to fit within [`DECIMAL_MAX_PRECISION`]
+const DECIMAL_MIN_ADJUSTED_SCALE: usize = 6;
+
+/// Returns the precision and scale of the result of `op` applied to decimals with
+/// precision and scale `left` and `right`, see [`add_decimal`]
+fn decimal_result_type(
+    op: MathOp,
+    (p1, s1): (usize, usize),
+    (p2, s2): (usize, usize),
+) -> (usize, usize) {
+    let (precision, scale) = match op {
+        MathOp::Add | MathOp::Subtract => {
+            let scale = s1.max(s2);
+            ((p1 - s1).max(p2 - s2) + scale + 1, scale)
+        }
+        MathOp::Multiply => (p1 + p2 + 1, s1 + s2),
+        MathOp::Divide => {
+            let scale = DECIMAL_MIN_ADJUSTED_SCALE.max(s1 + p2 + 1);
+            (p1 - s1 + s2 + scale, scale)
+        }
+        MathOp::Modulus => {
+            let scale = s1.max(s2);
+            ((p1 - s1).min(p2 - s2) + scale, scale)
+        }
+    };
+
+    if precision <= DECIMAL_MAX_PRECISION {
+        return (precision, scale);
+    }
+    let integral_digits = precision - scale;
+    let min_scale = scale.min(DECIMAL_MIN_ADJUSTED_SCALE);
+    let scale = DECIMAL_MAX_PRECISION
+        .saturating_sub(integral_digits)
+        .max(min_scale);
+    (DECIMAL_MAX_PRECISION, scale)
+}
+
+/// Applies `op` to decimal values `l` with scale `s1` and `r` with scale `s2`,
+/// returning a value with precision `precision` and scale `scale`
+fn decimal_value_op(
+    op: MathOp,
+    (l, s1): (i128, usize),
+    (r, s2): (i128, usize),
+    (precision, scale): (usize, usize),
+) -> Result<i128> {
+    let value = match op {
+        MathOp::Add | MathOp::Subtract => {
+            let s = s1.max(s2);
+            decimal_rescale(l, s1, s)
+                .zip(decimal_rescale(r, s2, s))
+                .and_then(|(l, r)| match op {
+                    MathOp::Add => l.checked_add(r),
+                    _ => l.checked_sub(r),
+                })
+                .and_then(|v| decimal_rescale(v, s, scale))
+        }
+        MathOp::Multiply => decimal_mul_rescale(l, r, s1 + s2, scale),
+        MathOp::Divide => {
+            if r == 0 {
+                return Err(ArrowError::DivideByZero);
+            }
+            // The quotient has scale `s1 - s2`
+            decimal_div_rescale(l, r, s1, scale + s2)
+        }
+        MathOp::Modulus => {
+            if r == 0 {
+                return Err(ArrowError::DivideByZero);
+            }
+            let s = s1.max(s2);
+            decimal_rescale(l, s1, s)
+                .zip(decimal_rescale(r, s2, s))
+                .and_then(|(l, r)| l.checked_rem(r))
+                .and_then(|v| decimal_rescale(v, s, scale))
+        }
+    };
+
+    let max = MAX_DECIMAL_FOR_EACH_PRECISION[precision - 1];
+    let min = MIN_DECIMAL_FOR_EACH_PRECISION[precision - 1];
+    match value {
+        Some(v) if v >= min && v <= max => Ok(v),
+        _ => Err(ArrowError::ComputeError(format!(
+            "Overflow happened on decimal {:?}: result does not fit in Decimal({}, {})",
+            op, precision, scale
+        ))),
+    }
+}
+
+/// Returns an error if `precision` and `scale` are not a valid decimal type
+fn validate_decimal_type((precision, scale): (usize, usize)) -> Result<()> {
+    if precision == 0 || precision > DECIMAL_MAX_PRECISION {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Decimal precision {} is not between 1 and {}",
+            precision, DECIMAL_MAX_PRECISION
+        )));
+    }
+    if scale > precision {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Decimal scale {} is greater than precision {}",
+            scale, precision
+        )));
+    }
+    Ok(())
+}
+
+fn decimal_op(
+    left: &DecimalArray,
+    right: &DecimalArray,
+    op: MathOp,
+) -> Result<DecimalArray> {
+    if left.len() != right.len() {
+        return Err(ArrowError::ComputeError(
+            "Cannot perform math operation on arrays of different length".to_string(),
+        ));
+    }
+    let l_type = (left.precision(), left.scale());
+    let r_type = (right.precision(), right.scale());
+    validate_decimal_type(l_type)?;
+    validate_decimal_type(r_type)?;
+    let result_type = decimal_result_type(op, l_type, r_type);
+
+    let array = left
+        .iter()
+        .zip(right.iter())
+        .map(|(l, r)| match (l, r) {
+            (Some(l), Some(r)) => {
+                decimal_value_op(op, (l, l_type.1), (r, r_type.1), result_type).map(Some)
+            }
+            _ => Ok(None),
+        })
+        .collect::<Result<DecimalArray>>()?;
+    array.with_precision_and_scale(result_type.0, result_type.1)
+}
+
+fn decimal_scalar_op(
+    array: &DecimalArray,
+    scalar: &Decimal128,
+    op: MathOp,
+) -> Result<DecimalArray> {
+    let l_type = (array.precision(), array.scale());
+    let r_type = (scalar.precision(), scalar.scale());
+    validate_decimal_type(l_type)?;
+    validate_decimal_type(r_type)?;
+    let result_type = decimal_result_type(op, l_type, r_type);
+    let r = scalar.as_i128();
+
+    if matches!(op, MathOp::Divide | MathOp::Modulus) && r == 0 {
+        return Err(ArrowError::DivideByZero);
+    }
+
+    let array = array
+        .iter()
+        .map(|l| {
+            l.map(|l| decimal_value_op(op, (l, l_type.1), (r, r_type.1), result_type))
+                .transpose()
+        })
+        .collect::<Result<DecimalArray>>()?;
+    array.with_precision_and_scale(result_type.0, result_type.1)
+}
+
+/// Perform `left + right` operation on two decimal arrays. If either left or right value
+/// is null then the result is also null.
+///
+/// The operands are rescaled to a common scale, and the precision and scale of the
+/// result are derived from the precision and scale of the operands, `(p1, s1)` and
+/// `(p2, s2)`, following the rules of SQL engines such as SQL Server, Hive and Spark:
+///
+/// | Operation | Result precision                          | Result scale          |
+/// |-----------|-------------------------------------------|-----------------------|
+/// | `+`, `-`  | `max(s1, s2) + max(p1 - s1, p2 - s2) + 1` | `max(s1, s2)`         |
+/// | `*`       | `p1 + p2 + 1`                             | `s1 + s2`             |
+/// | `/`       | `p1 - s1 + s2 + max(6, s1 + p2 + 1)`      | `max(6, s1 + p2 + 1)` |
+/// | `%`       | `min(p1 - s1, p2 - s2) + max(s1, s2)`     | `max(s1, s2)`         |
+///
+/// If the precision exceeds [`DECIMAL_MAX_PRECISION`], it is capped, and the scale is
+/// reduced to preserve the integral digits, but to no less than `min(scale, 6)`
+///
+/// Returns an error if a result does not fit in the result precision.
+///
+/// ```
+/// # use arrow::array::{Array, DecimalArray};
+/// # use arrow::compute::kernels::arithmetic::add_decimal;
+/// # use arrow::datatypes::DataType;
+/// // 1.5 + 0.25
+/// let a = DecimalArray::from_iter_values([15]).with_precision_and_scale(5, 1).unwrap();
+/// let b = DecimalArray::from_iter_values([25]).with_precision_and_scale(3, 2).unwrap();
+/// let c = add_decimal(&a, &b).unwrap();
+/// assert_eq!(c.data_type(), &DataType::Decimal(7, 2));
+/// assert_eq!(c.value_as_string(0), "1.75");
+/// ```
+pub fn add_decimal(left: &DecimalArray, right: &DecimalArray) -> Result<DecimalArray> {
+    decimal_op(left, right, MathOp::Add)
+}
+
+/// Add every value in a decimal array by a decimal scalar, see [`add_decimal`].
+pub fn add_decimal_scalar(
+    array: &DecimalArray,
+    scalar: &Decimal128,
+) -> Result<DecimalArray> {
+    decimal_scalar_op(array, scalar, MathOp::Add)
+}
+
+/// Perform `left - right` operation on two decimal arrays, see [`add_decimal`].
+pub fn subtract_decimal(
+    left: &DecimalArray,
+    right: &DecimalArray,
+) -> Result<DecimalArray> {
+    decimal_op(left, right, MathOp::Subtract)
+}
+
+/// Subtract every value in a decimal array by a decimal scalar, see [`add_decimal`].
+pub fn subtract_decimal_scalar(
+    array: &DecimalArray,
+    scalar: &Decimal128,
+) -> Result<DecimalArray> {
+    decimal_scalar_op(array, scalar, MathOp::Subtract)
+}
+
+/// Perform `left * right` operation on two decimal arrays, see [`add_decimal`].
+///
+/// If the scale of the result is reduced to fit within [`DECIMAL_MAX_PRECISION`], the
+/// result is rounded half away from zero.
+pub fn multiply_decimal(
+    left: &DecimalArray,
+    right: &DecimalArray,
+) -> Result<DecimalArray> {
+    decimal_op(left, right, MathOp::Multiply)
+}
+
+/// Multiply every value in a decimal array by a decimal scalar, see [`multiply_decimal`].
+pub fn multiply_decimal_scalar(
+    array: &DecimalArray,
+    scalar: &Decimal128,
+) -> Result<DecimalArray> {
+    decimal_scalar_op(array, scalar, MathOp::Multiply)
+}
+
+/// Perform `left / right` operation on two decimal arrays, see [`add_decimal`].
+/// If any right hand value is zero then the result of this operation will be
+/// `Err(ArrowError::DivideByZero)`.
+///
+/// The result is rounded half away from zero to the scale of the result.
+///
+/// ```
+/// # use arrow::array::{Array, DecimalArray};
+/// # use arrow::compute::kernels::arithmetic::divide_decimal;
+/// # use arrow::datatypes::DataType;
+/// // 1.00 / 3
+/// let a = DecimalArray::from_iter_values([100]).with_precision_and_scale(5, 2).unwrap();
+/// let b = DecimalArray::from_iter_values([3]).with_precision_and_scale(2, 0).unwrap();
+/// let c = divide_decimal(&a, &b).unwrap();
+/// assert_eq!(c.data_type(), &DataType::Decimal(9, 6));
+/// assert_eq!(c.value_as_string(0), "0.333333");
+/// ```
+pub fn divide_decimal(left: &DecimalArray, right: &DecimalArray) -> Result<DecimalArray> {
+    decimal_op(left, right, MathOp::Divide)
+}
+
+/// Divide every value in a decimal array by a decimal scalar, see [`divide_decimal`].
+pub fn divide_decimal_scalar(
+    array: &DecimalArray,
+    scalar: &Decimal128,
+) -> Result<DecimalArray> {
+    decimal_scalar_op(array, scalar, MathOp::Divide)
+}
+
+/// Perform `left % right` operation on two decimal arrays, see [`add_decimal`].
+/// If any right hand value is zero then the result of this operation will be
+/// `Err(ArrowError::DivideByZero)`.
+///
+/// The operands are rescaled to a common scale, and the result has the sign of `left`.
+///
+/// ```
+/// # use arrow::array::{Array, DecimalArray};
+/// # use arrow::compute::kernels::arithmetic::modulus_decimal;
+/// # use arrow::datatypes::DataType;
+/// // 7.50 % 2.0
+/// let a = DecimalArray::from_iter_values([750]).with_precision_and_scale(5, 2).unwrap();
+/// let b = DecimalArray::from_iter_values([20]).with_precision_and_scale(3, 1).unwrap();
+/// let c = modulus_decimal(&a, &b).unwrap();
+/// assert_eq!(c.data_type(), &DataType::Decimal(4, 2));
+/// assert_eq!(c.value_as_string(0), "1.50");
+/// ```
+pub fn modulus_decimal(
+    left: &DecimalArray,
+    right: &DecimalArray,
+) -> Result<DecimalArray> {
+    decimal_op(left, right, MathOp::Modulus)
+}
+
+/// Modulus every value in a decimal array by a decimal scalar, see [`modulus_decimal`].
+pub fn modulus_decimal_scalar(
+    array: &DecimalArray,
+    scalar: &Decimal128,
+) -> Result<DecimalArray> {
+    decimal_scalar_op(array, scalar, MathOp::Modulus)
+}
+
+/// Adds `months` to `datetime`, clamping the day to the last day of the resulting
+/// month, e.g. `2021-01-31` plus one month is `2021-02-28`
+fn shift_months(datetime: NaiveDateTime, months: i32) -> Option<NaiveDateTime> {
+    if months == 0 {
+        return Some(datetime);
+    }
+    let months = i64::from(datetime.year()) * 12
+        + i64::from(datetime.month0())
+        + i64::from(months);
+    let year = i32::try_from(months.div_euclid(12)).ok()?;
+    let month = months.rem_euclid(12) as u32 + 1;
+    let next_month = match month {
+        12 => NaiveDate::from_ymd_opt(year.checked_add(1)?, 1, 1)?,
+        _ => NaiveDate::from_ymd_opt(year, month + 1, 1)?,
+    };
+    let day = datetime.day().min(next_month.pred_opt()?.day());
+    Some(NaiveDate::from_ymd_opt(year, month, day)?.and_time(datetime.time()))
+}
+
+/// Adds an interval of `months`, `days` and `nanos` to the instant `value`, in
+/// nanoseconds since the UNIX epoch, returning `None` on overflow
+///
+/// Months and days are applied in the local time of `tz`, so that adding a day is
+/// not always the same as adding 24 hours, whereas `nanos` is an exact duration.
+fn add_interval_nanos(
+    value: i128,
+    tz: Option<TemporalTz>,
+    months: i32,
+    days: i32,
+    nanos: i128,
+) -> Option<i128> {
+    if months == 0 && days == 0 {
+        return value.checked_add(nanos);
+    }
+    let utc = nanos_to_datetime(value)?;
+    let local = match tz {
+        Some(tz) => tz.to_local(utc)?,
+        None => utc,
+    };
+    let local = shift_months(local, months)?
+        .checked_add_signed(chrono::Duration::days(days.into()))?;
+    let utc = match tz {
+        Some(tz) => tz.to_utc(local)?,
+        None => local,
+    };
+
+    datetime_to_nanos(utc).checked_add(nanos)
+}
+
+/// Adds or subtracts an `Interval` or `Duration` array `right` to a `Timestamp`,
+/// `Date32` or `Date64` array `left`, returning an array of the same type as `left`
+fn temporal_interval_op(
+    left: &dyn Array,
+    right: &dyn Array,
+    op: MathOp,
+) -> Result<ArrayRef> {
+    let (unit_nanos, tz) = match left.data_type() {
+        DataType::Date32 => (NANOS_PER_DAY, None),
+        DataType::Date64 => (time_unit_nanos(&TimeUnit::Millisecond), None),
+        DataType::Timestamp(unit, tz) => (
+            time_unit_nanos(unit),
+            tz.as_deref().map(TemporalTz::parse).transpose()?,
+        ),
+        t => {
+            return Err(ArrowError::ComputeError(format!(
+                "Arithmetic operation {:?} not supported for data type {:?}",
+                op, t
+            )))
+        }
+    };
+    let negate = op == MathOp::Subtract;
+    let overflow = || {
+        ArrowError::ComputeError(format!(
+            "Overflow happened on temporal {:?}: result does not fit in {:?}",
+            op,
+            left.data_type()
+        ))
+    };
+
+    let values = cast(&make_array(left.data().clone()), &DataType::Int64)?;
+    let values = as_primitive_array::<Int64Type>(&values);
+    let add = |value: i64, months: i32, days: i32, nanos: i128| -> Result<i64> {
+        let (months, days, nanos) = match negate {
+            true => (months.checked_neg(), days.checked_neg(), Some(-nanos)),
+            false => (Some(months), Some(days), Some(nanos)),
+        };
+        let result = add_interval_nanos(
+            i128::from(value) * i128::from(unit_nanos),
+            tz,
+            months.ok_or_else(overflow)?,
+            days.ok_or_else(overflow)?,
+            nanos.ok_or_else(overflow)?,
+        )
+        .ok_or_else(overflow)?;
+        // Truncate towards negative infinity, as if the instant were cast to `left`'s type
+        i64::try_from(result.div_euclid(i128::from(unit_nanos))).map_err(|_| overflow())
+    };
+
+    let result = match right.data_type() {
+        DataType::Interval(IntervalUnit::YearMonth) => {
+            temporal_values::<IntervalYearMonthType, _>(values, right, |v, months| {
+                add(v, months, 0, 0)
+            })
+        }
+        DataType::Interval(IntervalUnit::DayTime) => {
+            temporal_values::<IntervalDayTimeType, _>(values, right, |v, interval| {
+                let (days, millis) = IntervalDayTimeType::to_parts(interval);
+                add(v, 0, days, i128::from(millis) * 1_000_000)
+            })
+        }
+        DataType::Interval(IntervalUnit::MonthDayNano) => {
+            temporal_values::<IntervalMonthDayNanoType, _>(
+                values,
+                right,
+                |v, interval| {
+                    let (months, days, nanos) =
+                        IntervalMonthDayNanoType::to_parts(interval);
+                    add(v, months, days, nanos.into())
+                },
+            )
+        }
+        DataType::Duration(unit) => {
+            let duration_nanos = i128::from(time_unit_nanos(unit));
+            let right = cast(&make_array(right.data().clone()), &DataType::Int64)?;
+            temporal_values::<Int64Type, _>(values, right.as_ref(), |v, duration| {
+                add(v, 0, 0, i128::from(duration) * duration_nanos)
+            })
+        }
+        t => Err(ArrowError::ComputeError(format!(
+            "Cannot perform arithmetic operation {:?} on arrays of types {:?} and {:?}",
+            op,
+            left.data_type(),
+            t
+        ))),
+    }?;
+
+    make_temporal_array(&result, left.data_type()).ok_or_else(overflow)
+}
+
+/// Applies a fallible function to the values of an `Int64Array` and a primitive array,
+/// if either value is null the result is null
+fn temporal_values<R, F>(
+    left: &Int64Array,
+    right: &dyn Array,
+    op: F,
+) -> Result<Int64Array>
+where
+    R: ArrowPrimitiveType,
+    F: Fn(i64, R::Native) -> Result<i64>,
+{
+    if left.len() != right.len() {
+        return Err(ArrowError::ComputeError(
+            "Cannot perform math operation on arrays of different length".to_string(),
+        ));
+    }
+    left.iter()
+        .zip(as_primitive_array::<R>(right).iter())
+        .map(|(l, r)| match (l, r) {
+            (Some(l), Some(r)) => op(l, r).map(Some),
+            _ => Ok(None),
+        })
+        .collect()
+}
+
+/// Subtracts two `Timestamp` arrays of the same unit, returning a `Duration` array
+fn timestamp_difference(left: &dyn Array, right: &dyn Array) -> Result<ArrayRef> {
+    let unit = match (left.data_type(), right.data_type()) {
+        (DataType::Timestamp(l_unit, l_tz), DataType::Timestamp(r_unit, r_tz))
+            if l_unit == r_unit && l_tz.is_some() == r_tz.is_some() =>
+        {
+            l_unit.clone()
+        }
+        (l, r) => {
+            return Err(ArrowError::ComputeError(format!(
+                "Cannot subtract timestamps of types {:?} and {:?}, cast them to a common type first",
+                l, r
+            )))
+        }
+    };
+    let left = cast(&make_array(left.data().clone()), &DataType::Int64)?;
+    let right = cast(&make_array(right.data().clone()), &DataType::Int64)?;
+    let result = subtract_checked(
+        as_primitive_array::<Int64Type>(&left),
+        as_primitive_array::<Int64Type>(&right),
+    )?;
+    Ok(make_temporal_array(&result, &DataType::Duration(unit))
+        .expect("Duration has the same width as Int64"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::Int32Array;
+    use crate::datatypes::{Date32Type, Date64Type, TimestampSecondType};
+    use half::f16;
+
+    #[test]
+    fn test_primitive_array_add() {
+        let a = Int32Array::from(vec![5, 6, 7, 8, 9]);
+        let b = Int32Array::from(vec![6, 7, 8, 9, 8]);
+        let c = add(&a, &b).unwrap();
+        assert_eq!(11, c.value(0));
+        assert_eq!(13, c.value(1));
+        assert_eq!(15, c.value(2));
+        assert_eq!(17, c.value(3));
+        assert_eq!(17, c.value(4));
+    }
+
+    #[test]
+    fn test_primitive_array_add_sliced() {
+        let a = Int32Array::from(vec![0, 0, 0, 5, 6, 7, 8, 9, 0]);
+        let b = Int32Array::from(vec![0, 0, 0, 6, 7, 8, 9, 8, 0]);
+        let a = a.slice(3, 5);
+        let b = b.slice(3, 5);
+        let a = a.as_any().downcast_ref::<Int32Array>().unwrap();
+        let b = b.as_any().downcast_ref::<Int32Array>().unwrap();
+
+        assert_eq!(5, a.value(0));
+        assert_eq!(6, b.value(0));
+
+        let c = add(a, b).unwrap();
+        assert_eq!(5, c.len());
+        assert_eq!(11, c.value(0));
+        assert_eq!(13, c.value(1));
+        assert_eq!(15, c.value(2));
+        assert_eq!(17, c.value(3));
+        assert_eq!(17, c.value(4));
+    }
+
+    #[test]
+    fn test_primitive_array_add_mismatched_length() {
+        let a = Int32Array::from(vec![5, 6, 7, 8, 9]);
+        let b = Int32Array::from(vec![6, 7, 8]);
+        let e = add(&a, &b)
+            .err()
+            .expect("should have failed due to different lengths");
+        assert_eq!(
+            "ComputeError(\"Cannot perform math operation on arrays of different length\")",
+            format!("{:?}", e)
+        );
+    }
+
+    #[test]
+    fn test_primitive_array_add_scalar() {
+        let a = Int32Array::from(vec![15, 14, 9, 8, 1]);
+        let b = 3;
+        let c = add_scalar(&a, b).unwrap();
+        let expected = Int32Array::from(vec![18, 17, 12, 11, 4]);
+        assert_eq!(c, expected);
+    }
+
+    #[test]
+    fn test_primitive_array_add_scalar_sliced() {
+        let a = Int32Array::from(vec![Some(15), None, Some(9), Some(8), None]);
+        let a = a.slice(1, 4);
+        let a = as_primitive_array(&a);
+        let actual = add_scalar(a, 3).unwrap();
+        let expected = Int32Array::from(vec![None, Some(12), Some(11), None]);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_primitive_array_subtract() {
+        let a = Int32Array::from(vec![1, 2, 3, 4, 5]);
+        let b = Int32Array::from(vec![5, 4, 3, 2, 1]);
+        let c = subtract(&a, &b).unwrap();
+        assert_eq!(-4, c.value(0));
+        assert_eq!(-2, c.value(1));
+        assert_eq!(0, c.value(2));
+        assert_eq!(2, c.value(3));
+        assert_eq!(4, c.value(4));
+    }
+
+    #[test]
+    fn test_primitive_array_subtract_scalar() {
+        let a = Int32Array::from(vec![15, 14, 9, 8, 1]);
+        let b = 3;
+        let c = subtract_scalar(&a, b).unwrap();
+        let expected = Int32Array::from(vec![12, 11, 6, 5, -2]);
+        assert_eq!(c, expected);
+    }
+
+    #[test]
+    fn test_primitive_array_subtract_scalar_sliced() {
+        let a = Int32Array::from(vec![Some(15), None, Some(9), Some(8), None]);
+        let a = a.slice(1, 4);
+        let a = as_primitive_array(&a);
+        let actual = subtract_scalar(a, 3).unwrap();
+        let expected = Int32Array::from(vec![None, Some(6), Some(5), None]);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_primitive_array_multiply() {
+        let a = Int32Array::from(vec![5, 6, 7, 8, 9]);
+        let b = Int32Array::from(vec![6, 7, 8, 9, 8]);
+        let c = multiply(&a, &b).unwrap();
+        assert_eq!(30, c.value(0));
+        assert_eq!(42, c.value(1));
+        assert_eq!(56, c.value(2));
+        assert_eq!(72, c.value(3));
+        assert_eq!(72, c.value(4));
+    }
+
+    #[test]
+    fn test_primitive_array_multiply_scalar() {
+        let a = Int32Array::from(vec![15, 14, 9, 8, 1]);
+        let b = 3;
+        let c = multiply_scalar(&a, b).unwrap();
+        let expected = Int32Array::from(vec![45, 42, 27, 24, 3]);
+        assert_eq!(c, expected);
+    }
 
     #[test]
     fn test_primitive_array_multiply_scalar_sliced() {
@@ -1015,6 +1944,34 @@ mod tests {
         modulus(&a, &b).unwrap();
     }
 
+    #[test]
+    fn test_primitive_array_modulus_checked() {
+        let a = Int32Array::from(vec![Some(i32::MIN), None, Some(-7)]);
+        let b = Int32Array::from(vec![Some(3), Some(0), Some(2)]);
+        let c = modulus_checked(&a, &b).unwrap();
+        assert_eq!(c, Int32Array::from(vec![Some(-2), None, Some(-1)]));
+
+        let b = Int32Array::from(vec![Some(-1), Some(0), Some(2)]);
+        let err = modulus_checked(&a, &b).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Overflow happened on: -2147483648 % -1"
+        );
+        let b = Int32Array::from(vec![1, 1, 0]);
+        let err = modulus_checked(&a, &b).unwrap_err();
+        assert!(matches!(err, ArrowError::DivideByZero));
+
+        let c = modulus_scalar_checked(&a, 4).unwrap();
+        assert_eq!(c, Int32Array::from(vec![Some(0), None, Some(-3)]));
+        let err = modulus_scalar_checked(&a, -1).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Overflow happened on: -2147483648 % -1"
+        );
+        let err = modulus_scalar_checked(&a, 0).unwrap_err();
+        assert!(matches!(err, ArrowError::DivideByZero));
+    }
+
     #[test]
     fn test_primitive_array_divide_f64() {
         let a = Float64Array::from(vec![15.0, 15.0, 8.0]);
@@ -1075,4 +2032,725 @@ mod tests {
         let expected = Float64Array::from(vec![Some(1.0), None, Some(9.0)]);
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn test_primitive_array_add_checked() {
+        let a = Int32Array::from(vec![Some(1), None, Some(i32::MAX), Some(4)]);
+        let b = Int32Array::from(vec![Some(2), Some(i32::MAX), None, Some(5)]);
+        let c = add_checked(&a, &b).unwrap();
+        assert_eq!(c, Int32Array::from(vec![Some(3), None, None, Some(9)]));
+
+        let b = Int32Array::from(vec![Some(2), Some(3), Some(1), Some(5)]);
+        let err = add_checked(&a, &b).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Overflow happened on: 2147483647 + 1"
+        );
+
+        // Overflow in null slots is ignored
+        let a = Int32Array::from(vec![None, Some(1)]);
+        let c = add_scalar_checked(&a, i32::MAX - 1).unwrap();
+        assert_eq!(c, Int32Array::from(vec![None, Some(i32::MAX)]));
+        let err = add_scalar_checked(&a, i32::MAX).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Overflow happened on: 1 + 2147483647"
+        );
+    }
+
+    #[test]
+    fn test_primitive_array_subtract_multiply_checked() {
+        let a = UInt8Array::from(vec![Some(1), None, Some(200)]);
+        let b = UInt8Array::from(vec![Some(1), Some(3), Some(1)]);
+        let c = subtract_checked(&a, &b).unwrap();
+        assert_eq!(c, UInt8Array::from(vec![Some(0), None, Some(199)]));
+        assert!(subtract_checked(&b, &a).is_err());
+        assert!(subtract_scalar_checked(&a, 2).is_err());
+
+        let c = multiply_scalar_checked(&b, 85).unwrap();
+        assert_eq!(c, UInt8Array::from(vec![85, 255, 85]));
+        assert!(multiply_checked(&a, &UInt8Array::from(vec![1, 1, 2])).is_err());
+
+        let a = Float64Array::from(vec![f64::MAX]);
+        let c = multiply_checked(&a, &a).unwrap();
+        assert_eq!(c.value(0), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_primitive_array_divide_negate_checked() {
+        let a = Int8Array::from(vec![Some(i8::MIN), None, Some(6)]);
+        let b = Int8Array::from(vec![Some(2), Some(0), Some(-3)]);
+        let c = divide_checked(&a, &b).unwrap();
+        assert_eq!(c, Int8Array::from(vec![Some(-64), None, Some(-2)]));
+
+        let err = divide_scalar_checked(&a, -1).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Overflow happened on: -128 / -1"
+        );
+        let err = divide_scalar_checked(&a, 0).unwrap_err();
+        assert!(matches!(err, ArrowError::DivideByZero));
+        let err = divide_checked(&b, &b).unwrap_err();
+        assert!(matches!(err, ArrowError::DivideByZero));
+
+        let err = negate_checked(&a).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Overflow happened on: --128"
+        );
+        let c = negate_checked(&b).unwrap();
+        assert_eq!(c, Int8Array::from(vec![-2, 0, 3]));
+    }
+
+    #[test]
+    fn test_arithmetic_dyn() {
+        let a = Int32Array::from(vec![Some(i32::MAX), None, Some(3)]);
+        let b = Int32Array::from(vec![Some(1), Some(2), Some(4)]);
+
+        let c = add_dyn(&a, &b).unwrap();
+        let expected = Int32Array::from(vec![Some(i32::MIN), None, Some(7)]);
+        assert_eq!(as_primitive_array::<Int32Type>(&c), &expected);
+        assert!(add_dyn_checked(&a, &b).is_err());
+
+        let c = subtract_dyn(&b, &a).unwrap();
+        let expected = Int32Array::from(vec![Some(i32::MIN + 2), None, Some(1)]);
+        assert_eq!(as_primitive_array::<Int32Type>(&c), &expected);
+        assert!(subtract_dyn_checked(&b, &Int32Array::from(vec![3, 2, 1])).is_ok());
+
+        let c = multiply_dyn(&a, &b).unwrap();
+        let expected = Int32Array::from(vec![Some(i32::MAX), None, Some(12)]);
+        assert_eq!(as_primitive_array::<Int32Type>(&c), &expected);
+        let c = multiply_dyn_checked(&b, &b).unwrap();
+        let expected = Int32Array::from(vec![1, 4, 16]);
+        assert_eq!(as_primitive_array::<Int32Type>(&c), &expected);
+
+        let c = divide_dyn(&a, &b).unwrap();
+        let expected = Int32Array::from(vec![Some(i32::MAX), None, Some(0)]);
+        assert_eq!(as_primitive_array::<Int32Type>(&c), &expected);
+        let err = divide_dyn(&a, &Int32Array::from(vec![1, 1, 0])).unwrap_err();
+        assert!(matches!(err, ArrowError::DivideByZero));
+        let c = divide_dyn_checked(
+            &Float64Array::from(vec![1.]),
+            &Float64Array::from(vec![4.]),
+        );
+        let expected = Float64Array::from(vec![0.25]);
+        assert_eq!(as_primitive_array::<Float64Type>(&c.unwrap()), &expected);
+
+        let a = Int32Array::from(vec![Some(i32::MIN), None, Some(7)]);
+        let b = Int32Array::from(vec![Some(-1), Some(0), Some(-4)]);
+        let c = modulus_dyn(&a, &b).unwrap();
+        let expected = Int32Array::from(vec![Some(0), None, Some(3)]);
+        assert_eq!(as_primitive_array::<Int32Type>(&c), &expected);
+        let err = modulus_dyn_checked(&a, &b).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Overflow happened on: -2147483648 % -1"
+        );
+        let err = modulus_dyn(&a, &Int32Array::from(vec![1, 1, 0])).unwrap_err();
+        assert!(matches!(err, ArrowError::DivideByZero));
+        let err = modulus_dyn_checked(&a, &Int32Array::from(vec![1, 1, 0])).unwrap_err();
+        assert!(matches!(err, ArrowError::DivideByZero));
+
+        let f16s = |v: &[f32]| {
+            Float16Array::from_iter_values(v.iter().map(|v| f16::from_f32(*v)))
+        };
+        let x = f16s(&[1.5, -3.]);
+        let y = f16s(&[2., 2.]);
+        let c = add_dyn(&x, &y).unwrap();
+        assert_eq!(as_primitive_array::<Float16Type>(&c), &f16s(&[3.5, -1.]));
+        let c = modulus_dyn_checked(&x, &y).unwrap();
+        assert_eq!(as_primitive_array::<Float16Type>(&c), &f16s(&[1.5, -1.]));
+
+        let err = add_dyn(&a, &Int64Array::from(vec![1, 2, 3])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Cannot perform arithmetic operation Add on arrays of different types Int32 and Int64"
+        );
+
+        let s = StringArray::from(vec!["a"]);
+        let err = add_dyn(&s, &s).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Arithmetic operation Add not supported for data type Utf8"
+        );
+    }
+
+    #[test]
+    fn test_arithmetic_dyn_dictionary() {
+        let keys = Int8Array::from(vec![Some(1), None, Some(0), Some(1)]);
+        let values = Int64Array::from(vec![10, 20]);
+        let a = DictionaryArray::try_new(&keys, &values).unwrap();
+        let b = Int64Array::from(vec![Some(1), Some(2), None, Some(4)]);
+
+        let c = add_dyn(&a, &b).unwrap();
+        let expected = Int64Array::from(vec![Some(21), None, None, Some(24)]);
+        assert_eq!(as_primitive_array::<Int64Type>(&c), &expected);
+
+        let c = multiply_dyn_checked(&b, &a).unwrap();
+        let expected = Int64Array::from(vec![Some(20), None, None, Some(80)]);
+        assert_eq!(as_primitive_array::<Int64Type>(&c), &expected);
+
+        let c = subtract_dyn(&a, &a).unwrap();
+        let expected = Int64Array::from(vec![Some(0), None, Some(0), Some(0)]);
+        assert_eq!(as_primitive_array::<Int64Type>(&c), &expected);
+    }
+
+    #[test]
+    fn test_arithmetic_dyn_temporal() {
+        let a = DurationSecondArray::from(vec![Some(1), None, Some(i64::MAX)]);
+        let b = DurationSecondArray::from(vec![Some(2), Some(3), Some(1)]);
+        let c = add_dyn(&a, &b).unwrap();
+        assert_eq!(c.data_type(), a.data_type());
+        let expected = DurationSecondArray::from(vec![Some(3), None, Some(i64::MIN)]);
+        assert_eq!(as_primitive_array::<DurationSecondType>(&c), &expected);
+        assert!(add_dyn_checked(&a, &b).is_err());
+
+        let a = IntervalYearMonthArray::from(vec![1, 2]);
+        let c = subtract_dyn(&a, &a).unwrap();
+        let expected = IntervalYearMonthArray::from(vec![0, 0]);
+        assert_eq!(as_primitive_array::<IntervalYearMonthType>(&c), &expected);
+
+        let err = multiply_dyn(&b, &b).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Arithmetic operation Multiply not supported for data type Duration(Second)"
+        );
+    }
+
+    fn decimal_array(
+        values: Vec<Option<i128>>,
+        precision: usize,
+        scale: usize,
+    ) -> DecimalArray {
+        values
+            .into_iter()
+            .collect::<DecimalArray>()
+            .with_precision_and_scale(precision, scale)
+            .unwrap()
+    }
+
+    fn decimal_strings(array: &DecimalArray) -> Vec<Option<String>> {
+        (0..array.len())
+            .map(|i| array.is_valid(i).then(|| array.value_as_string(i)))
+            .collect()
+    }
+
+    #[test]
+    fn test_decimal_add_subtract() {
+        let a = decimal_array(vec![Some(15), None, Some(-123), Some(99999)], 5, 1);
+        let b = decimal_array(vec![Some(25), Some(1), Some(3), Some(1)], 3, 2);
+
+        let c = add_decimal(&a, &b).unwrap();
+        assert_eq!(c.data_type(), &DataType::Decimal(7, 2));
+        assert_eq!(
+            decimal_strings(&c),
+            vec![
+                Some("1.75".to_string()),
+                None,
+                Some("-12.27".to_string()),
+                Some("9999.91".to_string())
+            ]
+        );
+
+        let c = subtract_decimal(&b, &a).unwrap();
+        assert_eq!(c.data_type(), &DataType::Decimal(7, 2));
+        assert_eq!(
+            decimal_strings(&c),
+            vec![
+                Some("-1.25".to_string()),
+                None,
+                Some("12.33".to_string()),
+                Some("-9999.89".to_string())
+            ]
+        );
+
+        let scalar = Decimal128::new_from_i128(2, 0, 10);
+        let c = add_decimal_scalar(&a, &scalar).unwrap();
+        assert_eq!(c.data_type(), &DataType::Decimal(6, 1));
+        assert_eq!(c.value_as_string(0), "11.5");
+        let c = subtract_decimal_scalar(&a, &scalar).unwrap();
+        assert_eq!(c.value_as_string(2), "-22.3");
+    }
+
+    #[test]
+    fn test_decimal_multiply() {
+        let a = decimal_array(vec![Some(15), Some(-15), None], 5, 1);
+        let b = decimal_array(vec![Some(25), Some(25), Some(25)], 3, 2);
+        let c = multiply_decimal(&a, &b).unwrap();
+        assert_eq!(c.data_type(), &DataType::Decimal(9, 3));
+        assert_eq!(
+            decimal_strings(&c),
+            vec![Some("0.375".to_string()), Some("-0.375".to_string()), None]
+        );
+
+        // Precision is capped, reducing the scale
+        let a = decimal_array(vec![Some(15_000_000_000), Some(-1)], 38, 10);
+        let b = decimal_array(vec![Some(25_000_000_000), Some(5_000_000_000)], 38, 10);
+        let c = multiply_decimal(&a, &b).unwrap();
+        assert_eq!(c.data_type(), &DataType::Decimal(38, 6));
+        // -0.0000000001 * 0.5 rounds half away from zero
+        assert_eq!(
+            decimal_strings(&c),
+            vec![Some("3.750000".to_string()), Some("0.000000".to_string())]
+        );
+
+        // The product of the values does not fit in an i128, but the result does
+        let v = 10_i128.pow(21) - 1;
+        let a = decimal_array(vec![Some(v), Some(-v)], 38, 10);
+        let b = decimal_array(vec![Some(v), Some(v)], 38, 10);
+        let c = multiply_decimal(&a, &b).unwrap();
+        assert_eq!(c.data_type(), &DataType::Decimal(38, 6));
+        assert_eq!(
+            decimal_strings(&c),
+            vec![
+                Some("9999999999999999999980.000000".to_string()),
+                Some("-9999999999999999999980.000000".to_string())
+            ]
+        );
+
+        let scalar = Decimal128::new_from_i128(3, 1, -20);
+        let a = decimal_array(vec![Some(15), None], 5, 1);
+        let c = multiply_decimal_scalar(&a, &scalar).unwrap();
+        assert_eq!(c.data_type(), &DataType::Decimal(9, 2));
+        assert_eq!(decimal_strings(&c), vec![Some("-3.00".to_string()), None]);
+    }
+
+    #[test]
+    fn test_decimal_divide() {
+        let a = decimal_array(vec![Some(100), Some(200), Some(-200), None], 5, 2);
+        let b = decimal_array(vec![Some(3), Some(3), Some(3), Some(0)], 2, 0);
+        let c = divide_decimal(&a, &b).unwrap();
+        assert_eq!(c.data_type(), &DataType::Decimal(9, 6));
+        assert_eq!(
+            decimal_strings(&c),
+            vec![
+                Some("0.333333".to_string()),
+                Some("0.666667".to_string()),
+                Some("-0.666667".to_string()),
+                None
+            ]
+        );
+
+        let err = divide_decimal(&b, &b).unwrap_err();
+        assert!(matches!(err, ArrowError::DivideByZero));
+
+        // The upscaled dividend does not fit in an i128, but the result does
+        let v = 10_i128.pow(35);
+        let x = decimal_array(vec![Some(v), Some(-2 * v), Some(1)], 38, 0);
+        let y = decimal_array(vec![Some(3000), Some(3000), Some(-3)], 38, 0);
+        let c = divide_decimal(&x, &y).unwrap();
+        assert_eq!(c.data_type(), &DataType::Decimal(38, 6));
+        assert_eq!(
+            decimal_strings(&c),
+            vec![
+                Some("33333333333333333333333333333333.333333".to_string()),
+                Some("-66666666666666666666666666666666.666667".to_string()),
+                Some("-0.333333".to_string())
+            ]
+        );
+        let x = decimal_array(vec![Some(10_i128.pow(33))], 38, 0);
+        let y = decimal_array(vec![Some(1)], 38, 0);
+        let err = divide_decimal(&x, &y).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Overflow happened on decimal Divide: result does not fit in Decimal(38, 6)"
+        );
+
+        let scalar = Decimal128::new_from_i128(3, 2, 50);
+        let c = divide_decimal_scalar(&a, &scalar).unwrap();
+        assert_eq!(c.data_type(), &DataType::Decimal(11, 6));
+        assert_eq!(c.value_as_string(0), "2.000000");
+
+        let zero = Decimal128::new_from_i128(3, 2, 0);
+        let err = divide_decimal_scalar(&a, &zero).unwrap_err();
+        assert!(matches!(err, ArrowError::DivideByZero));
+    }
+
+    #[test]
+    fn test_decimal_modulus() {
+        let a = decimal_array(vec![Some(750), Some(-750), None, Some(12345)], 5, 2);
+        let b = decimal_array(vec![Some(20), Some(20), Some(10), Some(7)], 3, 1);
+        let c = modulus_decimal(&a, &b).unwrap();
+        assert_eq!(c.data_type(), &DataType::Decimal(4, 2));
+        assert_eq!(
+            decimal_strings(&c),
+            vec![
+                Some("1.50".to_string()),
+                Some("-1.50".to_string()),
+                None,
+                Some("0.25".to_string())
+            ]
+        );
+
+        let zero = decimal_array(vec![Some(20), Some(0), Some(10), Some(7)], 3, 1);
+        let err = modulus_decimal(&a, &zero).unwrap_err();
+        assert!(matches!(err, ArrowError::DivideByZero));
+
+        let scalar = Decimal128::new_from_i128(3, 1, 25);
+        let c = modulus_decimal_scalar(&a, &scalar).unwrap();
+        assert_eq!(c.data_type(), &DataType::Decimal(4, 2));
+        assert_eq!(
+            decimal_strings(&c),
+            vec![
+                Some("0.00".to_string()),
+                Some("0.00".to_string()),
+                None,
+                Some("0.95".to_string())
+            ]
+        );
+
+        let zero = Decimal128::new_from_i128(3, 1, 0);
+        let err = modulus_decimal_scalar(&a, &zero).unwrap_err();
+        assert!(matches!(err, ArrowError::DivideByZero));
+    }
+
+    #[test]
+    fn test_decimal_overflow() {
+        let max = MAX_DECIMAL_FOR_EACH_PRECISION[37];
+        let a = decimal_array(vec![Some(max), None], 38, 0);
+        let b = decimal_array(vec![Some(1), Some(1)], 38, 0);
+        let err = add_decimal(&a, &b).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Overflow happened on decimal Add: result does not fit in Decimal(38, 0)"
+        );
+
+        // Overflow is only detected for values that don't fit the result precision
+        let c = subtract_decimal(&a, &b).unwrap();
+        assert_eq!(c.value(0).as_i128(), max - 1);
+
+        let err = multiply_decimal(&a, &a).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Overflow happened on decimal Multiply: result does not fit in Decimal(38, 0)"
+        );
+    }
+
+    #[test]
+    fn test_decimal_invalid_scalar() {
+        let a = decimal_array(vec![Some(15), None], 5, 1);
+
+        let scalar = Decimal128::new_from_i128(2, 5, 1);
+        let err = add_decimal_scalar(&a, &scalar).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Decimal scale 5 is greater than precision 2"
+        );
+
+        let scalar = Decimal128::new_from_i128(0, 0, 1);
+        let err = multiply_decimal_scalar(&a, &scalar).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Decimal precision 0 is not between 1 and 38"
+        );
+
+        let scalar = Decimal128::new_from_i128(39, 0, 1);
+        let err = divide_decimal_scalar(&a, &scalar).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Decimal precision 39 is not between 1 and 38"
+        );
+    }
+
+    #[test]
+    fn test_decimal_dyn() {
+        let a = decimal_array(vec![Some(15), None], 5, 1);
+        let b = decimal_array(vec![Some(25), Some(1)], 3, 2);
+        let c = add_dyn(&a, &b).unwrap();
+        assert_eq!(c.data_type(), &DataType::Decimal(7, 2));
+        assert_eq!(
+            decimal_strings(as_decimal_array(&c)),
+            vec![Some("1.75".to_string()), None]
+        );
+
+        let c = divide_dyn(&a, &b).unwrap();
+        assert_eq!(as_decimal_array(&c).value_as_string(0), "6.000000");
+
+        // 1.5 % 0.25
+        let c = modulus_dyn(&a, &b).unwrap();
+        assert_eq!(c.data_type(), &DataType::Decimal(3, 2));
+        assert_eq!(
+            decimal_strings(as_decimal_array(&c)),
+            vec![Some("0.00".to_string()), None]
+        );
+        let b = decimal_array(vec![Some(4), Some(0)], 3, 1);
+        let c = modulus_dyn(&a, &b).unwrap();
+        assert_eq!(as_decimal_array(&c).value_as_string(0), "0.3");
+        let err = modulus_dyn(&b, &b).unwrap_err();
+        assert!(matches!(err, ArrowError::DivideByZero));
+    }
+
+    fn timestamp(s: &str) -> i64 {
+        s.parse::<NaiveDateTime>().unwrap().timestamp()
+    }
+
+    #[test]
+    fn test_timestamp_interval_arithmetic() {
+        let a = TimestampSecondArray::from_opt_vec(
+            vec![
+                Some(timestamp("2021-01-31T10:00:00")),
+                None,
+                Some(timestamp("2020-02-29T00:00:00")),
+            ],
+            None,
+        );
+        let b = IntervalYearMonthArray::from(vec![Some(1), Some(1), Some(12)]);
+        let c = add_dyn(&a, &b).unwrap();
+        let expected = TimestampSecondArray::from_opt_vec(
+            vec![
+                Some(timestamp("2021-02-28T10:00:00")),
+                None,
+                Some(timestamp("2021-02-28T00:00:00")),
+            ],
+            None,
+        );
+        assert_eq!(as_primitive_array::<TimestampSecondType>(&c), &expected);
+
+        // Addition is commutative
+        let c = add_dyn(&b, &a).unwrap();
+        assert_eq!(as_primitive_array::<TimestampSecondType>(&c), &expected);
+
+        let b = IntervalMonthDayNanoArray::from(vec![
+            IntervalMonthDayNanoType::make_value(-1, 1, 1_500_000_000),
+            IntervalMonthDayNanoType::make_value(0, 0, 0),
+            IntervalMonthDayNanoType::make_value(0, -1, -1),
+        ]);
+        let c = subtract_dyn(&a, &b).unwrap();
+        let expected = TimestampSecondArray::from_opt_vec(
+            vec![
+                Some(timestamp("2021-02-27T09:59:58")),
+                None,
+                Some(timestamp("2020-03-01T00:00:00")),
+            ],
+            None,
+        );
+        assert_eq!(as_primitive_array::<TimestampSecondType>(&c), &expected);
+
+        let b = IntervalDayTimeArray::from(vec![
+            IntervalDayTimeType::make_value(1, -1),
+            IntervalDayTimeType::make_value(0, 0),
+            IntervalDayTimeType::make_value(-1, 1000),
+        ]);
+        let c = add_dyn(&a, &b).unwrap();
+        let expected = TimestampSecondArray::from_opt_vec(
+            vec![
+                Some(timestamp("2021-02-01T09:59:59")),
+                None,
+                Some(timestamp("2020-02-28T00:00:01")),
+            ],
+            None,
+        );
+        assert_eq!(as_primitive_array::<TimestampSecondType>(&c), &expected);
+    }
+
+    #[test]
+    fn test_timestamp_duration_arithmetic() {
+        let a = TimestampSecondArray::from_vec(vec![10, 10, 10], Some("+02:00".into()));
+        let b = DurationMillisecondArray::from(vec![Some(1500), Some(-1500), None]);
+        let c = add_dyn(&a, &b).unwrap();
+        assert_eq!(c.data_type(), a.data_type());
+        let expected =
+            TimestampSecondArray::from_opt_vec(vec![Some(11), Some(8), None], None);
+        assert_eq!(
+            as_primitive_array::<TimestampSecondType>(&c).values(),
+            expected.values()
+        );
+        assert_eq!(c.null_count(), 1);
+
+        let b = DurationSecondArray::from(vec![1, 2, 3]);
+        let c = subtract_dyn(&a, &b).unwrap();
+        assert_eq!(
+            as_primitive_array::<TimestampSecondType>(&c).values(),
+            &[9, 8, 7]
+        );
+
+        let c = add_dyn(&b, &a).unwrap();
+        assert_eq!(
+            as_primitive_array::<TimestampSecondType>(&c).values(),
+            &[11, 12, 13]
+        );
+
+        let err = subtract_dyn(&b, &a).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Cannot perform arithmetic operation Subtract on arrays of different types Duration(Second) and Timestamp(Second, Some(\"+02:00\"))"
+        );
+    }
+
+    #[test]
+    fn test_timestamp_subtract() {
+        let a = TimestampMillisecondArray::from_opt_vec(
+            vec![Some(1000), None, Some(i64::MIN)],
+            None,
+        );
+        let b = TimestampMillisecondArray::from_opt_vec(
+            vec![Some(3500), Some(1), Some(0)],
+            None,
+        );
+        let c = subtract_dyn(&a, &b).unwrap();
+        assert_eq!(c.data_type(), &DataType::Duration(TimeUnit::Millisecond));
+        let c = as_primitive_array::<DurationMillisecondType>(&c);
+        assert_eq!(c.value(0), -2500);
+        assert!(c.is_null(1));
+
+        let a = TimestampMillisecondArray::from_vec(vec![1000], None);
+        let b = TimestampMillisecondArray::from_vec(vec![3500], None);
+        let err =
+            subtract_dyn(&a, &TimestampSecondArray::from_vec(vec![1], None)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Cannot subtract timestamps of types Timestamp(Millisecond, None) and Timestamp(Second, None), cast them to a common type first"
+        );
+
+        let err = add_dyn(&a, &b).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Arithmetic operation Add not supported for data type Timestamp(Millisecond, None)"
+        );
+
+        let a = TimestampMillisecondArray::from_vec(vec![i64::MIN], None);
+        let err = subtract_dyn(&a, &b).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Overflow happened on: -9223372036854775808 - 3500"
+        );
+    }
+
+    #[test]
+    fn test_date_interval_arithmetic() {
+        let epoch = NaiveDate::from_ymd(1970, 1, 1);
+        let days = |s: &str| (s.parse::<NaiveDate>().unwrap() - epoch).num_days() as i32;
+
+        let a =
+            Date32Array::from(vec![Some(days("2020-01-31")), Some(days("2020-03-01"))]);
+        let b = IntervalMonthDayNanoArray::from(vec![
+            IntervalMonthDayNanoType::make_value(1, 1, 0),
+            IntervalMonthDayNanoType::make_value(0, 0, -1),
+        ]);
+        let c = add_dyn(&a, &b).unwrap();
+        // A partial day is truncated towards the earlier date
+        assert_eq!(
+            as_primitive_array::<Date32Type>(&c),
+            &Date32Array::from(vec![days("2020-03-01"), days("2020-02-29")])
+        );
+
+        let b = DurationSecondArray::from(vec![86_400 * 2 - 1, 86_400]);
+        let c = subtract_dyn(&a, &b).unwrap();
+        assert_eq!(
+            as_primitive_array::<Date32Type>(&c),
+            &Date32Array::from(vec![days("2020-01-29"), days("2020-02-29")])
+        );
+
+        let ms = |s: &str| timestamp(s) * 1000;
+        let a = Date64Array::from(vec![ms("2020-01-31T00:00:00")]);
+        let b = IntervalDayTimeArray::from(vec![IntervalDayTimeType::make_value(29, 1)]);
+        let c = add_dyn(&a, &b).unwrap();
+        assert_eq!(
+            as_primitive_array::<Date64Type>(&c),
+            &Date64Array::from(vec![ms("2020-02-29T00:00:00") + 1])
+        );
+
+        let err = add_dyn(&a, &Int64Array::from(vec![1])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Cannot perform arithmetic operation Add on arrays of different types Date64 and Int64"
+        );
+    }
+
+    #[test]
+    fn test_temporal_overflow() {
+        let a = TimestampNanosecondArray::from_vec(vec![i64::MAX - 1], None);
+        let b =
+            IntervalMonthDayNanoArray::from(vec![IntervalMonthDayNanoType::make_value(
+                0, 0, 2,
+            )]);
+        let err = add_dyn(&a, &b).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Overflow happened on temporal Add: result does not fit in Timestamp(Nanosecond, None)"
+        );
+
+        let a = Date32Array::from(vec![0]);
+        let b = IntervalYearMonthArray::from(vec![i32::MAX]);
+        let err = add_dyn(&a, &b).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Overflow happened on temporal Add: result does not fit in Date32"
+        );
+
+        let b = IntervalYearMonthArray::from(vec![i32::MIN]);
+        let err = subtract_dyn(&a, &b).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Overflow happened on temporal Subtract: result does not fit in Date32"
+        );
+
+        let b = DurationSecondArray::from(vec![i64::MAX]);
+        let err = add_dyn(&a, &b).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Overflow happened on temporal Add: result does not fit in Date32"
+        );
+    }
+
+    #[test]
+    fn test_timestamp_fixed_offset_interval() {
+        // 2021-03-01T01:00:00 local time
+        let a = TimestampSecondArray::from_vec(
+            vec![timestamp("2021-02-28T23:00:00")],
+            Some("+02:00".to_string()),
+        );
+        let b = IntervalYearMonthArray::from(vec![1]);
+        let c = add_dyn(&a, &b).unwrap();
+        assert_eq!(
+            as_primitive_array::<TimestampSecondType>(&c).value(0),
+            timestamp("2021-03-31T23:00:00")
+        );
+
+        let a = TimestampSecondArray::from_vec(vec![0], Some("Mars/Olympus".into()));
+        let err = add_dyn(&a, &b).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Unable to parse timezone \"Mars/Olympus\""
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "chrono-tz")]
+    fn test_timestamp_dst_interval() {
+        let tz = Some("Europe/London".to_string());
+        let a = TimestampSecondArray::from_vec(
+            vec![
+                timestamp("2021-03-27T12:00:00"),
+                // 01:30 local time, which is skipped on 2021-03-28
+                timestamp("2021-03-27T01:30:00"),
+                // 01:30 BST, which occurs twice on 2021-10-31
+                timestamp("2021-10-30T00:30:00"),
+            ],
+            tz,
+        );
+        let b =
+            IntervalDayTimeArray::from(vec![IntervalDayTimeType::make_value(1, 0); 3]);
+        let c = add_dyn(&a, &b).unwrap();
+        assert_eq!(
+            as_primitive_array::<TimestampSecondType>(&c).values(),
+            &[
+                timestamp("2021-03-28T11:00:00"),
+                timestamp("2021-03-28T01:30:00"),
+                timestamp("2021-10-31T00:30:00"),
+            ]
+        );
+
+        // Durations are exact
+        let b = DurationSecondArray::from(vec![86_400; 3]);
+        let c = add_dyn(&a, &b).unwrap();
+        assert_eq!(
+            as_primitive_array::<TimestampSecondType>(&c).values(),
+            &[
+                timestamp("2021-03-28T12:00:00"),
+                timestamp("2021-03-28T01:30:00"),
+                timestamp("2021-10-31T00:30:00"),
+            ]
+        );
+    }
 }
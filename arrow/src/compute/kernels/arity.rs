@@ -19,7 +19,9 @@
 
 use crate::array::{Array, ArrayData, PrimitiveArray};
 use crate::buffer::Buffer;
+use crate::compute::util::combine_option_bitmap;
 use crate::datatypes::ArrowPrimitiveType;
+use crate::error::{ArrowError, Result};
 
 #[inline]
 fn into_primitive_array_data<I: ArrowPrimitiveType, O: ArrowPrimitiveType>(
@@ -78,10 +80,106 @@ where
     PrimitiveArray::<O>::from(data)
 }
 
+/// Applies a unary and fallible function to all valid values in a primitive array
+///
+/// Unlike [`unary`], the function is only evaluated for non-null values, with the
+/// value of null slots in the output unspecified, and the first error encountered,
+/// if any, is returned.
+///
+/// # Example
+/// ```rust
+/// # use arrow::array::Int8Array;
+/// # use arrow::compute::kernels::arity::try_unary;
+/// # use arrow::datatypes::{ArrowNativeTypeOp, Int8Type};
+/// let array = Int8Array::from(vec![Some(5), Some(7), None]);
+/// let c = try_unary::<_, _, Int8Type>(&array, |x| x.mul_checked(2)).unwrap();
+/// assert_eq!(c, Int8Array::from(vec![Some(10), Some(14), None]));
+///
+/// let array = Int8Array::from(vec![Some(5), Some(100), None]);
+/// assert!(try_unary::<_, _, Int8Type>(&array, |x| x.mul_checked(2)).is_err());
+/// ```
+pub fn try_unary<I, F, O>(array: &PrimitiveArray<I>, op: F) -> Result<PrimitiveArray<O>>
+where
+    I: ArrowPrimitiveType,
+    O: ArrowPrimitiveType,
+    F: Fn(I::Native) -> Result<O::Native>,
+{
+    let values = array.values().iter().enumerate().map(|(idx, v)| {
+        match array.null_count() == 0 || array.is_valid(idx) {
+            true => op(*v),
+            false => Ok(O::Native::default()),
+        }
+    });
+    // Soundness
+    //      `values` is an iterator with a known size because arrays are sized.
+    let buffer = unsafe { Buffer::try_from_trusted_len_iter(values)? };
+
+    let data = into_primitive_array_data::<_, O>(array, buffer);
+    Ok(PrimitiveArray::<O>::from(data))
+}
+
+/// Applies a binary and infallible function to the values of two primitive arrays
+/// of the same length, the output value is null if either input value is null.
+///
+/// Like [`unary`], the function is applied to all values, including those on null
+/// slots, and must therefore be infallible for any value of the corresponding types.
+///
+/// # Errors
+///
+/// This function errors if the arrays have different lengths
+///
+/// # Example
+/// ```rust
+/// # use arrow::array::{Int32Array, Int64Array};
+/// # use arrow::compute::kernels::arity::binary;
+/// # use arrow::datatypes::Int64Type;
+/// let a = Int32Array::from(vec![Some(5), Some(7), None]);
+/// let b = Int64Array::from(vec![Some(1), None, Some(3)]);
+/// let c = binary::<_, _, _, Int64Type>(&a, &b, |a, b| a as i64 * b).unwrap();
+/// assert_eq!(c, Int64Array::from(vec![Some(5), None, None]));
+/// ```
+pub fn binary<A, B, F, O>(
+    a: &PrimitiveArray<A>,
+    b: &PrimitiveArray<B>,
+    op: F,
+) -> Result<PrimitiveArray<O>>
+where
+    A: ArrowPrimitiveType,
+    B: ArrowPrimitiveType,
+    O: ArrowPrimitiveType,
+    F: Fn(A::Native, B::Native) -> O::Native,
+{
+    if a.len() != b.len() {
+        return Err(ArrowError::ComputeError(
+            "Cannot perform binary operation on arrays of different length".to_string(),
+        ));
+    }
+
+    let null_buffer = combine_option_bitmap(&[a.data_ref(), b.data_ref()], a.len())?;
+    let values = a.values().iter().zip(b.values()).map(|(a, b)| op(*a, *b));
+    // Soundness
+    //      `values` is an iterator with a known size because arrays are sized.
+    let buffer = unsafe { Buffer::from_trusted_len_iter(values) };
+
+    let data = unsafe {
+        ArrayData::new_unchecked(
+            O::DATA_TYPE,
+            a.len(),
+            None,
+            null_buffer,
+            0,
+            vec![buffer],
+            vec![],
+        )
+    };
+    Ok(PrimitiveArray::<O>::from(data))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::array::{as_primitive_array, Float64Array};
+    use crate::array::{as_primitive_array, Float64Array, Int32Array};
+    use crate::datatypes::Float64Type;
 
     #[test]
     fn test_unary_f64_slice() {
@@ -95,4 +193,24 @@ mod tests {
             Float64Array::from(vec![None, Some(7.0), None, Some(7.0)])
         )
     }
+
+    #[test]
+    fn test_binary_slice() {
+        let a = Int32Array::from(vec![Some(1), Some(2), None, Some(4)]);
+        let a = a.slice(1, 3);
+        let a: &Int32Array = as_primitive_array(&a);
+        let b = Float64Array::from(vec![Some(0.5), Some(1.5), None]);
+        let result: Float64Array = binary(a, &b, |a, b| a as f64 + b).unwrap();
+        assert_eq!(result, Float64Array::from(vec![Some(2.5), None, None]));
+
+        let err =
+            binary::<_, _, _, Float64Type>(a, &Float64Array::from(vec![1.0]), |a, b| {
+                a as f64 + b
+            })
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Cannot perform binary operation on arrays of different length"
+        );
+    }
 }
@@ -0,0 +1,282 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines bitwise kernels for integer arrays, such as `bitwise_and` and `shift_left`.
+//!
+//! For boolean arrays see the kernels in [`boolean`](crate::compute::kernels::boolean).
+//!
+//! ```
+//! # use arrow::array::UInt8Array;
+//! # use arrow::compute::kernels::bitwise::{bitwise_and_scalar, shift_right_scalar};
+//! // decode the upper nibble of packed flags
+//! let flags = UInt8Array::from(vec![Some(0b1010_0001), None, Some(0b0110_1111)]);
+//! let upper = shift_right_scalar(&bitwise_and_scalar(&flags, 0xF0).unwrap(), 4).unwrap();
+//! assert_eq!(upper, UInt8Array::from(vec![Some(0b1010), None, Some(0b0110)]));
+//! ```
+
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+use num::traits::{WrappingShl, WrappingShr};
+use num::ToPrimitive;
+
+use crate::array::PrimitiveArray;
+use crate::compute::kernels::arity::{binary, unary};
+use crate::datatypes::ArrowNumericType;
+use crate::error::Result;
+
+/// Converts a shift amount to the `u32` expected by [`WrappingShl`] and
+/// [`WrappingShr`], which take it modulo the bit width of the shifted type
+#[inline]
+fn shift_amount<T: ToPrimitive>(shift: T) -> u32 {
+    // Integer types can always be represented as i128
+    shift.to_i128().unwrap() as u32
+}
+
+/// Perform `left & right` operation on two arrays. If either left or right value is null
+/// then the result is also null.
+///
+/// # Errors
+///
+/// This function errors if the arrays have different lengths
+pub fn bitwise_and<T>(
+    left: &PrimitiveArray<T>,
+    right: &PrimitiveArray<T>,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: BitAnd<Output = T::Native>,
+{
+    binary(left, right, |a, b| a & b)
+}
+
+/// Perform `left | right` operation on two arrays. If either left or right value is null
+/// then the result is also null.
+///
+/// # Errors
+///
+/// This function errors if the arrays have different lengths
+pub fn bitwise_or<T>(
+    left: &PrimitiveArray<T>,
+    right: &PrimitiveArray<T>,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: BitOr<Output = T::Native>,
+{
+    binary(left, right, |a, b| a | b)
+}
+
+/// Perform `left ^ right` operation on two arrays. If either left or right value is null
+/// then the result is also null.
+///
+/// # Errors
+///
+/// This function errors if the arrays have different lengths
+pub fn bitwise_xor<T>(
+    left: &PrimitiveArray<T>,
+    right: &PrimitiveArray<T>,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: BitXor<Output = T::Native>,
+{
+    binary(left, right, |a, b| a ^ b)
+}
+
+/// Perform `!array` operation on an array. If a value is null then the result is
+/// also null.
+pub fn bitwise_not<T>(array: &PrimitiveArray<T>) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: Not<Output = T::Native>,
+{
+    Ok(unary(array, |value| !value))
+}
+
+/// Perform `array & scalar` operation on every value of an array. If a value is null
+/// then the result is also null.
+pub fn bitwise_and_scalar<T>(
+    array: &PrimitiveArray<T>,
+    scalar: T::Native,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: BitAnd<Output = T::Native>,
+{
+    Ok(unary(array, |value| value & scalar))
+}
+
+/// Perform `array | scalar` operation on every value of an array. If a value is null
+/// then the result is also null.
+pub fn bitwise_or_scalar<T>(
+    array: &PrimitiveArray<T>,
+    scalar: T::Native,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: BitOr<Output = T::Native>,
+{
+    Ok(unary(array, |value| value | scalar))
+}
+
+/// Perform `array ^ scalar` operation on every value of an array. If a value is null
+/// then the result is also null.
+pub fn bitwise_xor_scalar<T>(
+    array: &PrimitiveArray<T>,
+    scalar: T::Native,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: BitXor<Output = T::Native>,
+{
+    Ok(unary(array, |value| value ^ scalar))
+}
+
+/// Perform `left << right` operation on two arrays. If either left or right value is
+/// null then the result is also null.
+///
+/// The shift amount is taken modulo the bit width of the type, as in
+/// [`i32::wrapping_shl`], so shifting an `Int32Array` by 33 shifts it by 1.
+///
+/// # Errors
+///
+/// This function errors if the arrays have different lengths
+pub fn shift_left<T>(
+    left: &PrimitiveArray<T>,
+    right: &PrimitiveArray<T>,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: WrappingShl + ToPrimitive,
+{
+    binary(left, right, |a, b| a.wrapping_shl(shift_amount(b)))
+}
+
+/// Perform `left >> right` operation on two arrays. If either left or right value is
+/// null then the result is also null.
+///
+/// Signed values are shifted arithmetically, preserving their sign. The shift amount
+/// is taken modulo the bit width of the type, as in [`i32::wrapping_shr`].
+///
+/// # Errors
+///
+/// This function errors if the arrays have different lengths
+pub fn shift_right<T>(
+    left: &PrimitiveArray<T>,
+    right: &PrimitiveArray<T>,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: WrappingShr + ToPrimitive,
+{
+    binary(left, right, |a, b| a.wrapping_shr(shift_amount(b)))
+}
+
+/// Perform `array << scalar` operation on every value of an array. If a value is null
+/// then the result is also null.
+///
+/// The shift amount is taken modulo the bit width of the type, see [`shift_left`].
+pub fn shift_left_scalar<T>(
+    array: &PrimitiveArray<T>,
+    scalar: T::Native,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: WrappingShl + ToPrimitive,
+{
+    let shift = shift_amount(scalar);
+    Ok(unary(array, |value| value.wrapping_shl(shift)))
+}
+
+/// Perform `array >> scalar` operation on every value of an array. If a value is null
+/// then the result is also null.
+///
+/// The shift amount is taken modulo the bit width of the type, see [`shift_right`].
+pub fn shift_right_scalar<T>(
+    array: &PrimitiveArray<T>,
+    scalar: T::Native,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: WrappingShr + ToPrimitive,
+{
+    let shift = shift_amount(scalar);
+    Ok(unary(array, |value| value.wrapping_shr(shift)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::*;
+    use crate::datatypes::UInt16Type;
+
+    #[test]
+    fn test_bitwise_and_or_xor() {
+        let left = Int32Array::from(vec![Some(0b1100), None, Some(-1), Some(5)]);
+        let right = Int32Array::from(vec![Some(0b1010), Some(1), Some(7), None]);
+
+        let expected = Int32Array::from(vec![Some(0b1000), None, Some(7), None]);
+        assert_eq!(bitwise_and(&left, &right).unwrap(), expected);
+        let expected = Int32Array::from(vec![Some(0b1110), None, Some(-1), None]);
+        assert_eq!(bitwise_or(&left, &right).unwrap(), expected);
+        let expected = Int32Array::from(vec![Some(0b0110), None, Some(-8), None]);
+        assert_eq!(bitwise_xor(&left, &right).unwrap(), expected);
+
+        let right = Int32Array::from(vec![1]);
+        assert!(bitwise_and(&left, &right).is_err());
+    }
+
+    #[test]
+    fn test_bitwise_scalar() {
+        let array = UInt16Array::from(vec![Some(0xFF00), None, Some(0x0F0F)]);
+        let array = array.slice(1, 2);
+        let array = as_primitive_array::<UInt16Type>(&array);
+
+        let expected = UInt16Array::from(vec![None, Some(0x0F00)]);
+        assert_eq!(bitwise_and_scalar(array, 0xFF00).unwrap(), expected);
+        let expected = UInt16Array::from(vec![None, Some(0xFF0F)]);
+        assert_eq!(bitwise_or_scalar(array, 0xFF00).unwrap(), expected);
+        let expected = UInt16Array::from(vec![None, Some(0xF00F)]);
+        assert_eq!(bitwise_xor_scalar(array, 0xFF00).unwrap(), expected);
+        let expected = UInt16Array::from(vec![None, Some(0xF0F0)]);
+        assert_eq!(bitwise_not(array).unwrap(), expected);
+
+        let array = Int8Array::from(vec![Some(0), Some(-128), None]);
+        let expected = Int8Array::from(vec![Some(-1), Some(127), None]);
+        assert_eq!(bitwise_not(&array).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_shift() {
+        let left = Int64Array::from(vec![Some(1), Some(-16), Some(3), None, Some(1)]);
+        let right = Int64Array::from(vec![Some(4), Some(2), Some(65), Some(1), Some(-1)]);
+
+        let expected =
+            Int64Array::from(vec![Some(16), Some(-64), Some(6), None, Some(i64::MIN)]);
+        assert_eq!(shift_left(&left, &right).unwrap(), expected);
+        let expected = Int64Array::from(vec![Some(0), Some(-4), Some(1), None, Some(0)]);
+        assert_eq!(shift_right(&left, &right).unwrap(), expected);
+
+        let array = UInt8Array::from(vec![Some(0b1000_0001), None]);
+        let expected = UInt8Array::from(vec![Some(0b0000_0010), None]);
+        assert_eq!(shift_left_scalar(&array, 1).unwrap(), expected);
+        let expected = UInt8Array::from(vec![Some(0b0100_0000), None]);
+        assert_eq!(shift_right_scalar(&array, 1).unwrap(), expected);
+        // the shift amount wraps around the bit width
+        assert_eq!(shift_right_scalar(&array, 9).unwrap(), expected);
+    }
+}
@@ -35,6 +35,7 @@
 //! assert_eq!(7.0, c.value(2));
 //! ```
 
+use std::cmp::Ordering;
 use std::str;
 use std::sync::Arc;
 
@@ -42,8 +43,11 @@ use crate::buffer::MutableBuffer;
 use crate::compute::kernels::arithmetic::{divide, multiply};
 use crate::compute::kernels::arity::unary;
 use crate::compute::kernels::cast_utils::string_to_timestamp_nanos;
+use crate::compute::kernels::dictionary::dictionary_encode;
 use crate::datatypes::*;
 use crate::error::{ArrowError, Result};
+use crate::util::decimal::{decimal_rescale, Decimal128};
+use crate::util::display::array_value_to_string;
 use crate::{array::*, compute::take};
 use crate::{buffer::Buffer, util::serialization::lexical_to_string};
 use num::{NumCast, ToPrimitive};
@@ -68,13 +72,13 @@ pub fn can_cast_types(from_type: &DataType, to_type: &DataType) -> bool {
     }
 
     match (from_type, to_type) {
-        // TODO UTF8/unsigned numeric to decimal
+        // TODO unsigned numeric to decimal
         // cast one decimal type to another decimal type
         (Decimal(_, _), Decimal(_, _)) => true,
-        // signed numeric to decimal
-        (Int8 | Int16 | Int32 | Int64 | Float32 | Float64, Decimal(_, _)) |
-        // decimal to signed numeric
-        (Decimal(_, _), Int8 | Int16 | Int32 | Int64 | Float32 | Float64)
+        // signed numeric and strings to decimal
+        (Int8 | Int16 | Int32 | Int64 | Float32 | Float64 | Utf8 | LargeUtf8, Decimal(_, _)) |
+        // decimal to signed numeric and strings
+        (Decimal(_, _), Int8 | Int16 | Int32 | Int64 | Float32 | Float64 | Utf8 | LargeUtf8)
         | (
             Null,
             Boolean
@@ -141,16 +145,35 @@ pub fn can_cast_types(from_type: &DataType, to_type: &DataType) -> bool {
         ) => true,
         (Decimal(_, _), _) => false,
         (_, Decimal(_, _)) => false,
+        // struct fields are matched by name, missing fields must be nullable
+        (Struct(from_fields), Struct(to_fields)) => to_fields.iter().all(|to_field| {
+            match from_fields.iter().find(|f| f.name() == to_field.name()) {
+                Some(from_field) => {
+                    can_cast_types(from_field.data_type(), to_field.data_type())
+                }
+                None => to_field.is_nullable(),
+            }
+        }),
+        // nested types are formatted as JSON-like text, if their children can be
+        (
+            List(field) | LargeList(field) | FixedSizeList(field, _) | Map(field, _),
+            Utf8 | LargeUtf8,
+        ) => can_cast_types(field.data_type(), &Utf8),
+        (Struct(fields) | Union(fields, _, _), Utf8 | LargeUtf8) => fields
+            .iter()
+            .all(|field| can_cast_types(field.data_type(), &Utf8)),
         (Struct(_), _) => false,
         (_, Struct(_)) => false,
-        (LargeList(list_from), LargeList(list_to)) => {
-            can_cast_types(list_from.data_type(), list_to.data_type())
-        }
-        (List(list_from), List(list_to)) => {
-            can_cast_types(list_from.data_type(), list_to.data_type())
+        (
+            List(list_from) | LargeList(list_from),
+            List(list_to) | LargeList(list_to),
+        ) => can_cast_types(list_from.data_type(), list_to.data_type()),
+        (FixedSizeList(list_from, size_from), FixedSizeList(list_to, size_to)) => {
+            size_from == size_to
+                && can_cast_types(list_from.data_type(), list_to.data_type())
         }
-        (List(list_from), LargeList(list_to)) => {
-            list_from.data_type() == list_to.data_type()
+        (Map(entries_from, _), Map(entries_to, _)) => {
+            can_cast_types(entries_from.data_type(), entries_to.data_type())
         }
         (List(_), _) => false,
         (_, List(list_to)) => can_cast_types(from_type, list_to.data_type()),
@@ -173,7 +196,20 @@ pub fn can_cast_types(from_type: &DataType, to_type: &DataType) -> bool {
         (Timestamp(_, _), Utf8) | (Timestamp(_, _), LargeUtf8) => true,
         (Date32, Utf8) | (Date32, LargeUtf8) => true,
         (Date64, Utf8) | (Date64, LargeUtf8) => true,
-        (_, Utf8 | LargeUtf8) => DataType::is_numeric(from_type) || from_type == &Binary,
+        (_, Utf8 | LargeUtf8) => {
+            DataType::is_numeric(from_type)
+                || matches!(
+                    from_type,
+                    Float16
+                        | Binary
+                        | LargeBinary
+                        | FixedSizeBinary(_)
+                        | Time32(_)
+                        | Time64(_)
+                        | Duration(_)
+                        | Interval(_)
+                )
+        }
 
         // start numeric casts
         (
@@ -285,7 +321,14 @@ pub fn can_cast_types(from_type: &DataType, to_type: &DataType) -> bool {
 /// * Utf8 to numeric: strings that can't be parsed to numbers return null, float strings
 ///   in integer casts return null
 /// * Numeric to boolean: 0 returns `false`, any other value returns `true`
-/// * List to List: the underlying data type is cast
+/// * List to List: the underlying data type is cast, this also applies to `LargeList`,
+///   `FixedSizeList` of the same size and `Map`
+/// * Struct to Struct: fields are matched by name and cast, fields missing from the
+///   target are dropped and nullable target fields missing from the input are null
+/// * Nested types to Utf8: values are formatted as JSON-like text such as
+///   `{"a": [1, null], "b": "x"}`
+/// * Binary to Utf8: values are decoded as UTF-8, `FixedSizeBinary` and binary values
+///   nested in other types are formatted as hex
 /// * Primitive to List: a list array with 1 value per slot is created
 /// * Date32 and Date64: precision lost when going to higher interval
 /// * Time32 and Time64: precision lost when going to higher interval
@@ -293,7 +336,7 @@ pub fn can_cast_types(from_type: &DataType, to_type: &DataType) -> bool {
 /// * Temporal to/from backing primitive: zero-copy with data type change
 ///
 /// Unsupported Casts
-/// * To or from `StructArray`
+/// * Between `StructArray` and other types
 /// * List to primitive
 /// * Interval and duration
 pub fn cast(array: &ArrayRef, to_type: &DataType) -> Result<ArrayRef> {
@@ -302,48 +345,49 @@ pub fn cast(array: &ArrayRef, to_type: &DataType) -> Result<ArrayRef> {
 
 // cast the integer array to defined decimal data type array
 macro_rules! cast_integer_to_decimal {
-    ($ARRAY: expr, $ARRAY_TYPE: ident, $PRECISION : ident, $SCALE : ident) => {{
+    ($ARRAY: expr, $ARRAY_TYPE: ident, $PRECISION : ident, $SCALE : ident, $CAST_OPTIONS : ident) => {{
         let array = $ARRAY.as_any().downcast_ref::<$ARRAY_TYPE>().unwrap();
         let mul: i128 = 10_i128.pow(*$SCALE as u32);
-        let decimal_array = array
-            .iter()
-            .map(|v| {
-                v.map(|v| {
-                    let v = v as i128;
-                    // with_precision_and_scale validates the
-                    // value is within range for the output precision
-                    mul * v
+        let values = array.iter().map(|v| {
+            v.map(|v| {
+                (v as i128).checked_mul(mul).ok_or_else(|| {
+                    ArrowError::CastError(format!(
+                        "value of {} is out of range Decimal({}, {})",
+                        v, $PRECISION, $SCALE
+                    ))
                 })
             })
-            .collect::<DecimalArray>()
-            .with_precision_and_scale(*$PRECISION, *$SCALE)?;
-        Ok(Arc::new(decimal_array))
+        });
+        build_decimal_array(values, *$PRECISION, *$SCALE, $CAST_OPTIONS)
     }};
 }
 
 // cast the floating-point array to defined decimal data type array
 macro_rules! cast_floating_point_to_decimal {
-    ($ARRAY: expr, $ARRAY_TYPE: ident, $PRECISION : ident, $SCALE : ident) => {{
+    ($ARRAY: expr, $ARRAY_TYPE: ident, $PRECISION : ident, $SCALE : ident, $CAST_OPTIONS : ident) => {{
         let array = $ARRAY.as_any().downcast_ref::<$ARRAY_TYPE>().unwrap();
         let mul = 10_f64.powi(*$SCALE as i32);
-        let decimal_array = array
-            .iter()
-            .map(|v| {
-                v.map(|v| {
-                    // with_precision_and_scale validates the
-                    // value is within range for the output precision
-                    ((v as f64) * mul) as i128
-                })
+        let values = array.iter().map(|v| {
+            v.map(|v| {
+                // round half away from zero, NaN and infinite values are out of range
+                let scaled = ((v as f64) * mul).round();
+                if scaled >= i128::MIN as f64 && scaled < i128::MAX as f64 {
+                    Ok(scaled as i128)
+                } else {
+                    Err(ArrowError::CastError(format!(
+                        "value of {} is out of range Decimal({}, {})",
+                        v, $PRECISION, $SCALE
+                    )))
+                }
             })
-            .collect::<DecimalArray>()
-            .with_precision_and_scale(*$PRECISION, *$SCALE)?;
-        Ok(Arc::new(decimal_array))
+        });
+        build_decimal_array(values, *$PRECISION, *$SCALE, $CAST_OPTIONS)
     }};
 }
 
 // cast the decimal array to integer array
 macro_rules! cast_decimal_to_integer {
-    ($ARRAY:expr, $SCALE : ident, $VALUE_BUILDER: ident, $NATIVE_TYPE : ident, $DATA_TYPE : expr) => {{
+    ($ARRAY:expr, $SCALE : ident, $VALUE_BUILDER: ident, $NATIVE_TYPE : ident, $DATA_TYPE : expr, $CAST_OPTIONS : ident) => {{
         let array = $ARRAY.as_any().downcast_ref::<DecimalArray>().unwrap();
         let mut value_builder = $VALUE_BUILDER::new(array.len());
         let div: i128 = 10_i128.pow(*$SCALE as u32);
@@ -359,6 +403,8 @@ macro_rules! cast_decimal_to_integer {
                 // 128 is out of range i8
                 if v <= max_bound && v >= min_bound {
                     value_builder.append_value(v as $NATIVE_TYPE)?;
+                } else if $CAST_OPTIONS.safe {
+                    value_builder.append_null()?;
                 } else {
                     return Err(ArrowError::CastError(format!(
                         "value of {} is out of range {}",
@@ -402,15 +448,28 @@ macro_rules! cast_decimal_to_float {
 /// * Utf8 to numeric: strings that can't be parsed to numbers return null, float strings
 ///   in integer casts return null
 /// * Numeric to boolean: 0 returns `false`, any other value returns `true`
-/// * List to List: the underlying data type is cast
+/// * List to List: the underlying data type is cast, this also applies to `LargeList`,
+///   `FixedSizeList` of the same size and `Map`
+/// * Struct to Struct: fields are matched by name and cast, fields missing from the
+///   target are dropped and nullable target fields missing from the input are null
+/// * Nested types to Utf8: values are formatted as JSON-like text such as
+///   `{"a": [1, null], "b": "x"}`
+/// * Binary to Utf8: values are decoded as UTF-8, `FixedSizeBinary` and binary values
+///   nested in other types are formatted as hex
 /// * Primitive to List: a list array with 1 value per slot is created
 /// * Date32 and Date64: precision lost when going to higher interval
 /// * Time32 and Time64: precision lost when going to higher interval
 /// * Timestamp and Date{32|64}: precision lost when going to higher interval
 /// * Temporal to/from backing primitive: zero-copy with data type change
+/// * Decimal to Decimal: the scale is adjusted, rounding half away from zero
+/// * Float to Decimal: values are rounded half away from zero
+/// * Utf8 to Decimal: strings such as `-12.345` or `1.5e3` are parsed, extra
+///   fractional digits are rounded half away from zero, other strings return null or error
+/// * Numeric, Utf8 or Decimal to Decimal: values that don't fit the target precision
+///   return null or error
 ///
 /// Unsupported Casts
-/// * To or from `StructArray`
+/// * Between `StructArray` and other types
 /// * List to primitive
 pub fn cast_with_options(
     array: &ArrayRef,
@@ -425,21 +484,51 @@ pub fn cast_with_options(
         return Ok(array.clone());
     }
     match (from_type, to_type) {
-        (Decimal(_, s1), Decimal(p2, s2)) => cast_decimal_to_decimal(array, s1, p2, s2),
+        (Decimal(_, s1), Decimal(p2, s2)) => {
+            cast_decimal_to_decimal(array, s1, p2, s2, cast_options)
+        }
         (Decimal(_, scale), _) => {
             // cast decimal to other type
             match to_type {
                 Int8 => {
-                    cast_decimal_to_integer!(array, scale, Int8Builder, i8, Int8)
+                    cast_decimal_to_integer!(
+                        array,
+                        scale,
+                        Int8Builder,
+                        i8,
+                        Int8,
+                        cast_options
+                    )
                 }
                 Int16 => {
-                    cast_decimal_to_integer!(array, scale, Int16Builder, i16, Int16)
+                    cast_decimal_to_integer!(
+                        array,
+                        scale,
+                        Int16Builder,
+                        i16,
+                        Int16,
+                        cast_options
+                    )
                 }
                 Int32 => {
-                    cast_decimal_to_integer!(array, scale, Int32Builder, i32, Int32)
+                    cast_decimal_to_integer!(
+                        array,
+                        scale,
+                        Int32Builder,
+                        i32,
+                        Int32,
+                        cast_options
+                    )
                 }
                 Int64 => {
-                    cast_decimal_to_integer!(array, scale, Int64Builder, i64, Int64)
+                    cast_decimal_to_integer!(
+                        array,
+                        scale,
+                        Int64Builder,
+                        i64,
+                        Int64,
+                        cast_options
+                    )
                 }
                 Float32 => {
                     cast_decimal_to_float!(array, scale, Float32Builder, f32)
@@ -447,6 +536,8 @@ pub fn cast_with_options(
                 Float64 => {
                     cast_decimal_to_float!(array, scale, Float64Builder, f64)
                 }
+                Utf8 => cast_decimal_to_string::<i32>(array),
+                LargeUtf8 => cast_decimal_to_string::<i64>(array),
                 _ => Err(ArrowError::CastError(format!(
                     "Casting from {:?} to {:?} not supported",
                     from_type, to_type
@@ -456,24 +547,66 @@ pub fn cast_with_options(
         (_, Decimal(precision, scale)) => {
             // cast data to decimal
             match from_type {
-                // TODO now just support signed numeric to decimal, support decimal to numeric later
+                // TODO now just support signed numeric and strings to decimal
                 Int8 => {
-                    cast_integer_to_decimal!(array, Int8Array, precision, scale)
+                    cast_integer_to_decimal!(
+                        array,
+                        Int8Array,
+                        precision,
+                        scale,
+                        cast_options
+                    )
                 }
                 Int16 => {
-                    cast_integer_to_decimal!(array, Int16Array, precision, scale)
+                    cast_integer_to_decimal!(
+                        array,
+                        Int16Array,
+                        precision,
+                        scale,
+                        cast_options
+                    )
                 }
                 Int32 => {
-                    cast_integer_to_decimal!(array, Int32Array, precision, scale)
+                    cast_integer_to_decimal!(
+                        array,
+                        Int32Array,
+                        precision,
+                        scale,
+                        cast_options
+                    )
                 }
                 Int64 => {
-                    cast_integer_to_decimal!(array, Int64Array, precision, scale)
+                    cast_integer_to_decimal!(
+                        array,
+                        Int64Array,
+                        precision,
+                        scale,
+                        cast_options
+                    )
                 }
                 Float32 => {
-                    cast_floating_point_to_decimal!(array, Float32Array, precision, scale)
+                    cast_floating_point_to_decimal!(
+                        array,
+                        Float32Array,
+                        precision,
+                        scale,
+                        cast_options
+                    )
                 }
                 Float64 => {
-                    cast_floating_point_to_decimal!(array, Float64Array, precision, scale)
+                    cast_floating_point_to_decimal!(
+                        array,
+                        Float64Array,
+                        precision,
+                        scale,
+                        cast_options
+                    )
+                }
+                Utf8 => {
+                    cast_string_to_decimal::<i32>(array, precision, scale, cast_options)
+                }
+                LargeUtf8 => {
+                    cast_string_to_decimal::<i64>(array, precision, scale, cast_options)
                 }
                 _ => Err(ArrowError::CastError(format!(
                     "Casting from {:?} to {:?} not supported",
@@ -545,6 +678,27 @@ pub fn cast_with_options(
             | Dictionary(_, _),
             Null,
         ) => Ok(new_null_array(to_type, array.len())),
+        (Struct(_), Struct(to_fields)) => {
+            cast_struct(array, to_fields, to_type, cast_options)
+        }
+        (
+            List(_)
+            | LargeList(_)
+            | FixedSizeList(_, _)
+            | Struct(_)
+            | Map(_, _)
+            | Union(_, _, _),
+            Utf8,
+        ) => cast_values_to_string::<i32>(array),
+        (
+            List(_)
+            | LargeList(_)
+            | FixedSizeList(_, _)
+            | Struct(_)
+            | Map(_, _)
+            | Union(_, _, _),
+            LargeUtf8,
+        ) => cast_values_to_string::<i64>(array),
         (Struct(_), _) => Err(ArrowError::CastError(
             "Cannot cast from struct to other types".to_string(),
         )),
@@ -558,22 +712,32 @@ pub fn cast_with_options(
             cast_list_inner::<i64>(array, to, to_type, cast_options)
         }
         (List(list_from), LargeList(list_to)) => {
+            let array = cast_list_container::<i32, i64>(&**array, cast_options)?;
             if list_to.data_type() != list_from.data_type() {
-                Err(ArrowError::CastError(
-                    "cannot cast list to large-list with different child data".into(),
-                ))
+                cast_list_inner::<i64>(&array, list_to, to_type, cast_options)
             } else {
-                cast_list_container::<i32, i64>(&**array, cast_options)
+                Ok(array)
             }
         }
         (LargeList(list_from), List(list_to)) => {
+            let array = cast_list_container::<i64, i32>(&**array, cast_options)?;
             if list_to.data_type() != list_from.data_type() {
-                Err(ArrowError::CastError(
-                    "cannot cast large-list to list with different child data".into(),
-                ))
+                cast_list_inner::<i32>(&array, list_to, to_type, cast_options)
             } else {
-                cast_list_container::<i64, i32>(&**array, cast_options)
+                Ok(array)
+            }
+        }
+        (FixedSizeList(_, size_from), FixedSizeList(list_to, size_to)) => {
+            if size_from != size_to {
+                return Err(ArrowError::CastError(format!(
+                    "Cannot cast fixed-size list of size {} to size {}",
+                    size_from, size_to
+                )));
             }
+            cast_nested_children(array, list_to, to_type, cast_options)
+        }
+        (Map(_, _), Map(entries_to, _)) => {
+            cast_nested_children(array, entries_to, to_type, cast_options)
         }
         (List(_), _) => Err(ArrowError::CastError(
             "Cannot cast list to non-list data types".to_string(),
@@ -726,30 +890,10 @@ pub fn cast_with_options(
             },
             Date32 => cast_date32_to_string::<i32>(array),
             Date64 => cast_date64_to_string::<i32>(array),
-            Binary => {
-                let array = array.as_any().downcast_ref::<BinaryArray>().unwrap();
-                Ok(Arc::new(
-                    array
-                        .iter()
-                        .map(|maybe_value| match maybe_value {
-                            Some(value) => {
-                                let result = str::from_utf8(value);
-                                if cast_options.safe {
-                                    Ok(result.ok())
-                                } else {
-                                    Some(result.map_err(|_| {
-                                        ArrowError::CastError(
-                                            "Cannot cast binary to string".to_string(),
-                                        )
-                                    }))
-                                    .transpose()
-                                }
-                            }
-                            None => Ok(None),
-                        })
-                        .collect::<Result<StringArray>>()?,
-                ))
-            }
+            Binary => cast_binary_to_string::<i32, i32>(array, cast_options),
+            LargeBinary => cast_binary_to_string::<i64, i32>(array, cast_options),
+            Float16 | Time32(_) | Time64(_) | Duration(_) | Interval(_)
+            | FixedSizeBinary(_) => cast_values_to_string::<i32>(array),
             _ => Err(ArrowError::CastError(format!(
                 "Casting from {:?} to {:?} not supported",
                 from_type, to_type,
@@ -782,30 +926,10 @@ pub fn cast_with_options(
             },
             Date32 => cast_date32_to_string::<i64>(array),
             Date64 => cast_date64_to_string::<i64>(array),
-            Binary => {
-                let array = array.as_any().downcast_ref::<BinaryArray>().unwrap();
-                Ok(Arc::new(
-                    array
-                        .iter()
-                        .map(|maybe_value| match maybe_value {
-                            Some(value) => {
-                                let result = str::from_utf8(value);
-                                if cast_options.safe {
-                                    Ok(result.ok())
-                                } else {
-                                    Some(result.map_err(|_| {
-                                        ArrowError::CastError(
-                                            "Cannot cast binary to string".to_string(),
-                                        )
-                                    }))
-                                    .transpose()
-                                }
-                            }
-                            None => Ok(None),
-                        })
-                        .collect::<Result<LargeStringArray>>()?,
-                ))
-            }
+            Binary => cast_binary_to_string::<i32, i64>(array, cast_options),
+            LargeBinary => cast_binary_to_string::<i64, i64>(array, cast_options),
+            Float16 | Time32(_) | Time64(_) | Duration(_) | Interval(_)
+            | FixedSizeBinary(_) => cast_values_to_string::<i64>(array),
             _ => Err(ArrowError::CastError(format!(
                 "Casting from {:?} to {:?} not supported",
                 from_type, to_type,
@@ -1256,29 +1380,137 @@ fn cast_decimal_to_decimal(
     input_scale: &usize,
     output_precision: &usize,
     output_scale: &usize,
+    cast_options: &CastOptions,
 ) -> Result<ArrayRef> {
     let array = array.as_any().downcast_ref::<DecimalArray>().unwrap();
+    // For example, input_scale is 4 and output_scale is 3;
+    // Original value is 11235_i128, and will be cast to 1124_i128.
+    // If input_scale is 3 and output_scale is 4;
+    // Original value is 1123_i128, and will be cast to 11230_i128.
+    let values = array.iter().map(|v| {
+        v.map(|v| {
+            decimal_rescale(v, *input_scale, *output_scale).ok_or_else(|| {
+                ArrowError::CastError(format!(
+                    "value of {} is out of range Decimal({}, {})",
+                    Decimal128::new_from_i128(38, *input_scale, v).as_string(),
+                    output_precision,
+                    output_scale
+                ))
+            })
+        })
+    });
+    build_decimal_array(values, *output_precision, *output_scale, cast_options)
+}
 
-    let output_array = if input_scale > output_scale {
-        // For example, input_scale is 4 and output_scale is 3;
-        // Original value is 11234_i128, and will be cast to 1123_i128.
-        let div = 10_i128.pow((input_scale - output_scale) as u32);
-        array
-            .iter()
-            .map(|v| v.map(|v| v / div))
-            .collect::<DecimalArray>()
-    } else {
-        // For example, input_scale is 3 and output_scale is 4;
-        // Original value is 1123_i128, and will be cast to 11230_i128.
-        let mul = 10_i128.pow((output_scale - input_scale) as u32);
-        array
-            .iter()
-            .map(|v| v.map(|v| v * mul))
-            .collect::<DecimalArray>()
+/// Builds a [`DecimalArray`] of `precision` and `scale` from the results of casting
+/// each value. Values that failed to cast, or that don't fit `precision`, are null
+/// if `cast_options.safe` is set, and otherwise return an error
+fn build_decimal_array(
+    values: impl Iterator<Item = Option<Result<i128>>>,
+    precision: usize,
+    scale: usize,
+    cast_options: &CastOptions,
+) -> Result<ArrayRef> {
+    let mut builder = DecimalBuilder::new(values.size_hint().0, precision, scale);
+    for value in values {
+        // the builder validates that the value fits `precision`
+        match value.map(|v| v.and_then(|v| builder.append_value(v))) {
+            Some(Ok(())) => {}
+            Some(Err(_)) if cast_options.safe => builder.append_null()?,
+            Some(Err(e)) => return Err(e),
+            None => builder.append_null()?,
+        }
     }
-    .with_precision_and_scale(*output_precision, *output_scale)?;
+    Ok(Arc::new(builder.finish()))
+}
 
-    Ok(Arc::new(output_array))
+/// Cast a decimal array to a string array, e.g. `12345` with scale 2 is cast to `"123.45"`
+fn cast_decimal_to_string<OffsetSize: OffsetSizeTrait>(
+    array: &ArrayRef,
+) -> Result<ArrayRef> {
+    let array = array.as_any().downcast_ref::<DecimalArray>().unwrap();
+    let string_array = (0..array.len())
+        .map(|i| array.is_valid(i).then(|| array.value_as_string(i)))
+        .collect::<GenericStringArray<OffsetSize>>();
+    Ok(Arc::new(string_array))
+}
+
+/// Cast a string array to a decimal array of `precision` and `scale`
+fn cast_string_to_decimal<OffsetSize: OffsetSizeTrait>(
+    array: &ArrayRef,
+    precision: &usize,
+    scale: &usize,
+    cast_options: &CastOptions,
+) -> Result<ArrayRef> {
+    let array = array
+        .as_any()
+        .downcast_ref::<GenericStringArray<OffsetSize>>()
+        .unwrap();
+    let values = array.iter().map(|v| {
+        v.map(|v| {
+            parse_decimal(v, *scale).ok_or_else(|| {
+                ArrowError::CastError(format!(
+                    "Cannot cast string '{}' to value of Decimal({}, {}) type",
+                    v, precision, scale
+                ))
+            })
+        })
+    });
+    build_decimal_array(values, *precision, *scale, cast_options)
+}
+
+/// Parses a decimal string such as `-123.45` or `1.2e-3` into an integer with `scale`
+/// digits after the decimal point, rounding half away from zero if the string has
+/// more fractional digits.
+///
+/// Returns `None` if the string isn't a valid decimal or the result overflows `i128`
+fn parse_decimal(s: &str, scale: usize) -> Option<i128> {
+    let (mantissa, exponent) = match s.find(|c| c == 'e' || c == 'E') {
+        Some(idx) => (&s[..idx], s[idx + 1..].parse::<i32>().ok()?),
+        None => (s, 0),
+    };
+    let (negative, mantissa) = match mantissa.as_bytes().first() {
+        Some(b'-') => (true, &mantissa[1..]),
+        Some(b'+') => (false, &mantissa[1..]),
+        _ => (false, mantissa),
+    };
+    let (integer, fraction) = match mantissa.find('.') {
+        Some(idx) => (&mantissa[..idx], &mantissa[idx + 1..]),
+        None => (mantissa, ""),
+    };
+    let digits = integer.as_bytes().iter().chain(fraction.as_bytes());
+    if integer.is_empty() && fraction.is_empty()
+        || !digits.clone().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    // Zero is zero at any scale, even if the power of ten would overflow
+    if digits.clone().all(|b| *b == b'0') {
+        return Some(0);
+    }
+
+    // The power of ten to multiply the digits by to get a value with `scale`
+    let shift = exponent as i64 + scale as i64 - fraction.len() as i64;
+    // The number of leading digits that are kept, the next one is used for rounding
+    let keep = (integer.len() + fraction.len()) as i64 + shift.min(0);
+
+    let mut value: i128 = 0;
+    let mut round_up = false;
+    for (idx, digit) in digits.enumerate() {
+        let digit = (digit - b'0') as i128;
+        match (idx as i64).cmp(&keep) {
+            Ordering::Less => value = value.checked_mul(10)?.checked_add(digit)?,
+            Ordering::Equal => round_up = digit >= 5,
+            Ordering::Greater => break,
+        }
+    }
+    if shift > 0 {
+        value = value.checked_mul(10_i128.checked_pow(u32::try_from(shift).ok()?)?)?;
+    }
+    if round_up {
+        value = value.checked_add(1)?;
+    }
+    Some(if negative { -value } else { value })
 }
 
 /// Cast an array by changing its array_data type to the desired type
@@ -1399,6 +1631,299 @@ fn cast_date64_to_string<OffsetSize: OffsetSizeTrait>(
     ))
 }
 
+/// Cast a binary array to a string array, values that aren't valid UTF-8 are null
+/// or return an error
+fn cast_binary_to_string<OffsetSizeFrom, OffsetSizeTo>(
+    array: &ArrayRef,
+    cast_options: &CastOptions,
+) -> Result<ArrayRef>
+where
+    OffsetSizeFrom: OffsetSizeTrait,
+    OffsetSizeTo: OffsetSizeTrait,
+{
+    let array = array
+        .as_any()
+        .downcast_ref::<GenericBinaryArray<OffsetSizeFrom>>()
+        .unwrap();
+    let mut values = Vec::with_capacity(array.len());
+    for value in array.iter() {
+        match value.map(str::from_utf8) {
+            Some(Ok(value)) => values.push(Some(value)),
+            Some(Err(_)) if !cast_options.safe => {
+                return Err(ArrowError::CastError(
+                    "Cannot cast binary to string".to_string(),
+                ))
+            }
+            _ => values.push(None),
+        }
+    }
+    Ok(Arc::new(
+        values
+            .into_iter()
+            .collect::<GenericStringArray<OffsetSizeTo>>(),
+    ))
+}
+
+/// Cast an array to a string array, see [`value_strings`] for the format of the values
+fn cast_values_to_string<OffsetSize: OffsetSizeTrait>(
+    array: &ArrayRef,
+) -> Result<ArrayRef> {
+    Ok(Arc::new(
+        value_strings(array, false)?
+            .into_iter()
+            .collect::<GenericStringArray<OffsetSize>>(),
+    ))
+}
+
+/// Formats the values of `array` as text, or `None` for null values.
+///
+/// Lists, structs, maps and unions are formatted as JSON-like text, e.g. `[1, null]`,
+/// `{"a": "x", "b": 2}`, `{"key": "value"}` for maps and `{"field": 1}` for unions.
+/// Other values use the text of their cast to `Utf8`; binary values are formatted as
+/// hex, durations as ISO 8601 seconds such as `PT1.500S`, and times and intervals
+/// as in [`array_value_to_string`].
+///
+/// If `nested` is true the values are elements of a nested value, and strings and
+/// other non-numeric values are quoted.
+fn value_strings(array: &ArrayRef, nested: bool) -> Result<Vec<Option<String>>> {
+    use DataType::*;
+    let len = array.len();
+    let values = match array.data_type() {
+        Null => vec![None; len],
+        List(_) => list_value_strings::<i32>(array)?,
+        LargeList(_) => list_value_strings::<i64>(array)?,
+        FixedSizeList(_, size) => {
+            let list = array.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
+            let items = value_strings(&list.values(), true)?;
+            (0..len)
+                .map(|i| {
+                    let start = list.value_offset(i) as usize;
+                    let items = &items[start..start + *size as usize];
+                    list.is_valid(i).then(|| format_list(items))
+                })
+                .collect()
+        }
+        Map(_, _) => {
+            let map = array.as_any().downcast_ref::<MapArray>().unwrap();
+            let keys = value_strings(&map.keys(), true)?;
+            let values = value_strings(&map.values(), true)?;
+            let offsets = map.value_offsets();
+            (0..len)
+                .map(|i| {
+                    let (start, end) = (offsets[i] as usize, offsets[i + 1] as usize);
+                    let entries = (start..end).map(|j| (keys[j].as_deref(), &values[j]));
+                    map.is_valid(i).then(|| format_entries(entries))
+                })
+                .collect()
+        }
+        Struct(fields) => {
+            let struct_array = array.as_any().downcast_ref::<StructArray>().unwrap();
+            let columns = struct_array
+                .columns()
+                .into_iter()
+                .map(|column| value_strings(column, true))
+                .collect::<Result<Vec<_>>>()?;
+            let names = fields
+                .iter()
+                .map(|field| quote_string(field.name()))
+                .collect::<Vec<_>>();
+            (0..len)
+                .map(|i| {
+                    let entries = names
+                        .iter()
+                        .zip(&columns)
+                        .map(|(name, column)| (Some(name.as_str()), &column[i]));
+                    struct_array.is_valid(i).then(|| format_entries(entries))
+                })
+                .collect()
+        }
+        Union(fields, type_ids, _) => {
+            let union = array.as_any().downcast_ref::<UnionArray>().unwrap();
+            let children = (0..fields.len())
+                .map(|i| value_strings(&union.child(i as i8), true))
+                .collect::<Result<Vec<_>>>()?;
+            (0..len)
+                .map(|i| {
+                    let index = union.offset() + i;
+                    let type_id = union.type_id(index);
+                    let field = type_ids.iter().position(|t| *t == type_id).unwrap();
+                    let name = quote_string(fields[field].name());
+                    let value =
+                        &children[type_id as usize][union.value_offset(index) as usize];
+                    Some(format_entries(std::iter::once((
+                        Some(name.as_str()),
+                        value,
+                    ))))
+                })
+                .collect()
+        }
+        Dictionary(_, value_type) => value_strings(&cast(array, value_type)?, nested)?,
+        data_type => {
+            let values = leaf_value_strings(array)?;
+            let quoted = !(DataType::is_numeric(data_type)
+                || matches!(data_type, Boolean | Float16 | Decimal(_, _)));
+            if nested && quoted {
+                values
+                    .into_iter()
+                    .map(|v| v.map(|v| quote_string(&v)))
+                    .collect()
+            } else {
+                values
+            }
+        }
+    };
+    Ok(values)
+}
+
+/// Formats the values of a list array as JSON-like text, see [`value_strings`]
+fn list_value_strings<OffsetSize: OffsetSizeTrait>(
+    array: &ArrayRef,
+) -> Result<Vec<Option<String>>> {
+    let list = array
+        .as_any()
+        .downcast_ref::<GenericListArray<OffsetSize>>()
+        .unwrap();
+    let items = value_strings(&list.values(), true)?;
+    let offsets = list.value_offsets();
+    Ok((0..list.len())
+        .map(|i| {
+            let start = offsets[i].to_usize().unwrap();
+            let end = offsets[i + 1].to_usize().unwrap();
+            list.is_valid(i).then(|| format_list(&items[start..end]))
+        })
+        .collect())
+}
+
+/// Formats the values of an array that isn't nested as text, see [`value_strings`]
+fn leaf_value_strings(array: &ArrayRef) -> Result<Vec<Option<String>>> {
+    use std::fmt::Write;
+    use DataType::*;
+
+    let hex = |bytes: &[u8]| {
+        let mut hex = String::with_capacity(bytes.len() * 2);
+        for byte in bytes {
+            write!(hex, "{:02x}", byte).unwrap();
+        }
+        hex
+    };
+    let values = match array.data_type() {
+        Binary => {
+            let array = array.as_any().downcast_ref::<BinaryArray>().unwrap();
+            array.iter().map(|v| v.map(hex)).collect()
+        }
+        LargeBinary => {
+            let array = array.as_any().downcast_ref::<LargeBinaryArray>().unwrap();
+            array.iter().map(|v| v.map(hex)).collect()
+        }
+        FixedSizeBinary(_) => {
+            let array = array
+                .as_any()
+                .downcast_ref::<FixedSizeBinaryArray>()
+                .unwrap();
+            (0..array.len())
+                .map(|i| array.is_valid(i).then(|| hex(array.value(i))))
+                .collect()
+        }
+        Float16 => {
+            let array = array.as_any().downcast_ref::<Float16Array>().unwrap();
+            array
+                .iter()
+                .map(|v| v.map(|v| lexical_to_string(v.to_f32())))
+                .collect()
+        }
+        Duration(unit) => {
+            let array = cast(array, &Int64)?;
+            let array = array.as_any().downcast_ref::<Int64Array>().unwrap();
+            let digits = match unit {
+                TimeUnit::Second => 0,
+                TimeUnit::Millisecond => 3,
+                TimeUnit::Microsecond => 6,
+                TimeUnit::Nanosecond => 9,
+            };
+            array
+                .iter()
+                .map(|v| v.map(|v| format_duration(v, digits)))
+                .collect()
+        }
+        Time32(_) | Time64(_) | Interval(_) => (0..array.len())
+            .map(|i| {
+                array
+                    .is_valid(i)
+                    .then(|| array_value_to_string(array, i))
+                    .transpose()
+            })
+            .collect::<Result<_>>()?,
+        _ => {
+            let array = cast(array, &Utf8)?;
+            let array = array.as_any().downcast_ref::<StringArray>().unwrap();
+            array.iter().map(|v| v.map(str::to_string)).collect()
+        }
+    };
+    Ok(values)
+}
+
+/// Formats a duration of `value` units with `digits` fractional digits of a second
+/// as ISO 8601 seconds, e.g. `PT1.500S`
+fn format_duration(value: i64, digits: u32) -> String {
+    let sign = if value < 0 { "-" } else { "" };
+    let value = value.unsigned_abs();
+    let scale = 10_u64.pow(digits);
+    match value % scale {
+        0 => format!("{}PT{}S", sign, value / scale),
+        fraction => format!(
+            "{}PT{}.{:0width$}S",
+            sign,
+            value / scale,
+            fraction,
+            width = digits as usize
+        ),
+    }
+}
+
+/// Formats the elements of a list as `[a, b, null]`
+fn format_list(items: &[Option<String>]) -> String {
+    let items = items
+        .iter()
+        .map(|item| item.as_deref().unwrap_or("null"))
+        .collect::<Vec<_>>();
+    format!("[{}]", items.join(", "))
+}
+
+/// Formats the entries of a struct or map as `{"a": 1, "b": null}`
+fn format_entries<'a>(
+    entries: impl Iterator<Item = (Option<&'a str>, &'a Option<String>)>,
+) -> String {
+    let entries = entries
+        .map(|(key, value)| {
+            format!(
+                "{}: {}",
+                key.unwrap_or("null"),
+                value.as_deref().unwrap_or("null")
+            )
+        })
+        .collect::<Vec<_>>();
+    format!("{{{}}}", entries.join(", "))
+}
+
+/// Quotes `value` as a JSON string
+fn quote_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
 /// Cast numeric types to Utf8
 fn cast_numeric_to_string<FROM, OffsetSize>(array: &ArrayRef) -> Result<ArrayRef>
 where
@@ -1937,10 +2462,10 @@ fn cast_to_dictionary<K: ArrowDictionaryKeyType>(
             cast_options,
         ),
         Utf8 => pack_string_to_dictionary::<K>(array, cast_options),
-        _ => Err(ArrowError::CastError(format!(
-            "Unsupported output type for dictionary packing: {:?}",
-            dict_value_type
-        ))),
+        _ => {
+            let values = cast_with_options(array, dict_value_type, cast_options)?;
+            dictionary_encode(values.as_ref(), Some(&K::DATA_TYPE))
+        }
     }
 }
 
@@ -2070,6 +2595,87 @@ fn cast_list_inner<OffsetSize: OffsetSizeTrait>(
     Ok(Arc::new(list) as ArrayRef)
 }
 
+/// Helper function that casts the child of a `FixedSizeList` or `Map` array to the
+/// data type of `to`, keeping the array's buffers and validity.
+fn cast_nested_children(
+    array: &ArrayRef,
+    to: &Field,
+    to_type: &DataType,
+    cast_options: &CastOptions,
+) -> Result<ArrayRef> {
+    let data = array.data_ref();
+    let child = make_array(data.child_data()[0].clone());
+    let cast_child = cast_with_options(&child, to.data_type(), cast_options)?;
+    let array_data = unsafe {
+        ArrayData::new_unchecked(
+            to_type.clone(),
+            array.len(),
+            Some(data.null_count()),
+            data.null_buffer().cloned(),
+            array.offset(),
+            data.buffers().to_vec(),
+            vec![cast_child.data().clone()],
+        )
+    };
+    Ok(make_array(array_data))
+}
+
+/// Helper function that casts a struct array to a struct with `to_fields`.
+///
+/// Fields are matched by name and cast to the type of the target field, fields
+/// that are only in `array` are dropped and nullable fields that are missing from
+/// `array` are filled with nulls.
+fn cast_struct(
+    array: &ArrayRef,
+    to_fields: &[Field],
+    to_type: &DataType,
+    cast_options: &CastOptions,
+) -> Result<ArrayRef> {
+    let data = array.data_ref();
+    let from_fields = match data.data_type() {
+        DataType::Struct(fields) => fields,
+        _ => unreachable!(),
+    };
+    // slicing a struct array also slices its children, and so the children of
+    // `array` have the same length as `array`
+    let child_len = data.len();
+    let children = to_fields
+        .iter()
+        .map(|to_field| {
+            match from_fields.iter().position(|f| f.name() == to_field.name()) {
+                Some(idx) => {
+                    let child = make_array(data.child_data()[idx].clone());
+                    let child =
+                        cast_with_options(&child, to_field.data_type(), cast_options)?;
+                    Ok(child.data().clone())
+                }
+                None if to_field.is_nullable() => {
+                    Ok(new_null_array(to_field.data_type(), child_len)
+                        .data()
+                        .clone())
+                }
+                None => Err(ArrowError::CastError(format!(
+                    "Cannot cast to struct: non-nullable field '{}' is missing",
+                    to_field.name()
+                ))),
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let array_data = unsafe {
+        ArrayData::new_unchecked(
+            to_type.clone(),
+            data.len(),
+            Some(data.null_count()),
+            data.null_buffer().cloned(),
+            data.offset(),
+            vec![],
+            children,
+        )
+    };
+    Ok(make_array(array_data))
+}
+
 /// Helper function to cast from `Utf8` to `LargeUtf8` and vice versa. If the `LargeUtf8` is too large for
 /// a `Utf8` array it will return an Error.
 fn cast_str_container<OffsetSizeFrom, OffsetSizeTo>(array: &dyn Array) -> Result<ArrayRef>
@@ -2258,10 +2864,42 @@ mod tests {
         let array = vec![Some(123456), None];
         let input_decimal_array = create_decimal_array(&array, 10, 0).unwrap();
         let array = Arc::new(input_decimal_array) as ArrayRef;
-        let result = cast(&array, &DataType::Decimal(2, 2));
+        let options = CastOptions { safe: false };
+        let result = cast_with_options(&array, &DataType::Decimal(2, 2), &options);
         assert!(result.is_err());
         assert_eq!("Invalid argument error: 12345600 is too large to store in a Decimal of precision 2. Max is 99",
                    result.unwrap_err().to_string());
+        // the value is null with the default options
+        let result = cast(&array, &DataType::Decimal(2, 2)).unwrap();
+        assert_eq!(result.null_count(), 2);
+
+        // reducing the scale rounds half away from zero
+        let array = vec![Some(11234), Some(11235), Some(-11235), Some(-11234), None];
+        let input_decimal_array = create_decimal_array(&array, 10, 4).unwrap();
+        let array = Arc::new(input_decimal_array) as ArrayRef;
+        generate_cast_test_case!(
+            &array,
+            DecimalArray,
+            &DataType::Decimal(4, 3),
+            vec![
+                Some(Decimal128::new_from_i128(4, 3, 1123_i128)),
+                Some(Decimal128::new_from_i128(4, 3, 1124_i128)),
+                Some(Decimal128::new_from_i128(4, 3, -1124_i128)),
+                Some(Decimal128::new_from_i128(4, 3, -1123_i128)),
+                None
+            ]
+        );
+
+        // increasing the scale can overflow `i128`
+        let array = create_decimal_array(&[Some(10_i128.pow(37))], 38, 0).unwrap();
+        let array = Arc::new(array) as ArrayRef;
+        let result = cast_with_options(&array, &DataType::Decimal(38, 2), &options);
+        assert_eq!(
+            "Cast error: value of 10000000000000000000000000000000000000 is out of range Decimal(38, 2)",
+            result.unwrap_err().to_string()
+        );
+        let result = cast(&array, &DataType::Decimal(38, 2)).unwrap();
+        assert!(result.is_null(0));
     }
 
     #[test]
@@ -2320,11 +2958,14 @@ mod tests {
         let value_array: Vec<Option<i128>> = vec![Some(24400)];
         let decimal_array = create_decimal_array(&value_array, 38, 2).unwrap();
         let array = Arc::new(decimal_array) as ArrayRef;
-        let casted_array = cast(&array, &DataType::Int8);
+        let options = CastOptions { safe: false };
+        let casted_array = cast_with_options(&array, &DataType::Int8, &options);
         assert_eq!(
             "Cast error: value of 244 is out of range Int8".to_string(),
             casted_array.unwrap_err().to_string()
         );
+        let casted_array = cast(&array, &DataType::Int8).unwrap();
+        assert!(casted_array.is_null(0));
 
         // loss the precision: convert decimal to f32、f64
         // f32
@@ -2440,9 +3081,14 @@ mod tests {
         // the 100 will be converted to 1000_i128, but it is out of range for max value in the precision 3.
         let array = Int8Array::from(vec![1, 2, 3, 4, 100]);
         let array = Arc::new(array) as ArrayRef;
-        let casted_array = cast(&array, &DataType::Decimal(3, 1));
+        let options = CastOptions { safe: false };
+        let casted_array = cast_with_options(&array, &DataType::Decimal(3, 1), &options);
         assert!(casted_array.is_err());
         assert_eq!("Invalid argument error: 1000 is too large to store in a Decimal of precision 3. Max is 999", casted_array.unwrap_err().to_string());
+        // the overflowing value is null with the default options
+        let casted_array = cast(&array, &DataType::Decimal(3, 1)).unwrap();
+        assert_eq!(casted_array.null_count(), 1);
+        assert!(casted_array.is_null(4));
 
         // test f32 to decimal type
         let array = Float32Array::from(vec![
@@ -2463,8 +3109,8 @@ mod tests {
                 Some(Decimal128::new_from_i128(38, 6, 2200000_i128)),
                 Some(Decimal128::new_from_i128(38, 6, 4400000_i128)),
                 None,
-                Some(Decimal128::new_from_i128(38, 6, 1123456_i128)),
-                Some(Decimal128::new_from_i128(38, 6, 1123456_i128)),
+                Some(Decimal128::new_from_i128(38, 6, 1123457_i128)),
+                Some(Decimal128::new_from_i128(38, 6, 1123457_i128)),
             ]
         );
 
@@ -2488,11 +3134,118 @@ mod tests {
                 Some(Decimal128::new_from_i128(38, 6, 2200000_i128)),
                 Some(Decimal128::new_from_i128(38, 6, 4400000_i128)),
                 None,
-                Some(Decimal128::new_from_i128(38, 6, 1123456_i128)),
-                Some(Decimal128::new_from_i128(38, 6, 1123456_i128)),
-                Some(Decimal128::new_from_i128(38, 6, 1123456_i128)),
+                Some(Decimal128::new_from_i128(38, 6, 1123457_i128)),
+                Some(Decimal128::new_from_i128(38, 6, 1123457_i128)),
+                Some(Decimal128::new_from_i128(38, 6, 1123457_i128)),
             ]
         );
+
+        // NaN, infinite and too large values can't be represented
+        let array = Float64Array::from(vec![f64::NAN, f64::INFINITY, -1.5e38, -2.5]);
+        let array = Arc::new(array) as ArrayRef;
+        generate_cast_test_case!(
+            &array,
+            DecimalArray,
+            &DataType::Decimal(20, 0),
+            vec![
+                None,
+                None,
+                None,
+                Some(Decimal128::new_from_i128(20, 0, -3_i128))
+            ]
+        );
+        let result = cast_with_options(&array, &DataType::Decimal(20, 0), &options);
+        assert_eq!(
+            "Cast error: value of NaN is out of range Decimal(20, 0)",
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_cast_string_to_decimal() {
+        let decimal_type = DataType::Decimal(10, 2);
+        assert!(can_cast_types(&DataType::Utf8, &decimal_type));
+        assert!(can_cast_types(&DataType::LargeUtf8, &decimal_type));
+        let values = vec![
+            Some("123.45"),
+            Some("-1.2"),
+            Some("+7"),
+            Some(".125"),
+            Some("-0.125"),
+            Some("1.5e3"),
+            Some("12345E-4"),
+            Some("0000000000000000000000000000000000000000001.00"),
+            Some("1.23456789012345678901234567890123456789012345"),
+            Some("0e50"),
+            Some("-0.000E100"),
+            None,
+            Some("1.2.3"),
+            Some("abc"),
+            Some(""),
+            Some("-"),
+            Some("1e"),
+            Some("123456789"),
+        ];
+        let expected = vec![
+            Some(Decimal128::new_from_i128(10, 2, 12345_i128)),
+            Some(Decimal128::new_from_i128(10, 2, -120_i128)),
+            Some(Decimal128::new_from_i128(10, 2, 700_i128)),
+            Some(Decimal128::new_from_i128(10, 2, 13_i128)),
+            Some(Decimal128::new_from_i128(10, 2, -13_i128)),
+            Some(Decimal128::new_from_i128(10, 2, 150000_i128)),
+            Some(Decimal128::new_from_i128(10, 2, 123_i128)),
+            Some(Decimal128::new_from_i128(10, 2, 100_i128)),
+            Some(Decimal128::new_from_i128(10, 2, 123_i128)),
+            Some(Decimal128::new_from_i128(10, 2, 0_i128)),
+            Some(Decimal128::new_from_i128(10, 2, 0_i128)),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ];
+        let array = Arc::new(StringArray::from(values.clone())) as ArrayRef;
+        generate_cast_test_case!(&array, DecimalArray, &decimal_type, expected);
+        let array = Arc::new(LargeStringArray::from(values)) as ArrayRef;
+        generate_cast_test_case!(&array, DecimalArray, &decimal_type, expected);
+
+        let options = CastOptions { safe: false };
+        let array = Arc::new(StringArray::from(vec!["0e50"])) as ArrayRef;
+        let result = cast_with_options(&array, &decimal_type, &options).unwrap();
+        assert_eq!(as_decimal_array(&result).value_as_string(0), "0.00");
+        let array = Arc::new(StringArray::from(vec!["1.2.3"])) as ArrayRef;
+        let result = cast_with_options(&array, &decimal_type, &options);
+        assert_eq!(
+            "Cast error: Cannot cast string '1.2.3' to value of Decimal(10, 2) type",
+            result.unwrap_err().to_string()
+        );
+        let array = Arc::new(StringArray::from(vec!["123456789"])) as ArrayRef;
+        let result = cast_with_options(&array, &decimal_type, &options);
+        assert_eq!(
+            "Invalid argument error: 12345678900 is too large to store in a Decimal of precision 10. Max is 9999999999",
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_cast_decimal_to_string() {
+        let array = vec![Some(12345), Some(-5), Some(0), None];
+        let array = Arc::new(create_decimal_array(&array, 10, 3).unwrap()) as ArrayRef;
+        let expected = [Some("12.345"), Some("-0.005"), Some("0.000"), None];
+        generate_cast_test_case!(&array, StringArray, &DataType::Utf8, expected);
+        generate_cast_test_case!(
+            &array,
+            LargeStringArray,
+            &DataType::LargeUtf8,
+            expected
+        );
+
+        // round trip
+        let strings = cast(&array, &DataType::Utf8).unwrap();
+        let decimals = cast(&strings, &DataType::Decimal(10, 3)).unwrap();
+        assert_eq!(decimals.data(), array.data());
     }
 
     #[test]
@@ -4310,6 +5063,12 @@ mod tests {
         let cast_array = cast(&array, &cast_type).expect("cast failed");
         assert_eq!(cast_array.data_type(), &cast_type);
         assert_eq!(array_to_strings(&cast_array), expected);
+
+        // Cast to a dictionary (different value type, LargeUtf8)
+        let cast_type = Dictionary(Box::new(Int16), Box::new(LargeUtf8));
+        let cast_array = cast(&array, &cast_type).expect("cast failed");
+        assert_eq!(cast_array.data_type(), &cast_type);
+        assert_eq!(array_to_strings(&cast_array), expected);
     }
 
     #[test]
@@ -4618,6 +5377,10 @@ mod tests {
             Arc::new(UInt16Array::from(vec![1, 2])),
             Arc::new(UInt32Array::from(vec![1, 2])),
             Arc::new(UInt64Array::from(vec![1, 2])),
+            Arc::new(Float16Array::from_iter_values([
+                half::f16::from_f32(1.0),
+                half::f16::from_f32(2.0),
+            ])),
             Arc::new(Float32Array::from(vec![1.0, 2.0])),
             Arc::new(Float64Array::from(vec![1.0, 2.0])),
             Arc::new(TimestampSecondArray::from_vec(vec![1000, 2000], None)),
@@ -4863,4 +5626,318 @@ mod tests {
 
         assert_eq!(&out1, &out2.slice(1, 2))
     }
+
+    #[test]
+    fn test_cast_nested_to_string() {
+        let mut list_builder = ListBuilder::new(Int32Builder::new(4));
+        list_builder.values().append_value(1).unwrap();
+        list_builder.values().append_null().unwrap();
+        list_builder.append(true).unwrap();
+        list_builder.append(false).unwrap();
+        list_builder.values().append_value(3).unwrap();
+        list_builder.append(true).unwrap();
+        let list = Arc::new(list_builder.finish()) as ArrayRef;
+        let expected = [Some("[1, null]"), None, Some("[3]")];
+        generate_cast_test_case!(&list, StringArray, &DataType::Utf8, expected);
+        generate_cast_test_case!(&list, LargeStringArray, &DataType::LargeUtf8, expected);
+        let sliced = cast(&list.slice(1, 2), &DataType::Utf8).unwrap();
+        assert_eq!(&sliced, &cast(&list, &DataType::Utf8).unwrap().slice(1, 2));
+
+        let array = StructArray::from(vec![
+            (
+                Field::new("a", DataType::Utf8, true),
+                Arc::new(StringArray::from(vec![Some("x\"y"), None])) as ArrayRef,
+            ),
+            (
+                Field::new("b", DataType::Binary, false),
+                Arc::new(BinaryArray::from(vec![&[1_u8, 2][..], &[255_u8][..]]))
+                    as ArrayRef,
+            ),
+            (
+                Field::new("c", list.data_type().clone(), true),
+                list.slice(0, 2),
+            ),
+            (
+                Field::new("d", DataType::Date32, true),
+                Arc::new(Date32Array::from(vec![Some(0), None])) as ArrayRef,
+            ),
+        ]);
+        let array = Arc::new(array) as ArrayRef;
+        generate_cast_test_case!(
+            &array,
+            StringArray,
+            &DataType::Utf8,
+            [
+                Some(r#"{"a": "x\"y", "b": "0102", "c": [1, null], "d": "1970-01-01"}"#),
+                Some(r#"{"a": null, "b": "ff", "c": null, "d": null}"#),
+            ]
+        );
+
+        let mut builder =
+            MapBuilder::new(None, StringBuilder::new(3), Int32Builder::new(3));
+        builder.keys().append_value("a").unwrap();
+        builder.values().append_value(1).unwrap();
+        builder.keys().append_value("b").unwrap();
+        builder.values().append_null().unwrap();
+        builder.append(true).unwrap();
+        builder.append(false).unwrap();
+        let array = Arc::new(builder.finish()) as ArrayRef;
+        generate_cast_test_case!(
+            &array,
+            StringArray,
+            &DataType::Utf8,
+            [Some(r#"{"a": 1, "b": null}"#), None]
+        );
+
+        let array = Arc::new(make_union_array()) as ArrayRef;
+        generate_cast_test_case!(
+            &array,
+            StringArray,
+            &DataType::Utf8,
+            [Some(r#"{"a": 1}"#), Some(r#"{"b": 2}"#)]
+        );
+
+        let keys = Int8Array::from(vec![Some(1), None, Some(0)]);
+        let values = StringArray::from(vec!["x", "y"]);
+        let dictionary = DictionaryArray::try_new(&keys, &values).unwrap();
+        let list_data = ArrayData::builder(DataType::List(Box::new(Field::new(
+            "item",
+            dictionary.data_type().clone(),
+            true,
+        ))))
+        .len(1)
+        .add_buffer(Buffer::from_slice_ref(&[0, 3]))
+        .add_child_data(dictionary.data().clone())
+        .build()
+        .unwrap();
+        let array = Arc::new(ListArray::from(list_data)) as ArrayRef;
+        generate_cast_test_case!(
+            &array,
+            StringArray,
+            &DataType::Utf8,
+            [Some(r#"["y", null, "x"]"#)]
+        );
+    }
+
+    #[test]
+    fn test_cast_other_types_to_string() {
+        let array = Arc::new(make_fixed_size_binary_array()) as ArrayRef;
+        generate_cast_test_case!(
+            &array,
+            StringArray,
+            &DataType::Utf8,
+            [Some("68656c6c6f"), Some("7468657265"), Some("6172726f77")]
+        );
+
+        let array = Arc::new(LargeBinaryArray::from(vec![&b"foo"[..], &[0xff][..]]));
+        let array = array as ArrayRef;
+        generate_cast_test_case!(
+            &array,
+            StringArray,
+            &DataType::Utf8,
+            [Some("foo"), None]
+        );
+        let options = CastOptions { safe: false };
+        let result = cast_with_options(&array, &DataType::Utf8, &options);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Cast error: Cannot cast binary to string"
+        );
+
+        let array = Arc::new(DurationMillisecondArray::from(vec![
+            Some(1500),
+            Some(-2000),
+            None,
+        ])) as ArrayRef;
+        generate_cast_test_case!(
+            &array,
+            LargeStringArray,
+            &DataType::LargeUtf8,
+            [Some("PT1.500S"), Some("-PT2S"), None]
+        );
+
+        let array = Arc::new(Time32SecondArray::from(vec![3661])) as ArrayRef;
+        generate_cast_test_case!(
+            &array,
+            StringArray,
+            &DataType::Utf8,
+            [Some("01:01:01")]
+        );
+
+        let array = Float16Array::from_iter([
+            Some(half::f16::from_f32(1.5)),
+            None,
+            Some(half::f16::from_f32(-2.)),
+        ]);
+        let array = Arc::new(array) as ArrayRef;
+        assert!(can_cast_types(array.data_type(), &DataType::LargeUtf8));
+        generate_cast_test_case!(
+            &array,
+            LargeStringArray,
+            &DataType::LargeUtf8,
+            [Some("1.5"), None, Some("-2.0")]
+        );
+
+        let list_data = ArrayData::builder(DataType::List(Box::new(Field::new(
+            "item",
+            DataType::Float16,
+            true,
+        ))))
+        .len(1)
+        .add_buffer(Buffer::from_slice_ref(&[0, 3]))
+        .add_child_data(array.data().clone())
+        .build()
+        .unwrap();
+        let array = Arc::new(ListArray::from(list_data)) as ArrayRef;
+        assert!(can_cast_types(array.data_type(), &DataType::Utf8));
+        generate_cast_test_case!(
+            &array,
+            StringArray,
+            &DataType::Utf8,
+            [Some("[1.5, null, -2.0]")]
+        );
+    }
+
+    #[test]
+    fn test_cast_struct_to_struct() {
+        let inner = StructArray::from(vec![(
+            Field::new("x", DataType::Int8, true),
+            Arc::new(Int8Array::from(vec![Some(1), None, Some(3), Some(4)])) as ArrayRef,
+        )]);
+        let array = StructArray::from(vec![
+            (
+                Field::new("a", DataType::Int32, true),
+                Arc::new(Int32Array::from(vec![Some(1), Some(2), None, Some(4)]))
+                    as ArrayRef,
+            ),
+            (
+                Field::new("b", DataType::Utf8, false),
+                Arc::new(StringArray::from(vec!["w", "x", "y", "z"])) as ArrayRef,
+            ),
+            (
+                Field::new("c", inner.data_type().clone(), false),
+                Arc::new(inner) as ArrayRef,
+            ),
+        ]);
+        let array = Arc::new(array) as ArrayRef;
+
+        // reorder, cast nested fields, drop "b" and add the nullable field "d"
+        let to_type = DataType::Struct(vec![
+            Field::new(
+                "c",
+                DataType::Struct(vec![Field::new("x", DataType::Utf8, true)]),
+                false,
+            ),
+            Field::new("a", DataType::Int64, true),
+            Field::new("d", DataType::Float64, true),
+        ]);
+        assert!(can_cast_types(array.data_type(), &to_type));
+        let casted = cast(&array, &to_type).unwrap();
+        assert_eq!(casted.data_type(), &to_type);
+        let casted = casted.as_any().downcast_ref::<StructArray>().unwrap();
+
+        let c = casted.column(0);
+        let c = c.as_any().downcast_ref::<StructArray>().unwrap();
+        let x = c.column(0);
+        let x = x.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(
+            x,
+            &StringArray::from(vec![Some("1"), None, Some("3"), Some("4")])
+        );
+        let a = casted.column(1);
+        let a = a.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(a, &Int64Array::from(vec![Some(1), Some(2), None, Some(4)]));
+        let d = casted.column(2);
+        assert_eq!(d.data_type(), &DataType::Float64);
+        assert_eq!(d.null_count(), 4);
+
+        // the offset of a sliced array is taken into account
+        let sliced = cast(&array.slice(1, 2), &to_type).unwrap();
+        assert_eq!(&sliced, &cast(&array, &to_type).unwrap().slice(1, 2));
+
+        // a missing non-nullable field can't be filled with nulls
+        let to_type = DataType::Struct(vec![Field::new("d", DataType::Int32, false)]);
+        assert!(!can_cast_types(array.data_type(), &to_type));
+        assert_eq!(
+            cast(&array, &to_type).unwrap_err().to_string(),
+            "Cast error: Cannot cast to struct: non-nullable field 'd' is missing"
+        );
+    }
+
+    #[test]
+    fn test_cast_list_children() {
+        let array = Arc::new(make_list_array()) as ArrayRef;
+        let to_type =
+            DataType::LargeList(Box::new(Field::new("item", DataType::Utf8, true)));
+        assert!(can_cast_types(array.data_type(), &to_type));
+        let casted = cast(&array, &to_type).unwrap();
+        let list = casted.as_any().downcast_ref::<LargeListArray>().unwrap();
+        assert_eq!(list.len(), 3);
+        let values = list.value(2);
+        let values = values.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(values, &StringArray::from(vec!["6", "7"]));
+        assert_eq!(&cast(&casted, array.data_type()).unwrap(), &array);
+
+        let array = Arc::new(make_fixed_size_list_array()) as ArrayRef;
+        let to_type = DataType::FixedSizeList(
+            Box::new(Field::new("item", DataType::Float64, true)),
+            2,
+        );
+        assert!(can_cast_types(array.data_type(), &to_type));
+        let casted = cast(&array.slice(1, 2), &to_type).unwrap();
+        let casted = casted
+            .as_any()
+            .downcast_ref::<FixedSizeListArray>()
+            .unwrap();
+        assert_eq!(casted.len(), 2);
+        let values = casted.value(1);
+        let values = values.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(values, &Float64Array::from(vec![4.0, 5.0]));
+
+        let to_type = DataType::FixedSizeList(
+            Box::new(Field::new("item", DataType::Float64, true)),
+            3,
+        );
+        assert!(!can_cast_types(array.data_type(), &to_type));
+        assert_eq!(
+            cast(&array, &to_type).unwrap_err().to_string(),
+            "Cast error: Cannot cast fixed-size list of size 2 to size 3"
+        );
+    }
+
+    #[test]
+    fn test_cast_map_children() {
+        let mut builder =
+            MapBuilder::new(None, StringBuilder::new(3), Int32Builder::new(3));
+        builder.keys().append_value("a").unwrap();
+        builder.values().append_value(1).unwrap();
+        builder.keys().append_value("b").unwrap();
+        builder.values().append_null().unwrap();
+        builder.append(true).unwrap();
+        builder.append(false).unwrap();
+        builder.keys().append_value("c").unwrap();
+        builder.values().append_value(3).unwrap();
+        builder.append(true).unwrap();
+        let array = Arc::new(builder.finish()) as ArrayRef;
+
+        let to_type = DataType::Map(
+            Box::new(Field::new(
+                "entries",
+                DataType::Struct(vec![
+                    Field::new("keys", DataType::Utf8, false),
+                    Field::new("values", DataType::Int64, true),
+                ]),
+                false,
+            )),
+            false,
+        );
+        assert!(can_cast_types(array.data_type(), &to_type));
+        let casted = cast(&array, &to_type).unwrap();
+        assert_eq!(casted.data_type(), &to_type);
+        assert!(casted.is_null(1));
+        let casted = casted.as_any().downcast_ref::<MapArray>().unwrap();
+        let values = casted.values();
+        let values = values.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(values, &Int64Array::from(vec![Some(1), None, Some(3)]));
+    }
 }
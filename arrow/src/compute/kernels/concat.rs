@@ -31,8 +31,10 @@
 //! ```
 
 use crate::array::*;
+use crate::compute::kernels::dictionary::{share_dictionary, unify_dictionaries};
 use crate::datatypes::DataType;
 use crate::error::{ArrowError, Result};
+use crate::row::RowConverter;
 
 fn compute_str_values_length<Offset: OffsetSizeTrait>(arrays: &[&ArrayData]) -> usize {
     arrays
@@ -50,6 +52,10 @@ fn compute_str_values_length<Offset: OffsetSizeTrait>(arrays: &[&ArrayData]) ->
 }
 
 /// Concatenate multiple [Array] of the same type into a single [ArrayRef].
+///
+/// Dictionary arrays with different dictionaries are first unified with
+/// [`unify_dictionaries`], so that the result has a single dictionary without
+/// duplicate values.
 pub fn concat(arrays: &[&dyn Array]) -> Result<ArrayRef> {
     if arrays.is_empty() {
         return Err(ArrowError::ComputeError(
@@ -70,6 +76,16 @@ pub fn concat(arrays: &[&dyn Array]) -> Result<ArrayRef> {
         ));
     }
 
+    let data_type = arrays[0].data_type();
+    if matches!(data_type, DataType::Dictionary(_, _))
+        && RowConverter::supports_datatype(data_type)
+        && !share_dictionary(arrays)
+    {
+        let unified = unify_dictionaries(arrays)?;
+        let unified: Vec<_> = unified.iter().map(|a| a.as_ref()).collect();
+        return concat(&unified);
+    }
+
     let lengths = arrays.iter().map(|array| array.len()).collect::<Vec<_>>();
     let capacity = lengths.iter().sum();
 
@@ -505,6 +521,30 @@ mod tests {
         assert_eq!(concat, expected);
     }
 
+    #[test]
+    fn test_string_dictionary_array_unified() {
+        let input_1: DictionaryArray<Int32Type> =
+            vec!["a", "b", "a"].into_iter().collect();
+        let input_2: DictionaryArray<Int32Type> =
+            vec![Some("c"), None, Some("b")].into_iter().collect();
+
+        let concat = concat(&[&input_1 as _, &input_2 as _]).unwrap();
+        let concat = concat
+            .as_any()
+            .downcast_ref::<DictionaryArray<Int32Type>>()
+            .unwrap();
+
+        // duplicate values are not repeated in the dictionary
+        assert_eq!(
+            concat.values(),
+            &(Arc::new(StringArray::from(vec!["a", "b", "c"])) as ArrayRef)
+        );
+        assert_eq!(
+            concat.keys(),
+            &Int32Array::from(vec![Some(0), Some(1), Some(0), Some(2), None, Some(1)])
+        );
+    }
+
     #[test]
     fn test_concat_string_sizes() -> Result<()> {
         let a: LargeStringArray = ((0..150).map(|_| Some("foo"))).collect();
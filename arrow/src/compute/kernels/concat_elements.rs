@@ -16,8 +16,8 @@
 // under the License.
 
 use crate::array::*;
-use crate::compute::util::combine_option_bitmap;
-use crate::error::{ArrowError, Result};
+use crate::compute::util::{check_lengths, combine_option_bitmap};
+use crate::error::Result;
 
 /// Returns the elementwise concatenation of a [`StringArray`].
 ///
@@ -37,13 +37,7 @@ pub fn concat_elements_utf8<Offset: OffsetSizeTrait>(
     left: &GenericStringArray<Offset>,
     right: &GenericStringArray<Offset>,
 ) -> Result<GenericStringArray<Offset>> {
-    if left.len() != right.len() {
-        return Err(ArrowError::ComputeError(format!(
-            "Arrays must have the same length: {} != {}",
-            left.len(),
-            right.len()
-        )));
-    }
+    check_lengths(left, right)?;
 
     let output_bitmap = combine_option_bitmap(&[left.data(), right.data()], left.len())?;
 
@@ -0,0 +1,372 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines conditional kernels selecting each row from one of several arrays, such as
+//! [`coalesce`] and [`case_when`].
+//!
+//! Unlike chaining [`zip`](crate::compute::kernels::zip::zip), these kernels first
+//! determine the array each row is taken from, and then copy runs of rows from the
+//! same array with [`MutableArrayData`], and so support all data types, including
+//! nested types.
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use arrow::array::{ArrayRef, BooleanArray, Int32Array};
+//! # use arrow::compute::kernels::conditional::case_when;
+//! let x = Int32Array::from(vec![Some(5), Some(-2), None, Some(0)]);
+//! let positive = BooleanArray::from(vec![true, false, false, false]);
+//! let negative = BooleanArray::from(vec![false, true, false, false]);
+//!
+//! // CASE WHEN x > 0 THEN 1 WHEN x < 0 THEN -1 ELSE 0 END
+//! let result = case_when(
+//!     &[&positive, &negative],
+//!     &[
+//!         Arc::new(Int32Array::from(vec![1; 4])) as ArrayRef,
+//!         Arc::new(Int32Array::from(vec![-1; 4])) as ArrayRef,
+//!     ],
+//!     Some(&(Arc::new(Int32Array::from(vec![0; 4])) as ArrayRef)),
+//! )
+//! .unwrap();
+//! assert_eq!(
+//!     result.as_any().downcast_ref::<Int32Array>().unwrap(),
+//!     &Int32Array::from(vec![1, -1, 0, 0])
+//! );
+//! ```
+
+use crate::array::*;
+use crate::compute::kernels::filter::{prep_null_mask_filter, SlicesIterator};
+use crate::compute::util::{check_lengths, logical_null_buffer};
+use crate::error::{ArrowError, Result};
+use crate::util::bit_iterator::BitSliceIterator;
+
+/// The source of a row that is not taken from any array, and is null
+const NULL_SOURCE: usize = usize::MAX;
+
+/// Returns the first non-null value of each row of `arrays`, or null if the row is
+/// null in all of `arrays`.
+///
+/// This is equivalent to the SQL `COALESCE` function.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use arrow::array::{ArrayRef, StringArray};
+/// # use arrow::compute::kernels::conditional::coalesce;
+/// let a = Arc::new(StringArray::from(vec![Some("a"), None, None])) as ArrayRef;
+/// let b = Arc::new(StringArray::from(vec![Some("x"), Some("y"), None])) as ArrayRef;
+/// let result = coalesce(&[a, b]).unwrap();
+/// assert_eq!(
+///     result.as_any().downcast_ref::<StringArray>().unwrap(),
+///     &StringArray::from(vec![Some("a"), Some("y"), None])
+/// );
+/// ```
+///
+/// # Errors
+///
+/// This function errors if `arrays` is empty, or if the arrays have different data
+/// types or lengths.
+pub fn coalesce(arrays: &[ArrayRef]) -> Result<ArrayRef> {
+    let first = arrays.first().ok_or_else(|| {
+        ArrowError::InvalidArgumentError(
+            "coalesce requires at least one array".to_string(),
+        )
+    })?;
+    let arrays: Vec<_> = arrays.iter().map(|a| a.as_ref()).collect();
+    check_arrays(&arrays, first.as_ref())?;
+
+    let len = first.len();
+    let mut sources = vec![NULL_SOURCE; len];
+    let mut remaining = len;
+    for (idx, array) in arrays.iter().enumerate() {
+        // A dictionary key that refers to a null value is null
+        remaining -= match logical_null_buffer(*array)? {
+            None => assign(&mut sources, idx, std::iter::once((0, len))),
+            Some((nulls, offset)) => assign(
+                &mut sources,
+                idx,
+                BitSliceIterator::new(nulls.as_slice(), offset, len),
+            ),
+        };
+        if remaining == 0 {
+            break;
+        }
+    }
+
+    Ok(select(&arrays, &sources))
+}
+
+/// Selects each row from the value of the first of `conditions` that is true for that
+/// row, or from `else_value` if none of `conditions` is true. Rows for which no
+/// condition is true are null if `else_value` is `None`.
+///
+/// A null condition is treated as false. This is equivalent to the SQL expression
+/// `CASE WHEN conditions[0] THEN values[0] WHEN conditions[1] THEN values[1] ... ELSE
+/// else_value END`.
+///
+/// # Errors
+///
+/// This function errors if there are no conditions, if the number of conditions and
+/// values differ, or if the arrays have different lengths or `values` and `else_value`
+/// have different data types.
+pub fn case_when(
+    conditions: &[&BooleanArray],
+    values: &[ArrayRef],
+    else_value: Option<&ArrayRef>,
+) -> Result<ArrayRef> {
+    if conditions.is_empty() {
+        return Err(ArrowError::InvalidArgumentError(
+            "case_when requires at least one condition".to_string(),
+        ));
+    }
+    if conditions.len() != values.len() {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "case_when requires the same number of conditions and values, got {} and {}",
+            conditions.len(),
+            values.len()
+        )));
+    }
+    let len = conditions[0].len();
+    for condition in conditions {
+        check_lengths(conditions[0], *condition)?;
+    }
+    let arrays: Vec<_> = values
+        .iter()
+        .chain(else_value)
+        .map(|a| a.as_ref())
+        .collect();
+    check_arrays(&arrays, conditions[0])?;
+
+    let mut sources = vec![NULL_SOURCE; len];
+    let mut remaining = len;
+    for (idx, condition) in conditions.iter().enumerate() {
+        let prepared;
+        let condition = match condition.null_count() {
+            0 => *condition,
+            _ => {
+                prepared = prep_null_mask_filter(condition);
+                &prepared
+            }
+        };
+        remaining -= assign(&mut sources, idx, SlicesIterator::new(condition));
+        if remaining == 0 {
+            break;
+        }
+    }
+    if remaining > 0 && else_value.is_some() {
+        assign(&mut sources, values.len(), std::iter::once((0, len)));
+    }
+
+    Ok(select(&arrays, &sources))
+}
+
+/// Checks that `arrays` have the same data type, and the same length as `array`
+fn check_arrays(arrays: &[&dyn Array], array: &dyn Array) -> Result<()> {
+    let data_type = arrays[0].data_type();
+    if let Some(array) = arrays.iter().find(|a| a.data_type() != data_type) {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "It is not possible to select between arrays of different data types ({} and {})",
+            data_type,
+            array.data_type()
+        )));
+    }
+    arrays.iter().try_for_each(|a| check_lengths(array, *a))
+}
+
+/// Sets the source of the rows in `slices` without a source yet to `source`,
+/// returning the number of rows assigned
+fn assign(
+    sources: &mut [usize],
+    source: usize,
+    slices: impl Iterator<Item = (usize, usize)>,
+) -> usize {
+    let mut assigned = 0;
+    for (start, end) in slices {
+        for row_source in &mut sources[start..end] {
+            if *row_source == NULL_SOURCE {
+                *row_source = source;
+                assigned += 1;
+            }
+        }
+    }
+    assigned
+}
+
+/// Takes each row from the array in `arrays` given by `sources`, or null if its
+/// source is [`NULL_SOURCE`], copying runs of rows from the same array
+fn select(arrays: &[&dyn Array], sources: &[usize]) -> ArrayRef {
+    let data: Vec<_> = arrays.iter().map(|a| a.data()).collect();
+    let use_nulls = sources.contains(&NULL_SOURCE);
+    let mut mutable = MutableArrayData::new(data, use_nulls, sources.len());
+
+    let mut start = 0;
+    while start < sources.len() {
+        let source = sources[start];
+        let end = sources[start..]
+            .iter()
+            .position(|s| *s != source)
+            .map_or(sources.len(), |len| start + len);
+        match source {
+            NULL_SOURCE => mutable.extend_nulls(end - start),
+            source => mutable.extend(source, start, end),
+        }
+        start = end;
+    }
+
+    make_array(mutable.freeze())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::compute::cast;
+    use crate::datatypes::{DataType, Int32Type, Int8Type};
+
+    #[test]
+    fn test_coalesce() {
+        let a = Arc::new(Int32Array::from(vec![Some(1), None, None, None])) as ArrayRef;
+        let b =
+            Arc::new(Int32Array::from(vec![Some(5), Some(6), None, None])) as ArrayRef;
+        let c = Arc::new(Int32Array::from(vec![7, 8, 9, 10])) as ArrayRef;
+        let c = c.slice(0, 4);
+
+        let result = coalesce(&[a.clone(), b.clone()]).unwrap();
+        let expected = Int32Array::from(vec![Some(1), Some(6), None, None]);
+        assert_eq!(as_primitive_array::<Int32Type>(&result), &expected);
+
+        let result = coalesce(&[a.clone(), b, c]).unwrap();
+        let expected = Int32Array::from(vec![1, 6, 9, 10]);
+        assert_eq!(as_primitive_array::<Int32Type>(&result), &expected);
+        assert_eq!(result.null_count(), 0);
+
+        let result = coalesce(&[a.slice(1, 3)]).unwrap();
+        assert_eq!(result.len(), 3);
+        assert_eq!(result.null_count(), 3);
+
+        let l = Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            None,
+            Some(vec![Some(1)]),
+        ])) as ArrayRef;
+        let r = Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(2), None]),
+            Some(vec![]),
+        ])) as ArrayRef;
+        let result = coalesce(&[l, r]).unwrap();
+        let expected = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(2), None]),
+            Some(vec![Some(1)]),
+        ]);
+        assert_eq!(result.data(), expected.data());
+    }
+
+    #[test]
+    fn test_coalesce_dictionary() {
+        // The first key is valid, but refers to a null value
+        let keys = Int8Array::from(vec![Some(0), Some(1), None]);
+        let values = Arc::new(StringArray::from(vec![None, Some("a")])) as ArrayRef;
+        let a = DictionaryArray::<Int8Type>::try_new(&keys, &values).unwrap();
+        let b: DictionaryArray<Int8Type> = vec!["z", "y", "x"].into_iter().collect();
+
+        let result = coalesce(&[Arc::new(a) as ArrayRef, Arc::new(b)]).unwrap();
+        let result = cast(&result, &DataType::Utf8).unwrap();
+        let expected = StringArray::from(vec!["z", "a", "x"]);
+        assert_eq!(as_string_array(&result), &expected);
+    }
+
+    #[test]
+    fn test_case_when() {
+        let c1 = BooleanArray::from(vec![Some(true), None, Some(false), Some(true)]);
+        let c2 = BooleanArray::from(vec![Some(true), Some(true), None, Some(false)]);
+        let v1 = Arc::new(StringArray::from(vec!["a1", "b1", "c1", "d1"])) as ArrayRef;
+        let v2 = Arc::new(StringArray::from(vec![Some("a2"), None, Some("c2"), None]))
+            as ArrayRef;
+        let otherwise =
+            Arc::new(StringArray::from(vec!["a3", "b3", "c3", "d3"])) as ArrayRef;
+
+        let result =
+            case_when(&[&c1, &c2], &[v1.clone(), v2.clone()], Some(&otherwise)).unwrap();
+        let expected = StringArray::from(vec![Some("a1"), None, Some("c3"), Some("d1")]);
+        assert_eq!(as_string_array(&result), &expected);
+
+        let result = case_when(&[&c2, &c1], &[v2, v1], None).unwrap();
+        let expected = StringArray::from(vec![Some("a2"), None, None, Some("d1")]);
+        assert_eq!(as_string_array(&result), &expected);
+    }
+
+    #[test]
+    fn test_case_when_nested() {
+        fn struct_array(strings: Vec<&str>, ints: Vec<i32>) -> ArrayRef {
+            let strings = Arc::new(StringArray::from(strings)) as ArrayRef;
+            let ints = Arc::new(Int32Array::from(ints)) as ArrayRef;
+            Arc::new(StructArray::try_from(vec![("s", strings), ("i", ints)]).unwrap())
+        }
+
+        let condition = BooleanArray::from(vec![false, true, false]);
+        let values = [struct_array(vec!["x", "y", "z"], vec![1, 2, 3])];
+        let otherwise = struct_array(vec!["p", "q", "r"], vec![7, 8, 9]);
+
+        let result = case_when(&[&condition], &values, Some(&otherwise)).unwrap();
+        let expected = struct_array(vec!["p", "y", "r"], vec![7, 2, 9]);
+        assert_eq!(result.data(), expected.data());
+
+        let result = case_when(&[&condition], &values, None).unwrap();
+        let result = as_struct_array(&result);
+        assert_eq!(result.null_count(), 2);
+        assert!(result.is_valid(1));
+        assert_eq!(as_string_array(result.column(0)).value(1), "y");
+    }
+
+    #[test]
+    fn test_errors() {
+        let a = Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef;
+        let b = Arc::new(Int32Array::from(vec![1])) as ArrayRef;
+        let c = Arc::new(StringArray::from(vec!["a", "b"])) as ArrayRef;
+
+        let err = coalesce(&[]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: coalesce requires at least one array"
+        );
+        let err = coalesce(&[a.clone(), b]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Arrays must have the same length: 2 != 1"
+        );
+        let err = coalesce(&[a.clone(), c.clone()]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: It is not possible to select between arrays of different data types (Int32 and Utf8)"
+        );
+
+        let condition = BooleanArray::from(vec![true, false]);
+        let err = case_when(&[], &[], None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: case_when requires at least one condition"
+        );
+        let err = case_when(&[&condition], &[a.clone(), a.clone()], None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: case_when requires the same number of conditions and values, got 1 and 2"
+        );
+        let err = case_when(&[&condition], &[a], Some(&c)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: It is not possible to select between arrays of different data types (Int32 and Utf8)"
+        );
+    }
+}
@@ -0,0 +1,692 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines kernels for [`DictionaryArray`], such as encoding an array as a dictionary
+//! with [`dictionary_encode`], or merging the dictionaries of several arrays with
+//! [`unify_dictionaries`]
+//!
+//! ```
+//! # use arrow::array::{Array, DictionaryArray, StringArray};
+//! # use arrow::compute::kernels::dictionary::unify_dictionaries;
+//! # use arrow::datatypes::Int8Type;
+//! let a: DictionaryArray<Int8Type> = vec!["a", "b", "a"].into_iter().collect();
+//! let b: DictionaryArray<Int8Type> = vec!["c", "a"].into_iter().collect();
+//! let unified = unify_dictionaries(&[&a, &b]).unwrap();
+//!
+//! // Both arrays now share the same dictionary, without duplicate values
+//! let b = unified[1].as_any().downcast_ref::<DictionaryArray<Int8Type>>().unwrap();
+//! let expected = StringArray::from(vec!["a", "b", "c"]);
+//! assert_eq!(b.values().as_ref(), &expected);
+//! assert_eq!(b.keys().values(), &[2, 0]);
+//! ```
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use indexmap::IndexSet;
+
+use crate::array::*;
+use crate::compute::kernels::arity::try_unary;
+use crate::compute::kernels::cast::cast;
+use crate::compute::kernels::interleave::interleave;
+use crate::compute::kernels::take::take;
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+use crate::row::{OwnedRow, RowConverter, SortField};
+
+/// Calls `$func::<K>($args)` with the [`ArrowDictionaryKeyType`] `K` of the
+/// dictionary key type `$key_type`
+macro_rules! downcast_dictionary_key {
+    ($key_type: expr, $func: ident, $($args: expr),*) => {
+        match $key_type {
+            DataType::Int8 => $func::<Int8Type>($($args),*),
+            DataType::Int16 => $func::<Int16Type>($($args),*),
+            DataType::Int32 => $func::<Int32Type>($($args),*),
+            DataType::Int64 => $func::<Int64Type>($($args),*),
+            DataType::UInt8 => $func::<UInt8Type>($($args),*),
+            DataType::UInt16 => $func::<UInt16Type>($($args),*),
+            DataType::UInt32 => $func::<UInt32Type>($($args),*),
+            DataType::UInt64 => $func::<UInt64Type>($($args),*),
+            t => Err(ArrowError::InvalidArgumentError(format!(
+                "Dictionary key type {} is not supported",
+                t
+            ))),
+        }
+    };
+}
+
+/// Merges the values of several dictionaries into a single dictionary without
+/// duplicate values, returning for each dictionary the mapping from its keys to
+/// the keys of the merged dictionary.
+///
+/// Values are compared using the [row format](crate::row), and so the value type
+/// must be supported by [`RowConverter`]. Values keep their position as others are
+/// added, and so the merged dictionary of a [`DictionaryUnifier`] is always a prefix
+/// of its merged dictionary after adding more values.
+#[derive(Debug)]
+pub struct DictionaryUnifier {
+    value_type: DataType,
+    converter: RowConverter,
+    /// The distinct values, with their index being their key
+    distinct: IndexSet<OwnedRow>,
+    /// The arrays of values that contributed at least one distinct value
+    arrays: Vec<ArrayRef>,
+    /// The array and row of each distinct value
+    sources: Vec<(usize, usize)>,
+}
+
+impl DictionaryUnifier {
+    /// Create a new [`DictionaryUnifier`] for dictionaries of `value_type`
+    pub fn try_new(value_type: DataType) -> Result<Self> {
+        let converter = RowConverter::try_new(vec![SortField::new(value_type.clone())])?;
+        Ok(Self {
+            value_type,
+            converter,
+            distinct: IndexSet::new(),
+            arrays: vec![],
+            sources: vec![],
+        })
+    }
+
+    /// Returns the type of the values of the dictionaries
+    pub fn value_type(&self) -> &DataType {
+        &self.value_type
+    }
+
+    /// Returns the number of distinct values of the merged dictionary
+    pub fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Returns `true` if no values have been added
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+
+    /// Adds the values of a dictionary, returning the key in the merged dictionary
+    /// of each of them
+    pub fn push(&mut self, values: &ArrayRef) -> Result<Vec<usize>> {
+        if values.data_type() != &self.value_type {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Cannot merge dictionary values of type {} into a dictionary of {}",
+                values.data_type(),
+                self.value_type
+            )));
+        }
+
+        let rows = self
+            .converter
+            .convert_columns(std::slice::from_ref(values))?;
+        let array_idx = self.arrays.len();
+        let mapping: Vec<_> = rows
+            .iter()
+            .enumerate()
+            .map(|(idx, row)| match self.distinct.get_index_of(&row) {
+                Some(key) => key,
+                None => {
+                    self.sources.push((array_idx, idx));
+                    self.distinct.insert_full(row.owned()).0
+                }
+            })
+            .collect();
+        if self.sources.last().map(|(array, _)| *array) == Some(array_idx) {
+            self.arrays.push(values.clone());
+        }
+        Ok(mapping)
+    }
+
+    /// Returns the values of the merged dictionary
+    pub fn values(&self) -> Result<ArrayRef> {
+        self.values_since(0)
+    }
+
+    /// Returns the values of the merged dictionary after its first `len` values,
+    /// that is the values added since the merged dictionary had `len` values
+    pub fn values_since(&self, len: usize) -> Result<ArrayRef> {
+        let sources = &self.sources[len.min(self.sources.len())..];
+        if sources.is_empty() {
+            return Ok(new_empty_array(&self.value_type));
+        }
+        let arrays: Vec<_> = self.arrays.iter().map(|a| a.as_ref()).collect();
+        interleave(&arrays, sources)
+    }
+}
+
+/// Returns `true` if all of the dictionary `arrays` share the same dictionary
+/// values, that is the same buffers, without comparing the values themselves
+pub(crate) fn share_dictionary(arrays: &[&dyn Array]) -> bool {
+    arrays
+        .windows(2)
+        .all(|a| a[0].data().child_data()[0].ptr_eq(&a[1].data().child_data()[0]))
+}
+
+/// Returns the values of the dictionary `array` with keys of type `K`
+fn dictionary_values<K: ArrowDictionaryKeyType>(array: &dyn Array) -> Result<ArrayRef> {
+    Ok(as_dictionary_array::<K>(array).values().clone())
+}
+
+/// Replaces the dictionary of `array`, with keys of type `K`, with `values`,
+/// where `mapping` is the key in `values` of each key of `array`
+fn remap_keys<K: ArrowDictionaryKeyType>(
+    array: &dyn Array,
+    mapping: &[usize],
+    values: &ArrayRef,
+) -> Result<ArrayRef> {
+    let array = as_dictionary_array::<K>(array);
+    let keys = try_unary::<K, _, K>(array.keys(), |key| {
+        key.to_usize()
+            .and_then(|key| mapping.get(key))
+            .and_then(|key| K::Native::from_usize(*key))
+            .ok_or(ArrowError::DictionaryKeyOverflowError)
+    })?;
+    Ok(Arc::new(DictionaryArray::<K>::try_new(
+        &keys,
+        values.as_ref(),
+    )?))
+}
+
+/// Replaces the dictionary of the dictionary `array` with `values`, where `mapping`
+/// is the key in `values` of each key of `array`
+pub(crate) fn remap_dictionary(
+    array: &dyn Array,
+    mapping: &[usize],
+    values: &ArrayRef,
+) -> Result<ArrayRef> {
+    match array.data_type() {
+        DataType::Dictionary(key_type, _) => {
+            downcast_dictionary_key!(
+                key_type.as_ref(),
+                remap_keys,
+                array,
+                mapping,
+                values
+            )
+        }
+        t => Err(ArrowError::InvalidArgumentError(format!(
+            "Expected a dictionary array, got {}",
+            t
+        ))),
+    }
+}
+
+/// Returns `arrays`, which must be dictionary arrays of the same type, with their
+/// keys remapped to a single dictionary containing the distinct values of all of
+/// their dictionaries, in order of first appearance.
+///
+/// The returned arrays share the same dictionary values, and so can be efficiently
+/// concatenated or compared by key.
+///
+/// # Errors
+///
+/// This function errors if the arrays are not dictionary arrays of the same type,
+/// if their value type is not supported by the [row format](crate::row), or if the
+/// merged dictionary has more values than can be represented by the key type.
+pub fn unify_dictionaries(arrays: &[&dyn Array]) -> Result<Vec<ArrayRef>> {
+    let (key_type, value_type) = match arrays.first().map(|a| a.data_type()) {
+        Some(DataType::Dictionary(key_type, value_type)) => (key_type, value_type),
+        Some(t) => {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Expected dictionary arrays, got {}",
+                t
+            )))
+        }
+        None => return Ok(vec![]),
+    };
+    if let Some(array) = arrays
+        .iter()
+        .find(|a| a.data_type() != arrays[0].data_type())
+    {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "It is not possible to unify dictionaries of different types ({} and {})",
+            arrays[0].data_type(),
+            array.data_type()
+        )));
+    }
+
+    let mut unifier = DictionaryUnifier::try_new(value_type.as_ref().clone())?;
+    let mappings = arrays
+        .iter()
+        .map(|array| {
+            let values =
+                downcast_dictionary_key!(key_type.as_ref(), dictionary_values, *array)?;
+            unifier.push(&values)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let values = unifier.values()?;
+    arrays
+        .iter()
+        .zip(mappings)
+        .map(|(array, mapping)| remap_dictionary(*array, &mapping, &values))
+        .collect()
+}
+
+/// Dictionary encodes `array`, returning a [`DictionaryArray`] with keys of type
+/// `key_type`, whose values are the distinct values of `array` in order of first
+/// appearance. Nulls are encoded as null keys, rather than as a value.
+///
+/// If `key_type` is `None`, the smallest signed integer key type that can index all
+/// of the distinct values of `array` is used.
+///
+/// Primitive, string, binary, fixed size binary and decimal arrays are supported.
+///
+/// ```
+/// # use arrow::array::{Array, DictionaryArray, StringArray};
+/// # use arrow::compute::kernels::dictionary::dictionary_encode;
+/// # use arrow::datatypes::{DataType, Int8Type};
+/// let array = StringArray::from(vec![Some("a"), None, Some("b"), Some("a")]);
+/// let encoded = dictionary_encode(&array, None).unwrap();
+/// assert_eq!(
+///     encoded.data_type(),
+///     &DataType::Dictionary(Box::new(DataType::Int8), Box::new(DataType::Utf8))
+/// );
+///
+/// let encoded = encoded.as_any().downcast_ref::<DictionaryArray<Int8Type>>().unwrap();
+/// assert_eq!(encoded.values().as_ref(), &StringArray::from(vec!["a", "b"]));
+/// assert_eq!(encoded.keys().iter().collect::<Vec<_>>(), vec![Some(0), None, Some(1), Some(0)]);
+/// ```
+///
+/// # Errors
+///
+/// This function errors if the type of `array` is not supported, if `key_type` is not
+/// a dictionary key type, or if `array` has more distinct values than can be
+/// represented by `key_type`.
+pub fn dictionary_encode(
+    array: &dyn Array,
+    key_type: Option<&DataType>,
+) -> Result<ArrayRef> {
+    if let Some(key_type) = key_type {
+        return downcast_dictionary_key!(key_type, encode, array);
+    }
+
+    // Encode with the largest key type, and then narrow the keys to the smallest key
+    // type that can index all of the distinct values
+    let encoded = encode::<Int64Type>(array)?;
+    let distinct = encoded.data().child_data()[0].len();
+    let key_type = if distinct <= i8::MAX as usize + 1 {
+        DataType::Int8
+    } else if distinct <= i16::MAX as usize + 1 {
+        DataType::Int16
+    } else if distinct <= i32::MAX as usize + 1 {
+        DataType::Int32
+    } else {
+        return Ok(encoded);
+    };
+    let value_type = array.data_type().clone();
+    cast(
+        &encoded,
+        &DataType::Dictionary(Box::new(key_type), Box::new(value_type)),
+    )
+}
+
+/// `dictionary_encode` implementation for keys of type `K`
+fn encode<K: ArrowDictionaryKeyType>(array: &dyn Array) -> Result<ArrayRef> {
+    match array.data_type() {
+        DataType::Int8 => encode_primitive::<K, Int8Type>(array),
+        DataType::Int16 => encode_primitive::<K, Int16Type>(array),
+        DataType::Int32 => encode_primitive::<K, Int32Type>(array),
+        DataType::Int64 => encode_primitive::<K, Int64Type>(array),
+        DataType::UInt8 => encode_primitive::<K, UInt8Type>(array),
+        DataType::UInt16 => encode_primitive::<K, UInt16Type>(array),
+        DataType::UInt32 => encode_primitive::<K, UInt32Type>(array),
+        DataType::UInt64 => encode_primitive::<K, UInt64Type>(array),
+        DataType::Float16 => encode_primitive::<K, Float16Type>(array),
+        DataType::Float32 => encode_primitive::<K, Float32Type>(array),
+        DataType::Float64 => encode_primitive::<K, Float64Type>(array),
+        DataType::Date32 => encode_primitive::<K, Date32Type>(array),
+        DataType::Date64 => encode_primitive::<K, Date64Type>(array),
+        DataType::Time32(TimeUnit::Second) => {
+            encode_primitive::<K, Time32SecondType>(array)
+        }
+        DataType::Time32(TimeUnit::Millisecond) => {
+            encode_primitive::<K, Time32MillisecondType>(array)
+        }
+        DataType::Time64(TimeUnit::Microsecond) => {
+            encode_primitive::<K, Time64MicrosecondType>(array)
+        }
+        DataType::Time64(TimeUnit::Nanosecond) => {
+            encode_primitive::<K, Time64NanosecondType>(array)
+        }
+        DataType::Timestamp(TimeUnit::Second, _) => {
+            encode_primitive::<K, TimestampSecondType>(array)
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            encode_primitive::<K, TimestampMillisecondType>(array)
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            encode_primitive::<K, TimestampMicrosecondType>(array)
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            encode_primitive::<K, TimestampNanosecondType>(array)
+        }
+        DataType::Interval(IntervalUnit::YearMonth) => {
+            encode_primitive::<K, IntervalYearMonthType>(array)
+        }
+        DataType::Interval(IntervalUnit::DayTime) => {
+            encode_primitive::<K, IntervalDayTimeType>(array)
+        }
+        DataType::Interval(IntervalUnit::MonthDayNano) => {
+            encode_primitive::<K, IntervalMonthDayNanoType>(array)
+        }
+        DataType::Duration(TimeUnit::Second) => {
+            encode_primitive::<K, DurationSecondType>(array)
+        }
+        DataType::Duration(TimeUnit::Millisecond) => {
+            encode_primitive::<K, DurationMillisecondType>(array)
+        }
+        DataType::Duration(TimeUnit::Microsecond) => {
+            encode_primitive::<K, DurationMicrosecondType>(array)
+        }
+        DataType::Duration(TimeUnit::Nanosecond) => {
+            encode_primitive::<K, DurationNanosecondType>(array)
+        }
+        DataType::Utf8 => encode_string::<K>(array),
+        DataType::LargeUtf8 => {
+            encode_hashable::<K, _>(array, as_largestring_array(array))
+        }
+        DataType::Binary => {
+            let array = array
+                .as_any()
+                .downcast_ref::<BinaryArray>()
+                .expect("Unable to downcast to a binary array");
+            encode_hashable::<K, _>(array, array)
+        }
+        DataType::LargeBinary => {
+            let array = array
+                .as_any()
+                .downcast_ref::<LargeBinaryArray>()
+                .expect("Unable to downcast to a large binary array");
+            encode_hashable::<K, _>(array, array)
+        }
+        DataType::FixedSizeBinary(_) => {
+            let array = array
+                .as_any()
+                .downcast_ref::<FixedSizeBinaryArray>()
+                .expect("Unable to downcast to a fixed size binary array");
+            let values = (0..array.len()).map(|i| match array.is_valid(i) {
+                true => Some(array.value(i)),
+                false => None,
+            });
+            encode_hashable::<K, _>(array, values)
+        }
+        DataType::Decimal(_, _) => {
+            let array = array
+                .as_any()
+                .downcast_ref::<DecimalArray>()
+                .expect("Unable to downcast to a decimal array");
+            encode_hashable::<K, _>(array, array)
+        }
+        t => Err(ArrowError::InvalidArgumentError(format!(
+            "Dictionary encoding of {} is not supported",
+            t
+        ))),
+    }
+}
+
+/// `dictionary_encode` implementation for primitive arrays of type `V`
+fn encode_primitive<K, V>(array: &dyn Array) -> Result<ArrayRef>
+where
+    K: ArrowDictionaryKeyType,
+    V: ArrowPrimitiveType,
+{
+    let array = as_primitive_array::<V>(array);
+    let keys_builder = PrimitiveBuilder::<K>::new(array.len());
+    let values_builder = PrimitiveBuilder::<V>::new(0);
+    let mut builder = PrimitiveDictionaryBuilder::new(keys_builder, values_builder);
+    for value in array.iter() {
+        match value {
+            Some(value) => {
+                builder.append(value)?;
+            }
+            None => builder.append_null()?,
+        }
+    }
+    let dictionary = builder.finish();
+    if dictionary.values().data_type() == array.data_type() {
+        return Ok(Arc::new(dictionary));
+    }
+
+    // The builder does not preserve parameters of the value type, such as a timezone
+    let values = dictionary.values().data();
+    let values = ArrayData::builder(array.data_type().clone())
+        .len(values.len())
+        .offset(values.offset())
+        .null_bit_buffer(values.null_buffer().cloned())
+        .buffers(values.buffers().to_vec())
+        .build()?;
+    Ok(Arc::new(DictionaryArray::<K>::try_new(
+        dictionary.keys(),
+        &make_array(values),
+    )?))
+}
+
+/// `dictionary_encode` implementation for string arrays
+fn encode_string<K: ArrowDictionaryKeyType>(array: &dyn Array) -> Result<ArrayRef> {
+    let array = as_string_array(array);
+    let keys_builder = PrimitiveBuilder::<K>::new(array.len());
+    let values_builder = StringBuilder::new(0);
+    let mut builder = StringDictionaryBuilder::new(keys_builder, values_builder);
+    for value in array {
+        match value {
+            Some(value) => {
+                builder.append(value)?;
+            }
+            None => builder.append_null()?,
+        }
+    }
+    Ok(Arc::new(builder.finish()))
+}
+
+/// `dictionary_encode` implementation for arrays without a dictionary builder, where
+/// `values` are the values of `array`. The dictionary is built by taking the first
+/// occurrence of each distinct value from `array`.
+fn encode_hashable<K, V>(
+    array: &dyn Array,
+    values: impl IntoIterator<Item = Option<V>>,
+) -> Result<ArrayRef>
+where
+    K: ArrowDictionaryKeyType,
+    V: Hash + Eq,
+{
+    let mut distinct: HashMap<V, K::Native> = HashMap::new();
+    let mut first: Vec<u64> = vec![];
+    let keys = values
+        .into_iter()
+        .enumerate()
+        .map(|(idx, value)| {
+            value
+                .map(|value| match distinct.entry(value) {
+                    Entry::Occupied(entry) => Ok(*entry.get()),
+                    Entry::Vacant(entry) => {
+                        let key = K::Native::from_usize(first.len())
+                            .ok_or(ArrowError::DictionaryKeyOverflowError)?;
+                        first.push(idx as u64);
+                        Ok(*entry.insert(key))
+                    }
+                })
+                .transpose()
+        })
+        .collect::<Result<PrimitiveArray<K>>>()?;
+
+    let values = take(array, &UInt64Array::from(first), None)?;
+    Ok(Arc::new(DictionaryArray::<K>::try_new(
+        &keys,
+        values.as_ref(),
+    )?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unifier() {
+        let mut unifier = DictionaryUnifier::try_new(DataType::Utf8).unwrap();
+        assert!(unifier.is_empty());
+        assert_eq!(unifier.values().unwrap().len(), 0);
+
+        let a = Arc::new(StringArray::from(vec![Some("x"), Some("y"), None])) as ArrayRef;
+        assert_eq!(unifier.push(&a).unwrap(), vec![0, 1, 2]);
+        let b = Arc::new(StringArray::from(vec![
+            Some("z"),
+            None,
+            Some("x"),
+            Some("z"),
+        ])) as ArrayRef;
+        assert_eq!(unifier.push(&b).unwrap(), vec![3, 2, 0, 3]);
+        // arrays without new values are not retained
+        assert_eq!(unifier.push(&a).unwrap(), vec![0, 1, 2]);
+        assert_eq!(unifier.arrays.len(), 2);
+
+        assert_eq!(unifier.len(), 4);
+        let expected = StringArray::from(vec![Some("x"), Some("y"), None, Some("z")]);
+        assert_eq!(unifier.values().unwrap().as_ref(), &expected);
+        let expected = StringArray::from(vec!["z"]);
+        assert_eq!(unifier.values_since(3).unwrap().as_ref(), &expected);
+        assert_eq!(unifier.values_since(4).unwrap().len(), 0);
+
+        let c = Arc::new(Int32Array::from(vec![1])) as ArrayRef;
+        let err = unifier.push(&c).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Cannot merge dictionary values of type Int32 into a dictionary of Utf8"
+        );
+    }
+
+    #[test]
+    fn test_unify_dictionaries() {
+        let keys = UInt16Array::from(vec![Some(0), None, Some(1)]);
+        let values = Int64Array::from(vec![3, 1]);
+        let a = DictionaryArray::<UInt16Type>::try_new(&keys, &values).unwrap();
+        let keys = UInt16Array::from(vec![Some(1), Some(0), None, Some(1)]);
+        let values = Int64Array::from(vec![2, 3]);
+        let b = DictionaryArray::<UInt16Type>::try_new(&keys, &values).unwrap();
+        let b = b.slice(1, 3);
+
+        let unified = unify_dictionaries(&[&a, b.as_ref()]).unwrap();
+        assert!(share_dictionary(&[
+            unified[0].as_ref(),
+            unified[1].as_ref()
+        ]));
+
+        let expected_values = Int64Array::from(vec![3, 1, 2]);
+        let a = as_dictionary_array::<UInt16Type>(&unified[0]);
+        assert_eq!(a.values().as_ref(), &expected_values);
+        assert_eq!(a.keys(), &UInt16Array::from(vec![Some(0), None, Some(1)]));
+        let b = as_dictionary_array::<UInt16Type>(&unified[1]);
+        assert_eq!(b.keys(), &UInt16Array::from(vec![Some(2), None, Some(0)]));
+
+        let values = StringArray::from_iter_values((0..200).map(|i| i.to_string()));
+        let keys = Int8Array::from_iter_values(0..100);
+        let a =
+            DictionaryArray::<Int8Type>::try_new(&keys, values.slice(0, 100).as_ref())
+                .unwrap();
+        let b =
+            DictionaryArray::<Int8Type>::try_new(&keys, values.slice(100, 100).as_ref())
+                .unwrap();
+        let err = unify_dictionaries(&[&a, &b]).unwrap_err();
+        assert_eq!(err.to_string(), "Dictionary key bigger than the key type");
+
+        let c = Int8Array::from(vec![1]);
+        let err = unify_dictionaries(&[&a, &c]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: It is not possible to unify dictionaries of different types (Dictionary(Int8, Utf8) and Int8)"
+        );
+    }
+
+    #[test]
+    fn test_dictionary_encode() {
+        let array = TimestampSecondArray::from_opt_vec(
+            vec![Some(5), None, Some(3), Some(5), None],
+            Some("UTC".to_string()),
+        );
+        let encoded = dictionary_encode(&array, Some(&DataType::UInt32)).unwrap();
+        let encoded = as_dictionary_array::<UInt32Type>(&encoded);
+        assert_eq!(encoded.values().data_type(), array.data_type());
+        let expected = UInt32Array::from(vec![Some(0), None, Some(1), Some(0), None]);
+        assert_eq!(encoded.keys(), &expected);
+
+        let array = Int32Array::from_iter_values((0..1000).map(|i| i % 200));
+        let encoded = dictionary_encode(&array, None).unwrap();
+        let encoded = as_dictionary_array::<Int16Type>(&encoded);
+        assert_eq!(encoded.values().len(), 200);
+        assert_eq!(encoded.keys().value(999), 199);
+        let err = dictionary_encode(&array, Some(&DataType::Int8)).unwrap_err();
+        assert_eq!(err.to_string(), "Dictionary key bigger than the key type");
+
+        // The smallest key type that can index all distinct values is used
+        for (distinct, key_type) in [
+            (128, DataType::Int8),
+            (129, DataType::Int16),
+            (32768, DataType::Int16),
+            (32769, DataType::Int32),
+        ] {
+            let array = Int32Array::from_iter_values((0..distinct).rev());
+            let encoded = dictionary_encode(&array, None).unwrap();
+            assert_eq!(
+                encoded.data_type(),
+                &DataType::Dictionary(Box::new(key_type), Box::new(DataType::Int32))
+            );
+            let decoded = cast(&encoded, &DataType::Int32).unwrap();
+            assert_eq!(decoded.as_ref(), &array);
+        }
+
+        let array = BinaryArray::from(vec![Some(b"ab".as_ref()), None, Some(b"ab")]);
+        let array = array.slice(1, 2);
+        let encoded = dictionary_encode(array.as_ref(), None).unwrap();
+        let encoded = as_dictionary_array::<Int8Type>(&encoded);
+        assert_eq!(encoded.keys(), &Int8Array::from(vec![None, Some(0)]));
+        let expected = BinaryArray::from(vec![b"ab".as_ref()]);
+        assert_eq!(encoded.values().as_ref(), &expected);
+
+        let array = FixedSizeBinaryArray::try_from_sparse_iter(
+            vec![Some(vec![1, 2]), Some(vec![3, 4]), None, Some(vec![1, 2])].into_iter(),
+        )
+        .unwrap();
+        let encoded = dictionary_encode(&array, None).unwrap();
+        let encoded = as_dictionary_array::<Int8Type>(&encoded);
+        let expected = Int8Array::from(vec![Some(0), Some(1), None, Some(0)]);
+        assert_eq!(encoded.keys(), &expected);
+        assert_eq!(encoded.values().len(), 2);
+
+        let array = vec![Some(123_i128), Some(-1), Some(123)]
+            .into_iter()
+            .collect::<DecimalArray>()
+            .with_precision_and_scale(5, 2)
+            .unwrap();
+        let encoded = dictionary_encode(&array, Some(&DataType::UInt8)).unwrap();
+        let encoded = as_dictionary_array::<UInt8Type>(&encoded);
+        assert_eq!(encoded.keys(), &UInt8Array::from(vec![0, 1, 0]));
+        assert_eq!(encoded.values().data_type(), &DataType::Decimal(5, 2));
+
+        let array = BooleanArray::from(vec![true]);
+        let err = dictionary_encode(&array, None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Dictionary encoding of Boolean is not supported"
+        );
+        let array = Int32Array::from(vec![1]);
+        let err = dictionary_encode(&array, Some(&DataType::Utf8)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Dictionary key type Utf8 is not supported"
+        );
+    }
+}
@@ -0,0 +1,799 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines a hash based grouped aggregation kernel, that computes aggregates
+//! such as `count`, `sum`, `min`, `max` and `mean` for each distinct value of
+//! one or more key columns, across one or more [`RecordBatch`]
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use arrow::array::{ArrayRef, Float64Array, Int64Array, StringArray, UInt64Array};
+//! # use arrow::compute::kernels::group_by::{AggregateFunction, GroupedAggregator};
+//! # use arrow::record_batch::RecordBatch;
+//! let keys = Arc::new(StringArray::from(vec!["a", "b", "a"])) as ArrayRef;
+//! let values = Arc::new(Int64Array::from(vec![1, 2, 3])) as ArrayRef;
+//! let batch = RecordBatch::try_from_iter(vec![("k", keys), ("v", values)]).unwrap();
+//!
+//! let mut aggregator = GroupedAggregator::try_new(
+//!     batch.schema(),
+//!     vec![0],
+//!     vec![(1, AggregateFunction::Count), (1, AggregateFunction::Sum)],
+//! )
+//! .unwrap();
+//!
+//! // Batches can be fed in incrementally
+//! aggregator.update(&batch).unwrap();
+//! aggregator.update(&batch).unwrap();
+//!
+//! let output = aggregator.finish().unwrap();
+//! assert_eq!(output.schema().field(1).name(), "count(v)");
+//! assert_eq!(output.schema().field(2).name(), "sum(v)");
+//!
+//! // Groups are returned in order of first appearance
+//! let expected: Vec<ArrayRef> = vec![
+//!     Arc::new(StringArray::from(vec!["a", "b"])),
+//!     Arc::new(UInt64Array::from(vec![4, 2])),
+//!     Arc::new(Int64Array::from(vec![8, 4])),
+//! ];
+//! assert_eq!(output.columns(), &expected);
+//! ```
+
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Add;
+use std::sync::Arc;
+
+use indexmap::{Equivalent, IndexSet};
+use num::ToPrimitive;
+
+use crate::array::*;
+use crate::compute::kernels::hash::normalize_floats;
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+use crate::record_batch::RecordBatch;
+use crate::row::{OwnedRow, Row, RowConverter, SortField};
+
+/// An aggregate function that can be computed by [`GroupedAggregator`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AggregateFunction {
+    /// The number of non-null values, as a non-nullable `UInt64`
+    Count,
+    /// The sum of the non-null values, of the same type as the input, or null if
+    /// there are no non-null values
+    Sum,
+    /// The minimum non-null value, with the same semantics as [`min`](crate::compute::min)
+    Min,
+    /// The maximum non-null value, with the same semantics as [`max`](crate::compute::max)
+    Max,
+    /// The arithmetic mean of the non-null values as a `Float64`, or null if there
+    /// are no non-null values
+    Mean,
+}
+
+impl Display for AggregateFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            AggregateFunction::Count => "count",
+            AggregateFunction::Sum => "sum",
+            AggregateFunction::Min => "min",
+            AggregateFunction::Max => "max",
+            AggregateFunction::Mean => "mean",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Computes aggregates grouped by the distinct values of one or more key columns
+///
+/// Keys are compared using the [row format](crate::row), so any type supported by
+/// [`RowConverter`] may be used as a key, including strings, dictionaries, and
+/// combinations of multiple columns. Null key values form their own group.
+///
+/// As with the [hash kernels](crate::compute::kernels::hash), floating point `-0.0`
+/// and `0.0` are the same key, as are all `NaN` values.
+///
+/// The output contains one row per distinct key, in the order in which the keys
+/// were first seen, with the key columns followed by one column per aggregate
+#[derive(Debug)]
+pub struct GroupedAggregator {
+    schema: SchemaRef,
+    output_schema: SchemaRef,
+    group_by: Vec<usize>,
+    aggregates: Vec<(usize, Box<dyn GroupAccumulator>)>,
+    converter: RowConverter,
+    groups: IndexSet<OwnedRow>,
+}
+
+impl GroupedAggregator {
+    /// Create a new [`GroupedAggregator`] for batches of `schema`, grouping by the
+    /// columns at indices `group_by`, and computing the aggregates in `aggregates`,
+    /// where each is a column index and the function to compute on that column
+    ///
+    /// Returns an error if any of the columns are out of bounds, `group_by` is empty,
+    /// or the aggregate function is not supported for the type of its column
+    pub fn try_new(
+        schema: SchemaRef,
+        group_by: Vec<usize>,
+        aggregates: Vec<(usize, AggregateFunction)>,
+    ) -> Result<Self> {
+        if group_by.is_empty() {
+            return Err(ArrowError::InvalidArgumentError(
+                "Grouped aggregation requires at least one group by column".to_string(),
+            ));
+        }
+
+        let field = |idx: usize| {
+            schema.fields().get(idx).ok_or_else(|| {
+                ArrowError::InvalidArgumentError(format!(
+                    "Column index {} out of bounds for schema with {} fields",
+                    idx,
+                    schema.fields().len()
+                ))
+            })
+        };
+
+        let mut sort_fields = Vec::with_capacity(group_by.len());
+        let mut output_fields = Vec::with_capacity(group_by.len() + aggregates.len());
+        for idx in &group_by {
+            let field = field(*idx)?;
+            sort_fields.push(SortField::new(field.data_type().clone()));
+            output_fields.push(field.clone());
+        }
+        let converter = RowConverter::try_new(sort_fields)?;
+
+        let aggregates = aggregates
+            .into_iter()
+            .map(|(idx, function)| {
+                let field = field(idx)?;
+                let accumulator = new_accumulator(function, field.data_type())?;
+                output_fields.push(Field::new(
+                    &format!("{}({})", function, field.name()),
+                    accumulator.data_type(),
+                    function != AggregateFunction::Count,
+                ));
+                Ok((idx, accumulator))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            output_schema: Arc::new(Schema::new(output_fields)),
+            schema,
+            group_by,
+            aggregates,
+            converter,
+            groups: IndexSet::new(),
+        })
+    }
+
+    /// Returns the schema of the [`RecordBatch`] returned by [`Self::finish`]
+    pub fn output_schema(&self) -> SchemaRef {
+        self.output_schema.clone()
+    }
+
+    /// Returns the number of distinct groups seen so far
+    pub fn num_groups(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Updates the aggregates with the rows of `batch`
+    ///
+    /// Returns an error if the columns of `batch` do not match the schema
+    /// provided to [`Self::try_new`]
+    pub fn update(&mut self, batch: &RecordBatch) -> Result<()> {
+        let schema = batch.schema();
+        let matches = schema.fields().len() == self.schema.fields().len()
+            && schema
+                .fields()
+                .iter()
+                .zip(self.schema.fields())
+                .all(|(a, b)| a.data_type() == b.data_type());
+        if !matches {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Batch schema {} does not match aggregator schema {}",
+                schema, self.schema
+            )));
+        }
+
+        let keys = self
+            .group_by
+            .iter()
+            .map(|idx| normalize_floats(batch.column(*idx)))
+            .collect::<Result<Vec<_>>>()?;
+        let rows = self.converter.convert_columns(&keys)?;
+
+        let group_indices: Vec<usize> = rows
+            .iter()
+            .map(|row| match self.groups.get_index_of(&row) {
+                Some(idx) => idx,
+                None => self.groups.insert_full(row.owned()).0,
+            })
+            .collect();
+
+        let num_groups = self.groups.len();
+        for (idx, accumulator) in &mut self.aggregates {
+            accumulator.update(
+                &group_indices,
+                num_groups,
+                batch.column(*idx).as_ref(),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Returns a [`RecordBatch`] containing the aggregates computed for each group,
+    /// and resets this [`GroupedAggregator`] so that it can be reused
+    pub fn finish(&mut self) -> Result<RecordBatch> {
+        let groups = std::mem::take(&mut self.groups);
+        let mut columns = self
+            .converter
+            .convert_rows(groups.iter().map(|r| r.row()))?;
+        for (_, accumulator) in &mut self.aggregates {
+            columns.push(accumulator.finish(groups.len())?);
+        }
+        RecordBatch::try_new(self.output_schema(), columns)
+    }
+}
+
+/// Computes the aggregates in `aggregates` on `batch` grouped by the columns at
+/// indices `group_by`, see [`GroupedAggregator`]
+pub fn group_by(
+    batch: &RecordBatch,
+    group_by: &[usize],
+    aggregates: &[(usize, AggregateFunction)],
+) -> Result<RecordBatch> {
+    let mut aggregator = GroupedAggregator::try_new(
+        batch.schema(),
+        group_by.to_vec(),
+        aggregates.to_vec(),
+    )?;
+    aggregator.update(batch)?;
+    aggregator.finish()
+}
+
+/// Allows looking up a [`Row`] in an [`IndexSet`] of [`OwnedRow`] without
+/// allocating, their `Hash` implementations are consistent
+impl<'a> Equivalent<OwnedRow> for Row<'a> {
+    fn equivalent(&self, key: &OwnedRow) -> bool {
+        *self == key.row()
+    }
+}
+
+/// The state of an aggregate function for each group
+trait GroupAccumulator: Debug + Send {
+    /// The data type of the output of this accumulator
+    fn data_type(&self) -> DataType;
+
+    /// Updates the state with `values`, where `groups[i]` is the group index of `values[i]`
+    /// and `num_groups` is the total number of groups
+    fn update(
+        &mut self,
+        groups: &[usize],
+        num_groups: usize,
+        values: &dyn Array,
+    ) -> Result<()>;
+
+    /// Returns the aggregate for each of the `num_groups` groups, and resets the state
+    fn finish(&mut self, num_groups: usize) -> Result<ArrayRef>;
+}
+
+macro_rules! numeric_accumulator {
+    ($data_type:expr, $t:ident => $new:expr, $fallback:expr) => {
+        match $data_type {
+            DataType::Int8 => numeric_accumulator!(@new Int8Type, $t => $new),
+            DataType::Int16 => numeric_accumulator!(@new Int16Type, $t => $new),
+            DataType::Int32 => numeric_accumulator!(@new Int32Type, $t => $new),
+            DataType::Int64 => numeric_accumulator!(@new Int64Type, $t => $new),
+            DataType::UInt8 => numeric_accumulator!(@new UInt8Type, $t => $new),
+            DataType::UInt16 => numeric_accumulator!(@new UInt16Type, $t => $new),
+            DataType::UInt32 => numeric_accumulator!(@new UInt32Type, $t => $new),
+            DataType::UInt64 => numeric_accumulator!(@new UInt64Type, $t => $new),
+            DataType::Float32 => numeric_accumulator!(@new Float32Type, $t => $new),
+            DataType::Float64 => numeric_accumulator!(@new Float64Type, $t => $new),
+            _ => $fallback,
+        }
+    };
+    (@new $native:ty, $t:ident => $new:expr) => {{
+        type $t = $native;
+        Box::new($new) as Box<dyn GroupAccumulator>
+    }};
+}
+
+fn new_accumulator(
+    function: AggregateFunction,
+    data_type: &DataType,
+) -> Result<Box<dyn GroupAccumulator>> {
+    let unsupported = || {
+        Err(ArrowError::NotYetImplemented(format!(
+            "Grouped aggregate {} not supported for data type {:?}",
+            function, data_type
+        )))
+    };
+
+    let max = function == AggregateFunction::Max;
+    Ok(match function {
+        AggregateFunction::Count => Box::new(CountAccumulator::default()),
+        AggregateFunction::Sum => numeric_accumulator!(
+            data_type,
+            T => SumAccumulator::<T>::new(data_type),
+            return unsupported()
+        ),
+        AggregateFunction::Mean => numeric_accumulator!(
+            data_type,
+            T => MeanAccumulator::<T>::new(),
+            return unsupported()
+        ),
+        AggregateFunction::Min | AggregateFunction::Max => {
+            match data_type {
+                DataType::Date32 => {
+                    Box::new(MinMaxAccumulator::<Date32Type>::new(data_type, max))
+                }
+                DataType::Date64 => {
+                    Box::new(MinMaxAccumulator::<Date64Type>::new(data_type, max))
+                }
+                DataType::Timestamp(TimeUnit::Second, _) => Box::new(
+                    MinMaxAccumulator::<TimestampSecondType>::new(data_type, max),
+                ),
+                DataType::Timestamp(TimeUnit::Millisecond, _) => Box::new(
+                    MinMaxAccumulator::<TimestampMillisecondType>::new(data_type, max),
+                ),
+                DataType::Timestamp(TimeUnit::Microsecond, _) => Box::new(
+                    MinMaxAccumulator::<TimestampMicrosecondType>::new(data_type, max),
+                ),
+                DataType::Timestamp(TimeUnit::Nanosecond, _) => Box::new(
+                    MinMaxAccumulator::<TimestampNanosecondType>::new(data_type, max),
+                ),
+                _ => numeric_accumulator!(
+                    data_type,
+                    T => MinMaxAccumulator::<T>::new(data_type, max),
+                    return unsupported()
+                ),
+            }
+        }
+    })
+}
+
+#[derive(Debug, Default)]
+struct CountAccumulator {
+    counts: Vec<u64>,
+}
+
+impl GroupAccumulator for CountAccumulator {
+    fn data_type(&self) -> DataType {
+        DataType::UInt64
+    }
+
+    fn update(
+        &mut self,
+        groups: &[usize],
+        num_groups: usize,
+        values: &dyn Array,
+    ) -> Result<()> {
+        self.counts.resize(num_groups, 0);
+        for (idx, group) in groups.iter().enumerate() {
+            if values.is_valid(idx) {
+                self.counts[*group] += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self, num_groups: usize) -> Result<ArrayRef> {
+        let mut counts = std::mem::take(&mut self.counts);
+        counts.resize(num_groups, 0);
+        Ok(Arc::new(UInt64Array::from(counts)))
+    }
+}
+
+#[derive(Debug)]
+struct SumAccumulator<T: ArrowPrimitiveType> {
+    data_type: DataType,
+    sums: Vec<Option<T::Native>>,
+}
+
+impl<T: ArrowPrimitiveType> SumAccumulator<T> {
+    fn new(data_type: &DataType) -> Self {
+        Self {
+            data_type: data_type.clone(),
+            sums: vec![],
+        }
+    }
+}
+
+impl<T> GroupAccumulator for SumAccumulator<T>
+where
+    T: ArrowNumericType + Debug,
+    T::Native: Add<Output = T::Native>,
+{
+    fn data_type(&self) -> DataType {
+        self.data_type.clone()
+    }
+
+    fn update(
+        &mut self,
+        groups: &[usize],
+        num_groups: usize,
+        values: &dyn Array,
+    ) -> Result<()> {
+        self.sums.resize(num_groups, None);
+        let values = as_primitive_array::<T>(values);
+        for (value, group) in values.iter().zip(groups) {
+            if let Some(value) = value {
+                let sum = &mut self.sums[*group];
+                *sum = Some(match sum {
+                    Some(sum) => *sum + value,
+                    None => value,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self, num_groups: usize) -> Result<ArrayRef> {
+        let mut sums = std::mem::take(&mut self.sums);
+        sums.resize(num_groups, None);
+        let array: PrimitiveArray<T> = sums.into_iter().collect();
+        Ok(Arc::new(array))
+    }
+}
+
+#[derive(Debug)]
+struct MeanAccumulator<T> {
+    sums: Vec<f64>,
+    counts: Vec<u64>,
+    phantom: std::marker::PhantomData<T>,
+}
+
+impl<T> MeanAccumulator<T> {
+    fn new() -> Self {
+        Self {
+            sums: vec![],
+            counts: vec![],
+            phantom: Default::default(),
+        }
+    }
+}
+
+impl<T> GroupAccumulator for MeanAccumulator<T>
+where
+    T: ArrowNumericType + Debug + Send,
+    T::Native: ToPrimitive,
+{
+    fn data_type(&self) -> DataType {
+        DataType::Float64
+    }
+
+    fn update(
+        &mut self,
+        groups: &[usize],
+        num_groups: usize,
+        values: &dyn Array,
+    ) -> Result<()> {
+        self.sums.resize(num_groups, 0.);
+        self.counts.resize(num_groups, 0);
+        let values = as_primitive_array::<T>(values);
+        for (value, group) in values.iter().zip(groups) {
+            if let Some(value) = value {
+                // Native numeric types can always be represented as f64, albeit lossily
+                self.sums[*group] += value.to_f64().unwrap();
+                self.counts[*group] += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self, num_groups: usize) -> Result<ArrayRef> {
+        let mut sums = std::mem::take(&mut self.sums);
+        let mut counts = std::mem::take(&mut self.counts);
+        sums.resize(num_groups, 0.);
+        counts.resize(num_groups, 0);
+        let array: Float64Array = sums
+            .into_iter()
+            .zip(counts)
+            .map(|(sum, count)| (count != 0).then(|| sum / count as f64))
+            .collect();
+        Ok(Arc::new(array))
+    }
+}
+
+/// Generic test for NaN, the optimizer should be able to remove this for integer types.
+#[inline]
+fn is_nan<T: PartialOrd>(a: &T) -> bool {
+    #[allow(clippy::eq_op)]
+    !(a == a)
+}
+
+#[derive(Debug)]
+struct MinMaxAccumulator<T: ArrowPrimitiveType> {
+    data_type: DataType,
+    max: bool,
+    values: Vec<Option<T::Native>>,
+}
+
+impl<T: ArrowPrimitiveType> MinMaxAccumulator<T> {
+    fn new(data_type: &DataType, max: bool) -> Self {
+        Self {
+            data_type: data_type.clone(),
+            max,
+            values: vec![],
+        }
+    }
+}
+
+impl<T: ArrowPrimitiveType + Debug> GroupAccumulator for MinMaxAccumulator<T> {
+    fn data_type(&self) -> DataType {
+        self.data_type.clone()
+    }
+
+    fn update(
+        &mut self,
+        groups: &[usize],
+        num_groups: usize,
+        values: &dyn Array,
+    ) -> Result<()> {
+        self.values.resize(num_groups, None);
+        let values = as_primitive_array::<T>(values);
+        for (value, group) in values.iter().zip(groups) {
+            if let Some(value) = value {
+                let current = &mut self.values[*group];
+                // NaN is considered to be greater than any other value, as in `compute::max`
+                let replace = match current {
+                    None => true,
+                    Some(cur) if self.max => {
+                        (!is_nan(cur) & is_nan(&value)) || *cur < value
+                    }
+                    Some(cur) => (is_nan(cur) & !is_nan(&value)) || *cur > value,
+                };
+                if replace {
+                    *current = Some(value);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self, num_groups: usize) -> Result<ArrayRef> {
+        let mut values = std::mem::take(&mut self.values);
+        values.resize(num_groups, None);
+        let array: PrimitiveArray<T> = values.into_iter().collect();
+        // Preserve any timezone of the input
+        let data = ArrayData::builder(self.data_type.clone())
+            .len(array.len())
+            .add_buffer(array.data().buffers()[0].clone())
+            .null_bit_buffer(array.data().null_buffer().cloned())
+            .build()?;
+        Ok(make_array(data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch(keys: ArrayRef, values: ArrayRef) -> RecordBatch {
+        RecordBatch::try_from_iter(vec![("k", keys), ("v", values)]).unwrap()
+    }
+
+    #[test]
+    fn test_group_by_string() {
+        let keys = Arc::new(StringArray::from(vec![
+            Some("a"),
+            None,
+            Some("b"),
+            Some("a"),
+            None,
+            Some("c"),
+        ])) as ArrayRef;
+        let values = Arc::new(Int32Array::from(vec![
+            Some(1),
+            Some(2),
+            None,
+            Some(4),
+            Some(5),
+            None,
+        ])) as ArrayRef;
+        let batch = batch(keys, values);
+
+        let output = group_by(
+            &batch,
+            &[0],
+            &[
+                (1, AggregateFunction::Count),
+                (1, AggregateFunction::Sum),
+                (1, AggregateFunction::Min),
+                (1, AggregateFunction::Max),
+                (1, AggregateFunction::Mean),
+            ],
+        )
+        .unwrap();
+
+        let schema = output.schema();
+        let names: Vec<_> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+        assert_eq!(
+            names,
+            &["k", "count(v)", "sum(v)", "min(v)", "max(v)", "mean(v)"]
+        );
+        assert!(!schema.field(1).is_nullable());
+
+        let expected: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from(vec![
+                Some("a"),
+                None,
+                Some("b"),
+                Some("c"),
+            ])),
+            Arc::new(UInt64Array::from(vec![2, 2, 0, 0])),
+            Arc::new(Int32Array::from(vec![Some(5), Some(7), None, None])),
+            Arc::new(Int32Array::from(vec![Some(1), Some(2), None, None])),
+            Arc::new(Int32Array::from(vec![Some(4), Some(5), None, None])),
+            Arc::new(Float64Array::from(vec![Some(2.5), Some(3.5), None, None])),
+        ];
+        assert_eq!(output.columns(), &expected);
+    }
+
+    #[test]
+    fn test_group_by_incremental_dictionary() {
+        let b1 = batch(
+            Arc::new(
+                vec!["x", "y", "x"]
+                    .into_iter()
+                    .collect::<DictionaryArray<Int32Type>>(),
+            ),
+            Arc::new(Float64Array::from(vec![1., 2., 3.])),
+        );
+        // Second batch has a different dictionary
+        let b2 = batch(
+            Arc::new(
+                vec!["z", "y", "x"]
+                    .into_iter()
+                    .collect::<DictionaryArray<Int32Type>>(),
+            ),
+            Arc::new(Float64Array::from(vec![4., f64::NAN, 6.])),
+        );
+
+        let mut aggregator = GroupedAggregator::try_new(
+            b1.schema(),
+            vec![0],
+            vec![(1, AggregateFunction::Max), (1, AggregateFunction::Min)],
+        )
+        .unwrap();
+        aggregator.update(&b1).unwrap();
+        assert_eq!(aggregator.num_groups(), 2);
+        aggregator.update(&b2).unwrap();
+        assert_eq!(aggregator.num_groups(), 3);
+
+        let output = aggregator.finish().unwrap();
+        assert_eq!(output.schema(), aggregator.output_schema());
+        assert_eq!(output.column(0).data_type(), b1.column(0).data_type());
+
+        let keys = as_dictionary_array::<Int32Type>(output.column(0));
+        let values = as_string_array(keys.values());
+        let keys: Vec<_> = keys.keys_iter().map(|k| values.value(k.unwrap())).collect();
+        assert_eq!(keys, &["x", "y", "z"]);
+
+        let max = as_primitive_array::<Float64Type>(output.column(1));
+        assert_eq!(max.value(0), 6.);
+        assert!(max.value(1).is_nan());
+        assert_eq!(max.value(2), 4.);
+
+        let min = as_primitive_array::<Float64Type>(output.column(2));
+        assert_eq!(min.values(), &[1., 2., 4.]);
+
+        // Finish resets the aggregator
+        assert_eq!(aggregator.num_groups(), 0);
+        aggregator.update(&b1).unwrap();
+        let output = aggregator.finish().unwrap();
+        assert_eq!(output.num_rows(), 2);
+    }
+
+    #[test]
+    fn test_group_by_multiple_columns() {
+        let a = Arc::new(Int32Array::from(vec![
+            Some(1),
+            Some(1),
+            None,
+            Some(1),
+            None,
+        ])) as ArrayRef;
+        let b = Arc::new(StringArray::from(vec!["x", "y", "x", "x", "x"])) as ArrayRef;
+        let tz = Some("+01:00".to_string());
+        let v = Arc::new(TimestampSecondArray::from_opt_vec(
+            vec![Some(5), Some(4), Some(3), Some(2), Some(1)],
+            tz.clone(),
+        )) as ArrayRef;
+        let batch =
+            RecordBatch::try_from_iter(vec![("a", a), ("b", b), ("v", v)]).unwrap();
+
+        let output = group_by(
+            &batch,
+            &[0, 1],
+            &[(2, AggregateFunction::Min), (2, AggregateFunction::Count)],
+        )
+        .unwrap();
+
+        let expected: Vec<ArrayRef> = vec![
+            Arc::new(Int32Array::from(vec![Some(1), Some(1), None])),
+            Arc::new(StringArray::from(vec!["x", "y", "x"])),
+            Arc::new(TimestampSecondArray::from_opt_vec(
+                vec![Some(2), Some(4), Some(1)],
+                tz,
+            )),
+            Arc::new(UInt64Array::from(vec![2, 1, 2])),
+        ];
+        assert_eq!(output.columns(), &expected);
+    }
+
+    #[test]
+    fn test_group_by_float_keys() {
+        let keys = Arc::new(Float64Array::from(vec![
+            0.0,
+            -0.0,
+            f64::NAN,
+            -f64::NAN,
+            1.0,
+        ])) as ArrayRef;
+        let values = Arc::new(Int32Array::from(vec![1, 2, 3, 4, 5])) as ArrayRef;
+        let output =
+            group_by(&batch(keys, values), &[0], &[(1, AggregateFunction::Sum)]).unwrap();
+
+        // -0.0 and 0.0 are the same key, as are all NaN
+        assert_eq!(output.num_rows(), 3);
+        let keys = as_primitive_array::<Float64Type>(output.column(0));
+        assert_eq!(keys.value(0), 0.0);
+        assert!(keys.value(1).is_nan());
+        assert_eq!(keys.value(2), 1.0);
+        let sums = as_primitive_array::<Int32Type>(output.column(1));
+        assert_eq!(sums.values(), &[3, 7, 5]);
+    }
+
+    #[test]
+    fn test_group_by_errors() {
+        let batch = batch(
+            Arc::new(Int32Array::from(vec![1])),
+            Arc::new(StringArray::from(vec!["a"])),
+        );
+
+        let err = group_by(&batch, &[], &[]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Grouped aggregation requires at least one group by column"
+        );
+
+        let err = group_by(&batch, &[0], &[(2, AggregateFunction::Count)]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Column index 2 out of bounds for schema with 2 fields"
+        );
+
+        let err = group_by(&batch, &[0], &[(1, AggregateFunction::Sum)]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Not yet implemented: Grouped aggregate sum not supported for data type Utf8"
+        );
+
+        // Count is supported for any type
+        let output = group_by(&batch, &[0], &[(1, AggregateFunction::Count)]).unwrap();
+        assert_eq!(output.num_rows(), 1);
+
+        let mut aggregator =
+            GroupedAggregator::try_new(batch.schema(), vec![0], vec![]).unwrap();
+        let other = RecordBatch::try_from_iter(vec![(
+            "k",
+            Arc::new(Int64Array::from(vec![1])) as ArrayRef,
+        )])
+        .unwrap();
+        assert!(aggregator.update(&other).is_err());
+    }
+}
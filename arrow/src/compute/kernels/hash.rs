@@ -0,0 +1,822 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines kernels that compute a stable, seeded `u64` hash for each row of
+//! one or more arrays, and a kernel to hash partition a [`RecordBatch`]
+//!
+//! The hashes produced are stable across processes and releases of this crate
+//! on the same platform endianness, making them suitable for shuffling data
+//! between nodes. They are not cryptographically secure.
+//!
+//! Values that compare equal produce the same hash regardless of their physical
+//! representation, in particular:
+//!
+//! * A [`DictionaryArray`] hashes the same as the array of its decoded values
+//! * All nulls hash to the same value, irrespective of the data type
+//! * Floating point `-0.0` and `0.0` hash the same, as do all `NaN` values
+
+use std::sync::Arc;
+
+use half::f16;
+
+use crate::array::*;
+use crate::compute::kernels::arity::unary;
+use crate::compute::take;
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+use crate::record_batch::RecordBatch;
+
+/// The hash of a null value
+pub(crate) const NULL_HASH: u64 = 0x2F1B_D9A8_6C3E_5047;
+
+/// Multiplier used when mixing values, derived from the golden ratio
+const MUL: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// The seed used by [`hash_partition`]
+const PARTITION_SEED: u64 = 0;
+
+/// The finalization mix of MurmurHash3, forces all bits of a hash block to avalanche
+#[inline]
+fn fmix64(mut k: u64) -> u64 {
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    k ^= k >> 33;
+    k
+}
+
+/// Combines two hashes into a single hash, this is not commutative
+#[inline]
+pub fn combine_hashes(l: u64, r: u64) -> u64 {
+    fmix64(l.wrapping_mul(MUL).rotate_left(31) ^ r)
+}
+
+/// Hashes a byte slice
+#[inline]
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut h = (bytes.len() as u64).wrapping_mul(MUL);
+    let chunks = bytes.chunks_exact(8);
+    let remainder = chunks.remainder();
+    for chunk in chunks {
+        let v = u64::from_le_bytes(chunk.try_into().unwrap());
+        h = combine_hashes(h, v);
+    }
+    if !remainder.is_empty() {
+        let mut buf = [0_u8; 8];
+        buf[..remainder.len()].copy_from_slice(remainder);
+        h = combine_hashes(h, u64::from_le_bytes(buf));
+    }
+    fmix64(h)
+}
+
+/// A value that can be hashed independently of its position in an array
+trait HashValue {
+    fn hash_value(&self) -> u64;
+}
+
+macro_rules! hash_integer {
+    ($($t:ty),+) => {
+        $(impl HashValue for $t {
+            #[inline]
+            fn hash_value(&self) -> u64 {
+                fmix64((*self as u64) ^ MUL)
+            }
+        })+
+    };
+}
+
+hash_integer!(i8, i16, i32, i64, u8, u16, u32, u64, usize);
+
+impl HashValue for i128 {
+    #[inline]
+    fn hash_value(&self) -> u64 {
+        combine_hashes(
+            (*self as u64).hash_value(),
+            ((*self >> 64) as u64).hash_value(),
+        )
+    }
+}
+
+impl HashValue for bool {
+    #[inline]
+    fn hash_value(&self) -> u64 {
+        (*self as u8).hash_value()
+    }
+}
+
+macro_rules! hash_float {
+    ($($t:ty),+) => {
+        $(
+        impl HashValue for $t {
+            #[inline]
+            fn hash_value(&self) -> u64 {
+                // Normalize `-0.0` to `0.0` and all NaN to a canonical NaN,
+                // so that values that compare equal hash the same
+                let v = if self.is_nan() {
+                    <$t>::NAN
+                } else if *self == <$t>::default() {
+                    <$t>::default()
+                } else {
+                    *self
+                };
+                v.to_bits().hash_value()
+            }
+        })+
+    };
+}
+
+hash_float!(f16, f32, f64);
+
+impl HashValue for [u8] {
+    #[inline]
+    fn hash_value(&self) -> u64 {
+        hash_bytes(self)
+    }
+}
+
+impl HashValue for str {
+    #[inline]
+    fn hash_value(&self) -> u64 {
+        hash_bytes(self.as_bytes())
+    }
+}
+
+/// Computes the hash of each row of `array`, and combines it into `hashes`
+///
+/// This is the building block for [`hash_array`] and [`hash_arrays`], and allows
+/// reusing the hash buffer across batches
+///
+/// # Errors
+///
+/// Returns an error if `hashes` has a different length than `array`
+pub fn update_hashes(array: &dyn Array, hashes: &mut [u64]) -> Result<()> {
+    if array.len() != hashes.len() {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Hash buffer has length {} but array has length {}",
+            hashes.len(),
+            array.len()
+        )));
+    }
+    let values = value_hashes(array)?;
+    hashes
+        .iter_mut()
+        .zip(values)
+        .for_each(|(h, v)| *h = combine_hashes(*h, v));
+    Ok(())
+}
+
+/// Returns the hash of each row of `array` using the provided `seed`
+///
+/// # Example
+/// ```
+/// # use arrow::array::{Int32Array, DictionaryArray, StringArray};
+/// # use arrow::compute::kernels::hash::hash_array;
+/// # use arrow::datatypes::Int8Type;
+/// let a = StringArray::from(vec![Some("foo"), None, Some("bar"), Some("foo")]);
+/// let hashes = hash_array(&a, 42).unwrap();
+/// assert_eq!(hashes.value(0), hashes.value(3));
+/// assert_ne!(hashes.value(0), hashes.value(2));
+///
+/// // dictionaries hash the same as their values
+/// let dict: DictionaryArray<Int8Type> = vec![Some("foo"), None, Some("bar"), Some("foo")]
+///     .into_iter()
+///     .collect();
+/// assert_eq!(hash_array(&dict, 42).unwrap(), hashes);
+/// ```
+pub fn hash_array(array: &dyn Array, seed: u64) -> Result<UInt64Array> {
+    let mut hashes = vec![seed; array.len()];
+    update_hashes(array, &mut hashes)?;
+    Ok(hashes.into())
+}
+
+/// Returns the hash of each row across all of `arrays` using the provided `seed`,
+/// the order of the columns is significant
+///
+/// # Errors
+///
+/// Returns an error if `arrays` is empty or the arrays have different lengths
+pub fn hash_arrays(arrays: &[ArrayRef], seed: u64) -> Result<UInt64Array> {
+    let len = match arrays.first() {
+        Some(a) => a.len(),
+        None => {
+            return Err(ArrowError::InvalidArgumentError(
+                "Hash requires at least one column".to_string(),
+            ))
+        }
+    };
+    let mut hashes = vec![seed; len];
+    for array in arrays {
+        update_hashes(array.as_ref(), &mut hashes)?;
+    }
+    Ok(hashes.into())
+}
+
+/// Returns the hash of each row of `batch` across all its columns, using the provided `seed`
+pub fn hash_batch(batch: &RecordBatch, seed: u64) -> Result<UInt64Array> {
+    let mut hashes = vec![seed; batch.num_rows()];
+    for column in batch.columns() {
+        update_hashes(column.as_ref(), &mut hashes)?;
+    }
+    Ok(hashes.into())
+}
+
+/// Splits `batch` into `num_partitions` batches by the hash of the values in
+/// the columns at indices `columns`, using [`take`].
+///
+/// Rows with equal values in `columns` are always assigned the same partition, and
+/// the relative order of rows within a partition is preserved. The hash is
+/// stable, so the same key maps to the same partition across batches and processes.
+///
+/// # Example
+/// ```
+/// # use std::sync::Arc;
+/// # use arrow::array::{ArrayRef, Int32Array, StringArray};
+/// # use arrow::compute::kernels::hash::hash_partition;
+/// # use arrow::record_batch::RecordBatch;
+/// let keys = Arc::new(StringArray::from(vec!["a", "b", "a", "c", "b"])) as ArrayRef;
+/// let values = Arc::new(Int32Array::from(vec![1, 2, 3, 4, 5])) as ArrayRef;
+/// let batch = RecordBatch::try_from_iter(vec![("k", keys), ("v", values)]).unwrap();
+///
+/// let partitions = hash_partition(&batch, &[0], 3).unwrap();
+/// assert_eq!(partitions.len(), 3);
+/// let rows: usize = partitions.iter().map(|p| p.num_rows()).sum();
+/// assert_eq!(rows, 5);
+/// ```
+pub fn hash_partition(
+    batch: &RecordBatch,
+    columns: &[usize],
+    num_partitions: usize,
+) -> Result<Vec<RecordBatch>> {
+    if num_partitions == 0 {
+        return Err(ArrowError::InvalidArgumentError(
+            "Hash partition requires at least one partition".to_string(),
+        ));
+    }
+    let keys = columns
+        .iter()
+        .map(|idx| {
+            batch.columns().get(*idx).cloned().ok_or_else(|| {
+                ArrowError::InvalidArgumentError(format!(
+                    "Hash partition column index {} out of bounds for batch with {} columns",
+                    idx,
+                    batch.num_columns()
+                ))
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let hashes = hash_arrays(&keys, PARTITION_SEED)?;
+
+    let mut indices = vec![vec![]; num_partitions];
+    hashes.values().iter().enumerate().for_each(|(row, hash)| {
+        indices[(*hash % num_partitions as u64) as usize].push(row as u32)
+    });
+
+    indices
+        .into_iter()
+        .map(|indices| {
+            let indices = UInt32Array::from(indices);
+            let columns = batch
+                .columns()
+                .iter()
+                .map(|c| take(c.as_ref(), &indices, None))
+                .collect::<Result<Vec<_>>>()?;
+            RecordBatch::try_new(batch.schema(), columns)
+        })
+        .collect()
+}
+
+/// Returns the hash of the value of each row of `array`, independent of any seed
+pub(crate) fn value_hashes(array: &dyn Array) -> Result<Vec<u64>> {
+    use DataType::*;
+    use IntervalUnit::*;
+    use TimeUnit::*;
+
+    Ok(match array.data_type() {
+        Null => vec![NULL_HASH; array.len()],
+        Boolean => hash_iter(
+            as_boolean_array(array)
+                .iter()
+                .map(|v| v.map(|v| v.hash_value())),
+        ),
+        Int8 => primitive_hashes::<Int8Type>(array),
+        Int16 => primitive_hashes::<Int16Type>(array),
+        Int32 => primitive_hashes::<Int32Type>(array),
+        Int64 => primitive_hashes::<Int64Type>(array),
+        UInt8 => primitive_hashes::<UInt8Type>(array),
+        UInt16 => primitive_hashes::<UInt16Type>(array),
+        UInt32 => primitive_hashes::<UInt32Type>(array),
+        UInt64 => primitive_hashes::<UInt64Type>(array),
+        Float16 => primitive_hashes::<Float16Type>(array),
+        Float32 => primitive_hashes::<Float32Type>(array),
+        Float64 => primitive_hashes::<Float64Type>(array),
+        Date32 => primitive_hashes::<Date32Type>(array),
+        Date64 => primitive_hashes::<Date64Type>(array),
+        Time32(Second) => primitive_hashes::<Time32SecondType>(array),
+        Time32(Millisecond) => primitive_hashes::<Time32MillisecondType>(array),
+        Time64(Microsecond) => primitive_hashes::<Time64MicrosecondType>(array),
+        Time64(Nanosecond) => primitive_hashes::<Time64NanosecondType>(array),
+        Timestamp(Second, _) => primitive_hashes::<TimestampSecondType>(array),
+        Timestamp(Millisecond, _) => primitive_hashes::<TimestampMillisecondType>(array),
+        Timestamp(Microsecond, _) => primitive_hashes::<TimestampMicrosecondType>(array),
+        Timestamp(Nanosecond, _) => primitive_hashes::<TimestampNanosecondType>(array),
+        Duration(Second) => primitive_hashes::<DurationSecondType>(array),
+        Duration(Millisecond) => primitive_hashes::<DurationMillisecondType>(array),
+        Duration(Microsecond) => primitive_hashes::<DurationMicrosecondType>(array),
+        Duration(Nanosecond) => primitive_hashes::<DurationNanosecondType>(array),
+        Interval(YearMonth) => primitive_hashes::<IntervalYearMonthType>(array),
+        Interval(DayTime) => primitive_hashes::<IntervalDayTimeType>(array),
+        Interval(MonthDayNano) => primitive_hashes::<IntervalMonthDayNanoType>(array),
+        Decimal(_, _) => hash_iter(
+            as_decimal_array(array)
+                .iter()
+                .map(|v| v.map(|v| v.hash_value())),
+        ),
+        Utf8 => hash_iter(
+            as_string_array(array)
+                .iter()
+                .map(|v| v.map(str::hash_value)),
+        ),
+        LargeUtf8 => hash_iter(
+            as_largestring_array(array)
+                .iter()
+                .map(|v| v.map(str::hash_value)),
+        ),
+        Binary => hash_iter(
+            as_generic_binary_array::<i32>(array)
+                .iter()
+                .map(|v| v.map(<[u8]>::hash_value)),
+        ),
+        LargeBinary => hash_iter(
+            as_generic_binary_array::<i64>(array)
+                .iter()
+                .map(|v| v.map(<[u8]>::hash_value)),
+        ),
+        FixedSizeBinary(_) => {
+            let array = array
+                .as_any()
+                .downcast_ref::<FixedSizeBinaryArray>()
+                .unwrap();
+            hash_iter(
+                (0..array.len())
+                    .map(|i| array.is_valid(i).then(|| array.value(i).hash_value())),
+            )
+        }
+        List(_) => {
+            let array = as_list_array(array);
+            list_hashes(array, array.value_offsets(), array.values().as_ref())?
+        }
+        LargeList(_) => {
+            let array = as_large_list_array(array);
+            list_hashes(array, array.value_offsets(), array.values().as_ref())?
+        }
+        Map(_, _) => {
+            let array = as_map_array(array);
+            let entries = make_array(array.data().child_data()[0].clone());
+            list_hashes(array, array.value_offsets(), entries.as_ref())?
+        }
+        FixedSizeList(_, size) => {
+            let array = array.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
+            let offsets: Vec<i32> = (0..=array.len())
+                .map(|i| (array.offset() + i) as i32 * size)
+                .collect();
+            list_hashes(array, &offsets, array.values().as_ref())?
+        }
+        Struct(_) => {
+            let array = as_struct_array(array);
+            let mut hashes = vec![0; array.len()];
+            for column in array.columns() {
+                update_hashes(column.as_ref(), &mut hashes)?;
+            }
+            mask_nulls(array, &mut hashes);
+            hashes
+        }
+        Union(_, _, _) => {
+            let array = as_union_array(array);
+            let children = array
+                .data()
+                .child_data()
+                .iter()
+                .map(|child| value_hashes(make_array(child.clone()).as_ref()))
+                .collect::<Result<Vec<_>>>()?;
+            (0..array.len())
+                .map(|i| {
+                    let idx = array.offset() + i;
+                    let type_id = array.type_id(idx);
+                    let offset = array.value_offset(idx) as usize;
+                    let child = children[type_id as usize][offset];
+                    combine_hashes(type_id.hash_value(), child)
+                })
+                .collect()
+        }
+        t @ (Time32(_) | Time64(_)) => {
+            return Err(ArrowError::ComputeError(format!(
+                "Hash not supported for data type {:?}",
+                t
+            )))
+        }
+        Dictionary(key_type, _) => match key_type.as_ref() {
+            Int8 => dictionary_hashes::<Int8Type>(array)?,
+            Int16 => dictionary_hashes::<Int16Type>(array)?,
+            Int32 => dictionary_hashes::<Int32Type>(array)?,
+            Int64 => dictionary_hashes::<Int64Type>(array)?,
+            UInt8 => dictionary_hashes::<UInt8Type>(array)?,
+            UInt16 => dictionary_hashes::<UInt16Type>(array)?,
+            UInt32 => dictionary_hashes::<UInt32Type>(array)?,
+            UInt64 => dictionary_hashes::<UInt64Type>(array)?,
+            t => {
+                return Err(ArrowError::ComputeError(format!(
+                    "Hash not supported for dictionary key type {:?}",
+                    t
+                )))
+            }
+        },
+    })
+}
+
+/// Collects an iterator of optional hashes, replacing `None` with [`NULL_HASH`]
+fn hash_iter<I: Iterator<Item = Option<u64>>>(iter: I) -> Vec<u64> {
+    iter.map(|h| h.unwrap_or(NULL_HASH)).collect()
+}
+
+/// Replaces the hash of null slots in `array` with [`NULL_HASH`]
+fn mask_nulls(array: &dyn Array, hashes: &mut [u64]) {
+    if array.null_count() > 0 {
+        hashes.iter_mut().enumerate().for_each(|(i, h)| {
+            if array.is_null(i) {
+                *h = NULL_HASH
+            }
+        })
+    }
+}
+
+fn primitive_hashes<T>(array: &dyn Array) -> Vec<u64>
+where
+    T: ArrowPrimitiveType,
+    T::Native: HashValue,
+{
+    let array = as_primitive_array::<T>(array);
+    let mut hashes: Vec<u64> = array.values().iter().map(|v| v.hash_value()).collect();
+    mask_nulls(array, &mut hashes);
+    hashes
+}
+
+/// Hashes each list of `array`, delimited by `offsets` into `values`
+fn list_hashes<O: OffsetSizeTrait>(
+    array: &dyn Array,
+    offsets: &[O],
+    values: &dyn Array,
+) -> Result<Vec<u64>> {
+    let values = value_hashes(values)?;
+    let mut hashes: Vec<u64> = offsets
+        .windows(2)
+        .map(|w| {
+            let start = w[0].to_usize().unwrap();
+            let end = w[1].to_usize().unwrap();
+            values[start..end]
+                .iter()
+                .fold((end - start).hash_value(), |h, v| combine_hashes(h, *v))
+        })
+        .collect();
+    mask_nulls(array, &mut hashes);
+    Ok(hashes)
+}
+
+/// Hashes the values of a dictionary, so that it hashes the same as its decoded values
+fn dictionary_hashes<K: ArrowDictionaryKeyType>(array: &dyn Array) -> Result<Vec<u64>> {
+    let array = as_dictionary_array::<K>(array);
+    let values = value_hashes(array.values().as_ref())?;
+    Ok(array
+        .keys_iter()
+        .map(|key| key.map(|k| values[k]).unwrap_or(NULL_HASH))
+        .collect())
+}
+
+/// Returns a function replacing `-0.0` by `0.0` and all `NaN` by a canonical `NaN`
+macro_rules! canonical_float {
+    ($t:ty) => {
+        |v: $t| {
+            if v.is_nan() {
+                <$t>::NAN
+            } else if v == <$t>::default() {
+                <$t>::default()
+            } else {
+                v
+            }
+        }
+    };
+}
+
+/// Returns `array` with floating point `-0.0` replaced by `0.0`, and all `NaN` by a
+/// canonical `NaN`, so that values that hash the same are also equal in the
+/// [row format](crate::row). Arrays without floating point values are returned as is.
+pub(crate) fn normalize_floats(array: &ArrayRef) -> Result<ArrayRef> {
+    match array.data_type() {
+        DataType::Float16 => Ok(normalize_float::<Float16Type, _>(
+            array,
+            canonical_float!(f16),
+        )),
+        DataType::Float32 => Ok(normalize_float::<Float32Type, _>(
+            array,
+            canonical_float!(f32),
+        )),
+        DataType::Float64 => Ok(normalize_float::<Float64Type, _>(
+            array,
+            canonical_float!(f64),
+        )),
+        DataType::Dictionary(_, value_type)
+            if matches!(
+                value_type.as_ref(),
+                DataType::Float16 | DataType::Float32 | DataType::Float64
+            ) =>
+        {
+            let data = array.data();
+            let values = normalize_floats(&make_array(data.child_data()[0].clone()))?;
+            let data = ArrayData::builder(data.data_type().clone())
+                .len(data.len())
+                .offset(data.offset())
+                .null_bit_buffer(data.null_buffer().cloned())
+                .buffers(data.buffers().to_vec())
+                .child_data(vec![values.data().clone()])
+                .build()?;
+            Ok(make_array(data))
+        }
+        _ => Ok(array.clone()),
+    }
+}
+
+fn normalize_float<T, F>(array: &ArrayRef, op: F) -> ArrayRef
+where
+    T: ArrowPrimitiveType,
+    F: Fn(T::Native) -> T::Native,
+{
+    Arc::new(unary::<T, _, T>(as_primitive_array::<T>(array), op))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::Buffer;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_hash_primitive() {
+        let a = Int32Array::from(vec![Some(1), None, Some(2), Some(1), None]);
+        let hashes = hash_array(&a, 0).unwrap();
+        assert_eq!(hashes.len(), 5);
+        assert_eq!(hashes.null_count(), 0);
+        assert_eq!(hashes.value(0), hashes.value(3));
+        assert_eq!(hashes.value(1), hashes.value(4));
+        assert_ne!(hashes.value(0), hashes.value(2));
+        assert_ne!(hashes.value(0), hashes.value(1));
+
+        // Hashes are stable
+        assert_eq!(hash_array(&a, 0).unwrap(), hashes);
+
+        // Seed changes the hash
+        let seeded = hash_array(&a, 1).unwrap();
+        assert_ne!(seeded.value(0), hashes.value(0));
+
+        // Slicing doesn't change the hash
+        let sliced = a.slice(2, 3);
+        let sliced_hashes = hash_array(sliced.as_ref(), 0).unwrap();
+        assert_eq!(sliced_hashes.values(), &hashes.values()[2..]);
+    }
+
+    #[test]
+    fn test_hash_float() {
+        let a = Float64Array::from(vec![0.0, -0.0, f64::NAN, -f64::NAN, 1.0]);
+        let hashes = hash_array(&a, 0).unwrap();
+        assert_eq!(hashes.value(0), hashes.value(1));
+        assert_eq!(hashes.value(2), hashes.value(3));
+        assert_ne!(hashes.value(0), hashes.value(4));
+    }
+
+    #[test]
+    fn test_hash_strings() {
+        let a = StringArray::from(vec![
+            Some("hello"),
+            Some(""),
+            None,
+            Some("a much longer string that spans multiple words"),
+            Some("hello"),
+        ]);
+        let hashes = hash_array(&a, 0).unwrap();
+        assert_eq!(hashes.value(0), hashes.value(4));
+        assert_ne!(hashes.value(1), hashes.value(2));
+        assert_ne!(hashes.value(0), hashes.value(3));
+
+        // Different offset types hash the same
+        let large = LargeStringArray::from(vec![
+            Some("hello"),
+            Some(""),
+            None,
+            Some("a much longer string that spans multiple words"),
+            Some("hello"),
+        ]);
+        assert_eq!(hash_array(&large, 0).unwrap(), hashes);
+
+        // Nulls hash the same irrespective of type
+        let ints = Int64Array::from(vec![None]);
+        assert_eq!(hash_array(&ints, 0).unwrap().value(0), hashes.value(2));
+    }
+
+    #[test]
+    fn test_hash_dictionary() {
+        let values = vec![Some("a"), None, Some("b"), Some("a"), Some("c")];
+        let plain = StringArray::from(values.clone());
+        let dict: DictionaryArray<Int16Type> = values.into_iter().collect();
+
+        let expected = hash_array(&plain, 3).unwrap();
+        assert_eq!(hash_array(&dict, 3).unwrap(), expected);
+
+        // Dictionary with unused and repeated values
+        let keys = Int8Array::from(vec![Some(2), None, Some(0), Some(2), Some(3)]);
+        let values = StringArray::from(vec!["b", "unused", "a", "c"]);
+        let dict = DictionaryArray::try_new(&keys, &values).unwrap();
+        assert_eq!(hash_array(&dict, 3).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_hash_multiple_columns() {
+        let a = Arc::new(Int32Array::from(vec![1, 1, 2, 2])) as ArrayRef;
+        let b = Arc::new(StringArray::from(vec!["x", "x", "x", "y"])) as ArrayRef;
+
+        let hashes = hash_arrays(&[a.clone(), b.clone()], 0).unwrap();
+        assert_eq!(hashes.value(0), hashes.value(1));
+        assert_ne!(hashes.value(1), hashes.value(2));
+        assert_ne!(hashes.value(2), hashes.value(3));
+
+        // Column order is significant
+        let swapped = hash_arrays(&[b.clone(), a.clone()], 0).unwrap();
+        assert_ne!(swapped.value(0), hashes.value(0));
+
+        let batch = RecordBatch::try_from_iter(vec![("a", a.clone()), ("b", b)]).unwrap();
+        assert_eq!(hash_batch(&batch, 0).unwrap(), hashes);
+
+        let short = Arc::new(Int32Array::from(vec![1])) as ArrayRef;
+        let err = hash_arrays(&[a, short], 0).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Hash buffer has length 4 but array has length 1"
+        );
+
+        let err = hash_arrays(&[], 0).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Hash requires at least one column"
+        );
+    }
+
+    #[test]
+    fn test_hash_list() {
+        let data = vec![
+            Some(vec![Some(1), Some(2)]),
+            None,
+            Some(vec![]),
+            Some(vec![Some(1), Some(2)]),
+            Some(vec![Some(2), Some(1)]),
+            Some(vec![Some(1), None]),
+        ];
+        let list = ListArray::from_iter_primitive::<Int32Type, _, _>(data.clone());
+        let hashes = hash_array(&list, 0).unwrap();
+        assert_eq!(hashes.value(0), hashes.value(3));
+        assert_ne!(hashes.value(0), hashes.value(4));
+        assert_ne!(hashes.value(1), hashes.value(2));
+        assert_ne!(hashes.value(0), hashes.value(5));
+
+        let large = LargeListArray::from_iter_primitive::<Int32Type, _, _>(data);
+        assert_eq!(hash_array(&large, 0).unwrap(), hashes);
+
+        let sliced = list.slice(3, 2);
+        let sliced_hashes = hash_array(sliced.as_ref(), 0).unwrap();
+        assert_eq!(sliced_hashes.values(), &hashes.values()[3..5]);
+    }
+
+    #[test]
+    fn test_hash_fixed_size_list() {
+        let values = Int32Array::from(vec![1, 2, 3, 4, 1, 2]);
+        let data = ArrayData::builder(DataType::FixedSizeList(
+            Box::new(Field::new("item", DataType::Int32, true)),
+            2,
+        ))
+        .len(3)
+        .add_child_data(values.data().clone())
+        .build()
+        .unwrap();
+        let list = FixedSizeListArray::from(data);
+        let hashes = hash_array(&list, 0).unwrap();
+        assert_eq!(hashes.value(0), hashes.value(2));
+        assert_ne!(hashes.value(0), hashes.value(1));
+
+        let sliced = list.slice(1, 2);
+        let sliced_hashes = hash_array(sliced.as_ref(), 0).unwrap();
+        assert_eq!(sliced_hashes.values(), &hashes.values()[1..]);
+    }
+
+    #[test]
+    fn test_hash_struct() {
+        let a = Arc::new(Int32Array::from(vec![1, 1, 2, 1])) as ArrayRef;
+        let b = Arc::new(StringArray::from(vec!["x", "x", "x", "x"])) as ArrayRef;
+        let data = ArrayData::builder(DataType::Struct(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Utf8, false),
+        ]))
+        .len(4)
+        .null_bit_buffer(Some(Buffer::from([0b00000111])))
+        .add_child_data(a.data().clone())
+        .add_child_data(b.data().clone())
+        .build()
+        .unwrap();
+        let array = StructArray::from(data);
+
+        let hashes = hash_array(&array, 0).unwrap();
+        assert_eq!(hashes.value(0), hashes.value(1));
+        assert_ne!(hashes.value(0), hashes.value(2));
+        assert_ne!(hashes.value(0), hashes.value(3));
+
+        let nulls = Int32Array::from(vec![None]);
+        assert_eq!(hash_array(&nulls, 0).unwrap().value(0), hashes.value(3));
+    }
+
+    #[test]
+    fn test_hash_union() {
+        let mut builder = UnionBuilder::new_dense(4);
+        builder.append::<Int32Type>("a", 1).unwrap();
+        builder.append::<Float64Type>("b", 3.0).unwrap();
+        builder.append::<Int32Type>("a", 1).unwrap();
+        builder.append::<Int32Type>("a", 2).unwrap();
+        let union = builder.build().unwrap();
+
+        let hashes = hash_array(&union, 0).unwrap();
+        assert_eq!(hashes.value(0), hashes.value(2));
+        assert_ne!(hashes.value(0), hashes.value(1));
+        assert_ne!(hashes.value(0), hashes.value(3));
+    }
+
+    #[test]
+    fn test_hash_partition() {
+        let keys = Arc::new(StringArray::from(vec![
+            Some("a"),
+            Some("b"),
+            None,
+            Some("a"),
+            Some("c"),
+            Some("b"),
+            None,
+        ])) as ArrayRef;
+        let values = Arc::new(Int32Array::from_iter_values(0..7)) as ArrayRef;
+        let batch = RecordBatch::try_from_iter(vec![("k", keys), ("v", values)]).unwrap();
+
+        let partitions = hash_partition(&batch, &[0], 4).unwrap();
+        assert_eq!(partitions.len(), 4);
+
+        let mut seen = vec![];
+        for partition in &partitions {
+            assert_eq!(partition.schema(), batch.schema());
+            let keys = as_string_array(partition.column(0));
+            let values = as_primitive_array::<Int32Type>(partition.column(1));
+
+            // Relative order is preserved
+            assert!(values.values().windows(2).all(|w| w[0] < w[1]));
+            seen.extend(values.values().iter().copied());
+
+            // Each key occurs in only a single partition
+            for other in partitions.iter().filter(|p| !std::ptr::eq(*p, partition)) {
+                let other_keys = as_string_array(other.column(0));
+                for key in keys.iter() {
+                    assert!(!other_keys.iter().any(|k| k == key));
+                }
+            }
+        }
+        seen.sort_unstable();
+        assert_eq!(seen, (0..7).collect::<Vec<_>>());
+
+        let err = hash_partition(&batch, &[0], 0).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Hash partition requires at least one partition"
+        );
+
+        let err = hash_partition(&batch, &[2], 2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Hash partition column index 2 out of bounds for batch with 2 columns"
+        );
+    }
+}
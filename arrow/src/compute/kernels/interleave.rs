@@ -0,0 +1,452 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines interleave kernel for [Array], gathering rows from several arrays
+
+use std::sync::Arc;
+
+use crate::array::*;
+use crate::buffer::{Buffer, MutableBuffer};
+use crate::compute::kernels::dictionary::{share_dictionary, unify_dictionaries};
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+use crate::row::RowConverter;
+use crate::util::bit_util;
+
+macro_rules! downcast_interleave {
+    ($type: ty, $arrays: expr, $indices: expr) => {
+        interleave_primitive::<$type>($arrays, $indices)
+    };
+}
+
+/// Take elements by `(array, row)` index from a list of [Array], creating a new
+/// [Array] from those values.
+///
+/// Each element of `indices` is a pair `(array, row)`, where `array` is the position
+/// of an array in `arrays`, and `row` the position of a value in that array.
+///
+/// ```text
+/// ┌─────────────────┐      ┌─────────┐                                  ┌─────────────────┐
+/// │        A        │      │ (0, 0)  │        interleave(                │        A        │
+/// ├─────────────────┤      ├─────────┤          [values0, values1],     ├─────────────────┤
+/// │        D        │      │ (1, 0)  │          indices                 │        B        │
+/// └─────────────────┘      ├─────────┤        )                         ├─────────────────┤
+///   values array 0         │ (1, 1)  │      ─────────────────────────▶  │        C        │
+///                          ├─────────┤                                  ├─────────────────┤
+///                          │ (0, 1)  │                                  │        D        │
+///                          └─────────┘                                  └─────────────────┘
+/// ┌─────────────────┐       indices
+/// │        B        │        array
+/// ├─────────────────┤                                                    result
+/// │        C        │
+/// ├─────────────────┤
+/// │        E        │
+/// └─────────────────┘
+///   values array 1
+/// ```
+///
+/// This is equivalent to, but more efficient than, concatenating `arrays` and calling
+/// [`take`](crate::compute::kernels::take::take) on the result, as the concatenated
+/// array is never materialized.
+///
+/// # Errors
+///
+/// This function errors if `arrays` is empty, if the arrays have different data types,
+/// or if an index is out of bounds.
+///
+/// Dictionary arrays with different dictionaries are first unified with
+/// [`unify_dictionaries`], so that the result has a single dictionary without
+/// duplicate values.
+///
+/// # Examples
+///
+/// ```
+/// use arrow::array::{Array, Int32Array};
+/// use arrow::compute::interleave;
+///
+/// let a = Int32Array::from(vec![1, 2, 3]);
+/// let b = Int32Array::from(vec![Some(4), None]);
+/// let c = interleave(&[&a, &b], &[(0, 2), (1, 0), (1, 1), (0, 0)]).unwrap();
+/// assert_eq!(
+///     c.as_any().downcast_ref::<Int32Array>().unwrap(),
+///     &Int32Array::from(vec![Some(3), Some(4), None, Some(1)])
+/// );
+/// ```
+pub fn interleave(arrays: &[&dyn Array], indices: &[(usize, usize)]) -> Result<ArrayRef> {
+    let first = arrays.first().ok_or_else(|| {
+        ArrowError::InvalidArgumentError(
+            "interleave requires at least one array".to_string(),
+        )
+    })?;
+    let data_type = first.data_type();
+    if let Some(array) = arrays.iter().find(|a| a.data_type() != data_type) {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "It is not possible to interleave arrays of different data types ({} and {})",
+            data_type,
+            array.data_type()
+        )));
+    }
+    for (array, row) in indices {
+        let len = arrays
+            .get(*array)
+            .ok_or_else(|| {
+                ArrowError::InvalidArgumentError(format!(
+                    "Array index {} out of bounds for {} arrays",
+                    array,
+                    arrays.len()
+                ))
+            })?
+            .len();
+        if *row >= len {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Row index {} out of bounds for array {} of length {}",
+                row, array, len
+            )));
+        }
+    }
+
+    if indices.is_empty() {
+        return Ok(new_empty_array(data_type));
+    }
+
+    match data_type {
+        DataType::Int8 => downcast_interleave!(Int8Type, arrays, indices),
+        DataType::Int16 => downcast_interleave!(Int16Type, arrays, indices),
+        DataType::Int32 => downcast_interleave!(Int32Type, arrays, indices),
+        DataType::Int64 => downcast_interleave!(Int64Type, arrays, indices),
+        DataType::UInt8 => downcast_interleave!(UInt8Type, arrays, indices),
+        DataType::UInt16 => downcast_interleave!(UInt16Type, arrays, indices),
+        DataType::UInt32 => downcast_interleave!(UInt32Type, arrays, indices),
+        DataType::UInt64 => downcast_interleave!(UInt64Type, arrays, indices),
+        DataType::Float16 => downcast_interleave!(Float16Type, arrays, indices),
+        DataType::Float32 => downcast_interleave!(Float32Type, arrays, indices),
+        DataType::Float64 => downcast_interleave!(Float64Type, arrays, indices),
+        DataType::Date32 => downcast_interleave!(Date32Type, arrays, indices),
+        DataType::Date64 => downcast_interleave!(Date64Type, arrays, indices),
+        DataType::Time32(TimeUnit::Second) => {
+            downcast_interleave!(Time32SecondType, arrays, indices)
+        }
+        DataType::Time32(TimeUnit::Millisecond) => {
+            downcast_interleave!(Time32MillisecondType, arrays, indices)
+        }
+        DataType::Time64(TimeUnit::Microsecond) => {
+            downcast_interleave!(Time64MicrosecondType, arrays, indices)
+        }
+        DataType::Time64(TimeUnit::Nanosecond) => {
+            downcast_interleave!(Time64NanosecondType, arrays, indices)
+        }
+        DataType::Timestamp(TimeUnit::Second, _) => {
+            downcast_interleave!(TimestampSecondType, arrays, indices)
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            downcast_interleave!(TimestampMillisecondType, arrays, indices)
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            downcast_interleave!(TimestampMicrosecondType, arrays, indices)
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            downcast_interleave!(TimestampNanosecondType, arrays, indices)
+        }
+        DataType::Interval(IntervalUnit::YearMonth) => {
+            downcast_interleave!(IntervalYearMonthType, arrays, indices)
+        }
+        DataType::Interval(IntervalUnit::DayTime) => {
+            downcast_interleave!(IntervalDayTimeType, arrays, indices)
+        }
+        DataType::Interval(IntervalUnit::MonthDayNano) => {
+            downcast_interleave!(IntervalMonthDayNanoType, arrays, indices)
+        }
+        DataType::Duration(TimeUnit::Second) => {
+            downcast_interleave!(DurationSecondType, arrays, indices)
+        }
+        DataType::Duration(TimeUnit::Millisecond) => {
+            downcast_interleave!(DurationMillisecondType, arrays, indices)
+        }
+        DataType::Duration(TimeUnit::Microsecond) => {
+            downcast_interleave!(DurationMicrosecondType, arrays, indices)
+        }
+        DataType::Duration(TimeUnit::Nanosecond) => {
+            downcast_interleave!(DurationNanosecondType, arrays, indices)
+        }
+        DataType::Utf8 => interleave_string::<i32>(arrays, indices),
+        DataType::LargeUtf8 => interleave_string::<i64>(arrays, indices),
+        DataType::Dictionary(_, _)
+            if RowConverter::supports_datatype(data_type)
+                && !share_dictionary(arrays) =>
+        {
+            let unified = unify_dictionaries(arrays)?;
+            let unified: Vec<_> = unified.iter().map(|a| a.as_ref()).collect();
+            interleave_fallback(&unified, indices)
+        }
+        _ => interleave_fallback(arrays, indices),
+    }
+}
+
+/// Returns the validity bitmap of the interleaved values, or `None` if all of
+/// `arrays` are free of nulls
+fn interleave_nulls(arrays: &[&dyn Array], indices: &[(usize, usize)]) -> Option<Buffer> {
+    if arrays.iter().all(|array| array.null_count() == 0) {
+        return None;
+    }
+    let mut nulls = MutableBuffer::new_null(indices.len());
+    let slice = nulls.as_slice_mut();
+    for (i, (array, row)) in indices.iter().enumerate() {
+        if arrays[*array].is_valid(*row) {
+            bit_util::set_bit(slice, i);
+        }
+    }
+    Some(nulls.into())
+}
+
+/// `interleave` implementation for all primitive arrays
+fn interleave_primitive<T: ArrowPrimitiveType>(
+    arrays: &[&dyn Array],
+    indices: &[(usize, usize)],
+) -> Result<ArrayRef> {
+    let values: Vec<_> = arrays
+        .iter()
+        .map(|array| as_primitive_array::<T>(*array).values())
+        .collect();
+    let buffer: Buffer = indices
+        .iter()
+        .map(|(array, row)| values[*array][*row])
+        .collect();
+
+    let data = unsafe {
+        ArrayData::new_unchecked(
+            arrays[0].data_type().clone(),
+            indices.len(),
+            None,
+            interleave_nulls(arrays, indices),
+            0,
+            vec![buffer],
+            vec![],
+        )
+    };
+    Ok(Arc::new(PrimitiveArray::<T>::from(data)))
+}
+
+/// `interleave` implementation for string arrays
+fn interleave_string<OffsetSize: OffsetSizeTrait>(
+    arrays: &[&dyn Array],
+    indices: &[(usize, usize)],
+) -> Result<ArrayRef> {
+    let strings: Vec<_> = arrays
+        .iter()
+        .map(|array| {
+            array
+                .as_any()
+                .downcast_ref::<GenericStringArray<OffsetSize>>()
+                .expect("Unable to downcast to a string array")
+        })
+        .collect();
+
+    let mut offsets =
+        MutableBuffer::new((indices.len() + 1) * std::mem::size_of::<OffsetSize>());
+    let mut values = MutableBuffer::new(0);
+    offsets.push(OffsetSize::zero());
+    for (array, row) in indices {
+        let array = strings[*array];
+        if array.is_valid(*row) {
+            values.extend_from_slice(array.value(*row).as_bytes());
+        }
+        let offset = OffsetSize::from_usize(values.len())
+            .ok_or_else(|| ArrowError::ComputeError("offset overflow".to_string()))?;
+        offsets.push(offset);
+    }
+
+    let data = unsafe {
+        ArrayData::new_unchecked(
+            GenericStringArray::<OffsetSize>::get_data_type(),
+            indices.len(),
+            None,
+            interleave_nulls(arrays, indices),
+            0,
+            vec![offsets.into(), values.into()],
+            vec![],
+        )
+    };
+    Ok(Arc::new(GenericStringArray::<OffsetSize>::from(data)))
+}
+
+/// `interleave` implementation for all other arrays, copying runs of consecutive
+/// rows from the same array with [`MutableArrayData`]
+fn interleave_fallback(
+    arrays: &[&dyn Array],
+    indices: &[(usize, usize)],
+) -> Result<ArrayRef> {
+    let data: Vec<_> = arrays.iter().map(|array| array.data()).collect();
+    let use_nulls = arrays.iter().any(|array| array.null_count() > 0);
+    let mut mutable = MutableArrayData::new(data, use_nulls, indices.len());
+
+    let mut current = indices[0].0;
+    let mut start = indices[0].1;
+    let mut end = start + 1;
+    for (array, row) in &indices[1..] {
+        if *array == current && *row == end {
+            end += 1;
+            continue;
+        }
+        mutable.extend(current, start, end);
+        current = *array;
+        start = *row;
+        end = start + 1;
+    }
+    mutable.extend(current, start, end);
+
+    Ok(make_array(mutable.freeze()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primitive() {
+        let a = Int32Array::from(vec![1, 2, 3, 4]);
+        let b = Int32Array::from(vec![5, 6, 7]);
+        let b = b.slice(1, 2);
+        let c = Int32Array::from(vec![Some(8), None]);
+
+        let values = interleave(
+            &[&a, b.as_ref(), &c],
+            &[(0, 2), (2, 1), (1, 1), (2, 0), (0, 0)],
+        )
+        .unwrap();
+        let values = as_primitive_array::<Int32Type>(&values);
+        let expected = Int32Array::from(vec![Some(3), None, Some(7), Some(8), Some(1)]);
+        assert_eq!(values, &expected);
+
+        let values = interleave(&[&a, b.as_ref()], &[(1, 0), (0, 3)]).unwrap();
+        let values = as_primitive_array::<Int32Type>(&values);
+        assert_eq!(values, &Int32Array::from(vec![6, 4]));
+        assert!(values.data().null_buffer().is_none());
+
+        let values = interleave(&[&a], &[]).unwrap();
+        assert_eq!(values.len(), 0);
+        assert_eq!(values.data_type(), &DataType::Int32);
+    }
+
+    #[test]
+    fn test_timestamp_with_timezone() {
+        let a = TimestampMillisecondArray::from_vec(vec![1, 2], Some("UTC".to_string()));
+        let b = TimestampMillisecondArray::from_vec(vec![3], Some("UTC".to_string()));
+        let values = interleave(&[&a, &b], &[(1, 0), (0, 1)]).unwrap();
+        assert_eq!(values.data_type(), a.data_type());
+        let values = as_primitive_array::<TimestampMillisecondType>(&values);
+        assert_eq!(values.values(), &[3, 2]);
+    }
+
+    #[test]
+    fn test_strings() {
+        let a = StringArray::from(vec![Some("a"), None, Some("bc")]);
+        let b = StringArray::from(vec!["hello", "world"]);
+        let values =
+            interleave(&[&a, &b], &[(1, 1), (0, 1), (0, 2), (1, 0), (0, 0)]).unwrap();
+        let values = as_string_array(&values);
+        let expected = StringArray::from(vec![
+            Some("world"),
+            None,
+            Some("bc"),
+            Some("hello"),
+            Some("a"),
+        ]);
+        assert_eq!(values, &expected);
+
+        let a = LargeStringArray::from(vec!["x", "yz"]);
+        let values = interleave(&[&a, &a], &[(1, 1), (0, 0)]).unwrap();
+        let values = as_largestring_array(&values);
+        assert_eq!(values, &LargeStringArray::from(vec!["yz", "x"]));
+    }
+
+    #[test]
+    fn test_fallback() {
+        let a = BooleanArray::from(vec![Some(true), None, Some(false)]);
+        let b = BooleanArray::from(vec![false, true]);
+        let values =
+            interleave(&[&a, &b], &[(0, 0), (0, 1), (1, 1), (0, 2), (1, 0)]).unwrap();
+        let values = as_boolean_array(&values);
+        let expected = BooleanArray::from(vec![
+            Some(true),
+            None,
+            Some(true),
+            Some(false),
+            Some(false),
+        ]);
+        assert_eq!(values, &expected);
+
+        let a = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), Some(2)]),
+            None,
+        ]);
+        let b =
+            ListArray::from_iter_primitive::<Int32Type, _, _>(vec![Some(vec![Some(3)])]);
+        let values = interleave(&[&a, &b], &[(1, 0), (0, 1), (0, 0)]).unwrap();
+        let expected = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(3)]),
+            None,
+            Some(vec![Some(1), Some(2)]),
+        ]);
+        assert_eq!(values.data(), expected.data());
+
+        let a = DictionaryArray::<Int8Type>::from_iter(vec!["a", "b"]);
+        let b = DictionaryArray::<Int8Type>::from_iter(vec!["c", "a"]);
+        let values = interleave(&[&a, &b], &[(1, 0), (0, 1), (1, 1)]).unwrap();
+        let values = as_dictionary_array::<Int8Type>(&values);
+        let dictionary = as_string_array(values.values());
+        let strings: Vec<_> = values
+            .keys()
+            .iter()
+            .map(|key| dictionary.value(key.unwrap() as usize))
+            .collect();
+        assert_eq!(strings, vec!["c", "b", "a"]);
+        // the dictionaries are unified rather than concatenated
+        assert_eq!(dictionary, &StringArray::from(vec!["a", "b", "c"]));
+    }
+
+    #[test]
+    fn test_errors() {
+        let a = Int32Array::from(vec![1, 2]);
+        let b = Int64Array::from(vec![1]);
+        let err = interleave(&[], &[]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: interleave requires at least one array"
+        );
+        let err = interleave(&[&a, &b], &[(0, 0)]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: It is not possible to interleave arrays of different data types (Int32 and Int64)"
+        );
+        let err = interleave(&[&a], &[(1, 0)]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Array index 1 out of bounds for 1 arrays"
+        );
+        let err = interleave(&[&a], &[(0, 2)]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Row index 2 out of bounds for array 0 of length 2"
+        );
+
+        // validity is computed from the sliced arrays
+        let c = Int32Array::from(vec![None, Some(1)]);
+        let c = c.slice(1, 1);
+        let values = interleave(&[c.as_ref()], &[(0, 0)]).unwrap();
+        assert_eq!(values.null_count(), 0);
+    }
+}
@@ -0,0 +1,806 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines equi-join kernels that join two sets of [`RecordBatch`] on key columns,
+//! using either a hash join or a sort-merge join
+//!
+//! Keys are compared using the [row format](crate::row), and so any type supported
+//! by [`RowConverter`] can be used as a join key, including multiple columns. As
+//! with the [hash kernels](crate::compute::kernels::hash), floating point `-0.0` and
+//! `0.0` are equal keys, as are all `NaN` values.
+//!
+//! Null keys follow SQL semantics, a null key is never equal to any other key,
+//! including another null key. Rows with a null key are therefore never matched,
+//! but are still returned by outer and anti joins.
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use arrow::array::{ArrayRef, Int32Array, StringArray};
+//! # use arrow::compute::kernels::join::{hash_join, JoinType};
+//! # use arrow::record_batch::RecordBatch;
+//! let left = RecordBatch::try_from_iter(vec![
+//!     ("id", Arc::new(Int32Array::from(vec![1, 2, 3])) as ArrayRef),
+//!     ("name", Arc::new(StringArray::from(vec!["a", "b", "c"])) as ArrayRef),
+//! ])
+//! .unwrap();
+//! let right = RecordBatch::try_from_iter(vec![
+//!     ("id", Arc::new(Int32Array::from(vec![3, 1, 1])) as ArrayRef),
+//!     ("value", Arc::new(Int32Array::from(vec![30, 10, 11])) as ArrayRef),
+//! ])
+//! .unwrap();
+//!
+//! let joined = hash_join(&[left], &[right], &[(0, 0)], JoinType::Inner).unwrap();
+//! assert_eq!(joined.num_columns(), 4);
+//!
+//! let names = joined.column(1).as_any().downcast_ref::<StringArray>().unwrap();
+//! let values = joined.column(3).as_any().downcast_ref::<Int32Array>().unwrap();
+//! assert_eq!(names, &StringArray::from(vec!["a", "a", "c"]));
+//! assert_eq!(values, &Int32Array::from(vec![10, 11, 30]));
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::array::*;
+use crate::compute::kernels::hash::normalize_floats;
+use crate::compute::take;
+use crate::compute::util::logical_null_buffer;
+use crate::datatypes::{Field, Schema, SchemaRef};
+use crate::error::{ArrowError, Result};
+use crate::record_batch::RecordBatch;
+use crate::row::{Row, RowConverter, Rows, SortField};
+use crate::util::bit_util;
+
+/// The type of join to perform
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum JoinType {
+    /// Returns the pairs of left and right rows with equal keys
+    Inner,
+    /// Returns an [`JoinType::Inner`] join, plus the left rows with no matching
+    /// right row, paired with a null right row
+    Left,
+    /// Returns an [`JoinType::Inner`] join, plus the right rows with no matching
+    /// left row, paired with a null left row
+    Right,
+    /// Returns an [`JoinType::Inner`] join, plus both the left and right rows with
+    /// no match on the other side
+    Full,
+    /// Returns the left rows that have at least one matching right row
+    LeftSemi,
+    /// Returns the left rows that have no matching right row
+    LeftAnti,
+    /// Returns the right rows that have at least one matching left row
+    RightSemi,
+    /// Returns the right rows that have no matching left row
+    RightAnti,
+}
+
+/// Returns the indices of the left and right rows joined by a hash join of
+/// `left` and `right` on equality of their key columns
+///
+/// Each output row is given by the index at the same position in the left and the
+/// right indices, where a null index indicates the row is null for an outer join.
+///
+/// For semi and anti joins only the indices of the rows returned are populated, and
+/// the indices for the other side are empty
+///
+/// The output is ordered by the left rows, with any unmatched right rows following.
+/// The output of a [`JoinType::RightSemi`] join is ordered by the right rows
+///
+/// # Errors
+///
+/// Returns an error if the number or the types of the key columns differ, or the
+/// key types are not supported by [`RowConverter`]
+pub fn hash_join_indices(
+    left: &[ArrayRef],
+    right: &[ArrayRef],
+    join_type: JoinType,
+) -> Result<(UInt32Array, UInt32Array)> {
+    let keys = JoinKeys::try_new(left, right)?;
+
+    // Build a hash table on the right side, as left rows are emitted in order
+    let mut table: HashMap<Row<'_>, Vec<u32>> = HashMap::new();
+    for (idx, row) in keys.right_rows.iter().enumerate() {
+        if keys.right_valid[idx] {
+            table.entry(row).or_default().push(idx as u32);
+        }
+    }
+
+    let mut output = JoinOutput::new(join_type, keys.right_valid.len());
+    for (idx, row) in keys.left_rows.iter().enumerate() {
+        let matches = match keys.left_valid[idx] {
+            true => table.get(&row).map(|m| m.as_slice()),
+            false => None,
+        };
+        output.push_left(idx as u32, matches.unwrap_or_default());
+    }
+    Ok(output.finish())
+}
+
+/// Returns the indices of the left and right rows joined by a sort-merge join of
+/// `left` and `right` on equality of their key columns
+///
+/// The output format is the same as [`hash_join_indices`], however, the output is
+/// ordered by the join key, with rows with null keys following. The output of a
+/// [`JoinType::RightSemi`] join is ordered by the right rows
+///
+/// # Errors
+///
+/// Returns an error if the number or the types of the key columns differ, or the
+/// key types are not supported by [`RowConverter`]
+pub fn sort_merge_join_indices(
+    left: &[ArrayRef],
+    right: &[ArrayRef],
+    join_type: JoinType,
+) -> Result<(UInt32Array, UInt32Array)> {
+    let keys = JoinKeys::try_new(left, right)?;
+
+    // Stable sort of the non-null rows by key, preserving the input order of equal keys
+    let sorted = |rows: &Rows, valid: &[bool]| {
+        let mut indices: Vec<u32> = (0..rows.num_rows() as u32)
+            .filter(|idx| valid[*idx as usize])
+            .collect();
+        indices.sort_by(|a, b| rows.row(*a as usize).cmp(&rows.row(*b as usize)));
+        indices
+    };
+    let left_sorted = sorted(&keys.left_rows, &keys.left_valid);
+    let right_sorted = sorted(&keys.right_rows, &keys.right_valid);
+
+    let mut output = JoinOutput::new(join_type, keys.right_valid.len());
+    let (mut l, mut r) = (0, 0);
+    while l < left_sorted.len() {
+        let left_row = keys.left_rows.row(left_sorted[l] as usize);
+
+        // Find the end of the run of equal left keys
+        let left_end = l + left_sorted[l..]
+            .iter()
+            .take_while(|idx| keys.left_rows.row(**idx as usize) == left_row)
+            .count();
+
+        // Advance past the right rows less than this key
+        while r < right_sorted.len()
+            && keys.right_rows.row(right_sorted[r] as usize) < left_row
+        {
+            output.push_unmatched_right(right_sorted[r]);
+            r += 1;
+        }
+
+        // Find the run of right keys equal to this key
+        let right_end = r + right_sorted[r..]
+            .iter()
+            .take_while(|idx| keys.right_rows.row(**idx as usize) == left_row)
+            .count();
+
+        for idx in &left_sorted[l..left_end] {
+            output.push_left(*idx, &right_sorted[r..right_end]);
+        }
+        l = left_end;
+        r = right_end;
+    }
+    right_sorted[r..]
+        .iter()
+        .for_each(|idx| output.push_unmatched_right(*idx));
+
+    // Rows with null keys never match
+    (0..keys.left_valid.len() as u32)
+        .filter(|idx| !keys.left_valid[*idx as usize])
+        .for_each(|idx| output.push_left(idx, &[]));
+    (0..keys.right_valid.len() as u32)
+        .filter(|idx| !keys.right_valid[*idx as usize])
+        .for_each(|idx| output.push_unmatched_right(idx));
+
+    Ok(output.finish())
+}
+
+/// Joins `left` and `right` with a hash join on the pairs of left and right column
+/// indices in `on`, see [`hash_join_indices`]
+///
+/// The output contains the columns of `left` followed by the columns of `right`, or
+/// only those of one side for semi and anti joins. The columns of the side
+/// that may be null for an outer join are made nullable
+///
+/// # Errors
+///
+/// Returns an error if either `left` or `right` is empty, as the schema of the output
+/// cannot be determined. [`RecordBatch::new_empty`] can be used to join with
+/// an empty input
+pub fn hash_join(
+    left: &[RecordBatch],
+    right: &[RecordBatch],
+    on: &[(usize, usize)],
+    join_type: JoinType,
+) -> Result<RecordBatch> {
+    join_batches(left, right, on, join_type, hash_join_indices)
+}
+
+/// Joins `left` and `right` with a sort-merge join on the pairs of left and right
+/// column indices in `on`, see [`sort_merge_join_indices`] and [`hash_join`]
+pub fn sort_merge_join(
+    left: &[RecordBatch],
+    right: &[RecordBatch],
+    on: &[(usize, usize)],
+    join_type: JoinType,
+) -> Result<RecordBatch> {
+    join_batches(left, right, on, join_type, sort_merge_join_indices)
+}
+
+type JoinIndicesFn =
+    fn(&[ArrayRef], &[ArrayRef], JoinType) -> Result<(UInt32Array, UInt32Array)>;
+
+fn join_batches(
+    left: &[RecordBatch],
+    right: &[RecordBatch],
+    on: &[(usize, usize)],
+    join_type: JoinType,
+    indices: JoinIndicesFn,
+) -> Result<RecordBatch> {
+    let concat = |batches: &[RecordBatch], side: &str| match batches.first() {
+        Some(first) => RecordBatch::concat(&first.schema(), batches),
+        None => Err(ArrowError::InvalidArgumentError(format!(
+            "Join requires at least one {} batch",
+            side
+        ))),
+    };
+    let left = concat(left, "left")?;
+    let right = concat(right, "right")?;
+
+    let key = |batch: &RecordBatch, idx: usize| {
+        batch.columns().get(idx).cloned().ok_or_else(|| {
+            ArrowError::InvalidArgumentError(format!(
+                "Join column index {} out of bounds for batch with {} columns",
+                idx,
+                batch.num_columns()
+            ))
+        })
+    };
+    let left_keys = on
+        .iter()
+        .map(|(l, _)| key(&left, *l))
+        .collect::<Result<Vec<_>>>()?;
+    let right_keys = on
+        .iter()
+        .map(|(_, r)| key(&right, *r))
+        .collect::<Result<Vec<_>>>()?;
+
+    let (left_indices, right_indices) = indices(&left_keys, &right_keys, join_type)?;
+
+    let take_all = |batch: &RecordBatch,
+                    indices: &UInt32Array,
+                    nullable: bool|
+     -> Result<(Vec<Field>, Vec<ArrayRef>)> {
+        let fields = batch
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| {
+                Field::new(f.name(), f.data_type().clone(), f.is_nullable() || nullable)
+                    .with_metadata(f.metadata().cloned())
+            })
+            .collect();
+        let columns = batch
+            .columns()
+            .iter()
+            .map(|c| take(c.as_ref(), indices, None))
+            .collect::<Result<_>>()?;
+        Ok((fields, columns))
+    };
+
+    let (fields, columns) = match join_type {
+        JoinType::LeftSemi | JoinType::LeftAnti => take_all(&left, &left_indices, false)?,
+        JoinType::RightSemi | JoinType::RightAnti => {
+            take_all(&right, &right_indices, false)?
+        }
+        _ => {
+            let left_nullable = matches!(join_type, JoinType::Right | JoinType::Full);
+            let right_nullable = matches!(join_type, JoinType::Left | JoinType::Full);
+            let (mut fields, mut columns) =
+                take_all(&left, &left_indices, left_nullable)?;
+            let (r_fields, r_columns) = take_all(&right, &right_indices, right_nullable)?;
+            fields.extend(r_fields);
+            columns.extend(r_columns);
+            (fields, columns)
+        }
+    };
+
+    let schema: SchemaRef = Arc::new(Schema::new(fields));
+    RecordBatch::try_new(schema, columns)
+}
+
+/// The join keys of both sides of a join, converted to the row format
+struct JoinKeys {
+    left_rows: Rows,
+    left_valid: Vec<bool>,
+    right_rows: Rows,
+    right_valid: Vec<bool>,
+}
+
+impl JoinKeys {
+    fn try_new(left: &[ArrayRef], right: &[ArrayRef]) -> Result<Self> {
+        if left.is_empty() || left.len() != right.len() {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Join requires the same non-zero number of key columns on both sides, got {} and {}",
+                left.len(),
+                right.len()
+            )));
+        }
+
+        let fields = left
+            .iter()
+            .zip(right)
+            .map(|(l, r)| match l.data_type() == r.data_type() {
+                true => Ok(SortField::new(l.data_type().clone())),
+                false => Err(ArrowError::InvalidArgumentError(format!(
+                    "Join key types must match, got {:?} and {:?}",
+                    l.data_type(),
+                    r.data_type()
+                ))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        for keys in [left, right] {
+            if keys[0].len() > u32::MAX as usize {
+                return Err(ArrowError::ComputeError(
+                    "Join input exceeds the maximum of u32::MAX rows".to_string(),
+                ));
+            }
+        }
+
+        // Floats are normalized so that `-0.0` and `0.0`, and all NaN, are equal keys
+        let normalize = |keys: &[ArrayRef]| {
+            keys.iter()
+                .map(normalize_floats)
+                .collect::<Result<Vec<_>>>()
+        };
+        let converter = RowConverter::try_new(fields)?;
+        Ok(Self {
+            left_rows: converter.convert_columns(&normalize(left)?)?,
+            left_valid: valid_keys(left)?,
+            right_rows: converter.convert_columns(&normalize(right)?)?,
+            right_valid: valid_keys(right)?,
+        })
+    }
+}
+
+/// Returns whether each row has no null key columns, where a dictionary key that
+/// refers to a null value is null
+fn valid_keys(keys: &[ArrayRef]) -> Result<Vec<bool>> {
+    let mut valid = vec![true; keys[0].len()];
+    for key in keys {
+        if let Some((nulls, offset)) = logical_null_buffer(key.as_ref())? {
+            valid.iter_mut().enumerate().for_each(|(idx, v)| {
+                *v &= bit_util::get_bit(nulls.as_slice(), offset + idx)
+            });
+        }
+    }
+    Ok(valid)
+}
+
+/// Accumulates the output indices of a join
+struct JoinOutput {
+    join_type: JoinType,
+    left: Vec<Option<u32>>,
+    right: Vec<Option<u32>>,
+    right_matched: Vec<bool>,
+    right_emitted: Vec<bool>,
+}
+
+impl JoinOutput {
+    fn new(join_type: JoinType, right_len: usize) -> Self {
+        Self {
+            join_type,
+            left: vec![],
+            right: vec![],
+            right_matched: vec![false; right_len],
+            right_emitted: vec![false; right_len],
+        }
+    }
+
+    /// Records that the left row `idx` matches the right rows in `matches`
+    fn push_left(&mut self, idx: u32, matches: &[u32]) {
+        match self.join_type {
+            JoinType::Inner | JoinType::Left | JoinType::Right | JoinType::Full => {
+                for m in matches {
+                    self.left.push(Some(idx));
+                    self.right.push(Some(*m));
+                }
+                let outer = matches!(self.join_type, JoinType::Left | JoinType::Full);
+                if matches.is_empty() && outer {
+                    self.left.push(Some(idx));
+                    self.right.push(None);
+                }
+            }
+            JoinType::LeftSemi if !matches.is_empty() => self.left.push(Some(idx)),
+            JoinType::LeftAnti if matches.is_empty() => self.left.push(Some(idx)),
+            _ => {}
+        }
+        matches
+            .iter()
+            .for_each(|m| self.right_matched[*m as usize] = true);
+    }
+
+    /// Records the right row `idx`, which must not match any further left rows,
+    /// if it did not match any left row and has not already been recorded
+    fn push_unmatched_right(&mut self, idx: u32) {
+        let idx_usize = idx as usize;
+        if !self.right_matched[idx_usize] && !self.right_emitted[idx_usize] {
+            self.right_emitted[idx_usize] = true;
+            match self.join_type {
+                JoinType::Right | JoinType::Full => {
+                    self.left.push(None);
+                    self.right.push(Some(idx));
+                }
+                JoinType::RightAnti => self.right.push(Some(idx)),
+                _ => {}
+            }
+        }
+    }
+
+    fn finish(mut self) -> (UInt32Array, UInt32Array) {
+        match self.join_type {
+            JoinType::Right | JoinType::Full | JoinType::RightAnti => {
+                // Emit any unmatched right rows not already emitted
+                for idx in 0..self.right_matched.len() {
+                    self.push_unmatched_right(idx as u32);
+                }
+            }
+            JoinType::RightSemi => {
+                self.right = (0..self.right_matched.len() as u32)
+                    .filter(|idx| self.right_matched[*idx as usize])
+                    .map(Some)
+                    .collect();
+            }
+            _ => {}
+        }
+        (self.left.into(), self.right.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{thread_rng, Rng};
+
+    const ALL_JOIN_TYPES: [JoinType; 8] = [
+        JoinType::Inner,
+        JoinType::Left,
+        JoinType::Right,
+        JoinType::Full,
+        JoinType::LeftSemi,
+        JoinType::LeftAnti,
+        JoinType::RightSemi,
+        JoinType::RightAnti,
+    ];
+
+    /// Returns the output of a join as sorted pairs of optional indices
+    fn pairs(
+        join_type: JoinType,
+        (left, right): (UInt32Array, UInt32Array),
+    ) -> Vec<(Option<u32>, Option<u32>)> {
+        let mut pairs: Vec<_> = match join_type {
+            JoinType::LeftSemi | JoinType::LeftAnti => {
+                assert!(right.is_empty());
+                left.iter().map(|l| (l, None)).collect()
+            }
+            JoinType::RightSemi | JoinType::RightAnti => {
+                assert!(left.is_empty());
+                right.iter().map(|r| (None, r)).collect()
+            }
+            _ => left.iter().zip(right.iter()).collect(),
+        };
+        pairs.sort_unstable();
+        pairs
+    }
+
+    /// A nested loop join of `left` and `right`
+    fn nested_loop_join(
+        left: &Int32Array,
+        right: &Int32Array,
+        join_type: JoinType,
+    ) -> Vec<(Option<u32>, Option<u32>)> {
+        let matches = |l: usize, r: usize| {
+            left.is_valid(l) && right.is_valid(r) && left.value(l) == right.value(r)
+        };
+        let left_matched = |l: usize| (0..right.len()).any(|r| matches(l, r));
+        let right_matched = |r: usize| (0..left.len()).any(|l| matches(l, r));
+
+        let mut out = vec![];
+        match join_type {
+            JoinType::LeftSemi | JoinType::LeftAnti => {
+                let semi = join_type == JoinType::LeftSemi;
+                (0..left.len())
+                    .filter(|l| left_matched(*l) == semi)
+                    .for_each(|l| out.push((Some(l as u32), None)));
+            }
+            JoinType::RightSemi | JoinType::RightAnti => {
+                let semi = join_type == JoinType::RightSemi;
+                (0..right.len())
+                    .filter(|r| right_matched(*r) == semi)
+                    .for_each(|r| out.push((None, Some(r as u32))));
+            }
+            _ => {
+                for l in 0..left.len() {
+                    for r in 0..right.len() {
+                        if matches(l, r) {
+                            out.push((Some(l as u32), Some(r as u32)));
+                        }
+                    }
+                }
+                if matches!(join_type, JoinType::Left | JoinType::Full) {
+                    (0..left.len())
+                        .filter(|l| !left_matched(*l))
+                        .for_each(|l| out.push((Some(l as u32), None)));
+                }
+                if matches!(join_type, JoinType::Right | JoinType::Full) {
+                    (0..right.len())
+                        .filter(|r| !right_matched(*r))
+                        .for_each(|r| out.push((None, Some(r as u32))));
+                }
+            }
+        }
+        out.sort_unstable();
+        out
+    }
+
+    #[test]
+    fn test_join_indices() {
+        let left: Vec<ArrayRef> = vec![Arc::new(Int32Array::from(vec![
+            Some(1),
+            None,
+            Some(2),
+            Some(1),
+        ]))];
+        let right: Vec<ArrayRef> = vec![Arc::new(Int32Array::from(vec![
+            Some(3),
+            Some(1),
+            None,
+            Some(1),
+        ]))];
+
+        let (l, r) = hash_join_indices(&left, &right, JoinType::Inner).unwrap();
+        assert_eq!(l, UInt32Array::from(vec![0, 0, 3, 3]));
+        assert_eq!(r, UInt32Array::from(vec![1, 3, 1, 3]));
+
+        let (l, r) = hash_join_indices(&left, &right, JoinType::Full).unwrap();
+        assert_eq!(
+            l,
+            UInt32Array::from(vec![
+                Some(0),
+                Some(0),
+                Some(1),
+                Some(2),
+                Some(3),
+                Some(3),
+                None,
+                None
+            ])
+        );
+        assert_eq!(
+            r,
+            UInt32Array::from(vec![
+                Some(1),
+                Some(3),
+                None,
+                None,
+                Some(1),
+                Some(3),
+                Some(0),
+                Some(2)
+            ])
+        );
+
+        // Null keys are not matched by semi joins, but are returned by anti joins
+        let (l, r) = sort_merge_join_indices(&left, &right, JoinType::LeftAnti).unwrap();
+        assert_eq!(l, UInt32Array::from(vec![2, 1]));
+        assert!(r.is_empty());
+
+        let (l, r) = sort_merge_join_indices(&left, &right, JoinType::RightSemi).unwrap();
+        assert!(l.is_empty());
+        assert_eq!(r, UInt32Array::from(vec![1, 3]));
+    }
+
+    #[test]
+    fn test_join_multiple_keys() {
+        let left: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from(vec![
+                Some("a"),
+                Some("a"),
+                Some("b"),
+                None,
+            ])),
+            Arc::new(Int64Array::from(vec![1, 2, 1, 1])),
+        ];
+        let right: Vec<ArrayRef> = vec![
+            Arc::new(
+                vec![Some("b"), Some("a"), None, Some("a")]
+                    .into_iter()
+                    .collect::<DictionaryArray<crate::datatypes::Int8Type>>(),
+            ),
+            Arc::new(Int64Array::from(vec![1, 2, 1, 3])),
+        ];
+
+        let err = hash_join_indices(&left, &right, JoinType::Inner).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Join key types must match, got Utf8 and Dictionary(Int8, Utf8)"
+        );
+
+        let right = vec![
+            crate::compute::cast(&right[0], left[0].data_type()).unwrap(),
+            right[1].clone(),
+        ];
+        for join_type in ALL_JOIN_TYPES {
+            let hash = hash_join_indices(&left, &right, join_type).unwrap();
+            let merge = sort_merge_join_indices(&left, &right, join_type).unwrap();
+            assert_eq!(pairs(join_type, hash), pairs(join_type, merge));
+        }
+
+        let (l, r) = sort_merge_join_indices(&left, &right, JoinType::Inner).unwrap();
+        assert_eq!(l, UInt32Array::from(vec![1, 2]));
+        assert_eq!(r, UInt32Array::from(vec![1, 0]));
+
+        let err = hash_join_indices(&left, &right[..1], JoinType::Inner).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Join requires the same non-zero number of key columns on both sides, got 2 and 1"
+        );
+    }
+
+    #[test]
+    fn test_join_float_keys() {
+        let left: Vec<ArrayRef> =
+            vec![Arc::new(Float32Array::from(vec![0.0, f32::NAN, 2.0]))];
+        let right: Vec<ArrayRef> =
+            vec![Arc::new(Float32Array::from(vec![-f32::NAN, -0.0, 1.0]))];
+
+        // -0.0 and 0.0 are equal keys, as are all NaN
+        let (l, r) = hash_join_indices(&left, &right, JoinType::Inner).unwrap();
+        assert_eq!(l, UInt32Array::from(vec![0, 1]));
+        assert_eq!(r, UInt32Array::from(vec![1, 0]));
+        let merge = sort_merge_join_indices(&left, &right, JoinType::Inner).unwrap();
+        assert_eq!(
+            pairs(JoinType::Inner, merge),
+            vec![(Some(0), Some(1)), (Some(1), Some(0))]
+        );
+    }
+
+    #[test]
+    fn test_join_dictionary_null_values() {
+        let dictionary = |keys: Vec<i32>, values: Vec<Option<&str>>| -> ArrayRef {
+            let keys = Int32Array::from(keys);
+            let values = Arc::new(StringArray::from(values)) as ArrayRef;
+            Arc::new(
+                DictionaryArray::<crate::datatypes::Int32Type>::try_new(&keys, &values)
+                    .unwrap(),
+            )
+        };
+        // The keys are valid, but refer to null values, and so are null join keys
+        let left = vec![dictionary(vec![0, 1], vec![Some("a"), None])];
+        let right = vec![dictionary(vec![0, 1, 0], vec![None, Some("a")])];
+
+        let (l, r) = hash_join_indices(&left, &right, JoinType::Inner).unwrap();
+        assert_eq!(l, UInt32Array::from(vec![0]));
+        assert_eq!(r, UInt32Array::from(vec![1]));
+
+        for join_type in ALL_JOIN_TYPES {
+            let hash = hash_join_indices(&left, &right, join_type).unwrap();
+            let merge = sort_merge_join_indices(&left, &right, join_type).unwrap();
+            assert_eq!(pairs(join_type, hash), pairs(join_type, merge));
+        }
+
+        let (l, r) = hash_join_indices(&left, &right, JoinType::LeftAnti).unwrap();
+        assert_eq!(l, UInt32Array::from(vec![1]));
+        assert!(r.is_empty());
+    }
+
+    #[test]
+    fn test_join_fuzz() {
+        let mut rng = thread_rng();
+        let mut array = |len: usize| -> Int32Array {
+            (0..len)
+                .map(|_| rng.gen_bool(0.8).then(|| rng.gen_range(0..10)))
+                .collect()
+        };
+
+        for _ in 0..50 {
+            let left = array(30);
+            let right = array(20);
+            let left_keys = [make_array(left.data().clone())];
+            let right_keys = [make_array(right.data().clone())];
+
+            for join_type in ALL_JOIN_TYPES {
+                let expected = nested_loop_join(&left, &right, join_type);
+                let hash = hash_join_indices(&left_keys, &right_keys, join_type).unwrap();
+                assert_eq!(pairs(join_type, hash), expected, "{:?}", join_type);
+
+                let merge =
+                    sort_merge_join_indices(&left_keys, &right_keys, join_type).unwrap();
+                assert_eq!(pairs(join_type, merge), expected, "{:?}", join_type);
+            }
+        }
+    }
+
+    #[test]
+    fn test_join_batches() {
+        let left = RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef),
+            ("a", Arc::new(StringArray::from(vec!["x", "y"])) as ArrayRef),
+        ])
+        .unwrap();
+        let left2 = RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int32Array::from(vec![3])) as ArrayRef),
+            ("a", Arc::new(StringArray::from(vec!["z"])) as ArrayRef),
+        ])
+        .unwrap();
+        let right = RecordBatch::try_from_iter(vec![
+            ("b", Arc::new(Int64Array::from(vec![30, 40])) as ArrayRef),
+            ("id", Arc::new(Int32Array::from(vec![3, 4])) as ArrayRef),
+        ])
+        .unwrap();
+
+        let joined = hash_join(
+            &[left.clone(), left2.clone()],
+            std::slice::from_ref(&right),
+            &[(0, 1)],
+            JoinType::Left,
+        )
+        .unwrap();
+        let schema = joined.schema();
+        let names: Vec<_> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+        assert_eq!(names, &["id", "a", "b", "id"]);
+        assert!(!schema.field(0).is_nullable());
+        assert!(schema.field(2).is_nullable());
+
+        let expected: Vec<ArrayRef> = vec![
+            Arc::new(Int32Array::from(vec![1, 2, 3])),
+            Arc::new(StringArray::from(vec!["x", "y", "z"])),
+            Arc::new(Int64Array::from(vec![None, None, Some(30)])),
+            Arc::new(Int32Array::from(vec![None, None, Some(3)])),
+        ];
+        assert_eq!(joined.columns(), &expected);
+
+        let joined = sort_merge_join(
+            &[left.clone(), left2],
+            std::slice::from_ref(&right),
+            &[(0, 1)],
+            JoinType::RightAnti,
+        )
+        .unwrap();
+        assert_eq!(joined.schema(), right.schema());
+        assert_eq!(
+            joined.column(0).as_ref(),
+            &Int64Array::from(vec![40]) as &dyn Array
+        );
+
+        let err = hash_join(
+            &[],
+            std::slice::from_ref(&right),
+            &[(0, 1)],
+            JoinType::Inner,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Join requires at least one left batch"
+        );
+
+        let err = hash_join(&[left], &[right], &[(0, 2)], JoinType::Inner).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Join column index 2 out of bounds for batch with 2 columns"
+        );
+    }
+}
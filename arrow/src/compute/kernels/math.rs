@@ -0,0 +1,522 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines mathematical functions on numeric arrays, such as `abs`, `round`,
+//! `sqrt`, `ln` and trigonometric functions.
+//!
+//! Functions that are only meaningful for floating point values, such as `sqrt`,
+//! follow the semantics of the corresponding methods of [`f64`], returning NaN
+//! for values outside of their domain. If a value is null then the result is
+//! also null.
+//!
+//! ```
+//! # use arrow::array::Float64Array;
+//! # use arrow::compute::kernels::math::{round, sqrt, RoundingMode};
+//! let array = Float64Array::from(vec![Some(2.0), None, Some(9.0)]);
+//! let roots = round(&sqrt(&array).unwrap(), 2, RoundingMode::HalfAwayFromZero).unwrap();
+//! assert_eq!(roots, Float64Array::from(vec![Some(1.41), None, Some(3.0)]));
+//! ```
+
+use num::{Float, NumCast};
+
+use crate::array::*;
+use crate::compute::kernels::arity::{binary, try_unary, unary};
+use crate::datatypes::{ArrowFloatNumericType, ArrowNativeTypeOp, ArrowNumericType};
+use crate::error::Result;
+
+/// Returns the absolute value of each value of an array.
+///
+/// The absolute value of the minimum value of a signed integer type overflows, and
+/// wraps around to the minimum value. For an overflow-checking variant, use
+/// [`abs_checked`] instead.
+pub fn abs<T>(array: &PrimitiveArray<T>) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: ArrowNativeTypeOp,
+{
+    Ok(unary(array, |x| match x < T::Native::ZERO {
+        true => x.neg_wrapping(),
+        // clears the sign of `-0.0`
+        false if x.is_zero() => T::Native::ZERO,
+        false => x,
+    }))
+}
+
+/// Returns the absolute value of each value of an array.
+///
+/// This detects overflow and returns an `Err` for that. For an non-overflow-checking
+/// variant, use [`abs`] instead.
+pub fn abs_checked<T>(array: &PrimitiveArray<T>) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: ArrowNativeTypeOp,
+{
+    try_unary(array, |x| match x < T::Native::ZERO {
+        true => x.neg_checked(),
+        // clears the sign of `-0.0`
+        false if x.is_zero() => Ok(T::Native::ZERO),
+        false => Ok(x),
+    })
+}
+
+/// Returns the sign of each value of an array: `1` for positive values, `-1` for
+/// negative values, and `0` for zero. Floating point zeros and NaN are returned
+/// unchanged.
+pub fn signum<T>(array: &PrimitiveArray<T>) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: ArrowNativeTypeOp,
+{
+    let minus_one = T::Native::ZERO.sub_wrapping(T::Native::ONE);
+    Ok(unary(array, |x| {
+        if x > T::Native::ZERO {
+            T::Native::ONE
+        } else if x < T::Native::ZERO {
+            minus_one
+        } else {
+            x
+        }
+    }))
+}
+
+macro_rules! float_unary {
+    ($(#[$doc:meta])* $name:ident, $op:ident) => {
+        $(#[$doc])*
+        pub fn $name<T>(array: &PrimitiveArray<T>) -> Result<PrimitiveArray<T>>
+        where
+            T: ArrowFloatNumericType,
+            T::Native: Float,
+        {
+            Ok(unary(array, |x| x.$op()))
+        }
+    };
+}
+
+float_unary!(
+    /// Returns the largest integer less than or equal to each value of an array.
+    floor,
+    floor
+);
+float_unary!(
+    /// Returns the smallest integer greater than or equal to each value of an array.
+    ceil,
+    ceil
+);
+float_unary!(
+    /// Returns the integer part of each value of an array, rounding towards zero.
+    trunc,
+    trunc
+);
+float_unary!(
+    /// Returns the square root of each value of an array, NaN for negative values.
+    sqrt,
+    sqrt
+);
+float_unary!(
+    /// Returns the cube root of each value of an array.
+    cbrt,
+    cbrt
+);
+float_unary!(
+    /// Returns `e^x` for each value `x` of an array.
+    exp,
+    exp
+);
+float_unary!(
+    /// Returns the natural logarithm of each value of an array, NaN for negative
+    /// values and negative infinity for zero.
+    ln,
+    ln
+);
+float_unary!(
+    /// Returns the base 2 logarithm of each value of an array.
+    log2,
+    log2
+);
+float_unary!(
+    /// Returns the base 10 logarithm of each value of an array.
+    log10,
+    log10
+);
+float_unary!(
+    /// Returns the sine of each value of an array, in radians.
+    sin,
+    sin
+);
+float_unary!(
+    /// Returns the cosine of each value of an array, in radians.
+    cos,
+    cos
+);
+float_unary!(
+    /// Returns the tangent of each value of an array, in radians.
+    tan,
+    tan
+);
+float_unary!(
+    /// Returns the arcsine of each value of an array in radians, NaN for values
+    /// outside of `[-1, 1]`.
+    asin,
+    asin
+);
+float_unary!(
+    /// Returns the arccosine of each value of an array in radians, NaN for values
+    /// outside of `[-1, 1]`.
+    acos,
+    acos
+);
+float_unary!(
+    /// Returns the arctangent of each value of an array, in radians.
+    atan,
+    atan
+);
+float_unary!(
+    /// Returns the hyperbolic sine of each value of an array.
+    sinh,
+    sinh
+);
+float_unary!(
+    /// Returns the hyperbolic cosine of each value of an array.
+    cosh,
+    cosh
+);
+float_unary!(
+    /// Returns the hyperbolic tangent of each value of an array.
+    tanh,
+    tanh
+);
+float_unary!(
+    /// Converts each value of an array from radians to degrees.
+    degrees,
+    to_degrees
+);
+float_unary!(
+    /// Converts each value of an array from degrees to radians.
+    radians,
+    to_radians
+);
+
+/// Returns the logarithm of each value of an array with respect to `base`.
+pub fn log<T>(array: &PrimitiveArray<T>, base: T::Native) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowFloatNumericType,
+    T::Native: Float,
+{
+    Ok(unary(array, |x| x.log(base)))
+}
+
+/// Returns the four quadrant arctangent of `y` and `x` in radians. If either value
+/// is null then the result is also null.
+///
+/// # Errors
+///
+/// This function errors if the arrays have different lengths
+pub fn atan2<T>(y: &PrimitiveArray<T>, x: &PrimitiveArray<T>) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowFloatNumericType,
+    T::Native: Float,
+{
+    binary(y, x, |y, x| y.atan2(x))
+}
+
+/// How [`round`] and [`round_decimal`] round values that are halfway between two
+/// rounded values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Round half away from zero, e.g. `2.5` to `3` and `-2.5` to `-3`
+    HalfAwayFromZero,
+    /// Round half to the nearest even value, e.g. `2.5` to `2` and `3.5` to `4`,
+    /// also known as banker's rounding
+    HalfToEven,
+}
+
+/// Rounds each value of an array to `digits` decimal digits using `mode`. Negative
+/// `digits` round to the left of the decimal point, e.g. to the nearest hundred for
+/// `-2`.
+///
+/// As floating point values can't represent all decimal fractions exactly, the result
+/// is the closest floating point value to the rounded value. Values that have no
+/// more than `digits` decimal digits, such as very large values, are returned
+/// unchanged, and values rounded to more digits left of the decimal point than the
+/// type can represent are zero.
+pub fn round<T>(
+    array: &PrimitiveArray<T>,
+    digits: i32,
+    mode: RoundingMode,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowFloatNumericType,
+    T::Native: Float,
+{
+    let factor = <T::Native as NumCast>::from(10).unwrap().powi(digits.abs());
+    let half = <T::Native as NumCast>::from(0.5).unwrap();
+    let two = <T::Native as NumCast>::from(2).unwrap();
+    let round = |x: T::Native| match mode {
+        RoundingMode::HalfToEven if (x - x.trunc()).abs() == half => {
+            (x / two).round() * two
+        }
+        _ => x.round(),
+    };
+    // the magnitude above which all values are integers
+    let integral = T::Native::epsilon().recip();
+    Ok(unary(array, |x| match digits >= 0 {
+        true => {
+            let scaled = x * factor;
+            match scaled.is_finite() && scaled.abs() < integral {
+                true => round(scaled) / factor,
+                false => x,
+            }
+        }
+        false if !x.is_finite() => x,
+        // `x / factor` is `0.0` or `-0.0` if the factor overflows
+        false if factor.is_infinite() => x / factor,
+        false => round(x / factor) * factor,
+    }))
+}
+
+/// Rounds each value of a decimal array to `digits` decimal digits using `mode`,
+/// keeping the precision and scale of the array. Negative `digits` round to the left
+/// of the decimal point, e.g. to the nearest hundred for `-2`.
+///
+/// Returns an error if a rounded value doesn't fit in the precision of the array,
+/// e.g. when rounding `9.99` to one digit in a `Decimal(3, 2)` array.
+pub fn round_decimal(
+    array: &DecimalArray,
+    digits: i32,
+    mode: RoundingMode,
+) -> Result<DecimalArray> {
+    let (precision, scale) = (array.precision(), array.scale());
+    let mut builder = DecimalBuilder::new(array.len(), precision, scale);
+    let factor = match scale as i64 - digits as i64 {
+        d if d <= 0 => None,
+        d => Some(10_i128.checked_pow(d as u32)),
+    };
+    for value in array.iter() {
+        match (value, factor) {
+            (None, _) => builder.append_null()?,
+            (Some(v), None) => builder.append_value(v)?,
+            // the factor exceeds any possible value
+            (Some(_), Some(None)) => builder.append_value(0)?,
+            (Some(v), Some(Some(factor))) => {
+                let (q, r) = (v / factor, v % factor);
+                // `|r| < factor <= 10^38`, and so this cannot overflow
+                let twice = r.unsigned_abs() * 2;
+                let round_up = match mode {
+                    RoundingMode::HalfAwayFromZero => twice >= factor as u128,
+                    RoundingMode::HalfToEven => {
+                        twice > factor as u128 || (twice == factor as u128 && q % 2 != 0)
+                    }
+                };
+                let q = if round_up { q + v.signum() } else { q };
+                // overflows are detected when validating the precision
+                builder.append_value(q.saturating_mul(factor))?
+            }
+        }
+    }
+    Ok(builder.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datatypes::{Float32Type, Int8Type};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_abs_signum() {
+        let a = Int8Array::from(vec![Some(-3), None, Some(0), Some(5), Some(-128)]);
+        let expected = Int8Array::from(vec![Some(3), None, Some(0), Some(5), Some(-128)]);
+        assert_eq!(abs(&a).unwrap(), expected);
+        let err = abs_checked(&a).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Overflow happened on: --128"
+        );
+        let a = a.slice(0, 4);
+        let a = as_primitive_array::<Int8Type>(&a);
+        let expected = Int8Array::from(vec![Some(3), None, Some(0), Some(5)]);
+        assert_eq!(abs_checked(a).unwrap(), expected);
+
+        let expected = Int8Array::from(vec![Some(-1), None, Some(0), Some(1)]);
+        assert_eq!(signum(a).unwrap(), expected);
+
+        let a = Float64Array::from(vec![Some(-1.5), Some(0.0), Some(2.0), None]);
+        let expected = Float64Array::from(vec![Some(1.5), Some(0.0), Some(2.0), None]);
+        assert_eq!(abs(&a).unwrap(), expected);
+        let expected = Float64Array::from(vec![Some(-1.0), Some(0.0), Some(1.0), None]);
+        assert_eq!(signum(&a).unwrap(), expected);
+
+        let a = UInt32Array::from(vec![0, 7]);
+        assert_eq!(signum(&a).unwrap(), UInt32Array::from(vec![0, 1]));
+
+        let a = Float64Array::from(vec![-0.0]);
+        assert!(abs(&a).unwrap().value(0).is_sign_positive());
+        assert!(abs_checked(&a).unwrap().value(0).is_sign_positive());
+    }
+
+    #[test]
+    fn test_float_functions() {
+        let a = Float64Array::from(vec![Some(-1.5), None, Some(4.0)]);
+        assert_eq!(
+            floor(&a).unwrap(),
+            Float64Array::from(vec![Some(-2.0), None, Some(4.0)])
+        );
+        assert_eq!(
+            ceil(&a).unwrap(),
+            Float64Array::from(vec![Some(-1.0), None, Some(4.0)])
+        );
+        assert_eq!(
+            trunc(&a).unwrap(),
+            Float64Array::from(vec![Some(-1.0), None, Some(4.0)])
+        );
+        let roots = sqrt(&a).unwrap();
+        assert!(roots.value(0).is_nan());
+        assert!(roots.is_null(1));
+        assert_eq!(roots.value(2), 2.0);
+
+        let a = Float64Array::from(vec![1.0, 8.0, 100.0]);
+        assert_eq!(cbrt(&a).unwrap().value(1), 2.0);
+        assert_eq!(ln(&a).unwrap().value(0), 0.0);
+        assert_eq!(log2(&a).unwrap().value(1), 3.0);
+        assert_eq!(log10(&a).unwrap().value(2), 2.0);
+        assert_eq!(log(&a, 2.0).unwrap().value(1), 3.0);
+        assert_eq!(exp(&Float64Array::from(vec![0.0])).unwrap().value(0), 1.0);
+
+        let a = Float32Array::from(vec![0.0, 1.0]);
+        let expected = Float32Array::from(vec![0.0, 1.0_f32.sin()]);
+        assert_eq!(sin::<Float32Type>(&a).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_trigonometry() {
+        let a = Float64Array::from(vec![0.0, PI / 2.0]);
+        let sines = sin(&a).unwrap();
+        assert_eq!(sines.value(1), 1.0);
+        assert_eq!(cos(&a).unwrap().value(0), 1.0);
+        assert_eq!(tan(&a).unwrap().value(0), 0.0);
+        assert_eq!(asin(&sines).unwrap(), a);
+        assert_eq!(acos(&Float64Array::from(vec![1.0])).unwrap().value(0), 0.0);
+        assert_eq!(atan(&Float64Array::from(vec![0.0])).unwrap().value(0), 0.0);
+        assert_eq!(sinh(&a).unwrap().value(0), 0.0);
+        assert_eq!(cosh(&a).unwrap().value(0), 1.0);
+        assert_eq!(tanh(&a).unwrap().value(0), 0.0);
+        assert_eq!(degrees(&a).unwrap().value(1), 90.0);
+        assert_eq!(
+            radians(&Float64Array::from(vec![180.0])).unwrap().value(0),
+            PI
+        );
+
+        let y = Float64Array::from(vec![Some(1.0), Some(-1.0), None]);
+        let x = Float64Array::from(vec![Some(0.0), Some(-1.0), Some(1.0)]);
+        let expected =
+            Float64Array::from(vec![Some(PI / 2.0), Some(-3.0 * PI / 4.0), None]);
+        assert_eq!(atan2(&y, &x).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_round() {
+        let a = Float64Array::from(vec![
+            Some(2.5),
+            Some(-2.5),
+            Some(3.5),
+            Some(1.2345),
+            None,
+            Some(1250.0),
+        ]);
+        let rounded = round(&a, 0, RoundingMode::HalfAwayFromZero).unwrap();
+        let expected = Float64Array::from(vec![
+            Some(3.0),
+            Some(-3.0),
+            Some(4.0),
+            Some(1.0),
+            None,
+            Some(1250.0),
+        ]);
+        assert_eq!(rounded, expected);
+
+        let rounded = round(&a, 0, RoundingMode::HalfToEven).unwrap();
+        let expected = Float64Array::from(vec![
+            Some(2.0),
+            Some(-2.0),
+            Some(4.0),
+            Some(1.0),
+            None,
+            Some(1250.0),
+        ]);
+        assert_eq!(rounded, expected);
+
+        let rounded = round(&a, 2, RoundingMode::HalfAwayFromZero).unwrap();
+        assert_eq!(rounded.value(3), 1.23);
+        let rounded = round(&a, -2, RoundingMode::HalfAwayFromZero).unwrap();
+        assert_eq!(rounded.value(5), 1300.0);
+        let rounded = round(&a, -2, RoundingMode::HalfToEven).unwrap();
+        assert_eq!(rounded.value(5), 1200.0);
+
+        // values that would overflow when scaled are returned unchanged
+        let a = Float64Array::from(vec![1e300, -0.1, 0.0, f64::INFINITY]);
+        let rounded = round(&a, 10, RoundingMode::HalfAwayFromZero).unwrap();
+        assert_eq!(rounded, a);
+        let rounded = round(&a, 400, RoundingMode::HalfAwayFromZero).unwrap();
+        assert_eq!(rounded, a);
+        // values that are already integral when scaled are returned unchanged
+        let rounded = round(&a, 20, RoundingMode::HalfToEven).unwrap();
+        assert_eq!(rounded, a);
+        let a = Float32Array::from(vec![1e30, 1.25]);
+        let rounded = round(&a, 10, RoundingMode::HalfToEven).unwrap();
+        assert_eq!(rounded, a);
+
+        let a = Float64Array::from(vec![1e300, -1.0, f64::INFINITY]);
+        let rounded = round(&a, -400, RoundingMode::HalfAwayFromZero).unwrap();
+        assert_eq!(rounded.value(0), 0.0);
+        assert!(rounded.value(1) == 0.0 && rounded.value(1).is_sign_negative());
+        assert_eq!(rounded.value(2), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_round_decimal() {
+        let a = vec![Some(125), Some(-125), Some(135), None, Some(999)]
+            .into_iter()
+            .collect::<DecimalArray>()
+            .with_precision_and_scale(3, 2)
+            .unwrap();
+
+        let rounded = round_decimal(&a, 1, RoundingMode::HalfAwayFromZero);
+        let err = rounded.unwrap_err();
+        assert!(err.to_string().contains("999"), "{}", err);
+
+        let a = a.slice(0, 4);
+        let a = a.as_any().downcast_ref::<DecimalArray>().unwrap();
+        let rounded = round_decimal(a, 1, RoundingMode::HalfAwayFromZero).unwrap();
+        assert_eq!(rounded.data_type(), a.data_type());
+        let values: Vec<_> = rounded.iter().collect();
+        assert_eq!(values, vec![Some(130), Some(-130), Some(140), None]);
+
+        let rounded = round_decimal(a, 1, RoundingMode::HalfToEven).unwrap();
+        let values: Vec<_> = rounded.iter().collect();
+        assert_eq!(values, vec![Some(120), Some(-120), Some(140), None]);
+
+        let rounded = round_decimal(a, 0, RoundingMode::HalfAwayFromZero).unwrap();
+        let values: Vec<_> = rounded.iter().collect();
+        assert_eq!(values, vec![Some(100), Some(-100), Some(100), None]);
+
+        let rounded = round_decimal(a, 2, RoundingMode::HalfToEven).unwrap();
+        assert_eq!(&rounded, a);
+        let rounded = round_decimal(a, -40, RoundingMode::HalfToEven).unwrap();
+        let values: Vec<_> = rounded.iter().collect();
+        assert_eq!(values, vec![Some(0), Some(0), Some(0), None]);
+    }
+}
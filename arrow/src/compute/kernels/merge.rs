@@ -0,0 +1,533 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines a streaming k-way merge, that combines multiple streams of
+//! [`RecordBatch`], each sorted by the same sort columns, into a single sorted
+//! stream of [`RecordBatch`]
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use arrow::array::{ArrayRef, Int32Array};
+//! # use arrow::compute::kernels::merge::SortPreservingMerge;
+//! # use arrow::compute::SortOptions;
+//! # use arrow::record_batch::RecordBatch;
+//! let batch = |values: Vec<i32>| {
+//!     let values = Arc::new(Int32Array::from(values)) as ArrayRef;
+//!     RecordBatch::try_from_iter(vec![("a", values)])
+//! };
+//! let a = vec![batch(vec![1, 4]), batch(vec![5, 9])];
+//! let b = vec![batch(vec![2, 3, 6])];
+//! let schema = a[0].as_ref().unwrap().schema();
+//!
+//! let merge = SortPreservingMerge::try_new(
+//!     schema,
+//!     vec![a.into_iter(), b.into_iter()],
+//!     vec![(0, SortOptions::default())],
+//!     4,
+//! )
+//! .unwrap();
+//!
+//! let batches = merge.collect::<Result<Vec<_>, _>>().unwrap();
+//! let expected: Vec<ArrayRef> = vec![Arc::new(Int32Array::from(vec![1, 2, 3, 4]))];
+//! assert_eq!(batches[0].columns(), &expected);
+//! let expected: Vec<ArrayRef> = vec![Arc::new(Int32Array::from(vec![5, 6, 9]))];
+//! assert_eq!(batches[1].columns(), &expected);
+//! ```
+
+use std::cmp::Ordering;
+
+use crate::array::*;
+use crate::compute::kernels::sort::SortOptions;
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+use crate::record_batch::{RecordBatch, RecordBatchReader};
+use crate::row::{Row, RowConverter, Rows, SortField};
+
+/// The position of a [`SortPreservingMerge`] in the current batch of an input stream
+#[derive(Debug)]
+struct Cursor {
+    /// The index of the batch in [`SortPreservingMerge::batches`]
+    batch: usize,
+    /// The sort keys of the batch
+    rows: Rows,
+    /// The index of the next row of the batch
+    offset: usize,
+}
+
+impl Cursor {
+    fn row(&self) -> Row<'_> {
+        self.rows.row(self.offset)
+    }
+}
+
+/// Merges multiple streams of [`RecordBatch`], each sorted by the same sort columns,
+/// into a single sorted stream of [`RecordBatch`] with at most `batch_size` rows
+///
+/// Sort keys are compared using the [row format](crate::row), so any type supported
+/// by [`RowConverter`] may be used as a sort column. The merge is stable: rows with
+/// equal keys are returned in the order of their input streams, and in the order in
+/// which they appear within a stream.
+///
+/// Only the current batch of each input stream is buffered, in addition to the
+/// batches referenced by the rows of the next output batch.
+#[derive(Debug)]
+pub struct SortPreservingMerge<I> {
+    schema: SchemaRef,
+    sort_columns: Vec<usize>,
+    batch_size: usize,
+    converter: RowConverter,
+    streams: Vec<I>,
+    /// The current batch of each stream, or `None` if the stream is exhausted
+    cursors: Vec<Option<Cursor>>,
+    /// A binary min-heap of the indices of the streams with a current batch
+    heap: Vec<usize>,
+    /// The batches referenced by `cursors` and `indices`
+    batches: Vec<RecordBatch>,
+    /// The batch and row index of each row of the next output batch
+    indices: Vec<(usize, usize)>,
+    initialized: bool,
+    done: bool,
+}
+
+impl<I> SortPreservingMerge<I>
+where
+    I: Iterator<Item = Result<RecordBatch>>,
+{
+    /// Create a new [`SortPreservingMerge`] of `streams` of batches of `schema`, each
+    /// sorted by `sort_columns`, where each is a column index and the [`SortOptions`]
+    /// for that column, and returning batches with at most `batch_size` rows
+    ///
+    /// Returns an error if any of the columns are out of bounds, `sort_columns` is
+    /// empty, `batch_size` is zero, or the type of a sort column is not supported by
+    /// the row format
+    pub fn try_new(
+        schema: SchemaRef,
+        streams: Vec<I>,
+        sort_columns: Vec<(usize, SortOptions)>,
+        batch_size: usize,
+    ) -> Result<Self> {
+        if sort_columns.is_empty() {
+            return Err(ArrowError::InvalidArgumentError(
+                "Merge requires at least one sort column".to_string(),
+            ));
+        }
+        if batch_size == 0 {
+            return Err(ArrowError::InvalidArgumentError(
+                "Merge requires a batch size greater than zero".to_string(),
+            ));
+        }
+
+        let sort_fields = sort_columns
+            .iter()
+            .map(|(idx, options)| {
+                let field = schema.fields().get(*idx).ok_or_else(|| {
+                    ArrowError::InvalidArgumentError(format!(
+                        "Column index {} out of bounds for schema with {} fields",
+                        idx,
+                        schema.fields().len()
+                    ))
+                })?;
+                Ok(SortField::new_with_options(
+                    field.data_type().clone(),
+                    *options,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let converter = RowConverter::try_new(sort_fields)?;
+
+        Ok(Self {
+            schema,
+            sort_columns: sort_columns.into_iter().map(|(idx, _)| idx).collect(),
+            batch_size,
+            converter,
+            cursors: streams.iter().map(|_| None).collect(),
+            streams,
+            heap: vec![],
+            batches: vec![],
+            indices: Vec::with_capacity(batch_size),
+            initialized: false,
+            done: false,
+        })
+    }
+
+    /// Reads the next non-empty batch of the stream at `stream` into its cursor,
+    /// or clears the cursor if the stream is exhausted
+    fn advance_stream(&mut self, stream: usize) -> Result<()> {
+        self.cursors[stream] = None;
+        for batch in self.streams[stream].by_ref() {
+            let batch = batch?;
+            let schema = batch.schema();
+            let matches = schema.fields().len() == self.schema.fields().len()
+                && schema
+                    .fields()
+                    .iter()
+                    .zip(self.schema.fields())
+                    .all(|(a, b)| a.data_type() == b.data_type());
+            if !matches {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "Batch schema {} does not match merge schema {}",
+                    schema, self.schema
+                )));
+            }
+            if batch.num_rows() == 0 {
+                continue;
+            }
+
+            let keys: Vec<_> = self
+                .sort_columns
+                .iter()
+                .map(|idx| batch.column(*idx).clone())
+                .collect();
+            let rows = self.converter.convert_columns(&keys)?;
+            self.cursors[stream] = Some(Cursor {
+                batch: self.batches.len(),
+                rows,
+                offset: 0,
+            });
+            self.batches.push(batch);
+            break;
+        }
+        Ok(())
+    }
+
+    /// Reads the first batch of every stream
+    fn initialize(&mut self) -> Result<()> {
+        for stream in 0..self.streams.len() {
+            self.advance_stream(stream)?;
+        }
+        self.heap = (0..self.streams.len())
+            .filter(|stream| self.cursors[*stream].is_some())
+            .collect();
+        for pos in (0..self.heap.len() / 2).rev() {
+            sift_down(&mut self.heap, &self.cursors, pos);
+        }
+        Ok(())
+    }
+
+    /// Moves the smallest row of the streams to `indices` until a full batch is
+    /// buffered or all the streams are exhausted
+    fn fill_indices(&mut self) -> Result<()> {
+        while self.indices.len() < self.batch_size && !self.heap.is_empty() {
+            let stream = self.heap[0];
+            let cursor = self.cursors[stream].as_mut().unwrap();
+            self.indices.push((cursor.batch, cursor.offset));
+            cursor.offset += 1;
+
+            if cursor.offset == cursor.rows.num_rows() {
+                self.advance_stream(stream)?;
+                if self.cursors[stream].is_none() {
+                    let last = self.heap.pop().unwrap();
+                    if self.heap.is_empty() {
+                        break;
+                    }
+                    self.heap[0] = last;
+                }
+            }
+            sift_down(&mut self.heap, &self.cursors, 0);
+        }
+        Ok(())
+    }
+
+    /// Builds a [`RecordBatch`] from the rows in `indices`, and releases the batches
+    /// that are no longer referenced
+    fn build_batch(&mut self) -> Result<RecordBatch> {
+        let indices = std::mem::take(&mut self.indices);
+        let columns = (0..self.schema.fields().len())
+            .map(|col| {
+                let arrays: Vec<_> = self
+                    .batches
+                    .iter()
+                    .map(|batch| batch.column(col).data())
+                    .collect();
+                let mut mutable = MutableArrayData::new(arrays, false, indices.len());
+
+                // extend with runs of consecutive rows of the same batch
+                let mut start = 0;
+                for end in 1..=indices.len() {
+                    let (batch, row) = indices[end - 1];
+                    if end == indices.len() || indices[end] != (batch, row + 1) {
+                        let first = indices[start].1;
+                        mutable.extend(batch, first, row + 1);
+                        start = end;
+                    }
+                }
+                make_array(mutable.freeze())
+            })
+            .collect();
+        self.indices = indices;
+        self.indices.clear();
+
+        // keep only the current batch of each stream
+        let mut batches = Vec::with_capacity(self.cursors.len());
+        for cursor in self.cursors.iter_mut().flatten() {
+            batches.push(self.batches[cursor.batch].clone());
+            cursor.batch = batches.len() - 1;
+        }
+        self.batches = batches;
+
+        RecordBatch::try_new(self.schema.clone(), columns)
+    }
+}
+
+/// Restores the heap property of `heap` below `pos`, ordering streams by their
+/// current row and then by their index
+fn sift_down(heap: &mut [usize], cursors: &[Option<Cursor>], mut pos: usize) {
+    let less = |a: usize, b: usize| {
+        let a_row = cursors[a].as_ref().unwrap().row();
+        let b_row = cursors[b].as_ref().unwrap().row();
+        a_row.cmp(&b_row).then_with(|| a.cmp(&b)) == Ordering::Less
+    };
+    loop {
+        let mut smallest = pos;
+        for child in [2 * pos + 1, 2 * pos + 2] {
+            if child < heap.len() && less(heap[child], heap[smallest]) {
+                smallest = child;
+            }
+        }
+        if smallest == pos {
+            break;
+        }
+        heap.swap(pos, smallest);
+        pos = smallest;
+    }
+}
+
+impl<I> Iterator for SortPreservingMerge<I>
+where
+    I: Iterator<Item = Result<RecordBatch>>,
+{
+    type Item = Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if !self.initialized {
+            self.initialized = true;
+            if let Err(e) = self.initialize() {
+                self.done = true;
+                return Some(Err(e));
+            }
+        }
+        if let Err(e) = self.fill_indices() {
+            self.done = true;
+            return Some(Err(e));
+        }
+        if self.indices.is_empty() {
+            self.done = true;
+            return None;
+        }
+        Some(self.build_batch())
+    }
+}
+
+impl<I> RecordBatchReader for SortPreservingMerge<I>
+where
+    I: Iterator<Item = Result<RecordBatch>>,
+{
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compute::kernels::sort::{lexsort_to_indices, SortColumn};
+    use crate::compute::kernels::take::take;
+    use rand::{Rng, SeedableRng};
+    use std::sync::Arc;
+
+    fn batch(a: Vec<Option<i32>>, b: Vec<&str>) -> Result<RecordBatch> {
+        RecordBatch::try_from_iter(vec![
+            ("a", Arc::new(Int32Array::from(a)) as ArrayRef),
+            ("b", Arc::new(StringArray::from(b)) as ArrayRef),
+        ])
+    }
+
+    #[test]
+    fn test_merge() {
+        let streams = vec![
+            vec![
+                batch(vec![None, Some(1)], vec!["a", "b"]),
+                batch(vec![], vec![]),
+                batch(vec![Some(1), Some(5)], vec!["c", "d"]),
+            ],
+            vec![],
+            vec![batch(vec![Some(1), Some(2), Some(7)], vec!["e", "f", "g"])],
+        ];
+        let schema = streams[0][0].as_ref().unwrap().schema();
+        let merge = SortPreservingMerge::try_new(
+            schema,
+            streams.into_iter().map(|s| s.into_iter()).collect(),
+            vec![(0, SortOptions::default())],
+            3,
+        )
+        .unwrap();
+        assert_eq!(merge.schema().fields().len(), 2);
+
+        let batches = merge.collect::<Result<Vec<_>>>().unwrap();
+        let sizes: Vec<_> = batches.iter().map(|b| b.num_rows()).collect();
+        assert_eq!(sizes, vec![3, 3, 1]);
+
+        let output = RecordBatch::concat(&batches[0].schema(), &batches).unwrap();
+        let expected: Vec<ArrayRef> = vec![
+            Arc::new(Int32Array::from(vec![
+                None,
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(2),
+                Some(5),
+                Some(7),
+            ])),
+            Arc::new(StringArray::from(vec!["a", "b", "c", "e", "f", "d", "g"])),
+        ];
+        assert_eq!(output.columns(), &expected);
+    }
+
+    #[test]
+    fn test_merge_errors() {
+        let schema = batch(vec![], vec![]).unwrap().schema();
+        let empty = || Vec::<Result<RecordBatch>>::new().into_iter();
+
+        let err = SortPreservingMerge::try_new(schema.clone(), vec![empty()], vec![], 1)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Merge requires at least one sort column"
+        );
+
+        let err = SortPreservingMerge::try_new(
+            schema.clone(),
+            vec![empty()],
+            vec![(0, SortOptions::default())],
+            0,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Merge requires a batch size greater than zero"
+        );
+
+        let mut merge = SortPreservingMerge::try_new(
+            schema.clone(),
+            vec![empty()],
+            vec![(0, SortOptions::default())],
+            2,
+        )
+        .unwrap();
+        assert!(merge.next().is_none());
+
+        // errors of the input streams are returned, ending the merge
+        let streams = vec![
+            vec![batch(vec![Some(1)], vec!["a"])],
+            vec![Err(ArrowError::ComputeError("failed".to_string()))],
+        ];
+        let mut merge = SortPreservingMerge::try_new(
+            schema,
+            streams.into_iter().map(|s| s.into_iter()).collect(),
+            vec![(0, SortOptions::default())],
+            2,
+        )
+        .unwrap();
+        assert_eq!(
+            merge.next().unwrap().unwrap_err().to_string(),
+            "Compute error: failed"
+        );
+        assert!(merge.next().is_none());
+    }
+
+    #[test]
+    fn test_merge_random() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let options = SortOptions {
+            descending: true,
+            nulls_first: false,
+        };
+
+        let mut inputs = vec![];
+        let mut streams = vec![];
+        for stream in 0..5 {
+            let len = rng.gen_range(0..500);
+            let a = Arc::new(
+                (0..len)
+                    .map(|_| rng.gen_bool(0.9).then(|| rng.gen_range(0..20)))
+                    .collect::<Int32Array>(),
+            ) as ArrayRef;
+            let b = Arc::new(
+                (0..len)
+                    .map(|i| Some(format!("{}-{}", stream, i)))
+                    .collect::<StringArray>(),
+            ) as ArrayRef;
+            let columns = vec![SortColumn {
+                values: a.clone(),
+                options: Some(options),
+            }];
+            let indices = lexsort_to_indices(&columns, None).unwrap();
+            let a = take(a.as_ref(), &indices, None).unwrap();
+            let input = RecordBatch::try_from_iter(vec![("a", a), ("b", b)]).unwrap();
+
+            let mut batches = vec![];
+            let mut offset = 0;
+            while offset < len {
+                let batch_len = rng.gen_range(1..50).min(len - offset);
+                batches.push(Ok(input.slice(offset, batch_len)));
+                offset += batch_len;
+            }
+            inputs.push(input);
+            streams.push(batches.into_iter());
+        }
+
+        let schema = inputs[0].schema();
+        let merge =
+            SortPreservingMerge::try_new(schema.clone(), streams, vec![(0, options)], 64)
+                .unwrap();
+        let batches = merge.collect::<Result<Vec<_>>>().unwrap();
+        assert!(batches.iter().all(|b| b.num_rows() <= 64));
+        let output = RecordBatch::concat(&schema, &batches).unwrap();
+
+        let all = RecordBatch::concat(&schema, &inputs).unwrap();
+        assert_eq!(output.num_rows(), all.num_rows());
+
+        // rows are sorted, and rows with equal keys are in input order
+        let columns = vec![SortColumn {
+            values: all.column(0).clone(),
+            options: Some(options),
+        }];
+        let indices = lexsort_to_indices(&columns, None).unwrap();
+        let expected = take(all.column(0).as_ref(), &indices, None).unwrap();
+        assert_eq!(output.column(0), &expected);
+
+        let a = as_primitive_array::<Int32Type>(output.column(0));
+        let b = as_string_array(output.column(1));
+        let position = |i: usize| {
+            let (stream, row) = b.value(i).split_once('-').unwrap();
+            (
+                stream.parse::<usize>().unwrap(),
+                row.parse::<usize>().unwrap(),
+            )
+        };
+        for i in 1..output.num_rows() {
+            if a.is_valid(i) == a.is_valid(i - 1)
+                && (a.is_null(i) || a.value(i) == a.value(i - 1))
+            {
+                assert!(position(i - 1) < position(i));
+            }
+        }
+    }
+}
@@ -20,20 +20,33 @@
 pub mod aggregate;
 pub mod arithmetic;
 pub mod arity;
+pub mod bitwise;
 pub mod boolean;
 pub mod cast;
 pub mod cast_utils;
 pub mod comparison;
 pub mod concat;
 pub mod concat_elements;
+pub mod conditional;
+pub mod dictionary;
 pub mod filter;
+pub mod group_by;
+pub mod hash;
+pub mod interleave;
+pub mod join;
 pub mod length;
 pub mod limit;
+pub mod math;
+pub mod merge;
 pub mod partition;
 pub mod regexp;
+pub mod set;
+pub mod sketch;
 pub mod sort;
+pub mod string;
 pub mod substring;
 pub mod take;
 pub mod temporal;
+pub mod top_k;
 pub mod window;
 pub mod zip;
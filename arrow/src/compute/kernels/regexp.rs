@@ -15,19 +15,41 @@
 // specific language governing permissions and limitations
 // under the License.
 
-//! Defines kernel to extract substrings based on a regular
+//! Defines kernels to extract, replace and split substrings based on a regular
 //! expression of a \[Large\]StringArray
 
 use crate::array::{
-    ArrayRef, GenericStringArray, GenericStringBuilder, ListBuilder, OffsetSizeTrait,
+    Array, ArrayRef, GenericListArray, GenericListBuilder, GenericStringArray,
+    GenericStringBuilder, ListBuilder, OffsetSizeTrait,
 };
+use crate::compute::util::check_lengths;
 use crate::error::{ArrowError, Result};
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 use std::sync::Arc;
 
 use regex::Regex;
 
+/// Compiles `pattern` into a [`Regex`]
+fn compile_regex(pattern: &str) -> Result<Regex> {
+    Regex::new(pattern).map_err(|e| {
+        ArrowError::ComputeError(format!("Regular expression did not compile: {:?}", e))
+    })
+}
+
+/// Returns the compiled `pattern` from `patterns`, compiling and caching it if this is
+/// the first time it is used
+fn cached_regex<'a>(
+    patterns: &'a mut HashMap<String, Regex>,
+    pattern: &str,
+) -> Result<&'a Regex> {
+    if !patterns.contains_key(pattern) {
+        patterns.insert(pattern.to_string(), compile_regex(pattern)?);
+    }
+    Ok(&patterns[pattern])
+}
+
 /// Extract all groups matched by a regular expression for a given String array.
 pub fn regexp_match<OffsetSize: OffsetSizeTrait>(
     array: &GenericStringArray<OffsetSize>,
@@ -65,20 +87,7 @@ pub fn regexp_match<OffsetSize: OffsetSizeTrait>(
                     list_builder.append(true)?;
                 }
                 (Some(value), Some(pattern)) => {
-                    let existing_pattern = patterns.get(&pattern);
-                    let re = match existing_pattern {
-                        Some(re) => re.clone(),
-                        None => {
-                            let re = Regex::new(pattern.as_str()).map_err(|e| {
-                                ArrowError::ComputeError(format!(
-                                    "Regular expression did not compile: {:?}",
-                                    e
-                                ))
-                            })?;
-                            patterns.insert(pattern, re.clone());
-                            re
-                        }
-                    };
+                    let re = cached_regex(&mut patterns, &pattern)?;
                     match re.captures(value) {
                         Some(caps) => {
                             for m in caps.iter().skip(1).flatten() {
@@ -97,10 +106,228 @@ pub fn regexp_match<OffsetSize: OffsetSizeTrait>(
     Ok(Arc::new(list_builder.finish()))
 }
 
+/// Splits Postgres style `flags` into whether the `g` flag for a global match is set,
+/// and the remaining flags applied to `pattern`
+fn apply_flags<'a>(pattern: &'a str, flags: Option<&str>) -> (Cow<'a, str>, bool) {
+    let flags = flags.unwrap_or("");
+    let global = flags.contains('g');
+    let flags = flags.replace('g', "");
+    match flags.is_empty() {
+        true => (Cow::Borrowed(pattern), global),
+        false => (Cow::Owned(format!("(?{}){}", flags, pattern)), global),
+    }
+}
+
+/// Converts a Postgres style `replacement`, where `\1` to `\9` refer to capture groups
+/// and `\&` to the whole match, to the syntax of [`Regex::replace`]
+fn convert_replacement(replacement: &str) -> String {
+    let mut result = String::with_capacity(replacement.len());
+    let mut chars = replacement.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('$', _) => result.push_str("$$"),
+            ('\\', Some(&n)) if n.is_ascii_digit() => {
+                result.push_str(&format!("${{{}}}", n));
+                chars.next();
+            }
+            ('\\', Some('&')) => {
+                result.push_str("${0}");
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                result.push('\\');
+                chars.next();
+            }
+            (c, _) => result.push(c),
+        }
+    }
+    result
+}
+
+/// Replaces the first, or with `global` all, matches of `re` in `value`
+fn replace_matches<'a>(
+    re: &Regex,
+    value: &'a str,
+    replacement: &str,
+    global: bool,
+) -> Cow<'a, str> {
+    match global {
+        true => re.replace_all(value, replacement),
+        false => re.replace(value, replacement),
+    }
+}
+
+/// Replaces substrings of `array` matched by a regular expression with a replacement.
+///
+/// The regular expression, its flags and the replacement are given per row by
+/// `regex_array`, `flags_array` and `replacement_array`. Only the first match is
+/// replaced unless the flags contain `g`, other flags such as `i` are applied to the
+/// regular expression. Within a replacement, `\1` to `\9` insert the text matched by
+/// a capture group, `\&` the whole match and `\\` a backslash.
+///
+/// Regular expressions are compiled once for each distinct pattern and flags.
+pub fn regexp_replace<OffsetSize: OffsetSizeTrait>(
+    array: &GenericStringArray<OffsetSize>,
+    regex_array: &GenericStringArray<OffsetSize>,
+    replacement_array: &GenericStringArray<OffsetSize>,
+    flags_array: Option<&GenericStringArray<OffsetSize>>,
+) -> Result<GenericStringArray<OffsetSize>> {
+    check_lengths(array, regex_array)?;
+    check_lengths(array, replacement_array)?;
+    if let Some(flags) = flags_array {
+        check_lengths(array, flags)?;
+    }
+
+    let mut patterns: HashMap<String, Regex> = HashMap::new();
+    let mut builder = GenericStringBuilder::<OffsetSize>::new(0);
+    for i in 0..array.len() {
+        let flags = flags_array.and_then(|f| f.is_valid(i).then(|| f.value(i)));
+        match (
+            array.is_valid(i),
+            regex_array.is_valid(i),
+            replacement_array.is_valid(i),
+        ) {
+            (true, true, true) => {
+                let (pattern, global) = apply_flags(regex_array.value(i), flags);
+                let re = cached_regex(&mut patterns, &pattern)?;
+                let replacement = convert_replacement(replacement_array.value(i));
+                builder.append_value(replace_matches(
+                    re,
+                    array.value(i),
+                    &replacement,
+                    global,
+                ))?;
+            }
+            _ => builder.append_null()?,
+        }
+    }
+    Ok(builder.finish())
+}
+
+/// Replaces substrings of `array` matched by the regular expression `pattern` with
+/// `replacement`, see [`regexp_replace`]
+///
+/// ```
+/// # use arrow::array::StringArray;
+/// # use arrow::compute::kernels::regexp::regexp_replace_scalar;
+/// let array = StringArray::from(vec![Some("2022-06-01 12:00"), None]);
+/// let result = regexp_replace_scalar(&array, r"(\d+)-(\d+)-(\d+)", r"\3/\2/\1", None).unwrap();
+/// assert_eq!(result, StringArray::from(vec![Some("01/06/2022 12:00"), None]));
+///
+/// let array = StringArray::from(vec!["a1b22C333"]);
+/// let result = regexp_replace_scalar(&array, "[a-z]", "_", Some("gi")).unwrap();
+/// assert_eq!(result, StringArray::from(vec!["_1_22_333"]));
+/// ```
+pub fn regexp_replace_scalar<OffsetSize: OffsetSizeTrait>(
+    array: &GenericStringArray<OffsetSize>,
+    pattern: &str,
+    replacement: &str,
+    flags: Option<&str>,
+) -> Result<GenericStringArray<OffsetSize>> {
+    let (pattern, global) = apply_flags(pattern, flags);
+    let re = compile_regex(&pattern)?;
+    let replacement = convert_replacement(replacement);
+
+    let mut builder = GenericStringBuilder::<OffsetSize>::new(0);
+    for value in array.iter() {
+        builder.append_option(
+            value.map(|value| replace_matches(&re, value, &replacement, global)),
+        )?;
+    }
+    Ok(builder.finish())
+}
+
+/// Appends the substrings of `value` separated by matches of `re` to `builder`
+///
+/// As in Postgres, an empty match at the start or end of `value`, or immediately
+/// after another match, does not separate substrings
+fn append_split<OffsetSize: OffsetSizeTrait>(
+    builder: &mut GenericListBuilder<OffsetSize, GenericStringBuilder<OffsetSize>>,
+    re: &Regex,
+    value: &str,
+) -> Result<()> {
+    let mut start = 0;
+    for m in re.find_iter(value) {
+        if m.start() == m.end()
+            && (m.start() == 0 || m.start() == value.len() || m.start() == start)
+        {
+            continue;
+        }
+        builder.values().append_value(&value[start..m.start()])?;
+        start = m.end();
+    }
+    builder.values().append_value(&value[start..])?;
+    builder.append(true)
+}
+
+/// Splits every value of `array` on matches of a regular expression, returning a list
+/// of the substrings between matches.
+///
+/// The regular expression and its flags are given per row by `regex_array` and
+/// `flags_array`. An empty regular expression splits a string into its characters.
+///
+/// Regular expressions are compiled once for each distinct pattern and flags.
+pub fn regexp_split<OffsetSize: OffsetSizeTrait>(
+    array: &GenericStringArray<OffsetSize>,
+    regex_array: &GenericStringArray<OffsetSize>,
+    flags_array: Option<&GenericStringArray<OffsetSize>>,
+) -> Result<GenericListArray<OffsetSize>> {
+    check_lengths(array, regex_array)?;
+    if let Some(flags) = flags_array {
+        check_lengths(array, flags)?;
+    }
+
+    let mut patterns: HashMap<String, Regex> = HashMap::new();
+    let mut builder = GenericListBuilder::new(GenericStringBuilder::<OffsetSize>::new(0));
+    for i in 0..array.len() {
+        let flags = flags_array.and_then(|f| f.is_valid(i).then(|| f.value(i)));
+        match (array.is_valid(i), regex_array.is_valid(i)) {
+            (true, true) => {
+                let (pattern, _) = apply_flags(regex_array.value(i), flags);
+                let re = cached_regex(&mut patterns, &pattern)?;
+                append_split(&mut builder, re, array.value(i))?;
+            }
+            _ => builder.append(false)?,
+        }
+    }
+    Ok(builder.finish())
+}
+
+/// Splits every value of `array` on matches of the regular expression `pattern`, see
+/// [`regexp_split`]
+///
+/// ```
+/// # use arrow::array::{Array, StringArray};
+/// # use arrow::compute::kernels::regexp::regexp_split_scalar;
+/// let array = StringArray::from(vec![Some("a1b22c"), None]);
+/// let result = regexp_split_scalar(&array, r"\d+", None).unwrap();
+/// let fields = result.value(0);
+/// let fields = fields.as_any().downcast_ref::<StringArray>().unwrap();
+/// assert_eq!(fields, &StringArray::from(vec!["a", "b", "c"]));
+/// assert!(result.is_null(1));
+/// ```
+pub fn regexp_split_scalar<OffsetSize: OffsetSizeTrait>(
+    array: &GenericStringArray<OffsetSize>,
+    pattern: &str,
+    flags: Option<&str>,
+) -> Result<GenericListArray<OffsetSize>> {
+    let (pattern, _) = apply_flags(pattern, flags);
+    let re = compile_regex(&pattern)?;
+
+    let mut builder = GenericListBuilder::new(GenericStringBuilder::<OffsetSize>::new(0));
+    for value in array.iter() {
+        match value {
+            Some(value) => append_split(&mut builder, &re, value)?,
+            None => builder.append(false)?,
+        }
+    }
+    Ok(builder.finish())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::array::{ListArray, StringArray};
+    use crate::array::{LargeStringArray, ListArray, StringArray};
 
     #[test]
     fn match_single_group() -> Result<()> {
@@ -154,4 +381,129 @@ mod tests {
         assert_eq!(&expected, result);
         Ok(())
     }
+
+    #[test]
+    fn test_regexp_replace() -> Result<()> {
+        let array = StringArray::from(vec![
+            Some("foo bar foo"),
+            Some("foo bar foo"),
+            Some("Foo bar"),
+            Some("abc"),
+            None,
+            Some("abc"),
+        ]);
+        let pattern = StringArray::from(vec![
+            Some("foo"),
+            Some("(o+) (b)"),
+            Some("f(o)"),
+            Some("(b)"),
+            Some("a"),
+            None,
+        ]);
+        let replacement = StringArray::from(vec![
+            Some("x"),
+            Some(r"\2\1$1\\"),
+            Some(r"<\&\1>"),
+            Some(r"\3"),
+            Some("x"),
+            Some("x"),
+        ]);
+        let flags = StringArray::from(vec![Some("g"), None, Some("i"), None, None, None]);
+        let actual = regexp_replace(&array, &pattern, &replacement, Some(&flags))?;
+        let expected = StringArray::from(vec![
+            Some("x bar x"),
+            Some(r"fboo$1\ar foo"),
+            Some("<Foo>o bar"),
+            Some("ac"),
+            None,
+            None,
+        ]);
+        assert_eq!(actual, expected);
+
+        let actual = regexp_replace(&array, &pattern, &replacement, None)?;
+        assert_eq!(actual.value(0), "x bar foo");
+        assert_eq!(actual.value(2), "Foo bar");
+        Ok(())
+    }
+
+    #[test]
+    fn test_regexp_replace_scalar() -> Result<()> {
+        let array = LargeStringArray::from(vec![Some("aaa"), None, Some("")]);
+        let actual = regexp_replace_scalar(&array, "a*", "-", None)?;
+        assert_eq!(
+            actual,
+            LargeStringArray::from(vec![Some("-"), None, Some("-")])
+        );
+        let actual = regexp_replace_scalar(&array, "a", "b", Some("g"))?;
+        assert_eq!(
+            actual,
+            LargeStringArray::from(vec![Some("bbb"), None, Some("")])
+        );
+
+        let err = regexp_replace_scalar(&array, "(", "b", None).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Compute error: Regular expression did not compile"));
+
+        let pattern = LargeStringArray::from(vec!["a"]);
+        let err = regexp_replace(&array, &pattern, &pattern, None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Arrays must have the same length: 3 != 1"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_regexp_split() -> Result<()> {
+        fn values<O: OffsetSizeTrait>(
+            list: &GenericListArray<O>,
+        ) -> Vec<Option<Vec<String>>> {
+            list.iter()
+                .map(|v| {
+                    v.map(|v| {
+                        let v =
+                            v.as_any().downcast_ref::<GenericStringArray<O>>().unwrap();
+                        v.iter().map(|s| s.unwrap().to_string()).collect()
+                    })
+                })
+                .collect()
+        }
+        let strings = |s: &[&str]| Some(s.iter().map(|s| s.to_string()).collect());
+
+        let array = StringArray::from(vec![
+            Some("a, b,c"),
+            Some("abc"),
+            Some("aXbxc"),
+            Some(""),
+            None,
+        ]);
+        let pattern = StringArray::from(vec![
+            Some(r",\s*"),
+            Some(""),
+            Some("x"),
+            Some(","),
+            Some(","),
+        ]);
+        let flags = StringArray::from(vec![None, None, Some("i"), None, None]);
+        let actual = regexp_split(&array, &pattern, Some(&flags))?;
+        assert_eq!(
+            values(&actual),
+            vec![
+                strings(&["a", "b", "c"]),
+                strings(&["a", "b", "c"]),
+                strings(&["a", "b", "c"]),
+                strings(&[""]),
+                None
+            ]
+        );
+
+        let array = LargeStringArray::from(vec![Some("a1b22c"), Some("1"), None]);
+        let actual = regexp_split_scalar(&array, r"\d*", None)?;
+        assert_eq!(
+            values(&actual),
+            vec![strings(&["a", "b", "c"]), strings(&["", ""]), None]
+        );
+        Ok(())
+    }
 }
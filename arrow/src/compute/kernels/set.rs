@@ -0,0 +1,396 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines set kernels for [Array], such as `distinct`, `value_counts` and `is_in`
+//!
+//! Values are compared using the [row format](crate::row), so any type supported by
+//! [`RowConverter`] can be used, including strings, binary, fixed size binary and
+//! dictionaries, whose values rather than keys are compared.
+//!
+//! As with the [hash kernels](crate::compute::kernels::hash), floating point `-0.0`
+//! and `0.0` are equal, as are all `NaN` values.
+//!
+//! ```
+//! # use arrow::array::{Array, StringArray, UInt64Array};
+//! # use arrow::compute::kernels::set::value_counts;
+//! let array = StringArray::from(vec![Some("b"), Some("a"), None, Some("b")]);
+//! let counts = value_counts(&array).unwrap();
+//!
+//! // Values are returned in order of first appearance, with nulls counted as a value
+//! let expected = StringArray::from(vec![Some("b"), Some("a"), None]);
+//! assert_eq!(counts.column(0).as_ref(), &expected);
+//! let expected = UInt64Array::from(vec![2, 1, 1]);
+//! assert_eq!(counts.column(1).as_ref(), &expected);
+//! ```
+
+use std::sync::Arc;
+
+use indexmap::IndexSet;
+
+use crate::array::*;
+use crate::compute::kernels::hash::normalize_floats;
+use crate::compute::kernels::interleave::interleave;
+use crate::compute::kernels::take::take;
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+use crate::row::{OwnedRow, RowConverter, Rows, SortField};
+
+/// Converts `array` to the row format, normalizing floats so that `-0.0` and `0.0`,
+/// and all NaN, have the same row
+fn to_rows(array: &dyn Array) -> Result<Rows> {
+    let converter =
+        RowConverter::try_new(vec![SortField::new(array.data_type().clone())])?;
+    converter.convert_columns(&[normalize_floats(&make_array(array.data().clone()))?])
+}
+
+/// Returns the type of the values of `data_type`, that is the value type of
+/// dictionaries, and `data_type` itself otherwise
+fn value_type(data_type: &DataType) -> &DataType {
+    match data_type {
+        DataType::Dictionary(_, value_type) => value_type,
+        data_type => data_type,
+    }
+}
+
+/// Returns the index of the first occurrence of each distinct value of `array`, in
+/// the order in which they first appear. Nulls are considered equal to each other.
+///
+/// ```
+/// # use arrow::array::{Int32Array, UInt32Array};
+/// # use arrow::compute::kernels::set::distinct_indices;
+/// let array = Int32Array::from(vec![Some(3), None, Some(3), Some(1), None]);
+/// let indices = distinct_indices(&array).unwrap();
+/// assert_eq!(indices, UInt32Array::from(vec![0, 1, 3]));
+/// ```
+pub fn distinct_indices(array: &dyn Array) -> Result<UInt32Array> {
+    let rows = to_rows(array)?;
+    let mut distinct = IndexSet::new();
+    let mut indices = Vec::new();
+    for (idx, row) in rows.iter().enumerate() {
+        if distinct.get_index_of(&row).is_none() {
+            distinct.insert(row.owned());
+            indices.push(idx as u32);
+        }
+    }
+    Ok(UInt32Array::from(indices))
+}
+
+/// Returns the distinct values of `array`, also known as its unique values, in the
+/// order in which they first appear. Nulls are considered equal to each other, and
+/// so the result contains at most one null.
+///
+/// This is the equivalent of the `unique` function of other libraries, such as pandas.
+///
+/// ```
+/// # use arrow::array::StringArray;
+/// # use arrow::compute::kernels::set::distinct;
+/// let array = StringArray::from(vec!["b", "a", "b", "c", "a"]);
+/// let values = distinct(&array).unwrap();
+/// assert_eq!(values.as_ref(), &StringArray::from(vec!["b", "a", "c"]));
+/// ```
+pub fn distinct(array: &dyn Array) -> Result<ArrayRef> {
+    take(array, &distinct_indices(array)?, None)
+}
+
+/// Returns the distinct values of `array`, in the order in which they first appear,
+/// and the number of times each occurs, as a [`StructArray`] with a `values` field of
+/// the type of `array`, and a non-nullable `counts` field of type `UInt64`.
+///
+/// Nulls are considered equal to each other, and so are counted as a single value.
+pub fn value_counts(array: &dyn Array) -> Result<StructArray> {
+    let rows = to_rows(array)?;
+    let mut distinct = IndexSet::new();
+    let mut indices = Vec::new();
+    let mut counts = Vec::new();
+    for (idx, row) in rows.iter().enumerate() {
+        match distinct.get_index_of(&row) {
+            Some(value) => counts[value] += 1,
+            None => {
+                distinct.insert(row.owned());
+                indices.push(idx as u32);
+                counts.push(1_u64);
+            }
+        }
+    }
+
+    let values = take(array, &UInt32Array::from(indices), None)?;
+    Ok(StructArray::from(vec![
+        (
+            Field::new("values", array.data_type().clone(), true),
+            values,
+        ),
+        (
+            Field::new("counts", DataType::UInt64, false),
+            Arc::new(UInt64Array::from(counts)) as ArrayRef,
+        ),
+    ]))
+}
+
+/// Returns whether each value of `array` is contained in `value_set`, or null if the
+/// value is null. Nulls in `value_set` are ignored.
+///
+/// `array` and `value_set` must have the same type of values, but either may be
+/// dictionary encoded, e.g. a dictionary of strings can be tested against a string
+/// array.
+///
+/// ```
+/// # use arrow::array::{BooleanArray, DictionaryArray, StringArray};
+/// # use arrow::compute::kernels::set::is_in;
+/// # use arrow::datatypes::Int8Type;
+/// let array: DictionaryArray<Int8Type> =
+///     vec![Some("a"), Some("b"), None, Some("c")].into_iter().collect();
+/// let value_set = StringArray::from(vec!["c", "a"]);
+/// let result = is_in(&array, &value_set).unwrap();
+/// assert_eq!(result, BooleanArray::from(vec![Some(true), Some(false), None, Some(true)]));
+/// ```
+///
+/// # Errors
+///
+/// This function errors if the types of values of `array` and `value_set` differ, or
+/// are not supported by the [row format](crate::row)
+pub fn is_in(array: &dyn Array, value_set: &dyn Array) -> Result<BooleanArray> {
+    if value_type(array.data_type()) != value_type(value_set.data_type()) {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Cannot look up values of type {} in a value set of type {}",
+            array.data_type(),
+            value_set.data_type()
+        )));
+    }
+
+    let set_rows = to_rows(value_set)?;
+    let set: IndexSet<OwnedRow> = set_rows
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| value_set.is_valid(*idx))
+        .map(|(_, row)| row.owned())
+        .collect();
+
+    let rows = to_rows(array)?;
+    Ok(rows
+        .iter()
+        .enumerate()
+        .map(|(idx, row)| array.is_valid(idx).then(|| set.contains(&row)))
+        .collect())
+}
+
+/// Checks that `left` and `right` have the same data type
+fn check_same_type(left: &dyn Array, right: &dyn Array) -> Result<()> {
+    if left.data_type() != right.data_type() {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Set operations require arrays of the same data type, got {} and {}",
+            left.data_type(),
+            right.data_type()
+        )));
+    }
+    Ok(())
+}
+
+/// Returns the distinct values that are in `left` or `right`, in the order in which
+/// they first appear in `left` followed by `right`. Nulls are considered equal to
+/// each other.
+///
+/// ```
+/// # use arrow::array::Int32Array;
+/// # use arrow::compute::kernels::set::array_union;
+/// let left = Int32Array::from(vec![3, 1, 3]);
+/// let right = Int32Array::from(vec![2, 1, 4]);
+/// let union = array_union(&left, &right).unwrap();
+/// assert_eq!(union.as_ref(), &Int32Array::from(vec![3, 1, 2, 4]));
+/// ```
+pub fn array_union(left: &dyn Array, right: &dyn Array) -> Result<ArrayRef> {
+    check_same_type(left, right)?;
+    let mut distinct = IndexSet::new();
+    let mut indices = Vec::new();
+    for (array_idx, array) in [left, right].iter().enumerate() {
+        for (idx, row) in to_rows(*array)?.iter().enumerate() {
+            if distinct.get_index_of(&row).is_none() {
+                distinct.insert(row.owned());
+                indices.push((array_idx, idx));
+            }
+        }
+    }
+    interleave(&[left, right], &indices)
+}
+
+/// Returns the distinct values that are in both `left` and `right`, in the order in
+/// which they first appear in `left`. Nulls are considered equal to each other.
+///
+/// ```
+/// # use arrow::array::Int32Array;
+/// # use arrow::compute::kernels::set::array_intersect;
+/// let left = Int32Array::from(vec![3, 1, 3, 2]);
+/// let right = Int32Array::from(vec![2, 3, 4]);
+/// let intersection = array_intersect(&left, &right).unwrap();
+/// assert_eq!(intersection.as_ref(), &Int32Array::from(vec![3, 2]));
+/// ```
+pub fn array_intersect(left: &dyn Array, right: &dyn Array) -> Result<ArrayRef> {
+    check_same_type(left, right)?;
+    let right: IndexSet<OwnedRow> =
+        to_rows(right)?.iter().map(|row| row.owned()).collect();
+    let mut distinct = IndexSet::new();
+    let mut indices = Vec::new();
+    for (idx, row) in to_rows(left)?.iter().enumerate() {
+        if right.contains(&row) && distinct.get_index_of(&row).is_none() {
+            distinct.insert(row.owned());
+            indices.push(idx as u32);
+        }
+    }
+    take(left, &UInt32Array::from(indices), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distinct() {
+        let array = Float64Array::from(vec![
+            Some(1.0),
+            None,
+            Some(f64::NAN),
+            Some(1.0),
+            Some(-0.0),
+            None,
+            Some(0.0),
+            Some(-f64::NAN),
+        ]);
+        // -0.0 and 0.0 are equal, as are all NaN
+        let indices = distinct_indices(&array).unwrap();
+        assert_eq!(indices, UInt32Array::from(vec![0, 1, 2, 4]));
+
+        let array = BinaryArray::from(vec![b"ab".as_ref(), b"".as_ref(), b"ab".as_ref()]);
+        let values = distinct(&array).unwrap();
+        let expected = BinaryArray::from(vec![b"ab".as_ref(), b"".as_ref()]);
+        assert_eq!(values.as_ref(), &expected);
+
+        let array = Int32Array::from(Vec::<i32>::new());
+        assert_eq!(distinct(&array).unwrap().len(), 0);
+
+        let array = FixedSizeBinaryArray::from(vec![
+            Some(b"ab".as_ref()),
+            None,
+            Some(b"cd".as_ref()),
+            Some(b"ab".as_ref()),
+            None,
+        ]);
+        let values = distinct(&array).unwrap();
+        let expected = FixedSizeBinaryArray::from(vec![
+            Some(b"ab".as_ref()),
+            None,
+            Some(b"cd".as_ref()),
+        ]);
+        assert_eq!(values.as_ref(), &expected);
+    }
+
+    #[test]
+    fn test_value_counts_dictionary() {
+        // the values of the dictionary are compared, not its keys
+        let keys = Int8Array::from(vec![Some(0), Some(1), Some(2), None, Some(0)]);
+        let values = StringArray::from(vec!["x", "y", "x"]);
+        let array = DictionaryArray::<Int8Type>::try_new(&keys, &values).unwrap();
+
+        let counts = value_counts(&array).unwrap();
+        assert_eq!(
+            counts.data_type(),
+            &DataType::Struct(vec![
+                Field::new("values", array.data_type().clone(), true),
+                Field::new("counts", DataType::UInt64, false),
+            ])
+        );
+        let values = as_dictionary_array::<Int8Type>(counts.column(0));
+        assert_eq!(
+            values.keys(),
+            &Int8Array::from(vec![Some(0), Some(1), None])
+        );
+        let expected = UInt64Array::from(vec![3, 1, 1]);
+        assert_eq!(counts.column(1).as_ref(), &expected);
+    }
+
+    #[test]
+    fn test_float_values() {
+        let array = Float32Array::from(vec![0.0, -0.0, f32::NAN, -f32::NAN]);
+        let counts = value_counts(&array).unwrap();
+        let values = as_primitive_array::<Float32Type>(counts.column(0));
+        assert_eq!(values.len(), 2);
+        assert_eq!(values.value(0), 0.0);
+        assert!(values.value(1).is_nan());
+        let expected = UInt64Array::from(vec![2, 2]);
+        assert_eq!(counts.column(1).as_ref(), &expected);
+
+        let value_set = Float32Array::from(vec![-0.0, -f32::NAN]);
+        let expected = BooleanArray::from(vec![true, true, true, true]);
+        assert_eq!(is_in(&array, &value_set).unwrap(), expected);
+
+        let other = Float32Array::from(vec![-0.0, 1.0]);
+        let union = array_union(&array, &other).unwrap();
+        assert_eq!(union.len(), 3);
+        let intersect = array_intersect(&array, &other).unwrap();
+        assert_eq!(intersect.len(), 1);
+    }
+
+    #[test]
+    fn test_is_in() {
+        let array = Int64Array::from(vec![Some(1), None, Some(3), Some(4)]);
+        let value_set = Int64Array::from(vec![Some(4), None, Some(1)]);
+        let expected =
+            BooleanArray::from(vec![Some(true), None, Some(false), Some(true)]);
+        assert_eq!(is_in(&array, &value_set).unwrap(), expected);
+
+        let array = StringArray::from(vec!["a", "bc", "d"]);
+        let value_set: DictionaryArray<Int32Type> = vec!["d", "a"].into_iter().collect();
+        let expected = BooleanArray::from(vec![true, false, true]);
+        assert_eq!(is_in(&array, &value_set).unwrap(), expected);
+
+        let array = FixedSizeBinaryArray::from(vec![
+            Some(b"ab".as_ref()),
+            None,
+            Some(b"cd".as_ref()),
+        ]);
+        let value_set = FixedSizeBinaryArray::from(vec![b"cd".as_ref(), b"ef".as_ref()]);
+        let expected = BooleanArray::from(vec![Some(false), None, Some(true)]);
+        assert_eq!(is_in(&array, &value_set).unwrap(), expected);
+
+        let array = StringArray::from(vec!["a", "bc", "d"]);
+        let value_set = LargeStringArray::from(vec!["a"]);
+        let err = is_in(&array, &value_set).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Cannot look up values of type Utf8 in a value set of type LargeUtf8"
+        );
+    }
+
+    #[test]
+    fn test_union_intersect() {
+        let left = StringArray::from(vec![Some("a"), None, Some("b"), Some("a")]);
+        let right = StringArray::from(vec![Some("c"), Some("b"), None]);
+
+        let union = array_union(&left, &right).unwrap();
+        let expected = StringArray::from(vec![Some("a"), None, Some("b"), Some("c")]);
+        assert_eq!(union.as_ref(), &expected);
+
+        let intersection = array_intersect(&left, &right).unwrap();
+        let expected = StringArray::from(vec![None, Some("b")]);
+        assert_eq!(intersection.as_ref(), &expected);
+
+        let right = Int32Array::from(vec![1]);
+        let err = array_union(&left, &right).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Set operations require arrays of the same data type, got Utf8 and Int32"
+        );
+    }
+}
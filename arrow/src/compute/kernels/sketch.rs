@@ -0,0 +1,481 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines sketches that approximate aggregates in bounded memory: a [`TDigest`]
+//! for quantiles and a [`HyperLogLog`] for the number of distinct values.
+//!
+//! Sketches can be updated with many arrays and merged with each other, so that
+//! partial results computed on different batches or partitions can be combined.
+//!
+//! ```
+//! # use arrow::array::{Float64Array, StringArray};
+//! # use arrow::compute::kernels::sketch::{HyperLogLog, TDigest};
+//! let mut digest = TDigest::default();
+//! digest.update(&Float64Array::from_iter_values((0..1000).map(|v| v as f64)));
+//! let mut other = TDigest::default();
+//! other.update(&Float64Array::from_iter_values((1000..2000).map(|v| v as f64)));
+//! digest.merge(&other);
+//! let median = digest.quantile(0.5).unwrap().unwrap();
+//! assert!((median - 1000.).abs() < 10.);
+//!
+//! let mut hll = HyperLogLog::default();
+//! hll.update(&StringArray::from(vec!["a", "b", "a"])).unwrap();
+//! assert_eq!(hll.count(), 2);
+//! ```
+
+use std::cmp::Ordering;
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use num::ToPrimitive;
+
+use crate::array::*;
+use crate::compute::kernels::cast::cast;
+use crate::compute::kernels::hash::value_hashes;
+use crate::datatypes::{ArrowNumericType, DataType};
+use crate::error::{ArrowError, Result};
+
+/// Returns an error if `q` is not a valid quantile
+pub(crate) fn check_quantile(q: f64) -> Result<()> {
+    if (0.0..=1.0).contains(&q) {
+        Ok(())
+    } else {
+        Err(ArrowError::InvalidArgumentError(format!(
+            "Quantile must be between 0 and 1, got {}",
+            q
+        )))
+    }
+}
+
+/// A cluster of values of a [`TDigest`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+impl Centroid {
+    fn add(&mut self, other: &Centroid) {
+        self.weight += other.weight;
+        self.mean += (other.mean - self.mean) * other.weight / self.weight;
+    }
+}
+
+/// A [t-digest](https://arxiv.org/abs/1902.04023) that estimates quantiles of a
+/// distribution of values
+///
+/// Values are summarized by a sorted list of centroids, whose size is bounded by
+/// the `compression`. Centroids near the extremes of the distribution contain fewer
+/// values, such that extreme quantiles are estimated with a small relative error.
+///
+/// Null and NaN values are ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TDigest {
+    compression: f64,
+    centroids: Vec<Centroid>,
+    count: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Default for TDigest {
+    fn default() -> Self {
+        Self::new(100)
+    }
+}
+
+impl TDigest {
+    /// Create a new, empty [`TDigest`] with the given compression, higher values
+    /// result in more accurate estimates but use more memory
+    pub fn new(compression: usize) -> Self {
+        Self {
+            compression: compression.max(1) as f64,
+            centroids: vec![],
+            count: 0.,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    /// Returns the number of values summarized by this [`TDigest`]
+    pub fn count(&self) -> u64 {
+        self.count as u64
+    }
+
+    /// Returns the minimum value, or `None` if this [`TDigest`] is empty
+    pub fn min(&self) -> Option<f64> {
+        (self.count > 0.).then(|| self.min)
+    }
+
+    /// Returns the maximum value, or `None` if this [`TDigest`] is empty
+    pub fn max(&self) -> Option<f64> {
+        (self.count > 0.).then(|| self.max)
+    }
+
+    /// Adds the values of `array` to this [`TDigest`]
+    pub fn update<T>(&mut self, array: &PrimitiveArray<T>)
+    where
+        T: ArrowNumericType,
+        T::Native: ToPrimitive,
+    {
+        let mut values: Vec<f64> = array
+            .iter()
+            .flatten()
+            .map(|v| v.to_f64().unwrap())
+            .filter(|v| !v.is_nan())
+            .collect();
+        if values.is_empty() {
+            return;
+        }
+        values.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+
+        self.min = self.min.min(values[0]);
+        self.max = self.max.max(values[values.len() - 1]);
+        let centroids = values.into_iter().map(|mean| Centroid { mean, weight: 1. });
+        self.merge_centroids(centroids.collect());
+    }
+
+    /// Merges the values summarized by `other` into this [`TDigest`]
+    pub fn merge(&mut self, other: &TDigest) {
+        if other.count == 0. {
+            return;
+        }
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.merge_centroids(other.centroids.clone());
+    }
+
+    /// Merges the sorted `centroids` with the centroids of this [`TDigest`] and
+    /// compresses the result
+    fn merge_centroids(&mut self, centroids: Vec<Centroid>) {
+        let existing = std::mem::take(&mut self.centroids);
+        let mut merged = Vec::with_capacity(existing.len() + centroids.len());
+        let (mut a, mut b) = (
+            existing.into_iter().peekable(),
+            centroids.into_iter().peekable(),
+        );
+        loop {
+            let next = match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) if x.mean <= y.mean => a.next(),
+                (Some(_), Some(_)) => b.next(),
+                (Some(_), None) => a.next(),
+                (None, _) => b.next(),
+            };
+            match next {
+                Some(centroid) => merged.push(centroid),
+                None => break,
+            }
+        }
+
+        self.count = merged.iter().map(|c| c.weight).sum();
+        let mut merged = merged.into_iter();
+        let mut current = match merged.next() {
+            Some(current) => current,
+            None => return,
+        };
+        let mut weight_so_far = 0.;
+        let mut limit = self.count * self.q_limit(0.);
+        for centroid in merged {
+            if weight_so_far + current.weight + centroid.weight <= limit {
+                current.add(&centroid);
+            } else {
+                weight_so_far += current.weight;
+                self.centroids.push(current);
+                limit = self.count * self.q_limit(weight_so_far / self.count);
+                current = centroid;
+            }
+        }
+        self.centroids.push(current);
+    }
+
+    /// Returns the largest quantile that a centroid starting at quantile `q` may
+    /// extend to, using the `k1` scale function `k(q) = δ / 2π * asin(2q - 1)`
+    fn q_limit(&self, q: f64) -> f64 {
+        let k = (2. * q - 1.).asin() + 2. * PI / self.compression;
+        if k >= FRAC_PI_2 {
+            1.
+        } else {
+            (k.sin() + 1.) / 2.
+        }
+    }
+
+    /// Returns an estimate of the quantile `q` of the summarized values, or `None`
+    /// if this [`TDigest`] is empty
+    ///
+    /// Returns an error if `q` is not between 0 and 1
+    pub fn quantile(&self, q: f64) -> Result<Option<f64>> {
+        check_quantile(q)?;
+        if self.centroids.is_empty() {
+            return Ok(None);
+        }
+
+        // interpolate between the centers of the centroids, with the minimum
+        // and maximum at the start and end of the distribution
+        let target = q * self.count;
+        let interpolate = |(x0, y0): (f64, f64), (x1, y1): (f64, f64)| match x1 > x0 {
+            true => y0 + (y1 - y0) * (target - x0) / (x1 - x0),
+            false => y0,
+        };
+        let mut previous = (0., self.min);
+        let mut weight_so_far = 0.;
+        for centroid in &self.centroids {
+            let center = (weight_so_far + centroid.weight / 2., centroid.mean);
+            if target < center.0 {
+                let value = interpolate(previous, center);
+                return Ok(Some(value.max(self.min).min(self.max)));
+            }
+            previous = center;
+            weight_so_far += centroid.weight;
+        }
+        let value = interpolate(previous, (self.count, self.max));
+        Ok(Some(value.max(self.min).min(self.max)))
+    }
+}
+
+/// A [HyperLogLog](https://en.wikipedia.org/wiki/HyperLogLog) sketch that estimates
+/// the number of distinct values
+///
+/// Values are hashed with [`hash_array`](crate::compute::kernels::hash::hash_array),
+/// so values that compare equal are counted once regardless of their physical
+/// representation, for example a dictionary array is counted as its decoded values.
+/// Null values are ignored.
+///
+/// The relative standard error of the estimate is about `1.04 / sqrt(2^precision)`,
+/// which is 0.8% for the default precision of 14.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HyperLogLog {
+    precision: u8,
+    registers: Vec<u8>,
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        Self::try_new(14).unwrap()
+    }
+}
+
+impl HyperLogLog {
+    /// Create a new, empty [`HyperLogLog`] using `2^precision` registers
+    ///
+    /// Returns an error if `precision` is not between 4 and 18
+    pub fn try_new(precision: u8) -> Result<Self> {
+        if !(4..=18).contains(&precision) {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "HyperLogLog precision must be between 4 and 18, got {}",
+                precision
+            )));
+        }
+        Ok(Self {
+            precision,
+            registers: vec![0; 1 << precision],
+        })
+    }
+
+    /// Returns the precision of this [`HyperLogLog`]
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    /// Adds the values of `array` to this [`HyperLogLog`]
+    ///
+    /// Returns an error if the type of `array` can't be hashed
+    pub fn update(&mut self, array: &dyn Array) -> Result<()> {
+        if let DataType::Dictionary(_, value_type) = array.data_type() {
+            // The value of a valid key may be null
+            let values = cast(&make_array(array.data().clone()), value_type)?;
+            return self.update(values.as_ref());
+        }
+        for (i, hash) in value_hashes(array)?.into_iter().enumerate() {
+            if array.is_valid(i) {
+                self.insert_hash(hash);
+            }
+        }
+        Ok(())
+    }
+
+    fn insert_hash(&mut self, hash: u64) {
+        let p = self.precision as u32;
+        let index = (hash >> (64 - p)) as usize;
+        let rank = ((hash << p).leading_zeros() + 1).min(64 - p + 1) as u8;
+        let register = &mut self.registers[index];
+        *register = (*register).max(rank);
+    }
+
+    /// Merges the values counted by `other` into this [`HyperLogLog`]
+    ///
+    /// Returns an error if `other` has a different precision
+    pub fn merge(&mut self, other: &HyperLogLog) -> Result<()> {
+        if self.precision != other.precision {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Can't merge HyperLogLog of precision {} with precision {}",
+                other.precision, self.precision
+            )));
+        }
+        self.registers
+            .iter_mut()
+            .zip(&other.registers)
+            .for_each(|(a, b)| *a = (*a).max(*b));
+        Ok(())
+    }
+
+    /// Returns an estimate of the number of distinct values
+    pub fn count(&self) -> u64 {
+        let m = self.registers.len() as f64;
+        let alpha = 0.7213 / (1. + 1.079 / m);
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|r| 2_f64.powi(-(*r as i32)))
+            .sum();
+        let estimate = alpha * m * m / sum;
+
+        let zeros = self.registers.iter().filter(|r| **r == 0).count();
+        let estimate = match estimate.partial_cmp(&(2.5 * m)) {
+            // use linear counting for small cardinalities
+            Some(Ordering::Less | Ordering::Equal) if zeros > 0 => {
+                m * (m / zeros as f64).ln()
+            }
+            _ => estimate,
+        };
+        estimate.round() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datatypes::Int8Type;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_tdigest() {
+        let mut digest = TDigest::default();
+        assert_eq!(digest.quantile(0.5).unwrap(), None);
+        assert_eq!(digest.min(), None);
+
+        digest.update(&Float64Array::from(vec![
+            Some(3.),
+            None,
+            Some(1.),
+            Some(f64::NAN),
+            Some(2.),
+        ]));
+        assert_eq!(digest.count(), 3);
+        assert_eq!(digest.min(), Some(1.));
+        assert_eq!(digest.max(), Some(3.));
+        assert_eq!(digest.quantile(0.).unwrap(), Some(1.));
+        assert_eq!(digest.quantile(0.5).unwrap(), Some(2.));
+        assert_eq!(digest.quantile(1.).unwrap(), Some(3.));
+
+        let err = digest.quantile(1.5).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Quantile must be between 0 and 1, got 1.5"
+        );
+    }
+
+    #[test]
+    fn test_tdigest_accuracy() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let mut values: Vec<f64> =
+            (0..100_000).map(|_| rng.gen::<f64>().powi(2)).collect();
+
+        // each chunk is summarized separately and the digests merged
+        let mut digest = TDigest::default();
+        for chunk in values.chunks(7_000) {
+            let mut partial = TDigest::default();
+            partial.update(&Float64Array::from(chunk.to_vec()));
+            digest.merge(&partial);
+        }
+        assert_eq!(digest.count(), 100_000);
+        assert!(digest.centroids.len() < 200);
+
+        values.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+        for q in [0.001, 0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99, 0.999] {
+            let expected = values[(q * (values.len() - 1) as f64) as usize];
+            let actual = digest.quantile(q).unwrap().unwrap();
+            // the error is bounded by the rank of the estimate
+            let rank =
+                values.partition_point(|v| *v < actual) as f64 / values.len() as f64;
+            assert!(
+                (rank - q).abs() < 0.001,
+                "quantile {}: expected {} got {} (rank {})",
+                q,
+                expected,
+                actual,
+                rank
+            );
+        }
+    }
+
+    #[test]
+    fn test_hyperloglog() {
+        let mut hll = HyperLogLog::default();
+        assert_eq!(hll.count(), 0);
+
+        hll.update(&Int32Array::from(vec![Some(1), None, Some(2), Some(1)]))
+            .unwrap();
+        assert_eq!(hll.count(), 2);
+
+        let array = Int32Array::from(vec![Some(3), None, Some(4), None]);
+        let mut hll = HyperLogLog::default();
+        hll.update(&array.slice(1, 3)).unwrap();
+        assert_eq!(hll.count(), 1);
+
+        // dictionaries are counted as their values, ignoring null values
+        let values = StringArray::from(vec![Some("a"), None, Some("b")]);
+        let keys = Int8Array::from(vec![Some(0), Some(1), None, Some(2), Some(0)]);
+        let dict = DictionaryArray::<Int8Type>::try_new(&keys, &values).unwrap();
+        let mut hll = HyperLogLog::default();
+        hll.update(&dict).unwrap();
+        assert_eq!(hll.count(), 2);
+        hll.update(&StringArray::from(vec!["b", "c"])).unwrap();
+        assert_eq!(hll.count(), 3);
+
+        let other = HyperLogLog::try_new(10).unwrap();
+        let err = hll.merge(&other).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Can't merge HyperLogLog of precision 10 with precision 14"
+        );
+        let err = HyperLogLog::try_new(2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: HyperLogLog precision must be between 4 and 18, got 2"
+        );
+    }
+
+    #[test]
+    fn test_hyperloglog_accuracy() {
+        for distinct in [100, 10_000, 1_000_000] {
+            let mut hll = HyperLogLog::default();
+            let mut partial = HyperLogLog::default();
+            for chunk in (0..distinct as i64).collect::<Vec<_>>().chunks(100_000) {
+                partial.update(&Int64Array::from(chunk.to_vec())).unwrap();
+                // duplicate values don't change the estimate
+                partial.update(&Int64Array::from(chunk.to_vec())).unwrap();
+                hll.merge(&partial).unwrap();
+            }
+            let error = (hll.count() as f64 - distinct as f64).abs() / distinct as f64;
+            assert!(
+                error < 0.03,
+                "{} distinct values: {}",
+                distinct,
+                hll.count()
+            );
+        }
+    }
+}
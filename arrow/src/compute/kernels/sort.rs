@@ -17,6 +17,7 @@
 
 //! Defines sort kernel for `ArrayRef`
 
+use crate::array::build_compare_with_options;
 use crate::array::*;
 use crate::buffer::MutableBuffer;
 use crate::compute::take;
@@ -160,6 +161,11 @@ fn partition_validity(array: &ArrayRef) -> (Vec<u32>, Vec<u32>) {
 /// Sort elements from `ArrayRef` into an unsigned integer (`UInt32Array`) of indices.
 /// For floating point arrays any NaN values are considered to be greater than any other non-null value
 /// limit is an option for partial_sort
+///
+/// Nested types are sorted in the order defined by [build_compare]: lists element by
+/// element, structs field by field, and unions by type id and then by value, with
+/// the options applied to nested values as well. For example, null fields of a struct
+/// are ordered according to `nulls_first` and descending sorts reverse nested values.
 pub fn sort_to_indices(
     values: &ArrayRef,
     options: Option<SortOptions>,
@@ -269,64 +275,6 @@ pub fn sort_to_indices(
         }
         DataType::Utf8 => sort_string::<i32>(values, v, n, &options, limit),
         DataType::LargeUtf8 => sort_string::<i64>(values, v, n, &options, limit),
-        DataType::List(field) | DataType::FixedSizeList(field, _) => match field
-            .data_type()
-        {
-            DataType::Int8 => sort_list::<i32, Int8Type>(values, v, n, &options, limit),
-            DataType::Int16 => sort_list::<i32, Int16Type>(values, v, n, &options, limit),
-            DataType::Int32 => sort_list::<i32, Int32Type>(values, v, n, &options, limit),
-            DataType::Int64 => sort_list::<i32, Int64Type>(values, v, n, &options, limit),
-            DataType::UInt8 => sort_list::<i32, UInt8Type>(values, v, n, &options, limit),
-            DataType::UInt16 => {
-                sort_list::<i32, UInt16Type>(values, v, n, &options, limit)
-            }
-            DataType::UInt32 => {
-                sort_list::<i32, UInt32Type>(values, v, n, &options, limit)
-            }
-            DataType::UInt64 => {
-                sort_list::<i32, UInt64Type>(values, v, n, &options, limit)
-            }
-            DataType::Float32 => {
-                sort_list::<i32, Float32Type>(values, v, n, &options, limit)
-            }
-            DataType::Float64 => {
-                sort_list::<i32, Float64Type>(values, v, n, &options, limit)
-            }
-            t => {
-                return Err(ArrowError::ComputeError(format!(
-                    "Sort not supported for list type {:?}",
-                    t
-                )));
-            }
-        },
-        DataType::LargeList(field) => match field.data_type() {
-            DataType::Int8 => sort_list::<i64, Int8Type>(values, v, n, &options, limit),
-            DataType::Int16 => sort_list::<i64, Int16Type>(values, v, n, &options, limit),
-            DataType::Int32 => sort_list::<i64, Int32Type>(values, v, n, &options, limit),
-            DataType::Int64 => sort_list::<i64, Int64Type>(values, v, n, &options, limit),
-            DataType::UInt8 => sort_list::<i64, UInt8Type>(values, v, n, &options, limit),
-            DataType::UInt16 => {
-                sort_list::<i64, UInt16Type>(values, v, n, &options, limit)
-            }
-            DataType::UInt32 => {
-                sort_list::<i64, UInt32Type>(values, v, n, &options, limit)
-            }
-            DataType::UInt64 => {
-                sort_list::<i64, UInt64Type>(values, v, n, &options, limit)
-            }
-            DataType::Float32 => {
-                sort_list::<i64, Float32Type>(values, v, n, &options, limit)
-            }
-            DataType::Float64 => {
-                sort_list::<i64, Float64Type>(values, v, n, &options, limit)
-            }
-            t => {
-                return Err(ArrowError::ComputeError(format!(
-                    "Sort not supported for list type {:?}",
-                    t
-                )));
-            }
-        },
         DataType::Dictionary(key_type, value_type)
             if *value_type.as_ref() == DataType::Utf8 =>
         {
@@ -367,6 +315,17 @@ pub fn sort_to_indices(
             sort_binary::<i32>(values, v, n, &options, limit)
         }
         DataType::LargeBinary => sort_binary::<i64>(values, v, n, &options, limit),
+        DataType::Null
+        | DataType::Float16
+        | DataType::List(_)
+        | DataType::LargeList(_)
+        | DataType::FixedSizeList(_, _)
+        | DataType::Map(_, _)
+        | DataType::Struct(_)
+        | DataType::Union(_, _, _)
+        | DataType::Dictionary(_, _) => {
+            sort_by_comparator(values, v, n, &options, limit)?
+        }
         t => {
             return Err(ArrowError::ComputeError(format!(
                 "Sort not supported for data type {:?}",
@@ -703,22 +662,7 @@ where
     }
 }
 
-fn sort_list<S, T>(
-    values: &ArrayRef,
-    value_indices: Vec<u32>,
-    null_indices: Vec<u32>,
-    options: &SortOptions,
-    limit: Option<usize>,
-) -> UInt32Array
-where
-    S: OffsetSizeTrait,
-    T: ArrowPrimitiveType,
-    T::Native: std::cmp::PartialOrd,
-{
-    sort_list_inner::<S>(values, value_indices, null_indices, options, limit)
-}
-
-fn sort_list_inner<S>(
+fn sort_binary<S>(
     values: &ArrayRef,
     value_indices: Vec<u32>,
     mut null_indices: Vec<u32>,
@@ -728,12 +672,12 @@ fn sort_list_inner<S>(
 where
     S: OffsetSizeTrait,
 {
-    let mut valids: Vec<(u32, ArrayRef)> = values
+    let mut valids: Vec<(u32, &[u8])> = values
         .as_any()
-        .downcast_ref::<FixedSizeListArray>()
+        .downcast_ref::<FixedSizeBinaryArray>()
         .map_or_else(
             || {
-                let values = as_generic_list_array::<S>(values);
+                let values = as_generic_binary_array::<S>(values);
                 value_indices
                     .iter()
                     .copied()
@@ -755,7 +699,8 @@ where
     if let Some(limit) = limit {
         len = limit.min(len);
     }
-    sort_valids_array(descending, &mut valids, &mut null_indices, len);
+
+    sort_valids(descending, &mut valids, &mut null_indices, len, cmp);
 
     let mut valid_indices: Vec<u32> = valids.iter().map(|tuple| tuple.0).collect();
     if options.nulls_first {
@@ -769,70 +714,42 @@ where
     }
 }
 
-fn sort_binary<S>(
+/// Sort any values with the comparator returned by [build_compare_with_options]
+fn sort_by_comparator(
     values: &ArrayRef,
-    value_indices: Vec<u32>,
+    mut value_indices: Vec<u32>,
     mut null_indices: Vec<u32>,
     options: &SortOptions,
     limit: Option<usize>,
-) -> UInt32Array
-where
-    S: OffsetSizeTrait,
-{
-    let mut valids: Vec<(u32, &[u8])> = values
-        .as_any()
-        .downcast_ref::<FixedSizeBinaryArray>()
-        .map_or_else(
-            || {
-                let values = as_generic_binary_array::<S>(values);
-                value_indices
-                    .iter()
-                    .copied()
-                    .map(|index| (index, values.value(index as usize)))
-                    .collect()
-            },
-            |values| {
-                value_indices
-                    .iter()
-                    .copied()
-                    .map(|index| (index, values.value(index as usize)))
-                    .collect()
-            },
-        );
+) -> Result<UInt32Array> {
+    let compare = build_compare_with_options(values.as_ref(), values.as_ref(), options)?;
 
     let mut len = values.len();
-    let descending = options.descending;
-
     if let Some(limit) = limit {
         len = limit.min(len);
     }
+    let valids_len = value_indices.len();
+    if !options.descending {
+        sort_unstable_by(&mut value_indices, len.min(valids_len), |a, b| {
+            compare(*a as usize, *b as usize)
+        });
+    } else {
+        sort_unstable_by(&mut value_indices, len.min(valids_len), |a, b| {
+            compare(*a as usize, *b as usize).reverse()
+        });
+        // reverse to keep a stable ordering
+        null_indices.reverse();
+    }
 
-    sort_valids(descending, &mut valids, &mut null_indices, len, cmp);
-
-    let mut valid_indices: Vec<u32> = valids.iter().map(|tuple| tuple.0).collect();
-    if options.nulls_first {
-        null_indices.append(&mut valid_indices);
+    Ok(if options.nulls_first {
+        null_indices.append(&mut value_indices);
         null_indices.truncate(len);
         UInt32Array::from(null_indices)
     } else {
-        valid_indices.append(&mut null_indices);
-        valid_indices.truncate(len);
-        UInt32Array::from(valid_indices)
-    }
-}
-
-/// Compare two `Array`s based on the ordering defined in [build_compare]
-fn cmp_array(a: &dyn Array, b: &dyn Array) -> Ordering {
-    let cmp_op = build_compare(a, b).unwrap();
-    let length = a.len().max(b.len());
-
-    for i in 0..length {
-        let result = cmp_op(i, i);
-        if result != Ordering::Equal {
-            return result;
-        }
-    }
-    Ordering::Equal
+        value_indices.append(&mut null_indices);
+        value_indices.truncate(len);
+        UInt32Array::from(value_indices)
+    })
 }
 
 /// One column to be used in lexicographical sort
@@ -1017,10 +934,11 @@ impl LexicographicalComparator<'_> {
                 // use ArrayData for is_valid checks later to avoid dynamic call
                 let values = column.values.as_ref();
                 let data = values.data_ref();
+                let options = column.options.unwrap_or_default();
                 Ok((
                     data,
-                    build_compare(values, values)?,
-                    column.options.unwrap_or_default(),
+                    build_compare_with_options(values, values, &options)?,
+                    options,
                 ))
             })
             .collect::<Result<Vec<_>>>()?;
@@ -1047,29 +965,11 @@ fn sort_valids<T, U>(
     }
 }
 
-fn sort_valids_array<T>(
-    descending: bool,
-    valids: &mut [(u32, ArrayRef)],
-    nulls: &mut [T],
-    len: usize,
-) {
-    let valids_len = valids.len();
-    if !descending {
-        sort_unstable_by(valids, len.min(valids_len), |a, b| {
-            cmp_array(a.1.as_ref(), b.1.as_ref())
-        });
-    } else {
-        sort_unstable_by(valids, len.min(valids_len), |a, b| {
-            cmp_array(a.1.as_ref(), b.1.as_ref()).reverse()
-        });
-        // reverse to keep a stable ordering
-        nulls.reverse();
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::buffer::Buffer;
+    use crate::compute::cast;
     use crate::compute::util::tests::{
         build_fixed_size_list_nullable, build_generic_list_nullable,
     };
@@ -3212,6 +3112,146 @@ mod tests {
         test_lex_sort_arrays(input, expected, None);
     }
 
+    #[test]
+    fn test_sort_struct() {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(2),
+            None,
+            Some(1),
+            Some(2),
+            Some(1),
+        ]));
+        let b: ArrayRef = Arc::new(StringArray::from(vec![
+            Some("b"),
+            Some("c"),
+            None,
+            Some("a"),
+            Some("a"),
+        ]));
+        let array: ArrayRef = Arc::new(StructArray::from((
+            vec![
+                (Field::new("a", DataType::Int32, true), a),
+                (Field::new("b", DataType::Utf8, true), b),
+            ],
+            Buffer::from([0b00011011]),
+        )));
+
+        let indices = sort_to_indices(&array, None, None).unwrap();
+        assert_eq!(indices, UInt32Array::from(vec![2, 1, 4, 3, 0]));
+
+        let options = SortOptions {
+            descending: true,
+            nulls_first: false,
+        };
+        let indices = sort_to_indices(&array, Some(options), None).unwrap();
+        assert_eq!(indices, UInt32Array::from(vec![0, 3, 4, 1, 2]));
+
+        let indices = sort_to_indices(&array, Some(options), Some(2)).unwrap();
+        assert_eq!(indices, UInt32Array::from(vec![0, 3]));
+
+        let sorted = sort(&array, None).unwrap();
+        let sorted = sorted.as_any().downcast_ref::<StructArray>().unwrap();
+        assert!(sorted.is_null(0));
+        let a = as_primitive_array::<Int32Type>(sorted.column(0));
+        assert_eq!(
+            a.iter().skip(1).collect::<Vec<_>>(),
+            vec![None, Some(1), Some(2), Some(2)]
+        );
+    }
+
+    #[test]
+    fn test_sort_string_list() {
+        let mut builder = ListBuilder::new(StringBuilder::new(8));
+        builder.values().append_value("b").unwrap();
+        builder.append(true).unwrap();
+        builder.values().append_value("a").unwrap();
+        builder.values().append_value("c").unwrap();
+        builder.append(true).unwrap();
+        builder.append(false).unwrap();
+        builder.values().append_null().unwrap();
+        builder.append(true).unwrap();
+        builder.values().append_value("a").unwrap();
+        builder.append(true).unwrap();
+        let array: ArrayRef = Arc::new(builder.finish());
+
+        let indices = sort_to_indices(&array, None, None).unwrap();
+        assert_eq!(indices, UInt32Array::from(vec![2, 3, 4, 1, 0]));
+
+        let options = SortOptions {
+            descending: false,
+            nulls_first: false,
+        };
+        let indices = sort_to_indices(&array, Some(options), None).unwrap();
+        assert_eq!(indices, UInt32Array::from(vec![4, 1, 0, 3, 2]));
+    }
+
+    #[test]
+    fn test_sort_union() {
+        let mut builder = UnionBuilder::new_dense(4);
+        builder.append::<Int32Type>("a", 3).unwrap();
+        builder.append::<Float64Type>("b", 1.5).unwrap();
+        builder.append::<Int32Type>("a", 1).unwrap();
+        builder.append::<Float64Type>("b", -1.0).unwrap();
+        let array: ArrayRef = Arc::new(builder.build().unwrap());
+
+        let indices = sort_to_indices(&array, None, None).unwrap();
+        assert_eq!(indices, UInt32Array::from(vec![2, 0, 3, 1]));
+
+        let options = SortOptions {
+            descending: true,
+            nulls_first: true,
+        };
+        let indices = sort_to_indices(&array, Some(options), None).unwrap();
+        assert_eq!(indices, UInt32Array::from(vec![1, 3, 0, 2]));
+    }
+
+    #[test]
+    fn test_sort_primitive_dict() {
+        let values = Int32Array::from(vec![Some(5), Some(-1), None]);
+        let keys = Int16Array::from(vec![Some(0), None, Some(1), Some(2), Some(0)]);
+        let array: ArrayRef =
+            Arc::new(DictionaryArray::<Int16Type>::try_new(&keys, &values).unwrap());
+
+        let indices = sort_to_indices(&array, None, None).unwrap();
+        assert_eq!(indices.value(0), 1);
+        assert_eq!(indices.value(1), 3);
+        assert_eq!(indices.value(2), 2);
+
+        let sorted = sort(&array, None).unwrap();
+        let sorted = cast(&sorted, &DataType::Int32).unwrap();
+        assert_eq!(
+            as_primitive_array::<Int32Type>(&sorted)
+                .iter()
+                .collect::<Vec<_>>(),
+            vec![None, None, Some(-1), Some(5), Some(5)]
+        );
+    }
+
+    #[test]
+    fn test_lex_sort_struct() {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![Some(1), Some(1), None]));
+        let b: ArrayRef =
+            Arc::new(BooleanArray::from(vec![Some(true), None, Some(false)]));
+        let structs: ArrayRef =
+            Arc::new(StructArray::try_from(vec![("a", a), ("b", b)]).unwrap());
+        let input = vec![
+            SortColumn {
+                values: structs,
+                options: Some(SortOptions {
+                    descending: false,
+                    nulls_first: false,
+                }),
+            },
+            SortColumn {
+                values: Arc::new(Int64Array::from(vec![3, 2, 1])) as ArrayRef,
+                options: None,
+            },
+        ];
+
+        let indices = lexsort_to_indices(&input, None).unwrap();
+        assert_eq!(indices, UInt32Array::from(vec![0, 1, 2]));
+    }
+
     #[test]
     fn test_partial_sort() {
         let mut before: Vec<&str> = vec![
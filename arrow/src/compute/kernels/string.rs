@@ -0,0 +1,914 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines string manipulation kernels for [`GenericStringArray`], such as [`upper`],
+//! [`trim`], [`lpad`], [`replace`] and [`split`].
+//!
+//! Kernels taking an argument, such as the prefix of [`starts_with`], accept either
+//! an array of arguments, one per row, or a scalar argument for every row in their
+//! `_scalar` form, e.g. [`starts_with_scalar`]. A null value or argument results in a
+//! null value.
+//!
+//! Lengths and positions are measured in characters, i.e. Unicode scalar values.
+
+use std::sync::Arc;
+
+use crate::array::*;
+use crate::compute::util::check_lengths;
+use crate::error::{ArrowError, Result};
+
+/// Collects `values` into a [`GenericStringArray`] of `len` values
+fn collect_strings<O, I, S>(len: usize, values: I) -> Result<GenericStringArray<O>>
+where
+    O: OffsetSizeTrait,
+    I: IntoIterator<Item = Option<S>>,
+    S: AsRef<str>,
+{
+    let mut builder = GenericStringBuilder::<O>::with_capacity(len, 0);
+    for value in values {
+        builder.append_option(value)?;
+    }
+    Ok(builder.finish())
+}
+
+/// Collects `values` into a [`GenericStringArray`] of `len` values, returning the first
+/// error of `values`, if any
+fn try_collect_strings<O, I, S>(len: usize, values: I) -> Result<GenericStringArray<O>>
+where
+    O: OffsetSizeTrait,
+    I: IntoIterator<Item = Result<Option<S>>>,
+    S: AsRef<str>,
+{
+    let mut builder = GenericStringBuilder::<O>::with_capacity(len, 0);
+    for value in values {
+        builder.append_option(value?)?;
+    }
+    Ok(builder.finish())
+}
+
+/// Applies `op` to every valid value of `array`
+fn unary_string<'a, O, F, S>(
+    array: &'a GenericStringArray<O>,
+    op: F,
+) -> Result<GenericStringArray<O>>
+where
+    O: OffsetSizeTrait,
+    F: Fn(&'a str) -> S,
+    S: AsRef<str>,
+{
+    collect_strings(array.len(), array.iter().map(|v| v.map(&op)))
+}
+
+/// Creates an `Int32Array` or `Int64Array` for an offset size of `i32` or `i64`
+/// respectively, from `values` that are known to fit in `O`
+fn offset_size_array<O, I>(values: I) -> ArrayRef
+where
+    O: OffsetSizeTrait,
+    I: Iterator<Item = Option<usize>>,
+{
+    match O::IS_LARGE {
+        true => Arc::new(values.map(|v| v.map(|v| v as i64)).collect::<Int64Array>()),
+        false => Arc::new(values.map(|v| v.map(|v| v as i32)).collect::<Int32Array>()),
+    }
+}
+
+/// Converts every value of `array` to upper case
+///
+/// ```
+/// # use arrow::array::StringArray;
+/// # use arrow::compute::kernels::string::upper;
+/// let array = StringArray::from(vec![Some("arrow"), None, Some("straße")]);
+/// let result = upper(&array).unwrap();
+/// assert_eq!(result, StringArray::from(vec![Some("ARROW"), None, Some("STRASSE")]));
+/// ```
+pub fn upper<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+) -> Result<GenericStringArray<O>> {
+    unary_string(array, str::to_uppercase)
+}
+
+/// Converts every value of `array` to lower case
+pub fn lower<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+) -> Result<GenericStringArray<O>> {
+    unary_string(array, str::to_lowercase)
+}
+
+/// Reverses the characters of every value of `array`
+pub fn reverse<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+) -> Result<GenericStringArray<O>> {
+    unary_string(array, |v| v.chars().rev().collect::<String>())
+}
+
+/// Returns the number of characters in every value of `array`, as an `Int32Array`
+/// for a `StringArray` and as an `Int64Array` for a `LargeStringArray`.
+///
+/// Use [`length`](crate::compute::kernels::length::length) for the number of bytes.
+pub fn char_length<O: OffsetSizeTrait>(array: &GenericStringArray<O>) -> ArrayRef {
+    offset_size_array::<O, _>(array.iter().map(|v| v.map(|v| v.chars().count())))
+}
+
+/// Which ends of a string to trim
+#[derive(Debug, Clone, Copy)]
+enum TrimSide {
+    Both,
+    Left,
+    Right,
+}
+
+impl TrimSide {
+    fn trim<'a>(self, value: &'a str, characters: &str) -> &'a str {
+        let matches = |c: char| characters.contains(c);
+        match self {
+            Self::Both => value.trim_matches(matches),
+            Self::Left => value.trim_start_matches(matches),
+            Self::Right => value.trim_end_matches(matches),
+        }
+    }
+
+    fn trim_whitespace(self, value: &str) -> &str {
+        match self {
+            Self::Both => value.trim(),
+            Self::Left => value.trim_start(),
+            Self::Right => value.trim_end(),
+        }
+    }
+}
+
+fn trim_chars_impl<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    characters: &GenericStringArray<O>,
+    side: TrimSide,
+) -> Result<GenericStringArray<O>> {
+    check_lengths(array, characters)?;
+    let values = array.iter().zip(characters).map(|(v, c)| match (v, c) {
+        (Some(v), Some(c)) => Some(side.trim(v, c)),
+        _ => None,
+    });
+    collect_strings(array.len(), values)
+}
+
+/// Removes leading and trailing whitespace from every value of `array`
+///
+/// ```
+/// # use arrow::array::StringArray;
+/// # use arrow::compute::kernels::string::{trim, trim_chars_scalar};
+/// let array = StringArray::from(vec![Some("  arrow \n"), None, Some("xxrustxy")]);
+/// let result = trim(&array).unwrap();
+/// assert_eq!(result, StringArray::from(vec![Some("arrow"), None, Some("xxrustxy")]));
+/// let result = trim_chars_scalar(&array, "xy").unwrap();
+/// assert_eq!(result, StringArray::from(vec![Some("  arrow \n"), None, Some("rust")]));
+/// ```
+pub fn trim<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+) -> Result<GenericStringArray<O>> {
+    unary_string(array, |v| TrimSide::Both.trim_whitespace(v))
+}
+
+/// Removes leading whitespace from every value of `array`
+pub fn ltrim<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+) -> Result<GenericStringArray<O>> {
+    unary_string(array, |v| TrimSide::Left.trim_whitespace(v))
+}
+
+/// Removes trailing whitespace from every value of `array`
+pub fn rtrim<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+) -> Result<GenericStringArray<O>> {
+    unary_string(array, |v| TrimSide::Right.trim_whitespace(v))
+}
+
+/// Removes the longest prefix and suffix of every value of `array` consisting of
+/// the characters of the corresponding value of `characters`
+pub fn trim_chars<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    characters: &GenericStringArray<O>,
+) -> Result<GenericStringArray<O>> {
+    trim_chars_impl(array, characters, TrimSide::Both)
+}
+
+/// Removes the longest prefix of every value of `array` consisting of the characters
+/// of the corresponding value of `characters`
+pub fn ltrim_chars<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    characters: &GenericStringArray<O>,
+) -> Result<GenericStringArray<O>> {
+    trim_chars_impl(array, characters, TrimSide::Left)
+}
+
+/// Removes the longest suffix of every value of `array` consisting of the characters
+/// of the corresponding value of `characters`
+pub fn rtrim_chars<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    characters: &GenericStringArray<O>,
+) -> Result<GenericStringArray<O>> {
+    trim_chars_impl(array, characters, TrimSide::Right)
+}
+
+/// Removes the longest prefix and suffix of every value of `array` consisting of
+/// `characters`
+pub fn trim_chars_scalar<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    characters: &str,
+) -> Result<GenericStringArray<O>> {
+    unary_string(array, |v| TrimSide::Both.trim(v, characters))
+}
+
+/// Removes the longest prefix of every value of `array` consisting of `characters`
+pub fn ltrim_chars_scalar<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    characters: &str,
+) -> Result<GenericStringArray<O>> {
+    unary_string(array, |v| TrimSide::Left.trim(v, characters))
+}
+
+/// Removes the longest suffix of every value of `array` consisting of `characters`
+pub fn rtrim_chars_scalar<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    characters: &str,
+) -> Result<GenericStringArray<O>> {
+    unary_string(array, |v| TrimSide::Right.trim(v, characters))
+}
+
+/// Pads `value` to `length` characters by repeating `fill` on the left or right,
+/// truncating `value` if it is longer than `length`
+///
+/// Returns an error if the padded value would be longer than `max_len` bytes, or cannot
+/// be allocated
+fn pad(
+    value: &str,
+    length: i64,
+    fill: &str,
+    left: bool,
+    max_len: usize,
+) -> Result<String> {
+    let length = usize::try_from(length.max(0)).unwrap_or(usize::MAX);
+    if let Some((end, _)) = value.char_indices().nth(length) {
+        return Ok(value[..end].to_string());
+    }
+    let count = value.chars().count();
+    if count == length || fill.is_empty() {
+        return Ok(value.to_string());
+    }
+
+    // The padding is `fill` repeated, followed by a prefix of `fill`
+    let padding = length - count;
+    let fill_count = fill.chars().count();
+    let prefix_len: usize = fill
+        .chars()
+        .take(padding % fill_count)
+        .map(char::len_utf8)
+        .sum();
+    let padded_len = (padding / fill_count)
+        .checked_mul(fill.len())
+        .and_then(|len| len.checked_add(prefix_len + value.len()))
+        .filter(|len| *len <= max_len)
+        .ok_or_else(|| {
+            ArrowError::InvalidArgumentError(format!(
+                "Cannot pad to {} characters, the result would exceed the maximum string length of {} bytes",
+                length, max_len
+            ))
+        })?;
+
+    let mut padded = String::new();
+    padded.try_reserve_exact(padded_len).map_err(|e| {
+        ArrowError::MemoryError(format!(
+            "Cannot allocate {} bytes to pad to {} characters: {}",
+            padded_len, length, e
+        ))
+    })?;
+    let padding = fill.chars().cycle().take(padding);
+    match left {
+        true => padded.extend(padding.chain(value.chars())),
+        false => padded.extend(value.chars().chain(padding)),
+    }
+    Ok(padded)
+}
+
+/// Returns the maximum length in bytes of a value of a [`GenericStringArray<O>`]
+fn max_string_len<O: OffsetSizeTrait>() -> usize {
+    match O::IS_LARGE {
+        true => i64::MAX as usize,
+        false => i32::MAX as usize,
+    }
+}
+
+fn pad_impl<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    length: &Int64Array,
+    fill: &GenericStringArray<O>,
+    left: bool,
+) -> Result<GenericStringArray<O>> {
+    check_lengths(array, length)?;
+    check_lengths(array, fill)?;
+    let max_len = max_string_len::<O>();
+    let values = array
+        .iter()
+        .zip(length)
+        .zip(fill)
+        .map(|((v, l), f)| match (v, l, f) {
+            (Some(v), Some(l), Some(f)) => pad(v, l, f, left, max_len).map(Some),
+            _ => Ok(None),
+        });
+    try_collect_strings(array.len(), values)
+}
+
+fn pad_scalar_impl<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    length: i64,
+    fill: &str,
+    left: bool,
+) -> Result<GenericStringArray<O>> {
+    let max_len = max_string_len::<O>();
+    let values = array
+        .iter()
+        .map(|v| v.map(|v| pad(v, length, fill, left, max_len)).transpose());
+    try_collect_strings(array.len(), values)
+}
+
+/// Pads every value of `array` on the left to the corresponding value of `length`
+/// in characters, by repeating the corresponding value of `fill`. A value longer than
+/// `length` is truncated on the right.
+///
+/// Returns an error if a padded value would not fit in a [`GenericStringArray<O>`].
+///
+/// ```
+/// # use arrow::array::StringArray;
+/// # use arrow::compute::kernels::string::{lpad_scalar, rpad_scalar};
+/// let array = StringArray::from(vec![Some("7"), None, Some("12345")]);
+/// let result = lpad_scalar(&array, 3, "0").unwrap();
+/// assert_eq!(result, StringArray::from(vec![Some("007"), None, Some("123")]));
+/// let result = rpad_scalar(&array, 4, "ab").unwrap();
+/// assert_eq!(result, StringArray::from(vec![Some("7aba"), None, Some("1234")]));
+/// ```
+pub fn lpad<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    length: &Int64Array,
+    fill: &GenericStringArray<O>,
+) -> Result<GenericStringArray<O>> {
+    pad_impl(array, length, fill, true)
+}
+
+/// Pads every value of `array` on the right to the corresponding value of `length`
+/// in characters, by repeating the corresponding value of `fill`. A value longer than
+/// `length` is truncated on the right.
+pub fn rpad<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    length: &Int64Array,
+    fill: &GenericStringArray<O>,
+) -> Result<GenericStringArray<O>> {
+    pad_impl(array, length, fill, false)
+}
+
+/// Pads every value of `array` on the left to `length` characters by repeating
+/// `fill`, see [`lpad`]
+pub fn lpad_scalar<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    length: i64,
+    fill: &str,
+) -> Result<GenericStringArray<O>> {
+    pad_scalar_impl(array, length, fill, true)
+}
+
+/// Pads every value of `array` on the right to `length` characters by repeating
+/// `fill`, see [`rpad`]
+pub fn rpad_scalar<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    length: i64,
+    fill: &str,
+) -> Result<GenericStringArray<O>> {
+    pad_scalar_impl(array, length, fill, false)
+}
+
+/// Replaces all occurrences of `from` in `value` with `to`, an empty `from` matches
+/// nothing
+fn replace_value(value: &str, from: &str, to: &str) -> String {
+    match from.is_empty() {
+        true => value.to_string(),
+        false => value.replace(from, to),
+    }
+}
+
+/// Replaces all occurrences of the corresponding value of `from` in every value of
+/// `array` with the corresponding value of `to`. An empty `from` matches nothing.
+pub fn replace<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    from: &GenericStringArray<O>,
+    to: &GenericStringArray<O>,
+) -> Result<GenericStringArray<O>> {
+    check_lengths(array, from)?;
+    check_lengths(array, to)?;
+    let values = array
+        .iter()
+        .zip(from)
+        .zip(to)
+        .map(|((v, f), t)| Some(replace_value(v?, f?, t?)));
+    collect_strings(array.len(), values)
+}
+
+/// Replaces all occurrences of `from` in every value of `array` with `to`, see
+/// [`replace`]
+///
+/// ```
+/// # use arrow::array::StringArray;
+/// # use arrow::compute::kernels::string::replace_scalar;
+/// let array = StringArray::from(vec![Some("a-b-c"), None]);
+/// let result = replace_scalar(&array, "-", "::").unwrap();
+/// assert_eq!(result, StringArray::from(vec![Some("a::b::c"), None]));
+/// ```
+pub fn replace_scalar<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    from: &str,
+    to: &str,
+) -> Result<GenericStringArray<O>> {
+    unary_string(array, |v| replace_value(v, from, to))
+}
+
+/// Returns an error if the field position `n` of [`split_part`] is zero
+fn check_field_position(n: i64) -> Result<()> {
+    match n {
+        0 => Err(ArrowError::InvalidArgumentError(
+            "split_part field position must not be zero".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the field `n` of `value` split on `delimiter`, see [`split_part`]
+fn split_part_value<'a>(value: &'a str, delimiter: &str, n: i64) -> &'a str {
+    let index = (n.unsigned_abs() - 1) as usize;
+    match (n > 0, delimiter.is_empty()) {
+        (_, true) if index == 0 => value,
+        (_, true) => "",
+        (true, false) => value.split(delimiter).nth(index).unwrap_or(""),
+        (false, false) => value.rsplit(delimiter).nth(index).unwrap_or(""),
+    }
+}
+
+/// Splits every value of `array` on the corresponding value of `delimiter`, returning
+/// the corresponding field of `n`, counting from one.
+///
+/// A negative `n` counts fields from the end of the string, and a field that does not
+/// exist is an empty string. An empty delimiter does not split the string. Returns an
+/// error if any `n` is zero.
+pub fn split_part<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    delimiter: &GenericStringArray<O>,
+    n: &Int64Array,
+) -> Result<GenericStringArray<O>> {
+    check_lengths(array, delimiter)?;
+    check_lengths(array, n)?;
+    let values = array
+        .iter()
+        .zip(delimiter)
+        .zip(n)
+        .map(|((v, d), n)| match (v, d, n) {
+            (Some(v), Some(d), Some(n)) => {
+                check_field_position(n).map(|_| Some(split_part_value(v, d, n)))
+            }
+            _ => Ok(None),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    collect_strings(array.len(), values)
+}
+
+/// Splits every value of `array` on `delimiter`, returning field `n`, see
+/// [`split_part`]
+///
+/// ```
+/// # use arrow::array::StringArray;
+/// # use arrow::compute::kernels::string::split_part_scalar;
+/// let array = StringArray::from(vec![Some("a.b.c"), None, Some("a")]);
+/// let result = split_part_scalar(&array, ".", 2).unwrap();
+/// assert_eq!(result, StringArray::from(vec![Some("b"), None, Some("")]));
+/// let result = split_part_scalar(&array, ".", -1).unwrap();
+/// assert_eq!(result, StringArray::from(vec![Some("c"), None, Some("a")]));
+/// ```
+pub fn split_part_scalar<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    delimiter: &str,
+    n: i64,
+) -> Result<GenericStringArray<O>> {
+    check_field_position(n)?;
+    unary_string(array, |v| split_part_value(v, delimiter, n))
+}
+
+/// Appends the fields of `value` split on `delimiter` to `builder`
+fn append_split<O: OffsetSizeTrait>(
+    builder: &mut GenericListBuilder<O, GenericStringBuilder<O>>,
+    value: Option<(&str, &str)>,
+) -> Result<()> {
+    match value {
+        Some((value, "")) => {
+            builder.values().append_value(value)?;
+            builder.append(true)
+        }
+        Some((value, delimiter)) => {
+            for field in value.split(delimiter) {
+                builder.values().append_value(field)?;
+            }
+            builder.append(true)
+        }
+        None => builder.append(false),
+    }
+}
+
+/// Splits every value of `array` on the corresponding value of `delimiter`, returning
+/// a list of the fields of each value.
+///
+/// An empty delimiter does not split the string, and an empty string has a single
+/// empty field.
+pub fn split<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    delimiter: &GenericStringArray<O>,
+) -> Result<GenericListArray<O>> {
+    check_lengths(array, delimiter)?;
+    let mut builder =
+        GenericListBuilder::with_capacity(GenericStringBuilder::<O>::new(0), array.len());
+    for (v, d) in array.iter().zip(delimiter) {
+        append_split(&mut builder, v.zip(d))?;
+    }
+    Ok(builder.finish())
+}
+
+/// Splits every value of `array` on `delimiter`, see [`split`]
+///
+/// ```
+/// # use arrow::array::{Array, StringArray};
+/// # use arrow::compute::kernels::string::split_scalar;
+/// let array = StringArray::from(vec![Some("a,b"), None, Some("")]);
+/// let result = split_scalar(&array, ",").unwrap();
+/// assert_eq!(result.value_length(0), 2);
+/// assert!(result.is_null(1));
+/// assert_eq!(result.value_length(2), 1);
+/// ```
+pub fn split_scalar<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    delimiter: &str,
+) -> Result<GenericListArray<O>> {
+    let mut builder =
+        GenericListBuilder::with_capacity(GenericStringBuilder::<O>::new(0), array.len());
+    for v in array.iter() {
+        append_split(&mut builder, v.map(|v| (v, delimiter)))?;
+    }
+    Ok(builder.finish())
+}
+
+/// Returns whether every value of `left` starts with the corresponding value of `right`
+pub fn starts_with<O: OffsetSizeTrait>(
+    left: &GenericStringArray<O>,
+    right: &GenericStringArray<O>,
+) -> Result<BooleanArray> {
+    check_lengths(left, right)?;
+    Ok(left
+        .iter()
+        .zip(right)
+        .map(|(l, r)| Some(l?.starts_with(r?)))
+        .collect())
+}
+
+/// Returns whether every value of `left` starts with `right`
+///
+/// ```
+/// # use arrow::array::{BooleanArray, StringArray};
+/// # use arrow::compute::kernels::string::starts_with_scalar;
+/// let array = StringArray::from(vec![Some("arrow"), None, Some("parquet")]);
+/// let result = starts_with_scalar(&array, "ar").unwrap();
+/// assert_eq!(result, BooleanArray::from(vec![Some(true), None, Some(false)]));
+/// ```
+pub fn starts_with_scalar<O: OffsetSizeTrait>(
+    left: &GenericStringArray<O>,
+    right: &str,
+) -> Result<BooleanArray> {
+    Ok(left.iter().map(|l| Some(l?.starts_with(right))).collect())
+}
+
+/// Returns whether every value of `left` ends with the corresponding value of `right`
+pub fn ends_with<O: OffsetSizeTrait>(
+    left: &GenericStringArray<O>,
+    right: &GenericStringArray<O>,
+) -> Result<BooleanArray> {
+    check_lengths(left, right)?;
+    Ok(left
+        .iter()
+        .zip(right)
+        .map(|(l, r)| Some(l?.ends_with(r?)))
+        .collect())
+}
+
+/// Returns whether every value of `left` ends with `right`
+pub fn ends_with_scalar<O: OffsetSizeTrait>(
+    left: &GenericStringArray<O>,
+    right: &str,
+) -> Result<BooleanArray> {
+    Ok(left.iter().map(|l| Some(l?.ends_with(right))).collect())
+}
+
+/// Returns the position in characters of the first occurrence of `substring` in
+/// `value` counting from one, or zero if there is none
+fn strpos_value(value: &str, substring: &str) -> usize {
+    value
+        .find(substring)
+        .map(|i| value[..i].chars().count() + 1)
+        .unwrap_or(0)
+}
+
+/// Returns the position in characters of the first occurrence of the corresponding
+/// value of `substring` in every value of `array`, counting from one, or zero if there
+/// is none. The result is an `Int32Array` for a `StringArray` and an `Int64Array`
+/// for a `LargeStringArray`.
+pub fn strpos<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    substring: &GenericStringArray<O>,
+) -> Result<ArrayRef> {
+    check_lengths(array, substring)?;
+    Ok(offset_size_array::<O, _>(
+        array
+            .iter()
+            .zip(substring)
+            .map(|(v, s)| Some(strpos_value(v?, s?))),
+    ))
+}
+
+/// Returns the position in characters of the first occurrence of `substring` in
+/// every value of `array`, see [`strpos`]
+///
+/// ```
+/// # use arrow::array::{Int32Array, StringArray};
+/// # use arrow::compute::kernels::string::strpos_scalar;
+/// let array = StringArray::from(vec![Some("héllo"), None, Some("world")]);
+/// let result = strpos_scalar(&array, "l");
+/// let expected = Int32Array::from(vec![Some(3), None, Some(4)]);
+/// assert_eq!(result.as_any().downcast_ref::<Int32Array>().unwrap(), &expected);
+/// ```
+pub fn strpos_scalar<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    substring: &str,
+) -> ArrayRef {
+    offset_size_array::<O, _>(array.iter().map(|v| v.map(|v| strpos_value(v, substring))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datatypes::{DataType, Field};
+
+    #[test]
+    fn test_case_conversion() {
+        let array = LargeStringArray::from(vec![Some("Hello"), None, Some("ǅ ΣΑΣ")]);
+        assert_eq!(
+            upper(&array).unwrap(),
+            LargeStringArray::from(vec![Some("HELLO"), None, Some("Ǆ ΣΑΣ")])
+        );
+        assert_eq!(
+            lower(&array).unwrap(),
+            LargeStringArray::from(vec![Some("hello"), None, Some("ǆ σας")])
+        );
+
+        let sliced = array.slice(1, 2);
+        let sliced = sliced.as_any().downcast_ref::<LargeStringArray>().unwrap();
+        assert_eq!(
+            upper(sliced).unwrap(),
+            LargeStringArray::from(vec![None, Some("Ǆ ΣΑΣ")])
+        );
+    }
+
+    #[test]
+    fn test_reverse_and_char_length() {
+        let array = StringArray::from(vec![Some("añb"), None, Some("")]);
+        assert_eq!(
+            reverse(&array).unwrap(),
+            StringArray::from(vec![Some("bña"), None, Some("")])
+        );
+
+        let lengths = char_length(&array);
+        assert_eq!(
+            lengths.as_any().downcast_ref::<Int32Array>().unwrap(),
+            &Int32Array::from(vec![Some(3), None, Some(0)])
+        );
+
+        let array = LargeStringArray::from(vec!["añb"]);
+        let lengths = char_length(&array);
+        assert_eq!(
+            lengths.as_any().downcast_ref::<Int64Array>().unwrap(),
+            &Int64Array::from(vec![3])
+        );
+    }
+
+    #[test]
+    fn test_trim() {
+        let array = StringArray::from(vec![Some(" \ta b\n "), None, Some("")]);
+        assert_eq!(
+            trim(&array).unwrap(),
+            StringArray::from(vec![Some("a b"), None, Some("")])
+        );
+        assert_eq!(
+            ltrim(&array).unwrap(),
+            StringArray::from(vec![Some("a b\n "), None, Some("")])
+        );
+        assert_eq!(
+            rtrim(&array).unwrap(),
+            StringArray::from(vec![Some(" \ta b"), None, Some("")])
+        );
+
+        let array = StringArray::from(vec![Some("xyaxy"), Some("xyaxy"), Some("xyaxy")]);
+        let characters = StringArray::from(vec![Some("xy"), None, Some("")]);
+        assert_eq!(
+            trim_chars(&array, &characters).unwrap(),
+            StringArray::from(vec![Some("a"), None, Some("xyaxy")])
+        );
+        assert_eq!(
+            ltrim_chars(&array, &characters).unwrap(),
+            StringArray::from(vec![Some("axy"), None, Some("xyaxy")])
+        );
+        assert_eq!(
+            rtrim_chars(&array, &characters).unwrap(),
+            StringArray::from(vec![Some("xya"), None, Some("xyaxy")])
+        );
+        assert_eq!(
+            ltrim_chars_scalar(&array, "x").unwrap(),
+            StringArray::from(vec!["yaxy"; 3])
+        );
+        assert_eq!(
+            rtrim_chars_scalar(&array, "y").unwrap(),
+            StringArray::from(vec!["xyax"; 3])
+        );
+    }
+
+    #[test]
+    fn test_pad() {
+        let array = StringArray::from(vec![Some("ab"), Some("ab"), Some("añb"), None]);
+        let length = Int64Array::from(vec![Some(5), Some(-1), Some(2), Some(3)]);
+        let fill = StringArray::from(vec![Some("xy"), Some("x"), Some("x"), Some("x")]);
+        assert_eq!(
+            lpad(&array, &length, &fill).unwrap(),
+            StringArray::from(vec![Some("xyxab"), Some(""), Some("añ"), None])
+        );
+        assert_eq!(
+            rpad(&array, &length, &fill).unwrap(),
+            StringArray::from(vec![Some("abxyx"), Some(""), Some("añ"), None])
+        );
+
+        // An empty fill does not pad
+        assert_eq!(
+            lpad_scalar(&array, 3, "").unwrap(),
+            StringArray::from(vec![Some("ab"), Some("ab"), Some("añb"), None])
+        );
+        assert_eq!(
+            rpad_scalar(&array, 4, "é").unwrap(),
+            StringArray::from(vec![Some("abéé"), Some("abéé"), Some("añbé"), None])
+        );
+
+        let err = lpad(&array, &Int64Array::from(vec![1]), &fill).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Arrays must have the same length: 4 != 1"
+        );
+
+        // The padded values must fit in the offsets of the array
+        let err = lpad_scalar(&array, i64::MAX, "x").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Invalid argument error: Cannot pad to {} characters, the result would exceed the maximum string length of 2147483647 bytes",
+                i64::MAX
+            )
+        );
+        let err = rpad_scalar(&array, i32::MAX as i64, "é").unwrap_err();
+        assert!(matches!(err, ArrowError::InvalidArgumentError(_)));
+        // The allocation of a value that fits in the offsets may still fail
+        let large = LargeStringArray::from(vec![Some("ab"), None]);
+        let err = rpad_scalar(&large, i64::MAX, "xy").unwrap_err();
+        assert!(matches!(err, ArrowError::MemoryError(_)));
+    }
+
+    #[test]
+    fn test_replace() {
+        let array = StringArray::from(vec![Some("aaa"), Some("abc"), Some("abc"), None]);
+        let from = StringArray::from(vec![Some("aa"), Some(""), None, Some("a")]);
+        let to = StringArray::from(vec![Some("b"), Some("x"), Some("x"), Some("b")]);
+        assert_eq!(
+            replace(&array, &from, &to).unwrap(),
+            StringArray::from(vec![Some("ba"), Some("abc"), None, None])
+        );
+        assert_eq!(
+            replace_scalar(&array, "b", "").unwrap(),
+            StringArray::from(vec![Some("aaa"), Some("ac"), Some("ac"), None])
+        );
+    }
+
+    #[test]
+    fn test_split_part() {
+        let array = StringArray::from(vec![
+            Some("a,b,,c"),
+            Some("a,b,,c"),
+            Some("a,b,,c"),
+            Some("a,b,,c"),
+            None,
+        ]);
+        let delimiter = StringArray::from(vec![",", ",", ",,", "", ","]);
+        let n = Int64Array::from(vec![Some(3), Some(-4), Some(2), Some(1), Some(1)]);
+        assert_eq!(
+            split_part(&array, &delimiter, &n).unwrap(),
+            StringArray::from(vec![Some(""), Some("a"), Some("c"), Some("a,b,,c"), None])
+        );
+        assert_eq!(
+            split_part_scalar(&array, ",", 5).unwrap(),
+            StringArray::from(vec![Some(""), Some(""), Some(""), Some(""), None])
+        );
+
+        let err = split_part_scalar(&array, ",", 0).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: split_part field position must not be zero"
+        );
+        let n = Int64Array::from(vec![Some(1), Some(0), None, None, None]);
+        assert!(split_part(&array, &delimiter, &n).is_err());
+    }
+
+    #[test]
+    fn test_split() {
+        let array =
+            LargeStringArray::from(vec![Some("a,b"), Some("a,b"), None, Some("")]);
+        let delimiter =
+            LargeStringArray::from(vec![Some(","), Some(""), Some(","), Some(",")]);
+        let result = split(&array, &delimiter).unwrap();
+        assert_eq!(
+            result.data_type(),
+            &DataType::LargeList(Box::new(Field::new("item", DataType::LargeUtf8, true)))
+        );
+
+        let values: Vec<Option<Vec<String>>> = result
+            .iter()
+            .map(|list| {
+                list.map(|list| {
+                    let list = list.as_any().downcast_ref::<LargeStringArray>().unwrap();
+                    list.iter().map(|v| v.unwrap().to_string()).collect()
+                })
+            })
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                Some(vec!["a".to_string(), "b".to_string()]),
+                Some(vec!["a,b".to_string()]),
+                None,
+                Some(vec!["".to_string()])
+            ]
+        );
+
+        let result = split_scalar(&array, "b").unwrap();
+        assert_eq!(result.value_offsets(), &[0, 2, 4, 4, 5]);
+    }
+
+    #[test]
+    fn test_starts_ends_with() {
+        let left = StringArray::from(vec![Some("arrow"), Some("arrow"), None, Some("")]);
+        let right = StringArray::from(vec![Some("ar"), Some("ow"), Some(""), None]);
+        assert_eq!(
+            starts_with(&left, &right).unwrap(),
+            BooleanArray::from(vec![Some(true), Some(false), None, None])
+        );
+        assert_eq!(
+            ends_with(&left, &right).unwrap(),
+            BooleanArray::from(vec![Some(false), Some(true), None, None])
+        );
+        assert_eq!(
+            ends_with_scalar(&left, "").unwrap(),
+            BooleanArray::from(vec![Some(true), Some(true), None, Some(true)])
+        );
+    }
+
+    #[test]
+    fn test_strpos() {
+        let array = LargeStringArray::from(vec![Some("ñaña"), Some("abc"), None]);
+        let substring = LargeStringArray::from(vec![Some("ña"), Some(""), Some("a")]);
+        let result = strpos(&array, &substring).unwrap();
+        assert_eq!(
+            result.as_any().downcast_ref::<Int64Array>().unwrap(),
+            &Int64Array::from(vec![Some(1), Some(1), None])
+        );
+
+        let result = strpos_scalar(&array, "a");
+        assert_eq!(
+            result.as_any().downcast_ref::<Int64Array>().unwrap(),
+            &Int64Array::from(vec![Some(2), Some(1), None])
+        );
+    }
+}
@@ -106,6 +106,145 @@ macro_rules! extract_component_from_array {
     };
 }
 
+/// Nanoseconds in a second
+pub(crate) const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+/// Nanoseconds in a day, the unit of `Date32`
+pub(crate) const NANOS_PER_DAY: i64 = 86_400_000_000_000;
+
+/// Returns the number of nanoseconds in `unit`
+pub(crate) fn time_unit_nanos(unit: &TimeUnit) -> i64 {
+    match unit {
+        TimeUnit::Second => 1_000_000_000,
+        TimeUnit::Millisecond => 1_000_000,
+        TimeUnit::Microsecond => 1_000,
+        TimeUnit::Nanosecond => 1,
+    }
+}
+
+/// The timezone of a `Timestamp(_, Some(tz))` array, in which the calendar
+/// components of an interval are applied
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum TemporalTz {
+    Fixed(FixedOffset),
+    #[cfg(feature = "chrono-tz")]
+    Named(chrono_tz::Tz),
+}
+
+impl TemporalTz {
+    /// Parses a fixed offset such as `+05:00`, or with the `chrono-tz` feature, a
+    /// timezone name such as `Europe/London`
+    pub(crate) fn parse(tz: &str) -> Result<Self> {
+        if tz.eq_ignore_ascii_case("UTC") || tz == "Z" {
+            return Ok(Self::Fixed(FixedOffset::east(0)));
+        }
+        let mut parsed = Parsed::new();
+        if parse(&mut parsed, tz, StrftimeItems::new("%z")).is_ok() {
+            if let Ok(offset) = parsed.to_fixed_offset() {
+                return Ok(Self::Fixed(offset));
+            }
+        }
+        Self::parse_named(tz).ok_or_else(|| {
+            ArrowError::ComputeError(format!("Unable to parse timezone {:?}", tz))
+        })
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    fn parse_named(tz: &str) -> Option<Self> {
+        tz.parse::<chrono_tz::Tz>().ok().map(Self::Named)
+    }
+
+    #[cfg(not(feature = "chrono-tz"))]
+    fn parse_named(_tz: &str) -> Option<Self> {
+        None
+    }
+
+    /// Converts `utc` to the local time of this timezone
+    pub(crate) fn to_local(self, utc: NaiveDateTime) -> Option<NaiveDateTime> {
+        let offset = match self {
+            Self::Fixed(offset) => offset,
+            #[cfg(feature = "chrono-tz")]
+            Self::Named(tz) => {
+                use chrono::{Offset, TimeZone};
+                tz.offset_from_utc_datetime(&utc).fix()
+            }
+        };
+        utc.checked_add_signed(chrono::Duration::seconds(offset.local_minus_utc().into()))
+    }
+
+    /// Converts `local` in this timezone to UTC
+    ///
+    /// A local time that occurs twice resolves to the earlier instant, and a local time
+    /// skipped by a transition, e.g. when daylight saving time starts, is interpreted
+    /// with the offset before the transition, moving it forward by the length of the gap
+    pub(crate) fn to_utc(self, local: NaiveDateTime) -> Option<NaiveDateTime> {
+        let offset = match self {
+            Self::Fixed(offset) => offset,
+            #[cfg(feature = "chrono-tz")]
+            Self::Named(tz) => {
+                use chrono::{LocalResult, Offset, TimeZone};
+                match tz.from_local_datetime(&local) {
+                    LocalResult::Single(dt) | LocalResult::Ambiguous(dt, _) => {
+                        return Some(dt.naive_utc())
+                    }
+                    LocalResult::None => {
+                        let before =
+                            local.checked_sub_signed(chrono::Duration::days(1))?;
+                        tz.offset_from_utc_datetime(&before).fix()
+                    }
+                }
+            }
+        };
+        local.checked_sub_signed(chrono::Duration::seconds(
+            offset.local_minus_utc().into(),
+        ))
+    }
+}
+
+/// Converts an instant in nanoseconds since the UNIX epoch to a `NaiveDateTime`
+pub(crate) fn nanos_to_datetime(nanos: i128) -> Option<NaiveDateTime> {
+    let seconds = i64::try_from(nanos.div_euclid(NANOS_PER_SECOND)).ok()?;
+    NaiveDateTime::from_timestamp_opt(seconds, nanos.rem_euclid(NANOS_PER_SECOND) as u32)
+}
+
+/// Converts a `NaiveDateTime` to an instant in nanoseconds since the UNIX epoch
+pub(crate) fn datetime_to_nanos(datetime: NaiveDateTime) -> i128 {
+    i128::from(datetime.timestamp()) * NANOS_PER_SECOND
+        + i128::from(datetime.timestamp_subsec_nanos())
+}
+
+/// Creates an array of `data_type`, one of `Timestamp`, `Duration`, `Date32` or `Date64`,
+/// from
+/// `values` in the units of `data_type`, returning `None` if a value does not fit
+pub(crate) fn make_temporal_array(
+    values: &Int64Array,
+    data_type: &DataType,
+) -> Option<ArrayRef> {
+    let data = match data_type {
+        DataType::Date32 => values
+            .iter()
+            .map(|v| v.map(i32::try_from).transpose())
+            .collect::<std::result::Result<Int32Array, _>>()
+            .ok()?
+            .data()
+            .clone(),
+        _ => values.data().clone(),
+    };
+    // Safety: `data` is a valid primitive array of the same width as `data_type`
+    let data = unsafe {
+        ArrayData::new_unchecked(
+            data_type.clone(),
+            data.len(),
+            None,
+            data.null_buffer().cloned(),
+            data.offset(),
+            data.buffers().to_vec(),
+            vec![],
+        )
+    };
+    Some(make_array(data))
+}
+
 macro_rules! return_compute_error_with {
     ($msg:expr, $param:expr) => {
         return { Err(ArrowError::ComputeError(format!("{}: {:?}", $msg, $param))) }
@@ -400,6 +539,219 @@ where
     Ok(b.finish())
 }
 
+/// The granularity to truncate to in [`date_trunc`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DateTruncGranularity {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl DateTruncGranularity {
+    fn parse(granularity: &str) -> Result<Self> {
+        Ok(match granularity.to_ascii_lowercase().as_str() {
+            "second" => Self::Second,
+            "minute" => Self::Minute,
+            "hour" => Self::Hour,
+            "day" => Self::Day,
+            "week" => Self::Week,
+            "month" => Self::Month,
+            "quarter" => Self::Quarter,
+            "year" => Self::Year,
+            _ => {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "Unsupported date_trunc granularity: {:?}",
+                    granularity
+                )))
+            }
+        })
+    }
+
+    fn truncate(self, datetime: NaiveDateTime) -> Option<NaiveDateTime> {
+        let date = datetime.date();
+        match self {
+            Self::Second => datetime.with_nanosecond(0),
+            Self::Minute => date.and_hms_opt(datetime.hour(), datetime.minute(), 0),
+            Self::Hour => date.and_hms_opt(datetime.hour(), 0, 0),
+            Self::Day => date.and_hms_opt(0, 0, 0),
+            Self::Week => date
+                .checked_sub_signed(chrono::Duration::days(
+                    date.weekday().num_days_from_monday().into(),
+                ))?
+                .and_hms_opt(0, 0, 0),
+            Self::Month => NaiveDate::from_ymd_opt(date.year(), date.month(), 1)?
+                .and_hms_opt(0, 0, 0),
+            Self::Quarter => {
+                NaiveDate::from_ymd_opt(date.year(), date.quarter0() * 3 + 1, 1)?
+                    .and_hms_opt(0, 0, 0)
+            }
+            Self::Year => {
+                NaiveDate::from_ymd_opt(date.year(), 1, 1)?.and_hms_opt(0, 0, 0)
+            }
+        }
+    }
+}
+
+/// Applies `op` to every value of a `Timestamp`, `Date32` or `Date64` array, as a
+/// `NaiveDateTime` in the local time of the array's timezone, if any
+///
+/// Where the local time returned by `op` occurs twice, the instant with the same offset
+/// from UTC as the original value is preferred.
+fn local_datetime_op<T, F>(
+    array: &PrimitiveArray<T>,
+    name: &str,
+    op: F,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowTemporalType + ArrowNumericType,
+    i64: std::convert::From<T::Native>,
+    T::Native: TryFrom<i64>,
+    F: Fn(NaiveDateTime) -> Option<NaiveDateTime>,
+{
+    let (unit_nanos, tz) = match array.data_type() {
+        DataType::Date32 => (NANOS_PER_DAY, None),
+        DataType::Date64 => (time_unit_nanos(&TimeUnit::Millisecond), None),
+        DataType::Timestamp(unit, tz) => (
+            time_unit_nanos(unit),
+            tz.as_deref().map(TemporalTz::parse).transpose()?,
+        ),
+        dt => return_compute_error_with!(format!("{} does not support", name), dt),
+    };
+    let unit_nanos = i128::from(unit_nanos);
+
+    let apply = |value: T::Native| -> Option<T::Native> {
+        let utc = nanos_to_datetime(i128::from(i64::from(value)) * unit_nanos)?;
+        let utc = match tz {
+            Some(tz) => {
+                let local = tz.to_local(utc)?;
+                let result = op(local)?;
+                // Keep the original offset if it is valid for the result
+                let offset = local.signed_duration_since(utc);
+                match result.checked_sub_signed(offset) {
+                    Some(utc) if tz.to_local(utc) == Some(result) => utc,
+                    _ => tz.to_utc(result)?,
+                }
+            }
+            None => op(utc)?,
+        };
+        let value = i64::try_from(datetime_to_nanos(utc).div_euclid(unit_nanos)).ok()?;
+        T::Native::try_from(value).ok()
+    };
+
+    let values = array
+        .iter()
+        .map(|v| {
+            v.map(|v| {
+                apply(v).ok_or_else(|| {
+                    ArrowError::ComputeError(format!(
+                        "Overflow happened on {}: result does not fit in {:?}",
+                        name,
+                        array.data_type()
+                    ))
+                })
+            })
+            .transpose()
+        })
+        .collect::<Result<PrimitiveArray<T>>>()?;
+
+    // Preserve the timezone of the input
+    let data = values.data();
+    // Safety: `data` is a valid array of `T`, and the data type of the input only
+    // differs from `T::DATA_TYPE` in its timezone
+    let data = unsafe {
+        ArrayData::new_unchecked(
+            array.data_type().clone(),
+            data.len(),
+            None,
+            data.null_buffer().cloned(),
+            data.offset(),
+            data.buffers().to_vec(),
+            vec![],
+        )
+    };
+    Ok(PrimitiveArray::from(data))
+}
+
+/// Truncates each value of a temporal array to the start of its `granularity`, one of
+/// `"second"`, `"minute"`, `"hour"`, `"day"`, `"week"`, `"month"`, `"quarter"` or
+/// `"year"`. Weeks start on Monday.
+///
+/// Values of a `Timestamp(_, Some(tz))` array are truncated in the local time of `tz`,
+/// such that truncating to `"day"` returns local midnight.
+///
+/// ```
+/// # use arrow::array::TimestampSecondArray;
+/// # use arrow::compute::kernels::temporal::date_trunc;
+/// // 2021-08-17T10:20:30 and 2021-08-17T10:20:30+02:00
+/// let a = TimestampSecondArray::from_vec(vec![1629195630], None);
+/// assert_eq!(date_trunc(&a, "month").unwrap().value(0), 1627776000);
+/// let a = TimestampSecondArray::from_vec(vec![1629188430], Some("+02:00".to_string()));
+/// assert_eq!(date_trunc(&a, "month").unwrap().value(0), 1627768800);
+/// ```
+pub fn date_trunc<T>(
+    array: &PrimitiveArray<T>,
+    granularity: &str,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowTemporalType + ArrowNumericType,
+    i64: std::convert::From<T::Native>,
+    T::Native: TryFrom<i64>,
+{
+    let granularity = DateTruncGranularity::parse(granularity)?;
+    local_datetime_op(array, "date_trunc", |datetime| {
+        granularity.truncate(datetime)
+    })
+}
+
+/// Bins each value of a temporal array into buckets of width `stride`, aligned with
+/// `origin`, returning the start of each value's bucket.
+///
+/// Values of a `Timestamp(_, Some(tz))` array are binned in the local time of `tz`, with
+/// `origin` being a local time.
+///
+/// ```
+/// # use arrow::array::TimestampSecondArray;
+/// # use arrow::compute::kernels::temporal::date_bin;
+/// # use chrono::{Duration, NaiveDate};
+/// let origin = NaiveDate::from_ymd(2021, 1, 1).and_hms(0, 0, 0);
+/// // 2021-08-17T10:20:30
+/// let a = TimestampSecondArray::from_vec(vec![1629195630], None);
+/// let binned = date_bin(&a, Duration::minutes(15), origin).unwrap();
+/// // 2021-08-17T10:15:00
+/// assert_eq!(binned.value(0), 1629195300);
+/// ```
+pub fn date_bin<T>(
+    array: &PrimitiveArray<T>,
+    stride: chrono::Duration,
+    origin: NaiveDateTime,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowTemporalType + ArrowNumericType,
+    i64: std::convert::From<T::Native>,
+    T::Native: TryFrom<i64>,
+{
+    let stride = match stride.num_nanoseconds() {
+        Some(stride) if stride > 0 => i128::from(stride),
+        _ => {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "date_bin stride must be positive and at most {} nanoseconds, got {}",
+                i64::MAX,
+                stride
+            )))
+        }
+    };
+    let origin = datetime_to_nanos(origin);
+    local_datetime_op(array, "date_bin", |datetime| {
+        let offset = (datetime_to_nanos(datetime) - origin).div_euclid(stride) * stride;
+        nanos_to_datetime(origin + offset)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -893,4 +1245,174 @@ mod tests {
             Some(sydney_offset_with_dst)
         );
     }
+
+    fn timestamp_ms(s: &str) -> i64 {
+        s.parse::<NaiveDateTime>().unwrap().timestamp_millis()
+    }
+
+    #[test]
+    fn test_date_trunc_timestamp() {
+        let a = TimestampMillisecondArray::from_opt_vec(
+            vec![
+                Some(timestamp_ms("2021-08-18T10:20:30.456")),
+                None,
+                Some(timestamp_ms("1969-12-31T23:59:59.500")),
+            ],
+            None,
+        );
+        let cases = [
+            ("second", "2021-08-18T10:20:30", "1969-12-31T23:59:59"),
+            ("minute", "2021-08-18T10:20:00", "1969-12-31T23:59:00"),
+            ("hour", "2021-08-18T10:00:00", "1969-12-31T23:00:00"),
+            ("day", "2021-08-18T00:00:00", "1969-12-31T00:00:00"),
+            ("week", "2021-08-16T00:00:00", "1969-12-29T00:00:00"),
+            ("MONTH", "2021-08-01T00:00:00", "1969-12-01T00:00:00"),
+            ("quarter", "2021-07-01T00:00:00", "1969-10-01T00:00:00"),
+            ("year", "2021-01-01T00:00:00", "1969-01-01T00:00:00"),
+        ];
+        for (granularity, first, last) in cases {
+            let b = date_trunc(&a, granularity).unwrap();
+            let expected = TimestampMillisecondArray::from_opt_vec(
+                vec![Some(timestamp_ms(first)), None, Some(timestamp_ms(last))],
+                None,
+            );
+            assert_eq!(b, expected, "{}", granularity);
+        }
+
+        let err = date_trunc(&a, "fortnight").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Unsupported date_trunc granularity: \"fortnight\""
+        );
+
+        let a = Time32SecondArray::from(vec![1]);
+        let err = date_trunc(&a, "hour").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: date_trunc does not support: Time32(Second)"
+        );
+    }
+
+    #[test]
+    fn test_date_trunc_date() {
+        // 1970-01-08 is a Thursday
+        let a = Date32Array::from(vec![Some(7), None, Some(-1)]);
+        let b = date_trunc(&a, "week").unwrap();
+        assert_eq!(b, Date32Array::from(vec![Some(4), None, Some(-3)]));
+        let b = date_trunc(&a, "hour").unwrap();
+        assert_eq!(b, a);
+
+        let a = Date64Array::from(vec![timestamp_ms("2021-08-18T10:20:30")]);
+        let b = date_trunc(&a, "quarter").unwrap();
+        assert_eq!(
+            b,
+            Date64Array::from(vec![timestamp_ms("2021-07-01T00:00:00")])
+        );
+    }
+
+    #[test]
+    fn test_date_trunc_timestamp_with_timezone() {
+        // 2021-01-01T02:00:00-05:30
+        let a = TimestampSecondArray::from_vec(
+            vec![timestamp_ms("2021-01-01T07:30:00") / 1000],
+            Some("-05:30".to_string()),
+        );
+        let b = date_trunc(&a, "year").unwrap();
+        assert_eq!(b.data_type(), a.data_type());
+        assert_eq!(b.value(0), timestamp_ms("2021-01-01T05:30:00") / 1000);
+
+        let b = date_trunc(&a, "hour").unwrap();
+        assert_eq!(b.value(0), timestamp_ms("2021-01-01T07:30:00") / 1000);
+    }
+
+    #[test]
+    #[cfg(feature = "chrono-tz")]
+    fn test_date_trunc_timestamp_with_dst_timezone() {
+        let tz = Some("Europe/London".to_string());
+        let a = TimestampSecondArray::from_vec(
+            vec![
+                // 2021-03-28T12:30:00+01:00, daylight saving time started at 01:00
+                timestamp_ms("2021-03-28T11:30:00") / 1000,
+                // 2021-10-31T01:30:00+01:00, before daylight saving time ends
+                timestamp_ms("2021-10-31T00:30:00") / 1000,
+                // 2021-10-31T01:30:00+00:00, after daylight saving time ends
+                timestamp_ms("2021-10-31T01:30:00") / 1000,
+            ],
+            tz.clone(),
+        );
+        let b = date_trunc(&a, "day").unwrap();
+        let expected = TimestampSecondArray::from_vec(
+            vec![
+                timestamp_ms("2021-03-28T00:00:00") / 1000,
+                timestamp_ms("2021-10-30T23:00:00") / 1000,
+                timestamp_ms("2021-10-30T23:00:00") / 1000,
+            ],
+            tz.clone(),
+        );
+        assert_eq!(b, expected);
+
+        let b = date_trunc(&a, "hour").unwrap();
+        let expected = TimestampSecondArray::from_vec(
+            vec![
+                timestamp_ms("2021-03-28T11:00:00") / 1000,
+                timestamp_ms("2021-10-31T00:00:00") / 1000,
+                timestamp_ms("2021-10-31T01:00:00") / 1000,
+            ],
+            tz,
+        );
+        assert_eq!(b, expected);
+    }
+
+    #[test]
+    fn test_date_bin() {
+        let origin = "2021-01-01T00:07:00".parse::<NaiveDateTime>().unwrap();
+        let a = TimestampMicrosecondArray::from_opt_vec(
+            vec![
+                Some(timestamp_ms("2021-08-18T10:20:30") * 1000),
+                None,
+                Some(timestamp_ms("2020-12-31T23:59:00") * 1000),
+            ],
+            None,
+        );
+        let b = date_bin(&a, chrono::Duration::minutes(15), origin).unwrap();
+        let expected = TimestampMicrosecondArray::from_opt_vec(
+            vec![
+                Some(timestamp_ms("2021-08-18T10:07:00") * 1000),
+                None,
+                Some(timestamp_ms("2020-12-31T23:52:00") * 1000),
+            ],
+            None,
+        );
+        assert_eq!(b, expected);
+
+        // Bins are in local time
+        let tz = Some("+02:00".to_string());
+        let a = TimestampSecondArray::from_vec(
+            vec![timestamp_ms("2021-08-18T23:00:00") / 1000],
+            tz.clone(),
+        );
+        let b = date_bin(
+            &a,
+            chrono::Duration::days(1),
+            origin.date().and_hms(0, 0, 0),
+        )
+        .unwrap();
+        assert_eq!(
+            b,
+            TimestampSecondArray::from_vec(
+                vec![timestamp_ms("2021-08-18T22:00:00") / 1000],
+                tz
+            )
+        );
+
+        let a = Date32Array::from(vec![0, 7, 13]);
+        let b = date_bin(&a, chrono::Duration::weeks(1), origin).unwrap();
+        assert_eq!(b, Date32Array::from(vec![-6, 1, 8]));
+
+        let err = date_bin(&a, chrono::Duration::zero(), origin).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: date_bin stride must be positive and at most 9223372036854775807 nanoseconds, got PT0S"
+        );
+    }
 }
@@ -0,0 +1,498 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines a top-k kernel, that computes the first `k` rows of one or more
+//! [`RecordBatch`] ordered by one or more sort columns, without sorting the
+//! whole input
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use arrow::array::{ArrayRef, Int64Array, StringArray};
+//! # use arrow::compute::kernels::top_k::TopK;
+//! # use arrow::compute::SortOptions;
+//! # use arrow::record_batch::RecordBatch;
+//! let keys = Arc::new(StringArray::from(vec!["a", "b", "a", "b"])) as ArrayRef;
+//! let times = Arc::new(Int64Array::from(vec![1, 4, 3, 2])) as ArrayRef;
+//! let batch = RecordBatch::try_from_iter(vec![("k", keys), ("t", times)]).unwrap();
+//!
+//! // The two most recent rows, ordered by `t` descending and then `k`
+//! let descending = SortOptions {
+//!     descending: true,
+//!     nulls_first: false,
+//! };
+//! let mut top_k = TopK::try_new(
+//!     batch.schema(),
+//!     vec![(1, descending), (0, SortOptions::default())],
+//!     2,
+//! )
+//! .unwrap();
+//!
+//! // Batches can be fed in incrementally
+//! top_k.update(&batch.slice(0, 2)).unwrap();
+//! top_k.update(&batch.slice(2, 2)).unwrap();
+//!
+//! let output = top_k.finish().unwrap();
+//! let expected: Vec<ArrayRef> = vec![
+//!     Arc::new(StringArray::from(vec!["b", "a"])),
+//!     Arc::new(Int64Array::from(vec![4, 3])),
+//! ];
+//! assert_eq!(output.columns(), &expected);
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::compute::kernels::interleave::interleave;
+use crate::compute::kernels::sort::SortOptions;
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+use crate::record_batch::RecordBatch;
+use crate::row::{OwnedRow, RowConverter, SortField};
+
+/// A row of the input retained by [`TopK`]
+#[derive(Debug)]
+struct TopKRow {
+    /// The sort key of the row
+    key: OwnedRow,
+    /// The index of the batch in [`TopK::batches`] containing the row
+    batch: usize,
+    /// The index of the row in its batch
+    index: usize,
+}
+
+impl TopKRow {
+    /// Rows with equal keys are ordered by their position in the input, so that
+    /// the earliest rows are retained
+    fn position(&self) -> (usize, usize) {
+        (self.batch, self.index)
+    }
+}
+
+impl PartialEq for TopKRow {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for TopKRow {}
+
+impl PartialOrd for TopKRow {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TopKRow {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key
+            .cmp(&other.key)
+            .then_with(|| self.position().cmp(&other.position()))
+    }
+}
+
+/// Computes the first `k` rows of a stream of [`RecordBatch`] in the order of
+/// one or more sort columns
+///
+/// Sort keys are compared using the [row format](crate::row), so any type supported
+/// by [`RowConverter`] may be used as a sort column. Only the `k` smallest keys seen
+/// so far are kept in a heap, so each row is compared against the current `k`-th row
+/// instead of sorting the whole input. Rows with equal keys are returned in the order
+/// in which they were seen.
+///
+/// Rows of the input batches are retained until they are evicted from the heap; to
+/// bound memory use the retained rows are periodically compacted into a single batch.
+#[derive(Debug)]
+pub struct TopK {
+    schema: SchemaRef,
+    sort_columns: Vec<usize>,
+    k: usize,
+    converter: RowConverter,
+    heap: BinaryHeap<TopKRow>,
+    /// The batches referenced by `heap`, `None` once they are no longer referenced
+    batches: Vec<Option<RecordBatch>>,
+    /// The number of rows in `heap` referencing each batch in `batches`
+    batch_rows: Vec<usize>,
+}
+
+impl TopK {
+    /// Create a new [`TopK`] for batches of `schema`, returning the first `k` rows
+    /// ordered by `sort_columns`, where each is a column index and the [`SortOptions`]
+    /// for that column. Earlier sort columns take precedence over later ones.
+    ///
+    /// Returns an error if any of the columns are out of bounds, `sort_columns` is
+    /// empty, or the type of a sort column is not supported by the row format
+    pub fn try_new(
+        schema: SchemaRef,
+        sort_columns: Vec<(usize, SortOptions)>,
+        k: usize,
+    ) -> Result<Self> {
+        if sort_columns.is_empty() {
+            return Err(ArrowError::InvalidArgumentError(
+                "Top-k requires at least one sort column".to_string(),
+            ));
+        }
+
+        let sort_fields = sort_columns
+            .iter()
+            .map(|(idx, options)| {
+                let field = schema.fields().get(*idx).ok_or_else(|| {
+                    ArrowError::InvalidArgumentError(format!(
+                        "Column index {} out of bounds for schema with {} fields",
+                        idx,
+                        schema.fields().len()
+                    ))
+                })?;
+                Ok(SortField::new_with_options(
+                    field.data_type().clone(),
+                    *options,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let converter = RowConverter::try_new(sort_fields)?;
+
+        Ok(Self {
+            schema,
+            sort_columns: sort_columns.into_iter().map(|(idx, _)| idx).collect(),
+            k,
+            converter,
+            heap: BinaryHeap::with_capacity(k),
+            batches: vec![],
+            batch_rows: vec![],
+        })
+    }
+
+    /// Returns the number of rows currently retained, at most `k`
+    pub fn num_rows(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Updates the top-k rows with the rows of `batch`
+    ///
+    /// Returns an error if the columns of `batch` do not match the schema
+    /// provided to [`Self::try_new`]
+    pub fn update(&mut self, batch: &RecordBatch) -> Result<()> {
+        let schema = batch.schema();
+        let matches = schema.fields().len() == self.schema.fields().len()
+            && schema
+                .fields()
+                .iter()
+                .zip(self.schema.fields())
+                .all(|(a, b)| a.data_type() == b.data_type());
+        if !matches {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Batch schema {} does not match top-k schema {}",
+                schema, self.schema
+            )));
+        }
+        if self.k == 0 || batch.num_rows() == 0 {
+            return Ok(());
+        }
+
+        let keys: Vec<_> = self
+            .sort_columns
+            .iter()
+            .map(|idx| batch.column(*idx).clone())
+            .collect();
+        let rows = self.converter.convert_columns(&keys)?;
+
+        let batch_idx = self.batches.len();
+        let mut retained = 0;
+        for (index, key) in rows.iter().enumerate() {
+            if self.heap.len() < self.k {
+                self.heap.push(TopKRow {
+                    key: key.owned(),
+                    batch: batch_idx,
+                    index,
+                });
+                retained += 1;
+                continue;
+            }
+
+            // rows of this batch are ordered after any retained row with an equal key
+            let mut top = self.heap.peek_mut().unwrap();
+            if key < top.key.row() {
+                if top.batch == batch_idx {
+                    retained -= 1;
+                } else {
+                    self.batch_rows[top.batch] -= 1;
+                    if self.batch_rows[top.batch] == 0 {
+                        self.batches[top.batch] = None;
+                    }
+                }
+                *top = TopKRow {
+                    key: key.owned(),
+                    batch: batch_idx,
+                    index,
+                };
+                retained += 1;
+            }
+        }
+
+        self.batches.push((retained > 0).then(|| batch.clone()));
+        self.batch_rows.push(retained);
+
+        let retained_rows: usize = self
+            .batches
+            .iter()
+            .flatten()
+            .map(|batch| batch.num_rows())
+            .sum();
+        if retained_rows > 2 * self.k.max(batch.num_rows()) {
+            self.compact()?;
+        }
+        Ok(())
+    }
+
+    /// Returns a [`RecordBatch`] containing the retained rows in sorted order
+    fn take_sorted(&self, rows: &[TopKRow]) -> Result<RecordBatch> {
+        if rows.is_empty() {
+            return Ok(RecordBatch::new_empty(self.schema.clone()));
+        }
+
+        // the rows are gathered from the retained batches without concatenating them
+        let mut positions = vec![0; self.batches.len()];
+        let mut batches = Vec::new();
+        for (idx, batch) in self.batches.iter().enumerate() {
+            if let Some(batch) = batch {
+                positions[idx] = batches.len();
+                batches.push(batch);
+            }
+        }
+
+        let indices: Vec<_> = rows
+            .iter()
+            .map(|row| (positions[row.batch], row.index))
+            .collect();
+        let columns = (0..self.schema.fields().len())
+            .map(|col| {
+                let arrays: Vec<_> = batches
+                    .iter()
+                    .map(|batch| batch.column(col).as_ref())
+                    .collect();
+                interleave(&arrays, &indices)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        RecordBatch::try_new(self.schema.clone(), columns)
+    }
+
+    /// Replaces the retained batches with a single batch containing only the rows
+    /// in the heap
+    fn compact(&mut self) -> Result<()> {
+        let rows = std::mem::take(&mut self.heap).into_sorted_vec();
+        let batch = self.take_sorted(&rows)?;
+        self.heap = rows
+            .into_iter()
+            .enumerate()
+            .map(|(index, row)| TopKRow {
+                key: row.key,
+                batch: 0,
+                index,
+            })
+            .collect();
+        self.batch_rows = vec![self.heap.len()];
+        self.batches = vec![Some(batch)];
+        Ok(())
+    }
+
+    /// Returns a [`RecordBatch`] containing the first `k` rows seen in sorted order,
+    /// and resets this [`TopK`] so that it can be reused
+    pub fn finish(&mut self) -> Result<RecordBatch> {
+        let rows = std::mem::take(&mut self.heap).into_sorted_vec();
+        let batch = self.take_sorted(&rows);
+        self.batches.clear();
+        self.batch_rows.clear();
+        batch
+    }
+}
+
+/// Returns the first `k` rows of `batches` ordered by `sort_columns`, see [`TopK`]
+pub fn top_k(
+    batches: &[RecordBatch],
+    sort_columns: &[(usize, SortOptions)],
+    k: usize,
+) -> Result<RecordBatch> {
+    let schema = match batches.first() {
+        Some(batch) => batch.schema(),
+        None => {
+            return Err(ArrowError::InvalidArgumentError(
+                "Top-k requires at least one batch".to_string(),
+            ))
+        }
+    };
+    let mut top_k = TopK::try_new(schema, sort_columns.to_vec(), k)?;
+    for batch in batches {
+        top_k.update(batch)?;
+    }
+    top_k.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::*;
+    use crate::compute::kernels::sort::{lexsort_to_indices, SortColumn};
+    use crate::compute::kernels::take::take;
+    use rand::{Rng, SeedableRng};
+    use std::sync::Arc;
+
+    fn descending() -> SortOptions {
+        SortOptions {
+            descending: true,
+            nulls_first: false,
+        }
+    }
+
+    #[test]
+    fn test_top_k() {
+        let a = Arc::new(Int32Array::from(vec![
+            Some(3),
+            None,
+            Some(1),
+            Some(3),
+            Some(2),
+        ])) as ArrayRef;
+        let b = Arc::new(StringArray::from(vec!["a", "b", "c", "d", "e"])) as ArrayRef;
+        let batch = RecordBatch::try_from_iter(vec![("a", a), ("b", b)]).unwrap();
+
+        let output = top_k(
+            &[batch.slice(0, 2), batch.slice(2, 3)],
+            &[(0, descending())],
+            3,
+        )
+        .unwrap();
+        let expected: Vec<ArrayRef> = vec![
+            Arc::new(Int32Array::from(vec![3, 3, 2])),
+            Arc::new(StringArray::from(vec!["a", "d", "e"])),
+        ];
+        assert_eq!(output.columns(), &expected);
+
+        let output = top_k(
+            std::slice::from_ref(&batch),
+            &[(0, SortOptions::default())],
+            2,
+        )
+        .unwrap();
+        let expected: Vec<ArrayRef> = vec![
+            Arc::new(Int32Array::from(vec![None, Some(1)])),
+            Arc::new(StringArray::from(vec!["b", "c"])),
+        ];
+        assert_eq!(output.columns(), &expected);
+
+        let output = top_k(
+            std::slice::from_ref(&batch),
+            &[(0, SortOptions::default())],
+            0,
+        )
+        .unwrap();
+        assert_eq!(output.num_rows(), 0);
+
+        let output = top_k(&[batch], &[(1, descending())], 10).unwrap();
+        assert_eq!(output.num_rows(), 5);
+        let expected = StringArray::from(vec!["e", "d", "c", "b", "a"]);
+        assert_eq!(output.column(1).as_ref(), &expected as &dyn Array);
+    }
+
+    #[test]
+    fn test_top_k_errors() {
+        let a = Arc::new(Int32Array::from(vec![1])) as ArrayRef;
+        let batch = RecordBatch::try_from_iter(vec![("a", a)]).unwrap();
+
+        let err = TopK::try_new(batch.schema(), vec![], 1).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Top-k requires at least one sort column"
+        );
+
+        let err = TopK::try_new(batch.schema(), vec![(1, descending())], 1).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Column index 1 out of bounds for schema with 1 fields"
+        );
+
+        let mut top_k =
+            TopK::try_new(batch.schema(), vec![(0, descending())], 1).unwrap();
+        let b = Arc::new(Int64Array::from(vec![1])) as ArrayRef;
+        let other = RecordBatch::try_from_iter(vec![("a", b)]).unwrap();
+        assert!(top_k.update(&other).is_err());
+    }
+
+    #[test]
+    fn test_top_k_random() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let len = 2000;
+        let a = Arc::new(
+            (0..len)
+                .map(|_| rng.gen_bool(0.9).then(|| rng.gen_range(0..50)))
+                .collect::<Int32Array>(),
+        ) as ArrayRef;
+        let b = Arc::new(
+            (0..len)
+                .map(|_| Some(rng.gen_range(0..1000).to_string()))
+                .collect::<StringArray>(),
+        ) as ArrayRef;
+        let c = Arc::new(Int64Array::from_iter_values(0..len as i64)) as ArrayRef;
+        let batch = RecordBatch::try_from_iter(vec![
+            ("a", a.clone()),
+            ("b", b.clone()),
+            ("c", c),
+        ])
+        .unwrap();
+        let batches: Vec<_> =
+            (0..len).step_by(100).map(|i| batch.slice(i, 100)).collect();
+
+        for k in [0, 1, 10, 99, 100, 250, 3000] {
+            let output = top_k(
+                &batches,
+                &[(0, descending()), (1, SortOptions::default())],
+                k,
+            )
+            .unwrap();
+
+            let columns = vec![
+                SortColumn {
+                    values: a.clone(),
+                    options: Some(descending()),
+                },
+                SortColumn {
+                    values: b.clone(),
+                    options: None,
+                },
+            ];
+            let indices = lexsort_to_indices(&columns, None).unwrap();
+            let indices = indices.slice(0, k.min(len));
+            let expected =
+                take(a.as_ref(), as_primitive_array::<UInt32Type>(&indices), None)
+                    .unwrap();
+            assert_eq!(output.num_rows(), k.min(len));
+            assert_eq!(output.column(0), &expected);
+
+            // rows with equal keys are returned in input order
+            let positions = as_primitive_array::<Int64Type>(output.column(2));
+            for i in 1..output.num_rows() {
+                let same_key = (0..2).all(|col| {
+                    let column = output.column(col);
+                    let cmp = build_compare(column.as_ref(), column.as_ref()).unwrap();
+                    column.is_valid(i) == column.is_valid(i - 1)
+                        && (column.is_null(i) || cmp(i - 1, i) == Ordering::Equal)
+                });
+                if same_key {
+                    assert!(positions.value(i - 1) < positions.value(i));
+                }
+            }
+        }
+    }
+}
@@ -15,10 +15,45 @@
 // specific language governing permissions and limitations
 // under the License.
 
-//! Defines windowing functions, like `shift`ing
+//! Defines windowing functions, like `shift`ing, ranking functions such as `rank`,
+//! and aggregates over window frames such as `window_sum`.
+//!
+//! Except for `shift`, the functions take the ranges of rows of each partition,
+//! such as returned by [`lexicographical_partition_ranges`], and expect the rows of
+//! each partition to be sorted in the window order.
+//!
+//! ```
+//! use arrow::array::{Int32Array, UInt64Array};
+//! use arrow::compute::{dense_rank, lexicographical_partition_ranges, SortColumn};
+//! use std::sync::Arc;
+//!
+//! // SELECT dense_rank() OVER (PARTITION BY department ORDER BY salary)
+//! let department = Arc::new(Int32Array::from(vec![1, 1, 1, 2, 2]));
+//! let salary = Arc::new(Int32Array::from(vec![10, 20, 20, 15, 30]));
+//! let partitions: Vec<_> = lexicographical_partition_ranges(&[SortColumn {
+//!     values: department,
+//!     options: None,
+//! }])
+//! .unwrap()
+//! .collect();
+//! let order_by = [SortColumn {
+//!     values: salary,
+//!     options: None,
+//! }];
+//! let ranks = dense_rank(&partitions, &order_by).unwrap();
+//! assert_eq!(ranks, UInt64Array::from(vec![1, 2, 2, 1, 2]));
+//! ```
 
-use crate::array::{Array, ArrayRef};
-use crate::error::Result;
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use crate::array::{Array, ArrayRef, Float64Array, PrimitiveArray, UInt64Array};
+use crate::compute::kernels::aggregate::is_nan;
+use crate::compute::kernels::interleave::interleave;
+use crate::compute::kernels::partition::lexicographical_partition_ranges;
+use crate::compute::kernels::sort::SortColumn;
+use crate::datatypes::{ArrowNativeTypeOp, ArrowNumericType};
+use crate::error::{ArrowError, Result};
 use crate::{
     array::{make_array, new_null_array},
     compute::concat,
@@ -83,10 +118,589 @@ pub fn shift(array: &dyn Array, offset: i64) -> Result<ArrayRef> {
     }
 }
 
+/// Returns the number of rows covered by `partitions`, checking that they are
+/// contiguous and start at `0`
+fn num_rows(partitions: &[Range<usize>]) -> Result<usize> {
+    let mut end = 0;
+    for partition in partitions {
+        if partition.start != end || partition.end < partition.start {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Partition ranges must be contiguous and start at 0, got {:?} after {}",
+                partition, end
+            )));
+        }
+        end = partition.end;
+    }
+    Ok(end)
+}
+
+/// Checks that an array of length `len` has a row for each row of `partitions`
+fn check_len(len: usize, partitions: &[Range<usize>]) -> Result<()> {
+    let rows = num_rows(partitions)?;
+    if len != rows {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Array of length {} does not match partitions of {} rows",
+            len, rows
+        )));
+    }
+    Ok(())
+}
+
+/// Returns the ranges of rows that are equal according to `order_by` and in the
+/// same partition, or the partitions themselves if `order_by` is empty
+fn peer_ranges(
+    partitions: &[Range<usize>],
+    order_by: &[SortColumn],
+) -> Result<Vec<Range<usize>>> {
+    let len = num_rows(partitions)?;
+    if order_by.is_empty() {
+        return Ok(partitions.to_vec());
+    }
+    if let Some(column) = order_by.iter().find(|c| c.values.len() != len) {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Window order column has {} rows, expected {}",
+            column.values.len(),
+            len
+        )));
+    }
+
+    let mut ends: Vec<_> = partitions
+        .iter()
+        .map(|p| p.end)
+        .chain(lexicographical_partition_ranges(order_by)?.map(|p| p.end))
+        .collect();
+    ends.sort_unstable();
+    ends.dedup();
+
+    let mut start = 0;
+    Ok(ends
+        .into_iter()
+        .filter(|end| *end > 0)
+        .map(|end| {
+            let range = start..end;
+            start = end;
+            range
+        })
+        .collect())
+}
+
+/// Returns the 1-based number of each row within its partition.
+///
+/// `partitions` are the contiguous ranges of rows of each partition, as returned by
+/// [`lexicographical_partition_ranges`], with the rows of each partition already
+/// sorted in the window order.
+///
+/// ```
+/// use arrow::array::UInt64Array;
+/// use arrow::compute::row_number;
+///
+/// let numbers = row_number(&[0..2, 2..5]).unwrap();
+/// assert_eq!(numbers, UInt64Array::from(vec![1, 2, 1, 2, 3]));
+/// ```
+pub fn row_number(partitions: &[Range<usize>]) -> Result<UInt64Array> {
+    num_rows(partitions)?;
+    Ok(partitions
+        .iter()
+        .flat_map(|p| 1..=p.len() as u64)
+        .map(Some)
+        .collect())
+}
+
+/// Returns the rank of each row within its partition, with gaps: rows that are equal
+/// according to `order_by` have the same rank, which is one more than the number of
+/// rows before them in the partition.
+///
+/// `partitions` are the contiguous ranges of rows of each partition, as returned by
+/// [`lexicographical_partition_ranges`], with the rows of each partition already
+/// sorted by `order_by`. If `order_by` is empty, all rows of a partition are equal.
+///
+/// ```
+/// use arrow::array::{Int32Array, UInt64Array};
+/// use arrow::compute::{rank, SortColumn};
+/// use std::sync::Arc;
+///
+/// let order_by = vec![SortColumn {
+///     values: Arc::new(Int32Array::from(vec![1, 1, 2, 5, 5, 7])),
+///     options: None,
+/// }];
+/// let ranks = rank(&[0..6], &order_by).unwrap();
+/// assert_eq!(ranks, UInt64Array::from(vec![1, 1, 3, 4, 4, 6]));
+/// ```
+pub fn rank(partitions: &[Range<usize>], order_by: &[SortColumn]) -> Result<UInt64Array> {
+    let peers = peer_ranges(partitions, order_by)?;
+    let mut ranks = Vec::with_capacity(num_rows(partitions)?);
+    let mut partitions = partitions.iter().peekable();
+    for peer in peers {
+        while partitions
+            .peek()
+            .map(|p| p.end <= peer.start)
+            .unwrap_or(false)
+        {
+            partitions.next();
+        }
+        let start = partitions.peek().map(|p| p.start).unwrap_or_default();
+        let rank = (peer.start - start + 1) as u64;
+        ranks.extend(std::iter::repeat(Some(rank)).take(peer.len()));
+    }
+    Ok(ranks.into_iter().collect())
+}
+
+/// Returns the rank of each row within its partition, without gaps: rows that are
+/// equal according to `order_by` have the same rank, which is one more than the
+/// number of distinct preceding rows in the partition.
+///
+/// See [`rank`] for the expected layout of `partitions` and `order_by`.
+pub fn dense_rank(
+    partitions: &[Range<usize>],
+    order_by: &[SortColumn],
+) -> Result<UInt64Array> {
+    let peers = peer_ranges(partitions, order_by)?;
+    let mut ranks = Vec::with_capacity(num_rows(partitions)?);
+    let mut partitions = partitions.iter().peekable();
+    let mut rank = 0;
+    for peer in peers {
+        while partitions
+            .peek()
+            .map(|p| p.end <= peer.start)
+            .unwrap_or(false)
+        {
+            partitions.next();
+        }
+        rank = match partitions.peek() {
+            Some(p) if p.start == peer.start => 1,
+            _ => rank + 1,
+        };
+        ranks.extend(std::iter::repeat(Some(rank)).take(peer.len()));
+    }
+    Ok(ranks.into_iter().collect())
+}
+
+/// Returns the relative rank of each row within its partition, computed as
+/// `(rank - 1) / (partition rows - 1)`, or `0` for partitions of a single row.
+///
+/// See [`rank`] for the expected layout of `partitions` and `order_by`.
+pub fn percent_rank(
+    partitions: &[Range<usize>],
+    order_by: &[SortColumn],
+) -> Result<Float64Array> {
+    let ranks = rank(partitions, order_by)?;
+    let ranks = ranks.values();
+    Ok(partitions
+        .iter()
+        .flat_map(|p| {
+            let denominator = p.len().saturating_sub(1).max(1) as f64;
+            ranks[p.clone()]
+                .iter()
+                .map(move |rank| Some((rank - 1) as f64 / denominator))
+        })
+        .collect())
+}
+
+/// Returns the cumulative distribution of each row within its partition, that is the
+/// number of rows preceding or equal to it according to `order_by` divided by the
+/// number of rows in the partition.
+///
+/// See [`rank`] for the expected layout of `partitions` and `order_by`.
+pub fn cume_dist(
+    partitions: &[Range<usize>],
+    order_by: &[SortColumn],
+) -> Result<Float64Array> {
+    let peers = peer_ranges(partitions, order_by)?;
+    let mut dists = Vec::with_capacity(num_rows(partitions)?);
+    let mut partitions = partitions.iter().peekable();
+    for peer in peers {
+        while partitions
+            .peek()
+            .map(|p| p.end <= peer.start)
+            .unwrap_or(false)
+        {
+            partitions.next();
+        }
+        let partition = partitions.peek().unwrap();
+        let dist = (peer.end - partition.start) as f64 / partition.len() as f64;
+        dists.extend(std::iter::repeat(Some(dist)).take(peer.len()));
+    }
+    Ok(dists.into_iter().collect())
+}
+
+/// Divides the rows of each partition into `buckets` groups of rows, as equal in size
+/// as possible, and returns the 1-based number of the group of each row. Earlier
+/// groups get the extra rows if the partition doesn't divide evenly.
+///
+/// # Errors
+///
+/// This function errors if `buckets` is zero, or the partitions are invalid
+pub fn ntile(partitions: &[Range<usize>], buckets: u64) -> Result<UInt64Array> {
+    if buckets == 0 {
+        return Err(ArrowError::InvalidArgumentError(
+            "ntile requires a positive number of buckets".to_string(),
+        ));
+    }
+    num_rows(partitions)?;
+    Ok(partitions
+        .iter()
+        .flat_map(|p| {
+            let len = p.len() as u64;
+            let (size, larger) = (len / buckets, len % buckets);
+            (0..len).map(move |row| {
+                // the first `larger` buckets have `size + 1` rows
+                let bucket = match row < larger * (size + 1) {
+                    true => row / (size + 1),
+                    false => larger + (row - larger * (size + 1)) / size,
+                };
+                Some(bucket + 1)
+            })
+        })
+        .collect())
+}
+
+/// Returns the values of `array` from `offset` rows before or after each row
+fn lag_lead(
+    array: &dyn Array,
+    partitions: &[Range<usize>],
+    offset: usize,
+    default: Option<&dyn Array>,
+    lag: bool,
+) -> Result<ArrayRef> {
+    check_len(array.len(), partitions)?;
+    let nulls;
+    let default = match default {
+        Some(default) => {
+            if default.len() != 1 || default.data_type() != array.data_type() {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "Default value must be an array of length 1 with data type {}, got length {} with data type {}",
+                    array.data_type(),
+                    default.len(),
+                    default.data_type()
+                )));
+            }
+            default
+        }
+        None => {
+            nulls = new_null_array(array.data_type(), 1);
+            nulls.as_ref()
+        }
+    };
+
+    let indices: Vec<_> = partitions
+        .iter()
+        .flat_map(|p| {
+            let p = p.clone();
+            p.clone().map(move |row| {
+                let source = match lag {
+                    true => row.checked_sub(offset).filter(|r| *r >= p.start),
+                    false => row.checked_add(offset).filter(|r| *r < p.end),
+                };
+                source.map(|r| (0, r)).unwrap_or((1, 0))
+            })
+        })
+        .collect();
+    interleave(&[array, default], &indices)
+}
+
+/// Returns the value of `array` `offset` rows before each row in the same partition,
+/// or `default` if there is no such row. `default` must be an array of a single
+/// value of the same type as `array`, and if `None` a null is used.
+///
+/// `partitions` are the contiguous ranges of rows of each partition, as returned by
+/// [`lexicographical_partition_ranges`], with the rows of each partition already
+/// sorted in the window order.
+///
+/// ```
+/// use arrow::array::{Array, Int32Array};
+/// use arrow::compute::lag;
+///
+/// let array = Int32Array::from(vec![1, 2, 3, 4, 5]);
+/// let default = Int32Array::from(vec![0]);
+/// let lagged = lag(&array, &[0..3, 3..5], 1, Some(&default)).unwrap();
+/// assert_eq!(
+///     lagged.as_any().downcast_ref::<Int32Array>().unwrap(),
+///     &Int32Array::from(vec![0, 1, 2, 0, 4])
+/// );
+/// ```
+pub fn lag(
+    array: &dyn Array,
+    partitions: &[Range<usize>],
+    offset: usize,
+    default: Option<&dyn Array>,
+) -> Result<ArrayRef> {
+    lag_lead(array, partitions, offset, default, true)
+}
+
+/// Returns the value of `array` `offset` rows after each row in the same partition,
+/// or `default` if there is no such row. See [`lag`] for the meaning of the arguments.
+pub fn lead(
+    array: &dyn Array,
+    partitions: &[Range<usize>],
+    offset: usize,
+    default: Option<&dyn Array>,
+) -> Result<ArrayRef> {
+    lag_lead(array, partitions, offset, default, false)
+}
+
+/// A bound of a [`WindowFrame`], relative to the current row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowBound {
+    /// The first row of the partition
+    UnboundedPreceding,
+    /// The given number of rows before the current row
+    Preceding(usize),
+    /// The current row
+    CurrentRow,
+    /// The given number of rows after the current row
+    Following(usize),
+    /// The last row of the partition
+    UnboundedFollowing,
+}
+
+/// The rows of a partition that a window aggregate such as [`window_sum`] is computed
+/// over for each row, from `start` to `end` inclusive, as in a SQL
+/// `ROWS BETWEEN start AND end` clause.
+///
+/// The frame is clamped to the partition of the row, and is empty if `start` is after
+/// `end`. The default frame is a running aggregate, from the first row of the
+/// partition to the current row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowFrame {
+    /// The first row of the frame
+    pub start: WindowBound,
+    /// The last row of the frame
+    pub end: WindowBound,
+}
+
+impl Default for WindowFrame {
+    fn default() -> Self {
+        Self {
+            start: WindowBound::UnboundedPreceding,
+            end: WindowBound::CurrentRow,
+        }
+    }
+}
+
+impl WindowFrame {
+    /// Returns the range of rows of the frame of each row
+    fn ranges(&self, partitions: &[Range<usize>]) -> Result<Vec<Range<usize>>> {
+        if self.start == WindowBound::UnboundedFollowing {
+            return Err(ArrowError::InvalidArgumentError(
+                "Window frame cannot start at UNBOUNDED FOLLOWING".to_string(),
+            ));
+        }
+        if self.end == WindowBound::UnboundedPreceding {
+            return Err(ArrowError::InvalidArgumentError(
+                "Window frame cannot end at UNBOUNDED PRECEDING".to_string(),
+            ));
+        }
+        let mut ranges = Vec::with_capacity(num_rows(partitions)?);
+        for p in partitions {
+            // the position of the bound relative to `row`, which may be outside of
+            // the partition, as an i128 so that it cannot overflow
+            let position = |bound: WindowBound, row: usize| match bound {
+                WindowBound::UnboundedPreceding => p.start as i128,
+                WindowBound::Preceding(n) => row as i128 - n as i128,
+                WindowBound::CurrentRow => row as i128,
+                WindowBound::Following(n) => row as i128 + n as i128,
+                WindowBound::UnboundedFollowing => p.end as i128 - 1,
+            };
+            let clamp = |row: i128| row.clamp(p.start as i128, p.end as i128) as usize;
+            for row in p.clone() {
+                let start = clamp(position(self.start, row));
+                // the end bound is inclusive
+                let end = clamp(position(self.end, row) + 1);
+                ranges.push(start..end.max(start));
+            }
+        }
+        Ok(ranges)
+    }
+}
+
+/// Returns the range of rows of the frame of each row of an array of length `len`
+fn frame_ranges(
+    len: usize,
+    partitions: &[Range<usize>],
+    frame: &WindowFrame,
+) -> Result<Vec<Range<usize>>> {
+    check_len(len, partitions)?;
+    frame.ranges(partitions)
+}
+
+/// Returns the number of non-null values of `array` in the window `frame` of each row.
+///
+/// `partitions` are the contiguous ranges of rows of each partition, as returned by
+/// [`lexicographical_partition_ranges`], with the rows of each partition already
+/// sorted in the window order.
+///
+/// ```
+/// use arrow::array::{Int32Array, UInt64Array};
+/// use arrow::compute::{window_count, WindowBound, WindowFrame};
+///
+/// let array = Int32Array::from(vec![Some(1), None, Some(3), Some(4)]);
+/// let frame = WindowFrame {
+///     start: WindowBound::Preceding(1),
+///     end: WindowBound::Following(1),
+/// };
+/// let counts = window_count(&array, &[0..4], &frame).unwrap();
+/// assert_eq!(counts, UInt64Array::from(vec![1, 2, 2, 2]));
+/// ```
+pub fn window_count(
+    array: &dyn Array,
+    partitions: &[Range<usize>],
+    frame: &WindowFrame,
+) -> Result<UInt64Array> {
+    let ranges = frame_ranges(array.len(), partitions, frame)?;
+    let mut counts = Vec::with_capacity(array.len() + 1);
+    counts.push(0);
+    for row in 0..array.len() {
+        counts.push(counts[row] + array.is_valid(row) as u64);
+    }
+    Ok(ranges
+        .into_iter()
+        .map(|range| Some(counts[range.end] - counts[range.start]))
+        .collect())
+}
+
+/// Returns the sum of the non-null values of `array` in the window `frame` of each
+/// row, or null if there are none. Overflows wrap around, as in
+/// [`sum`](crate::compute::kernels::aggregate::sum).
+///
+/// See [`window_count`] for the expected layout of `partitions`.
+///
+/// ```
+/// use arrow::array::Int32Array;
+/// use arrow::compute::{window_sum, WindowFrame};
+///
+/// let array = Int32Array::from(vec![Some(1), None, Some(3), Some(4)]);
+/// let sums = window_sum(&array, &[0..2, 2..4], &WindowFrame::default()).unwrap();
+/// assert_eq!(sums, Int32Array::from(vec![Some(1), Some(1), Some(3), Some(7)]));
+/// ```
+pub fn window_sum<T>(
+    array: &PrimitiveArray<T>,
+    partitions: &[Range<usize>],
+    frame: &WindowFrame,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: ArrowNativeTypeOp,
+{
+    let ranges = frame_ranges(array.len(), partitions, frame)?;
+    let add = |sum: Option<T::Native>, rows: Range<usize>| {
+        rows.filter(|row| array.is_valid(*row))
+            .map(|row| array.value(row))
+            .fold(sum, |sum, value| match sum {
+                Some(sum) => Some(sum.add_wrapping(value)),
+                None => Some(value),
+            })
+    };
+
+    // the sum of a frame is computed from the sum of the previous frame if that
+    // is a prefix or a suffix of it, which is the case for all frames starting at
+    // UNBOUNDED PRECEDING, or when visited in reverse, ending at UNBOUNDED FOLLOWING
+    let mut sums = vec![None; array.len()];
+    let mut previous = 0..0;
+    let mut sum = None;
+    let mut visit = |row: usize| {
+        let range = ranges[row].clone();
+        sum = if range.start == previous.start && range.end >= previous.end {
+            add(sum, previous.end..range.end)
+        } else if range.end == previous.end && range.start <= previous.start {
+            add(sum, range.start..previous.start)
+        } else {
+            add(None, range.clone())
+        };
+        previous = range;
+        sums[row] = sum;
+    };
+    match frame.start != WindowBound::UnboundedPreceding
+        && frame.end == WindowBound::UnboundedFollowing
+    {
+        true => (0..array.len()).rev().for_each(&mut visit),
+        false => (0..array.len()).for_each(&mut visit),
+    }
+    Ok(sums.into_iter().collect())
+}
+
+/// Returns the value of `array` in the frame of each row that is not replaced by a
+/// later value according to `replaces`
+fn window_min_max<T, F>(
+    array: &PrimitiveArray<T>,
+    partitions: &[Range<usize>],
+    frame: &WindowFrame,
+    replaces: F,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    F: Fn(T::Native, T::Native) -> bool,
+{
+    let ranges = frame_ranges(array.len(), partitions, frame)?;
+
+    // the start and end of the frames never decrease, and so a queue of the rows
+    // that may be the result of a later frame is kept, whose first row is the
+    // result of the current frame
+    let mut queue = VecDeque::new();
+    let mut next = 0;
+    Ok(ranges
+        .into_iter()
+        .map(|range| {
+            for row in next..range.end {
+                if array.is_valid(row) {
+                    let value = array.value(row);
+                    while matches!(queue.back(), Some(last) if replaces(value, array.value(*last)))
+                    {
+                        queue.pop_back();
+                    }
+                    queue.push_back(row);
+                }
+            }
+            next = next.max(range.end);
+            while matches!(queue.front(), Some(first) if *first < range.start) {
+                queue.pop_front();
+            }
+            queue.front().map(|row| array.value(*row))
+        })
+        .collect())
+}
+
+/// Returns the minimum non-null value of `array` in the window `frame` of each row,
+/// or null if there are none. NaN values are considered to be greater than any
+/// other value, as in [`min`](crate::compute::kernels::aggregate::min).
+///
+/// See [`window_count`] for the expected layout of `partitions`.
+pub fn window_min<T>(
+    array: &PrimitiveArray<T>,
+    partitions: &[Range<usize>],
+    frame: &WindowFrame,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+{
+    window_min_max(array, partitions, frame, |value, other| {
+        !((is_nan(value) & !is_nan(other)) || value > other)
+    })
+}
+
+/// Returns the maximum non-null value of `array` in the window `frame` of each row,
+/// or null if there are none. NaN values are considered to be greater than any
+/// other value, as in [`max`](crate::compute::kernels::aggregate::max).
+///
+/// See [`window_count`] for the expected layout of `partitions`.
+pub fn window_max<T>(
+    array: &PrimitiveArray<T>,
+    partitions: &[Range<usize>],
+    frame: &WindowFrame,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+{
+    window_min_max(array, partitions, frame, |value, other| {
+        !((is_nan(other) & !is_nan(value)) || other > value)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::array::{Float64Array, Int32Array, Int32DictionaryArray};
+    use crate::array::{Int32Array, Int32DictionaryArray, Int64Array, StringArray};
+    use rand::{Rng, SeedableRng};
+    use std::sync::Arc;
 
     #[test]
     fn test_shift_neg() {
@@ -195,4 +809,335 @@ mod tests {
         let expected: Int32Array = vec![None, None, None].into();
         assert_eq!(res.as_ref(), &expected);
     }
+
+    fn order_by(values: Vec<i32>) -> Vec<SortColumn> {
+        vec![SortColumn {
+            values: Arc::new(Int32Array::from(values)),
+            options: None,
+        }]
+    }
+
+    #[test]
+    fn test_ranking() {
+        let partitions = [0..4, 4..7];
+        // the last row of the first partition equals the first row of the second
+        let order_by = order_by(vec![1, 1, 2, 5, 5, 6, 6]);
+
+        let expected = UInt64Array::from(vec![1, 2, 3, 4, 1, 2, 3]);
+        assert_eq!(row_number(&partitions).unwrap(), expected);
+        let expected = UInt64Array::from(vec![1, 1, 3, 4, 1, 2, 2]);
+        assert_eq!(rank(&partitions, &order_by).unwrap(), expected);
+        let expected = UInt64Array::from(vec![1, 1, 2, 3, 1, 2, 2]);
+        assert_eq!(dense_rank(&partitions, &order_by).unwrap(), expected);
+        let expected = Float64Array::from(vec![0.0, 0.0, 2.0 / 3.0, 1.0, 0.0, 0.5, 0.5]);
+        assert_eq!(percent_rank(&partitions, &order_by).unwrap(), expected);
+        let expected = Float64Array::from(vec![0.5, 0.5, 0.75, 1.0, 1.0 / 3.0, 1.0, 1.0]);
+        assert_eq!(cume_dist(&partitions, &order_by).unwrap(), expected);
+
+        // without an order, all rows of a partition are peers
+        let expected = UInt64Array::from(vec![1, 1, 1, 1, 1, 1, 1]);
+        assert_eq!(rank(&partitions, &[]).unwrap(), expected);
+        assert_eq!(dense_rank(&partitions, &[]).unwrap(), expected);
+        let expected = Float64Array::from(vec![1.0; 7]);
+        assert_eq!(cume_dist(&partitions, &[]).unwrap(), expected);
+
+        let expected = Float64Array::from(vec![0.0, 0.0, 0.0]);
+        assert_eq!(percent_rank(&[0..1, 1..3], &[]).unwrap(), expected);
+        assert_eq!(rank(&[], &[]).unwrap().len(), 0);
+
+        let err = rank(&[0..3, 3..4], &order_by).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Window order column has 7 rows, expected 4"
+        );
+        let err = row_number(&[0..3, 4..7]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Partition ranges must be contiguous and start at 0, got 4..7 after 3"
+        );
+    }
+
+    #[test]
+    fn test_ntile() {
+        let expected = UInt64Array::from(vec![1, 1, 2, 2, 3, 1, 2, 1]);
+        assert_eq!(ntile(&[0..5, 5..7, 7..8], 3).unwrap(), expected);
+        let expected = UInt64Array::from(vec![1, 1, 1, 2, 2, 3, 3, 4, 4, 1]);
+        assert_eq!(ntile(&[0..0, 0..9, 9..10], 4).unwrap(), expected);
+        let err = ntile(&[0..2, 2..5], 0).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: ntile requires a positive number of buckets"
+        );
+    }
+
+    #[test]
+    fn test_lag_lead() {
+        let array =
+            StringArray::from(vec![Some("a"), None, Some("c"), Some("d"), Some("e")]);
+        let partitions = [0..3, 3..5];
+
+        let lagged = lag(&array, &partitions, 1, None).unwrap();
+        let expected = StringArray::from(vec![None, Some("a"), None, None, Some("d")]);
+        assert_eq!(lagged.as_ref(), &expected);
+
+        let default = StringArray::from(vec!["x"]);
+        let led = lead(&array, &partitions, 2, Some(&default)).unwrap();
+        let expected = StringArray::from(vec![
+            Some("c"),
+            Some("x"),
+            Some("x"),
+            Some("x"),
+            Some("x"),
+        ]);
+        assert_eq!(led.as_ref(), &expected);
+
+        let led = lead(&array, &partitions, 0, None).unwrap();
+        assert_eq!(led.as_ref(), &array);
+        let lagged = lag(&array, &partitions, usize::MAX, Some(&default)).unwrap();
+        assert_eq!(lagged.as_ref(), &StringArray::from(vec!["x"; 5]));
+
+        let default = Int32Array::from(vec![0]);
+        let err = lag(&array, &partitions, 1, Some(&default)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Default value must be an array of length 1 with data type Utf8, got length 1 with data type Int32"
+        );
+        let err = lead(&array, &[0..1, 1..3], 1, None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Array of length 5 does not match partitions of 3 rows"
+        );
+    }
+
+    #[test]
+    fn test_window_aggregates() {
+        let array = Int32Array::from(vec![
+            Some(1),
+            None,
+            Some(3),
+            Some(4),
+            Some(2),
+            Some(7),
+            Some(-1),
+        ]);
+        let partitions = [0..5, 5..7];
+        let check = |frame: WindowFrame,
+                     sums: Vec<Option<i32>>,
+                     mins: Vec<Option<i32>>,
+                     maxs: Vec<Option<i32>>,
+                     counts: Vec<u64>| {
+            let sum = window_sum(&array, &partitions, &frame).unwrap();
+            assert_eq!(sum, Int32Array::from(sums), "{:?}", frame);
+            let min = window_min(&array, &partitions, &frame).unwrap();
+            assert_eq!(min, Int32Array::from(mins), "{:?}", frame);
+            let max = window_max(&array, &partitions, &frame).unwrap();
+            assert_eq!(max, Int32Array::from(maxs), "{:?}", frame);
+            let count = window_count(&array, &partitions, &frame).unwrap();
+            assert_eq!(count, UInt64Array::from(counts), "{:?}", frame);
+        };
+
+        check(
+            WindowFrame::default(),
+            vec![
+                Some(1),
+                Some(1),
+                Some(4),
+                Some(8),
+                Some(10),
+                Some(7),
+                Some(6),
+            ],
+            vec![
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(7),
+                Some(-1),
+            ],
+            vec![
+                Some(1),
+                Some(1),
+                Some(3),
+                Some(4),
+                Some(4),
+                Some(7),
+                Some(7),
+            ],
+            vec![1, 1, 2, 3, 4, 1, 2],
+        );
+        check(
+            WindowFrame {
+                start: WindowBound::Preceding(1),
+                end: WindowBound::Following(1),
+            },
+            vec![
+                Some(1),
+                Some(4),
+                Some(7),
+                Some(9),
+                Some(6),
+                Some(6),
+                Some(6),
+            ],
+            vec![
+                Some(1),
+                Some(1),
+                Some(3),
+                Some(2),
+                Some(2),
+                Some(-1),
+                Some(-1),
+            ],
+            vec![
+                Some(1),
+                Some(3),
+                Some(4),
+                Some(4),
+                Some(4),
+                Some(7),
+                Some(7),
+            ],
+            vec![1, 2, 2, 3, 2, 2, 2],
+        );
+        check(
+            WindowFrame {
+                start: WindowBound::CurrentRow,
+                end: WindowBound::UnboundedFollowing,
+            },
+            vec![
+                Some(10),
+                Some(9),
+                Some(9),
+                Some(6),
+                Some(2),
+                Some(6),
+                Some(-1),
+            ],
+            vec![
+                Some(1),
+                Some(2),
+                Some(2),
+                Some(2),
+                Some(2),
+                Some(-1),
+                Some(-1),
+            ],
+            vec![
+                Some(4),
+                Some(4),
+                Some(4),
+                Some(4),
+                Some(2),
+                Some(7),
+                Some(-1),
+            ],
+            vec![4, 3, 3, 2, 1, 2, 1],
+        );
+        check(
+            WindowFrame {
+                start: WindowBound::Following(1),
+                end: WindowBound::Following(2),
+            },
+            vec![Some(3), Some(7), Some(6), Some(2), None, Some(-1), None],
+            vec![Some(3), Some(3), Some(2), Some(2), None, Some(-1), None],
+            vec![Some(3), Some(4), Some(4), Some(2), None, Some(-1), None],
+            vec![1, 2, 2, 1, 0, 1, 0],
+        );
+        check(
+            WindowFrame {
+                start: WindowBound::Preceding(3),
+                end: WindowBound::Preceding(2),
+            },
+            vec![None, None, Some(1), Some(1), Some(3), None, None],
+            vec![None, None, Some(1), Some(1), Some(3), None, None],
+            vec![None, None, Some(1), Some(1), Some(3), None, None],
+            vec![0, 0, 1, 1, 1, 0, 0],
+        );
+
+        let frame = WindowFrame {
+            start: WindowBound::UnboundedFollowing,
+            end: WindowBound::UnboundedFollowing,
+        };
+        let err = window_count(&array, &partitions, &frame).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Window frame cannot start at UNBOUNDED FOLLOWING"
+        );
+    }
+
+    #[test]
+    fn test_window_min_max_nan() {
+        let array = Float64Array::from(vec![1.0, f64::NAN, 0.5]);
+        let frame = WindowFrame::default();
+        let partitions = [0..3, 3..3];
+        let min = window_min(&array, &partitions, &frame).unwrap();
+        assert_eq!(min, Float64Array::from(vec![1.0, 1.0, 0.5]));
+        let max = window_max(&array, &partitions, &frame).unwrap();
+        assert_eq!(max.value(0), 1.0);
+        assert!(max.value(1).is_nan());
+        assert!(max.value(2).is_nan());
+        assert_eq!(max.null_count(), 0);
+    }
+
+    #[test]
+    fn test_window_aggregates_random() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let array: Int64Array = (0..200)
+            .map(|_| rng.gen_bool(0.8).then(|| rng.gen_range(-100..100)))
+            .collect();
+        let mut partitions = vec![];
+        let mut start = 0;
+        while start < array.len() {
+            let end = (start + rng.gen_range(0..40)).min(array.len());
+            partitions.push(start..end);
+            start = end;
+        }
+
+        let bounds = [
+            WindowBound::UnboundedPreceding,
+            WindowBound::Preceding(3),
+            WindowBound::Preceding(1),
+            WindowBound::CurrentRow,
+            WindowBound::Following(2),
+            WindowBound::UnboundedFollowing,
+        ];
+        for start in bounds.iter().take(5) {
+            for end in bounds.iter().skip(1) {
+                let frame = WindowFrame {
+                    start: *start,
+                    end: *end,
+                };
+                let sums = window_sum(&array, &partitions, &frame).unwrap();
+                let mins = window_min(&array, &partitions, &frame).unwrap();
+                let maxs = window_max(&array, &partitions, &frame).unwrap();
+                for p in &partitions {
+                    for row in p.clone() {
+                        let position = |bound: &WindowBound| match bound {
+                            WindowBound::UnboundedPreceding => p.start as i64,
+                            WindowBound::Preceding(n) => row as i64 - *n as i64,
+                            WindowBound::CurrentRow => row as i64,
+                            WindowBound::Following(n) => row as i64 + *n as i64,
+                            WindowBound::UnboundedFollowing => p.end as i64 - 1,
+                        };
+                        let values: Vec<_> = (p.start..p.end)
+                            .filter(|r| {
+                                let r = *r as i64;
+                                r >= position(start) && r <= position(end)
+                            })
+                            .filter(|r| array.is_valid(*r))
+                            .map(|r| array.value(r))
+                            .collect();
+                        let sum = (!values.is_empty()).then(|| values.iter().sum());
+                        assert_eq!(sums.is_valid(row).then(|| sums.value(row)), sum);
+                        let min = values.iter().min().copied();
+                        assert_eq!(mins.is_valid(row).then(|| mins.value(row)), min);
+                        let max = values.iter().max().copied();
+                        assert_eq!(maxs.is_valid(row).then(|| maxs.value(row)), max);
+                    }
+                }
+            }
+        }
+    }
 }
@@ -24,11 +24,14 @@ mod util;
 pub use self::kernels::aggregate::*;
 pub use self::kernels::arithmetic::*;
 pub use self::kernels::arity::*;
+pub use self::kernels::bitwise::*;
 pub use self::kernels::boolean::*;
 pub use self::kernels::cast::*;
 pub use self::kernels::comparison::*;
 pub use self::kernels::concat::*;
 pub use self::kernels::filter::*;
+pub use self::kernels::hash::*;
+pub use self::kernels::interleave::*;
 pub use self::kernels::limit::*;
 pub use self::kernels::partition::*;
 pub use self::kernels::regexp::*;
@@ -19,6 +19,7 @@
 
 use crate::array::*;
 use crate::buffer::{buffer_bin_and, Buffer};
+use crate::compute::kernels::cast::cast;
 use crate::datatypes::*;
 use crate::error::{ArrowError, Result};
 use num::{One, ToPrimitive, Zero};
@@ -138,6 +139,34 @@ where
     Ok(PrimitiveArray::<UInt32Type>::from(values))
 }
 
+/// Returns an error if `left` and `right` have different lengths
+pub(super) fn check_lengths(left: &dyn Array, right: &dyn Array) -> Result<()> {
+    if left.len() != right.len() {
+        return Err(ArrowError::ComputeError(format!(
+            "Arrays must have the same length: {} != {}",
+            left.len(),
+            right.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Returns the null bitmap of `array` and its offset in bits, or `None` if `array` has
+/// no null bitmap. Unlike [`ArrayData::null_buffer`], a dictionary key that refers to a
+/// null value is null.
+pub(super) fn logical_null_buffer(array: &dyn Array) -> Result<Option<(Buffer, usize)>> {
+    let data = array.data();
+    if let DataType::Dictionary(_, value_type) = data.data_type() {
+        if data.child_data()[0].null_count() > 0 {
+            let unpacked = cast(&make_array(data.clone()), value_type)?;
+            return logical_null_buffer(unpacked.as_ref());
+        }
+    }
+    Ok(data
+        .null_buffer()
+        .map(|nulls| (nulls.clone(), data.offset())))
+}
+
 #[cfg(test)]
 pub(super) mod tests {
     use super::*;
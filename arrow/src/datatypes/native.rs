@@ -16,6 +16,7 @@
 // under the License.
 
 use super::DataType;
+use crate::error::{ArrowError, Result};
 use half::f16;
 use serde_json::{Number, Value};
 
@@ -389,6 +390,203 @@ impl private::Sealed for f32 {}
 impl ArrowNativeType for f64 {}
 impl private::Sealed for f64 {}
 
+/// Trait for [`ArrowNativeType`] that adds checked and wrapping arithmetic operations.
+///
+/// The checked operations return an [`ArrowError::ComputeError`] if the result
+/// overflows the type, or an [`ArrowError::DivideByZero`] when dividing by zero,
+/// whereas the wrapping operations wrap around at the boundary of the type.
+///
+/// Floating point types never overflow, and so both variants are equivalent,
+/// except that the checked division and remainder return an error if the divisor is zero
+pub trait ArrowNativeTypeOp: ArrowNativeType {
+    /// The additive identity
+    const ZERO: Self;
+
+    /// The multiplicative identity
+    const ONE: Self;
+
+    fn add_checked(self, rhs: Self) -> Result<Self>;
+
+    fn add_wrapping(self, rhs: Self) -> Self;
+
+    fn sub_checked(self, rhs: Self) -> Result<Self>;
+
+    fn sub_wrapping(self, rhs: Self) -> Self;
+
+    fn mul_checked(self, rhs: Self) -> Result<Self>;
+
+    fn mul_wrapping(self, rhs: Self) -> Self;
+
+    fn div_checked(self, rhs: Self) -> Result<Self>;
+
+    /// Wrapping division, panics if `rhs` is zero for integer types
+    fn div_wrapping(self, rhs: Self) -> Self;
+
+    fn mod_checked(self, rhs: Self) -> Result<Self>;
+
+    /// Wrapping remainder, panics if `rhs` is zero for integer types
+    fn mod_wrapping(self, rhs: Self) -> Self;
+
+    fn neg_checked(self) -> Result<Self>;
+
+    fn neg_wrapping(self) -> Self;
+
+    fn is_zero(self) -> bool {
+        self == Self::ZERO
+    }
+}
+
+fn overflow_error<T: std::fmt::Debug>(l: T, op: &str, r: T) -> ArrowError {
+    ArrowError::ComputeError(format!("Overflow happened on: {:?} {} {:?}", l, op, r))
+}
+
+macro_rules! native_type_op {
+    ($t:ty) => {
+        impl ArrowNativeTypeOp for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn add_checked(self, rhs: Self) -> Result<Self> {
+                self.checked_add(rhs)
+                    .ok_or_else(|| overflow_error(self, "+", rhs))
+            }
+
+            fn add_wrapping(self, rhs: Self) -> Self {
+                self.wrapping_add(rhs)
+            }
+
+            fn sub_checked(self, rhs: Self) -> Result<Self> {
+                self.checked_sub(rhs)
+                    .ok_or_else(|| overflow_error(self, "-", rhs))
+            }
+
+            fn sub_wrapping(self, rhs: Self) -> Self {
+                self.wrapping_sub(rhs)
+            }
+
+            fn mul_checked(self, rhs: Self) -> Result<Self> {
+                self.checked_mul(rhs)
+                    .ok_or_else(|| overflow_error(self, "*", rhs))
+            }
+
+            fn mul_wrapping(self, rhs: Self) -> Self {
+                self.wrapping_mul(rhs)
+            }
+
+            fn div_checked(self, rhs: Self) -> Result<Self> {
+                if rhs == 0 {
+                    return Err(ArrowError::DivideByZero);
+                }
+                self.checked_div(rhs)
+                    .ok_or_else(|| overflow_error(self, "/", rhs))
+            }
+
+            fn div_wrapping(self, rhs: Self) -> Self {
+                self.wrapping_div(rhs)
+            }
+
+            fn mod_checked(self, rhs: Self) -> Result<Self> {
+                if rhs == 0 {
+                    return Err(ArrowError::DivideByZero);
+                }
+                self.checked_rem(rhs)
+                    .ok_or_else(|| overflow_error(self, "%", rhs))
+            }
+
+            fn mod_wrapping(self, rhs: Self) -> Self {
+                self.wrapping_rem(rhs)
+            }
+
+            fn neg_checked(self) -> Result<Self> {
+                self.checked_neg().ok_or_else(|| {
+                    ArrowError::ComputeError(format!("Overflow happened on: -{:?}", self))
+                })
+            }
+
+            fn neg_wrapping(self) -> Self {
+                self.wrapping_neg()
+            }
+        }
+    };
+}
+
+native_type_op!(i8);
+native_type_op!(i16);
+native_type_op!(i32);
+native_type_op!(i64);
+native_type_op!(i128);
+native_type_op!(u8);
+native_type_op!(u16);
+native_type_op!(u32);
+native_type_op!(u64);
+
+macro_rules! native_type_float_op {
+    ($t:ty, $zero:expr, $one:expr) => {
+        impl ArrowNativeTypeOp for $t {
+            const ZERO: Self = $zero;
+            const ONE: Self = $one;
+
+            fn add_checked(self, rhs: Self) -> Result<Self> {
+                Ok(self + rhs)
+            }
+
+            fn add_wrapping(self, rhs: Self) -> Self {
+                self + rhs
+            }
+
+            fn sub_checked(self, rhs: Self) -> Result<Self> {
+                Ok(self - rhs)
+            }
+
+            fn sub_wrapping(self, rhs: Self) -> Self {
+                self - rhs
+            }
+
+            fn mul_checked(self, rhs: Self) -> Result<Self> {
+                Ok(self * rhs)
+            }
+
+            fn mul_wrapping(self, rhs: Self) -> Self {
+                self * rhs
+            }
+
+            fn div_checked(self, rhs: Self) -> Result<Self> {
+                if rhs.is_zero() {
+                    return Err(ArrowError::DivideByZero);
+                }
+                Ok(self / rhs)
+            }
+
+            fn div_wrapping(self, rhs: Self) -> Self {
+                self / rhs
+            }
+
+            fn mod_checked(self, rhs: Self) -> Result<Self> {
+                if rhs.is_zero() {
+                    return Err(ArrowError::DivideByZero);
+                }
+                Ok(self % rhs)
+            }
+
+            fn mod_wrapping(self, rhs: Self) -> Self {
+                self % rhs
+            }
+
+            fn neg_checked(self) -> Result<Self> {
+                Ok(-self)
+            }
+
+            fn neg_wrapping(self) -> Self {
+                -self
+            }
+        }
+    };
+}
+
+native_type_float_op!(f16, f16::ZERO, f16::ONE);
+native_type_float_op!(f32, 0., 1.);
+native_type_float_op!(f64, 0., 1.);
+
 /// Allows conversion from supported Arrow types to a byte slice.
 pub trait ToByteSlice {
     /// Converts this instance into a byte slice
@@ -124,6 +124,34 @@ make_type!(
     DataType::Duration(TimeUnit::Nanosecond)
 );
 
+impl IntervalDayTimeType {
+    /// Creates a `IntervalDayTimeType::Native` from a number of days and milliseconds
+    pub fn make_value(days: i32, millis: i32) -> i64 {
+        (i64::from(days) << 32) | i64::from(millis as u32)
+    }
+
+    /// Returns the number of days and milliseconds of a `IntervalDayTimeType::Native`
+    pub fn to_parts(value: i64) -> (i32, i32) {
+        ((value >> 32) as i32, value as i32)
+    }
+}
+
+impl IntervalMonthDayNanoType {
+    /// Creates a `IntervalMonthDayNanoType::Native` from a number of months, days
+    /// and nanoseconds
+    pub fn make_value(months: i32, days: i32, nanos: i64) -> i128 {
+        (i128::from(months) << 96)
+            | (i128::from(days as u32) << 64)
+            | i128::from(nanos as u64)
+    }
+
+    /// Returns the number of months, days and nanoseconds of a
+    /// `IntervalMonthDayNanoType::Native`
+    pub fn to_parts(value: i128) -> (i32, i32, i64) {
+        ((value >> 96) as i32, (value >> 64) as i32, value as i64)
+    }
+}
+
 /// A subtype of primitive type that represents legal dictionary keys.
 /// See <https://arrow.apache.org/docs/format/Columnar.html>
 pub trait ArrowDictionaryKeyType: ArrowPrimitiveType {}
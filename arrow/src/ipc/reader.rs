@@ -26,7 +26,7 @@ use std::sync::Arc;
 
 use crate::array::*;
 use crate::buffer::Buffer;
-use crate::compute::cast;
+use crate::compute::{cast, concat};
 use crate::datatypes::{DataType, Field, IntervalUnit, Schema, SchemaRef, UnionMode};
 use crate::error::{ArrowError, Result};
 use crate::ipc;
@@ -683,12 +683,6 @@ pub fn read_dictionary(
     dictionaries_by_id: &mut HashMap<i64, ArrayRef>,
     metadata: &ipc::MetadataVersion,
 ) -> Result<()> {
-    if batch.isDelta() {
-        return Err(ArrowError::IoError(
-            "delta dictionary batches not supported".to_string(),
-        ));
-    }
-
     let id = batch.id();
     let fields_using_this_dictionary = schema.fields_with_dict_id(id);
     let first_field = fields_using_this_dictionary.first().ok_or_else(|| {
@@ -722,10 +716,24 @@ pub fn read_dictionary(
         ArrowError::InvalidArgumentError("dictionary id not found in schema".to_string())
     })?;
 
+    // A delta dictionary batch appends its values to the existing dictionary
+    let dictionary_values = match batch.isDelta() {
+        true => {
+            let existing = dictionaries_by_id.get(&id).ok_or_else(|| {
+                ArrowError::InvalidArgumentError(format!(
+                    "delta dictionary batch for dictionary id {} without an existing dictionary",
+                    id
+                ))
+            })?;
+            concat(&[existing.as_ref(), dictionary_values.as_ref()])?
+        }
+        false => dictionary_values,
+    };
+
     // We don't currently record the isOrdered field. This could be general
     // attributes of arrays.
     // Add (possibly multiple) array refs to the dictionaries array.
-    dictionaries_by_id.insert(id, dictionary_values.clone());
+    dictionaries_by_id.insert(id, dictionary_values);
 
     Ok(())
 }
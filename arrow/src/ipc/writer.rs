@@ -20,8 +20,10 @@
 //! The `FileWriter` and `StreamWriter` have similar interfaces,
 //! however the `FileWriter` expects a reader that supports `Seek`ing
 
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::io::{BufWriter, Write};
+use std::sync::Arc;
 
 use flatbuffers::FlatBufferBuilder;
 
@@ -30,10 +32,13 @@ use crate::array::{
     make_array, Array, ArrayData, ArrayRef, FixedSizeListArray,
 };
 use crate::buffer::{Buffer, MutableBuffer};
+use crate::compute::concat;
+use crate::compute::kernels::dictionary::{remap_dictionary, DictionaryUnifier};
 use crate::datatypes::*;
 use crate::error::{ArrowError, Result};
 use crate::ipc;
 use crate::record_batch::RecordBatch;
+use crate::row::RowConverter;
 use crate::util::bit_util;
 
 use ipc::CONTINUATION_MARKER;
@@ -274,6 +279,7 @@ impl IpcDataGenerator {
                     encoded_dictionaries.push(self.dictionary_batch_to_bytes(
                         dict_id,
                         dict_values,
+                        false,
                         write_options,
                     ));
                 }
@@ -297,18 +303,52 @@ impl IpcDataGenerator {
     ) -> Result<(Vec<EncodedData>, EncodedData)> {
         let schema = batch.schema();
         let mut encoded_dictionaries = Vec::with_capacity(schema.all_fields().len());
+        let mut columns = Vec::with_capacity(batch.num_columns());
+        let mut remapped = false;
 
         for (i, field) in schema.fields().iter().enumerate() {
             let column = batch.column(i);
-            self.encode_dictionaries(
-                field,
-                column,
-                &mut encoded_dictionaries,
-                dictionary_tracker,
-                write_options,
-            )?;
+            match (column.data_type(), field.dict_id()) {
+                // Dictionaries that cannot be replaced are merged into a single
+                // dictionary, written as deltas
+                (DataType::Dictionary(_, value_type), Some(dict_id))
+                    if dictionary_tracker.error_on_replacement
+                        && RowConverter::supports_datatype(value_type) =>
+                {
+                    let (column, values) =
+                        dictionary_tracker.insert_unified(dict_id, column)?;
+                    if let Some((values, is_delta)) = values {
+                        encoded_dictionaries.push(self.dictionary_batch_to_bytes(
+                            dict_id,
+                            values.data(),
+                            is_delta,
+                            write_options,
+                        ));
+                    }
+                    remapped |= !Arc::ptr_eq(&column, batch.column(i));
+                    columns.push(column);
+                }
+                _ => {
+                    self.encode_dictionaries(
+                        field,
+                        column,
+                        &mut encoded_dictionaries,
+                        dictionary_tracker,
+                        write_options,
+                    )?;
+                    columns.push(column.clone());
+                }
+            }
         }
 
+        let unified;
+        let batch = match remapped {
+            true => {
+                unified = RecordBatch::try_new(schema, columns)?;
+                &unified
+            }
+            false => batch,
+        };
         let encoded_message = self.record_batch_to_bytes(batch, write_options);
         Ok((encoded_dictionaries, encoded_message))
     }
@@ -374,6 +414,7 @@ impl IpcDataGenerator {
         &self,
         dict_id: i64,
         array_data: &ArrayData,
+        is_delta: bool,
         write_options: &IpcWriteOptions,
     ) -> EncodedData {
         let mut fbb = FlatBufferBuilder::new();
@@ -409,6 +450,7 @@ impl IpcDataGenerator {
             let mut batch_builder = ipc::DictionaryBatchBuilder::new(&mut fbb);
             batch_builder.add_id(dict_id);
             batch_builder.add_data(root);
+            batch_builder.add_isDelta(is_delta);
             batch_builder.finish().as_union_value()
         };
 
@@ -434,8 +476,13 @@ impl IpcDataGenerator {
 /// Keeps track of dictionaries that have been written, to avoid emitting the same dictionary
 /// multiple times. Can optionally error if an update to an existing dictionary is attempted, which
 /// isn't allowed in the `FileWriter`.
+///
+/// When configured to error on replacement, dictionaries whose values are supported by the
+/// [row format](crate::row) are instead merged with a [`DictionaryUnifier`], and written as
+/// delta dictionaries containing only the new values.
 pub struct DictionaryTracker {
     written: HashMap<i64, ArrayRef>,
+    unifiers: HashMap<i64, UnifiedDictionary>,
     error_on_replacement: bool,
 }
 
@@ -443,6 +490,7 @@ impl DictionaryTracker {
     pub fn new(error_on_replacement: bool) -> Self {
         Self {
             written: HashMap::new(),
+            unifiers: HashMap::new(),
             error_on_replacement,
         }
     }
@@ -478,6 +526,102 @@ impl DictionaryTracker {
         self.written.insert(dict_id, column.clone());
         Ok(true)
     }
+
+    /// Keep track of the dictionary with the given ID, merging its values with the values
+    /// written previously. Returns `column` with its keys remapped to the merged dictionary,
+    /// and the dictionary values to emit, if any, along with whether they are a delta to be
+    /// appended to the previously emitted values.
+    fn insert_unified(
+        &mut self,
+        dict_id: i64,
+        column: &ArrayRef,
+    ) -> Result<(ArrayRef, Option<(ArrayRef, bool)>)> {
+        let values = make_array(column.data().child_data()[0].clone());
+
+        let last = match self.written.get(&dict_id) {
+            Some(last) => last,
+            None => {
+                self.written.insert(dict_id, column.clone());
+                return Ok((column.clone(), Some((values, false))));
+            }
+        };
+        if last.data().child_data()[0] == column.data().child_data()[0] {
+            // Same dictionary values => no need to emit it again
+            return Ok((column.clone(), None));
+        }
+
+        let unified = match self.unifiers.entry(dict_id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                // The values written so far are the start of the merged dictionary
+                let written = make_array(last.data().child_data()[0].clone());
+                let mut unifier =
+                    DictionaryUnifier::try_new(written.data_type().clone())?;
+                let mapping = unifier.push(&written)?;
+                // The written values may contain duplicates, in which case a key
+                // refers to the first occurrence of its value
+                let mut indices = Vec::with_capacity(unifier.len());
+                for (idx, key) in mapping.into_iter().enumerate() {
+                    if key == indices.len() {
+                        indices.push(idx);
+                    }
+                }
+                let duplicated =
+                    (indices.len() < written.len()).then(|| (written, indices.len()));
+                entry.insert(UnifiedDictionary {
+                    unifier,
+                    indices,
+                    duplicated,
+                })
+            }
+        };
+
+        let len = unified.unifier.len();
+        let mapping = unified.unifier.push(&values)?;
+        let delta = match unified.unifier.len() > len {
+            true => {
+                // Values added since are appended to the emitted dictionary
+                let offset = match &unified.duplicated {
+                    Some((written, distinct)) => written.len() - distinct,
+                    None => 0,
+                };
+                let new_len = unified.unifier.len();
+                unified
+                    .indices
+                    .extend((len..new_len).map(|key| key + offset));
+                Some((unified.unifier.values_since(len)?, true))
+            }
+            false => None,
+        };
+
+        let mapping: Vec<_> = mapping.iter().map(|key| unified.indices[*key]).collect();
+        let column = remap_dictionary(column.as_ref(), &mapping, &unified.values()?)?;
+        self.written.insert(dict_id, column.clone());
+        Ok((column, delta))
+    }
+}
+
+/// A dictionary merged across batches by [`DictionaryTracker::insert_unified`]
+struct UnifiedDictionary {
+    unifier: DictionaryUnifier,
+    /// The index in the emitted dictionary of the value of each key of `unifier`
+    indices: Vec<usize>,
+    /// The first dictionary emitted and its number of distinct values, if it contains
+    /// duplicate values, and so is not the start of the values of `unifier`
+    duplicated: Option<(ArrayRef, usize)>,
+}
+
+impl UnifiedDictionary {
+    /// Returns the values of the emitted dictionary, including any delta dictionaries
+    fn values(&self) -> Result<ArrayRef> {
+        match &self.duplicated {
+            Some((written, distinct)) => {
+                let since = self.unifier.values_since(*distinct)?;
+                concat(&[written.as_ref(), since.as_ref()])
+            }
+            None => self.unifier.values(),
+        }
+    }
 }
 
 pub struct FileWriter<W: Write> {
@@ -1498,6 +1642,91 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_write_file_unified_dictionaries() {
+        let a: DictionaryArray<Int32Type> = vec!["a", "b", "a"].into_iter().collect();
+        let b: DictionaryArray<Int32Type> =
+            vec![Some("c"), None, Some("a")].into_iter().collect();
+        let c: DictionaryArray<Int32Type> = vec!["b"].into_iter().collect();
+        let field = Field::new_dict("dict", a.data_type().clone(), true, 1, false);
+        let schema = Arc::new(Schema::new(vec![field]));
+        let batches: Vec<_> = [a, b, c]
+            .into_iter()
+            .map(|array| {
+                RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap()
+            })
+            .collect();
+
+        let mut writer = FileWriter::try_new(vec![], &schema).unwrap();
+        for batch in &batches {
+            writer.write(batch).unwrap();
+        }
+        writer.finish().unwrap();
+        // the second batch adds a delta dictionary, the third batch none
+        assert_eq!(writer.dictionary_blocks.len(), 2);
+        let data = writer.into_inner().unwrap();
+
+        let reader = FileReader::try_new(std::io::Cursor::new(data), None).unwrap();
+        let read: Vec<_> = reader.map(|batch| batch.unwrap()).collect();
+        assert_eq!(read.len(), 3);
+        for (read, batch) in read.iter().zip(&batches) {
+            let read = as_dictionary_array::<Int32Type>(read.column(0));
+            let values = as_string_array(read.values());
+            let expected = as_dictionary_array::<Int32Type>(batch.column(0));
+            let expected_values = as_string_array(expected.values());
+            let read: Vec<_> = read
+                .keys()
+                .iter()
+                .map(|key| key.map(|key| values.value(key as usize)))
+                .collect();
+            let expected: Vec<_> = expected
+                .keys()
+                .iter()
+                .map(|key| key.map(|key| expected_values.value(key as usize)))
+                .collect();
+            assert_eq!(read, expected);
+        }
+    }
+
+    #[test]
+    fn test_write_file_unified_duplicate_dictionary() {
+        // the first dictionary contains the value "a" twice
+        let keys = Int32Array::from(vec![0, 1]);
+        let values = Arc::new(StringArray::from(vec!["a", "a"])) as ArrayRef;
+        let a = DictionaryArray::<Int32Type>::try_new(&keys, &values).unwrap();
+        let b: DictionaryArray<Int32Type> = vec!["b", "a"].into_iter().collect();
+        let c: DictionaryArray<Int32Type> = vec!["c", "b", "a"].into_iter().collect();
+        let field = Field::new_dict("dict", a.data_type().clone(), true, 1, false);
+        let schema = Arc::new(Schema::new(vec![field]));
+
+        let mut writer = FileWriter::try_new(vec![], &schema).unwrap();
+        for array in [a, b, c] {
+            let batch =
+                RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+            writer.write(&batch).unwrap();
+        }
+        writer.finish().unwrap();
+        let data = writer.into_inner().unwrap();
+
+        let reader = FileReader::try_new(std::io::Cursor::new(data), None).unwrap();
+        let read: Vec<_> = reader
+            .map(|batch| {
+                let batch = batch.unwrap();
+                let array = as_dictionary_array::<Int32Type>(batch.column(0));
+                let values = as_string_array(array.values());
+                array
+                    .keys()
+                    .iter()
+                    .map(|key| values.value(key.unwrap() as usize).to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(
+            read,
+            vec![vec!["a", "a"], vec!["b", "a"], vec!["c", "b", "a"]]
+        );
+    }
+
     #[test]
     fn test_write_union_file_v4_v5() {
         write_union_file(
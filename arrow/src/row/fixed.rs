@@ -0,0 +1,322 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Encoding of fixed width types (primitives, booleans and fixed size binary)
+
+use crate::array::{
+    Array, ArrayData, ArrayDataBuilder, BooleanBufferBuilder, FixedSizeBinaryArray,
+};
+use crate::buffer::MutableBuffer;
+use crate::compute::SortOptions;
+use crate::datatypes::{ArrowNativeType, DataType};
+use crate::row::{null_sentinel, Rows};
+use half::f16;
+
+/// Encodes a value of a particular fixed width type into bytes according to the rules
+/// described on [`super::RowConverter`]
+pub trait FixedLengthEncoding: Copy {
+    /// The number of bytes a value of this type occupies in a row, including the
+    /// leading null sentinel
+    const ENCODED_LEN: usize = 1 + std::mem::size_of::<Self::Encoded>();
+
+    type Encoded: Sized + Copy + Default + AsRef<[u8]> + AsMut<[u8]>;
+
+    fn encode(self) -> Self::Encoded;
+
+    fn decode(encoded: Self::Encoded) -> Self;
+}
+
+impl FixedLengthEncoding for bool {
+    type Encoded = [u8; 1];
+
+    fn encode(self) -> [u8; 1] {
+        [self as u8]
+    }
+
+    fn decode(encoded: Self::Encoded) -> Self {
+        encoded[0] != 0
+    }
+}
+
+macro_rules! encode_signed {
+    ($n:expr, $t:ty) => {
+        impl FixedLengthEncoding for $t {
+            type Encoded = [u8; $n];
+
+            fn encode(self) -> [u8; $n] {
+                let mut b = self.to_be_bytes();
+                // Toggle top "sign" bit to ensure consistent sort order
+                b[0] ^= 0x80;
+                b
+            }
+
+            fn decode(mut encoded: Self::Encoded) -> Self {
+                // Toggle top "sign" bit
+                encoded[0] ^= 0x80;
+                Self::from_be_bytes(encoded)
+            }
+        }
+    };
+}
+
+encode_signed!(1, i8);
+encode_signed!(2, i16);
+encode_signed!(4, i32);
+encode_signed!(8, i64);
+encode_signed!(16, i128);
+
+macro_rules! encode_unsigned {
+    ($n:expr, $t:ty) => {
+        impl FixedLengthEncoding for $t {
+            type Encoded = [u8; $n];
+
+            fn encode(self) -> [u8; $n] {
+                self.to_be_bytes()
+            }
+
+            fn decode(encoded: Self::Encoded) -> Self {
+                Self::from_be_bytes(encoded)
+            }
+        }
+    };
+}
+
+encode_unsigned!(1, u8);
+encode_unsigned!(2, u16);
+encode_unsigned!(4, u32);
+encode_unsigned!(8, u64);
+
+// Floats are encoded by flipping all the bits of negative values and the sign bit of
+// positive values, this yields the IEEE 754 totalOrder when compared as signed integers
+macro_rules! encode_float {
+    ($n:expr, $t:ty, $bits:ty, $ubits:ty) => {
+        impl FixedLengthEncoding for $t {
+            type Encoded = [u8; $n];
+
+            fn encode(self) -> [u8; $n] {
+                let s = self.to_bits() as $bits;
+                let val = s ^ (((s >> (<$bits>::BITS - 1)) as $ubits) >> 1) as $bits;
+                val.encode()
+            }
+
+            fn decode(encoded: Self::Encoded) -> Self {
+                let bits = <$bits>::decode(encoded);
+                let val =
+                    bits ^ (((bits >> (<$bits>::BITS - 1)) as $ubits) >> 1) as $bits;
+                Self::from_bits(val as $ubits)
+            }
+        }
+    };
+}
+
+encode_float!(2, f16, i16, u16);
+encode_float!(4, f32, i32, u32);
+encode_float!(8, f64, i64, u64);
+
+/// Fixed width types are encoded as
+///
+/// - 1 byte `0` if null or non-zero if valid
+/// - bytes of [`FixedLengthEncoding`]
+pub fn encode<T: FixedLengthEncoding, I: IntoIterator<Item = Option<T>>>(
+    out: &mut Rows,
+    i: I,
+    opts: SortOptions,
+) {
+    for (offset, maybe_val) in out.offsets.iter_mut().skip(1).zip(i) {
+        let end_offset = *offset + T::ENCODED_LEN;
+        if let Some(val) = maybe_val {
+            let to_write = &mut out.buffer[*offset..end_offset];
+            to_write[0] = 1;
+            let mut encoded = val.encode();
+            if opts.descending {
+                // Flip bits to reverse order
+                encoded.as_mut().iter_mut().for_each(|v| *v = !*v)
+            }
+            to_write[1..].copy_from_slice(encoded.as_ref())
+        } else {
+            out.buffer[*offset] = null_sentinel(opts);
+        }
+        *offset = end_offset;
+    }
+}
+
+/// Encodes the values of a primitive `ArrayData` whose native type is `T`
+pub fn encode_primitive<T>(out: &mut Rows, data: &ArrayData, opts: SortOptions)
+where
+    T: ArrowNativeType + FixedLengthEncoding,
+{
+    let values = &data.buffers()[0].typed_data::<T>()[data.offset()..][..data.len()];
+    let iter = values
+        .iter()
+        .enumerate()
+        .map(|(idx, v)| data.is_valid(idx).then(|| *v));
+    encode(out, iter, opts)
+}
+
+/// Encodes the values of a `FixedSizeBinaryArray` as
+///
+/// - 1 byte `0` if null or `1` if valid
+/// - the bytes of the value, or zeros if null
+pub fn encode_fixed_size_binary(
+    out: &mut Rows,
+    array: &FixedSizeBinaryArray,
+    opts: SortOptions,
+) {
+    let value_len = array.value_length() as usize;
+    for (idx, offset) in out.offsets.iter_mut().skip(1).enumerate() {
+        let end_offset = *offset + 1 + value_len;
+        if array.is_valid(idx) {
+            let to_write = &mut out.buffer[*offset..end_offset];
+            to_write[0] = 1;
+            to_write[1..].copy_from_slice(array.value(idx));
+            if opts.descending {
+                // Flip bits to reverse order
+                to_write[1..].iter_mut().for_each(|v| *v = !*v)
+            }
+        } else {
+            out.buffer[*offset] = null_sentinel(opts);
+        }
+        *offset = end_offset;
+    }
+}
+
+/// Decodes a single value from the start of `row`, advancing `row` past it
+fn decode_value<T: FixedLengthEncoding>(
+    row: &mut &[u8],
+    options: SortOptions,
+) -> Option<T> {
+    let valid = row[0] == 1;
+    let value = valid.then(|| {
+        let mut encoded = T::Encoded::default();
+        encoded.as_mut().copy_from_slice(&row[1..T::ENCODED_LEN]);
+        if options.descending {
+            encoded.as_mut().iter_mut().for_each(|v| *v = !*v)
+        }
+        T::decode(encoded)
+    });
+    *row = &row[T::ENCODED_LEN..];
+    value
+}
+
+/// Decodes a `BooleanArray` from rows
+pub fn decode_bool(rows: &mut [&[u8]], options: SortOptions) -> ArrayData {
+    let len = rows.len();
+    let mut nulls = BooleanBufferBuilder::new(len);
+    let mut values = BooleanBufferBuilder::new(len);
+    let mut null_count = 0;
+
+    for row in rows {
+        match decode_value::<bool>(row, options) {
+            Some(v) => {
+                nulls.append(true);
+                values.append(v);
+            }
+            None => {
+                null_count += 1;
+                nulls.append(false);
+                values.append(false);
+            }
+        }
+    }
+
+    let builder = ArrayDataBuilder::new(DataType::Boolean)
+        .len(len)
+        .null_count(null_count)
+        .add_buffer(values.finish())
+        .null_bit_buffer(Some(nulls.finish()));
+
+    // SAFETY: Buffers correct length
+    unsafe { builder.build_unchecked() }
+}
+
+/// Decodes a primitive array of `data_type` whose native type is `T` from rows
+pub fn decode_primitive<T>(
+    rows: &mut [&[u8]],
+    data_type: DataType,
+    options: SortOptions,
+) -> ArrayData
+where
+    T: ArrowNativeType + FixedLengthEncoding,
+{
+    let len = rows.len();
+    let mut nulls = BooleanBufferBuilder::new(len);
+    let mut values = MutableBuffer::new(std::mem::size_of::<T>() * len);
+    let mut null_count = 0;
+
+    for row in rows {
+        match decode_value::<T>(row, options) {
+            Some(v) => {
+                nulls.append(true);
+                values.push(v);
+            }
+            None => {
+                null_count += 1;
+                nulls.append(false);
+                values.push(T::default());
+            }
+        }
+    }
+
+    let builder = ArrayDataBuilder::new(data_type)
+        .len(len)
+        .null_count(null_count)
+        .add_buffer(values.into())
+        .null_bit_buffer(Some(nulls.finish()));
+
+    // SAFETY: Buffers correct length
+    unsafe { builder.build_unchecked() }
+}
+
+/// Decodes a `FixedSizeBinaryArray` with values of `size` bytes from rows
+pub fn decode_fixed_size_binary(
+    rows: &mut [&[u8]],
+    size: i32,
+    options: SortOptions,
+) -> ArrayData {
+    let len = rows.len();
+    let value_len = size as usize;
+    let mut nulls = BooleanBufferBuilder::new(len);
+    let mut values = MutableBuffer::new(value_len * len);
+    let mut null_count = 0;
+
+    for row in rows {
+        let valid = row[0] == 1;
+        if valid {
+            let start = values.len();
+            values.extend_from_slice(&row[1..1 + value_len]);
+            if options.descending {
+                values.as_slice_mut()[start..]
+                    .iter_mut()
+                    .for_each(|v| *v = !*v)
+            }
+        } else {
+            null_count += 1;
+            values.extend_zeros(value_len);
+        }
+        nulls.append(valid);
+        *row = &row[1 + value_len..];
+    }
+
+    let builder = ArrayDataBuilder::new(DataType::FixedSizeBinary(size))
+        .len(len)
+        .null_count(null_count)
+        .add_buffer(values.into())
+        .null_bit_buffer(Some(nulls.finish()));
+
+    // SAFETY: Buffers correct length
+    unsafe { builder.build_unchecked() }
+}
@@ -0,0 +1,1417 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A comparable row-oriented representation of a collection of [`Array`]
+//!
+//! As [`Row`] are [normalized for sorting], they can be very efficiently [compared](PartialOrd),
+//! using [`memcmp`] under the hood, or used in [non-comparison sorts] such as [radix sort]. This
+//! makes the row format ideal for implementing efficient multi-column sorting,
+//! grouping, aggregation, windowing and more.
+//!
+//! _Comparing [`Rows`] generated by different [`RowConverter`] is not guaranteed to
+//! yield a meaningful ordering_
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use arrow::row::{RowConverter, SortField};
+//! # use arrow::array::{ArrayRef, Int32Array, StringArray};
+//! # use arrow::array::{as_primitive_array, as_string_array};
+//! # use arrow::datatypes::{DataType, Int32Type};
+//! #
+//! let a1 = Arc::new(Int32Array::from_iter_values([-1, -1, 0, 3, 3])) as ArrayRef;
+//! let a2 = Arc::new(StringArray::from_iter_values(["a", "b", "c", "d", "d"])) as ArrayRef;
+//!
+//! // Convert arrays to rows
+//! let converter = RowConverter::try_new(vec![
+//!     SortField::new(DataType::Int32),
+//!     SortField::new(DataType::Utf8),
+//! ]).unwrap();
+//! let rows = converter.convert_columns(&[a1, a2]).unwrap();
+//!
+//! // Compare rows
+//! for i in 0..4 {
+//!     assert!(rows.row(i) <= rows.row(i + 1));
+//! }
+//! assert_eq!(rows.row(3), rows.row(4));
+//!
+//! // Convert rows back to arrays
+//! let converted = converter.convert_rows(rows.iter()).unwrap();
+//! let c1 = as_primitive_array::<Int32Type>(converted[0].as_ref());
+//! assert_eq!(c1.values(), &[-1, -1, 0, 3, 3]);
+//!
+//! let c2 = as_string_array(converted[1].as_ref());
+//! let c2_values: Vec<_> = c2.iter().flatten().collect();
+//! assert_eq!(&c2_values, &["a", "b", "c", "d", "d"]);
+//! ```
+//!
+//! # Lexicographic Sorts (lexsort)
+//!
+//! The row format can also be used to implement a fast multi-column / lexicographic sort
+//!
+//! ```
+//! # use arrow::row::{RowConverter, SortField};
+//! # use arrow::array::{ArrayRef, UInt32Array};
+//! # use arrow::compute::{SortColumn, SortOptions};
+//! fn lexsort_to_indices(arrays: &[ArrayRef]) -> UInt32Array {
+//!     let fields = arrays
+//!         .iter()
+//!         .map(|a| SortField::new(a.data_type().clone()))
+//!         .collect();
+//!     let converter = RowConverter::try_new(fields).unwrap();
+//!     let rows = converter.convert_columns(&arrays).unwrap();
+//!     let mut sort: Vec<_> = rows.iter().enumerate().collect();
+//!     sort.sort_unstable_by(|(_, a), (_, b)| a.cmp(b));
+//!     UInt32Array::from_iter_values(sort.iter().map(|(i, _)| *i as u32))
+//! }
+//! ```
+//!
+//! # Format
+//!
+//! Each column is encoded in turn and the encodings are concatenated:
+//!
+//! * Fixed width types (integers, floats, booleans, temporal types and decimals) are
+//!   written as a null byte followed by a big endian representation of the value, with
+//!   the sign bit of signed integers flipped and floats transformed to order by
+//!   IEEE 754 totalOrder. Null values are zero padded to the same width.
+//! * Fixed size binary is written as a null byte followed by the bytes of the value,
+//!   null values are zero padded to the same width.
+//! * Variable length types (strings and binary) are written as a null byte, followed
+//!   by the data split into fixed size blocks, each terminated by a continuation byte.
+//! * Dictionaries are encoded using the encoding of the corresponding value, the
+//!   output therefore doesn't depend on the dictionary keys.
+//!
+//! A null is encoded as `0` if [`SortOptions::nulls_first`] is `true`, and `0xFF` otherwise.
+//! If [`SortOptions::descending`] is `true` the bytes of valid values are inverted.
+//!
+//! [non-comparison sorts]:[https://en.wikipedia.org/wiki/Sorting_algorithm#Non-comparison_sorts]
+//! [radix sort]:[https://en.wikipedia.org/wiki/Radix_sort]
+//! [normalized for sorting]:[https://citeseerx.ist.psu.edu/viewdoc/download?doi=10.1.1.83.1080&rep=rep1&type=pdf]
+//! [`memcmp`]:[https://www.man7.org/linux/man-pages/man3/memcmp.3.html]
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use crate::array::*;
+use crate::compute::{take, SortOptions};
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+use crate::row::fixed::FixedLengthEncoding;
+
+mod fixed;
+mod variable;
+
+/// Invokes the generic function `$f` with the native type of the primitive
+/// [`DataType`] `$dt`, evaluating to `$fallback` if `$dt` is not primitive
+macro_rules! downcast_native {
+    ($dt:expr, $($f:ident)::+ ($($args:expr),*), $fallback:expr) => {
+        match $dt {
+            DataType::Int8 => $($f)::+::<i8>($($args),*),
+            DataType::Int16 => $($f)::+::<i16>($($args),*),
+            DataType::Int32
+            | DataType::Date32
+            | DataType::Time32(_)
+            | DataType::Interval(IntervalUnit::YearMonth) => $($f)::+::<i32>($($args),*),
+            DataType::Int64
+            | DataType::Date64
+            | DataType::Time64(_)
+            | DataType::Timestamp(_, _)
+            | DataType::Duration(_)
+            | DataType::Interval(IntervalUnit::DayTime) => $($f)::+::<i64>($($args),*),
+            DataType::UInt8 => $($f)::+::<u8>($($args),*),
+            DataType::UInt16 => $($f)::+::<u16>($($args),*),
+            DataType::UInt32 => $($f)::+::<u32>($($args),*),
+            DataType::UInt64 => $($f)::+::<u64>($($args),*),
+            DataType::Float16 => $($f)::+::<half::f16>($($args),*),
+            DataType::Float32 => $($f)::+::<f32>($($args),*),
+            DataType::Float64 => $($f)::+::<f64>($($args),*),
+            DataType::Decimal(_, _)
+            | DataType::Interval(IntervalUnit::MonthDayNano) => $($f)::+::<i128>($($args),*),
+            _ => $fallback,
+        }
+    };
+}
+
+/// Converts [`ArrayRef`] columns into a row-oriented format that can be compared
+/// using [`memcmp`](https://www.man7.org/linux/man-pages/man3/memcmp.3.html), and
+/// converted back into [`ArrayRef`] columns
+///
+/// See the [module level documentation](self) for more information
+#[derive(Debug)]
+pub struct RowConverter {
+    fields: Arc<[SortField]>,
+}
+
+/// Configure the data type and sort order for a given column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortField {
+    /// Sort options
+    options: SortOptions,
+    /// Data type
+    data_type: DataType,
+}
+
+impl SortField {
+    /// Create a new column with the given data type
+    pub fn new(data_type: DataType) -> Self {
+        Self::new_with_options(data_type, Default::default())
+    }
+
+    /// Create a new column with the given data type and [`SortOptions`]
+    pub fn new_with_options(data_type: DataType, options: SortOptions) -> Self {
+        Self { options, data_type }
+    }
+
+    /// Returns the data type of this column
+    pub fn data_type(&self) -> &DataType {
+        &self.data_type
+    }
+
+    /// Returns the [`SortOptions`] of this column
+    pub fn options(&self) -> SortOptions {
+        self.options
+    }
+}
+
+impl RowConverter {
+    /// Create a new [`RowConverter`] with the provided schema
+    ///
+    /// Returns an error if any of the provided data types is not supported
+    pub fn try_new(fields: Vec<SortField>) -> Result<Self> {
+        if let Some(f) = fields
+            .iter()
+            .find(|f| !Self::supports_datatype(&f.data_type))
+        {
+            return Err(ArrowError::NotYetImplemented(format!(
+                "Row format support not yet implemented for: {:?}",
+                f.data_type
+            )));
+        }
+        Ok(Self {
+            fields: fields.into(),
+        })
+    }
+
+    /// Returns `true` if the row format supports the provided [`DataType`]
+    pub fn supports_datatype(data_type: &DataType) -> bool {
+        match data_type {
+            DataType::Boolean
+            | DataType::Utf8
+            | DataType::LargeUtf8
+            | DataType::Binary
+            | DataType::LargeBinary => true,
+            DataType::Dictionary(key, value) => {
+                DataType::is_dictionary_key_type(key)
+                    && !matches!(value.as_ref(), DataType::Dictionary(_, _))
+                    && Self::supports_datatype(value)
+            }
+            dt => fixed_encoded_len(dt).is_some(),
+        }
+    }
+
+    /// Returns the fields of this [`RowConverter`]
+    pub fn fields(&self) -> &[SortField] {
+        &self.fields
+    }
+
+    /// Convert [`ArrayRef`] columns into [`Rows`]
+    ///
+    /// See [`Row`] for information on when [`Row`] can be compared
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number, lengths or types of `columns` do not match
+    /// the fields this [`RowConverter`] was created with
+    pub fn convert_columns(&self, columns: &[ArrayRef]) -> Result<Rows> {
+        if columns.len() != self.fields.len() {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Incorrect number of arrays provided to RowConverter, expected {} got {}",
+                self.fields.len(),
+                columns.len()
+            )));
+        }
+
+        let num_rows = columns.first().map(|x| x.len()).unwrap_or(0);
+        for (column, field) in columns.iter().zip(self.fields.iter()) {
+            if column.len() != num_rows {
+                return Err(ArrowError::InvalidArgumentError(
+                    "RowConverter columns have different row counts".to_string(),
+                ));
+            }
+            if column.data_type() != &field.data_type {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "RowConverter column schema mismatch, expected {:?} got {:?}",
+                    field.data_type,
+                    column.data_type()
+                )));
+            }
+        }
+
+        let encoders = columns
+            .iter()
+            .zip(self.fields.iter())
+            .map(|(column, field)| Encoder::try_new(column, field))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut rows = new_empty_rows(columns, &encoders, Arc::clone(&self.fields));
+
+        for ((column, field), encoder) in
+            columns.iter().zip(self.fields.iter()).zip(&encoders)
+        {
+            encode_column(&mut rows, column, field.options, encoder)
+        }
+
+        debug_assert_eq!(*rows.offsets.last().unwrap(), rows.buffer.len());
+        Ok(rows)
+    }
+
+    /// Convert [`Row`] back into [`ArrayRef`] columns
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the rows were not produced by this [`RowConverter`]
+    pub fn convert_rows<'a, I>(&self, rows: I) -> Result<Vec<ArrayRef>>
+    where
+        I: IntoIterator<Item = Row<'a>>,
+    {
+        let mut rows = rows
+            .into_iter()
+            .map(|row| match Arc::ptr_eq(row.fields, &self.fields) {
+                true => Ok(row.data),
+                false => Err(ArrowError::InvalidArgumentError(
+                    "rows were not produced by this RowConverter".to_string(),
+                )),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        self.fields
+            .iter()
+            .map(|field| {
+                // SAFETY
+                // We have validated that the rows came from this RowConverter
+                // and therefore must be valid
+                unsafe { decode_column(field, &mut rows) }
+            })
+            .collect()
+    }
+}
+
+/// A row-oriented representation of arrow data, that is normalized for comparison
+///
+/// See the [module level documentation](self) and [`RowConverter`] for more details
+#[derive(Debug)]
+pub struct Rows {
+    /// Underlying row bytes
+    buffer: Box<[u8]>,
+    /// Row `i` has data `&buffer[offsets[i]..offsets[i+1]]`
+    offsets: Box<[usize]>,
+    /// The schema for these rows
+    fields: Arc<[SortField]>,
+}
+
+impl Rows {
+    /// Returns the row at index `row`
+    pub fn row(&self, row: usize) -> Row<'_> {
+        let end = self.offsets[row + 1];
+        let start = self.offsets[row];
+        Row {
+            data: &self.buffer[start..end],
+            fields: &self.fields,
+        }
+    }
+
+    /// Returns the number of rows
+    pub fn num_rows(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Returns an iterator over the [`Row`] in this [`Rows`]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Row<'_>> + '_ {
+        (0..self.num_rows()).map(move |idx| self.row(idx))
+    }
+
+    /// Returns the total size of this [`Rows`] in bytes
+    pub fn size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.buffer.len()
+            + self.offsets.len() * std::mem::size_of::<usize>()
+    }
+}
+
+/// A comparable representation of a row
+///
+/// Two [`Row`] can be compared if they both belong to [`Rows`] returned by calls to
+/// [`RowConverter::convert_columns`] on the same [`RowConverter`]
+///
+/// Otherwise any ordering established by comparing the [`Row`] is arbitrary
+#[derive(Debug, Copy, Clone)]
+pub struct Row<'a> {
+    data: &'a [u8],
+    fields: &'a Arc<[SortField]>,
+}
+
+impl<'a> Row<'a> {
+    /// Create owned version of the row to detach it from the shared [`Rows`]
+    pub fn owned(&self) -> OwnedRow {
+        OwnedRow {
+            data: self.data.into(),
+            fields: Arc::clone(self.fields),
+        }
+    }
+}
+
+// Manually derive these as don't wish to include `fields`
+
+impl<'a> PartialEq for Row<'a> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.data.eq(other.data)
+    }
+}
+
+impl<'a> Eq for Row<'a> {}
+
+impl<'a> PartialOrd for Row<'a> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Ord for Row<'a> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.data.cmp(other.data)
+    }
+}
+
+impl<'a> Hash for Row<'a> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data.hash(state)
+    }
+}
+
+impl<'a> AsRef<[u8]> for Row<'a> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.data
+    }
+}
+
+/// Owned version of a [`Row`] that can be moved/cloned freely.
+///
+/// This contains the data for the one specific row (not the entire buffer of all rows).
+#[derive(Debug, Clone)]
+pub struct OwnedRow {
+    data: Box<[u8]>,
+    fields: Arc<[SortField]>,
+}
+
+impl OwnedRow {
+    /// Get borrowed [`Row`] from owned version.
+    ///
+    /// This is helpful if you want to compare an [`OwnedRow`] with a [`Row`].
+    pub fn row(&self) -> Row<'_> {
+        Row {
+            data: &self.data,
+            fields: &self.fields,
+        }
+    }
+}
+
+// Manually derive these as don't wish to include `fields`. Also we just want to use the same `Row` implementations here.
+
+impl PartialEq for OwnedRow {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.row().eq(&other.row())
+    }
+}
+
+impl Eq for OwnedRow {}
+
+impl PartialOrd for OwnedRow {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OwnedRow {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.row().cmp(&other.row())
+    }
+}
+
+impl Hash for OwnedRow {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.row().hash(state)
+    }
+}
+
+impl AsRef<[u8]> for OwnedRow {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// Returns the byte used to encode a null value with the given [`SortOptions`]
+#[inline]
+fn null_sentinel(options: SortOptions) -> u8 {
+    match options.nulls_first {
+        true => 0,
+        false => 0xFF,
+    }
+}
+
+fn native_encoded_len<T: FixedLengthEncoding>() -> Option<usize> {
+    Some(T::ENCODED_LEN)
+}
+
+/// Returns the encoded length of a value of the fixed width [`DataType`] `dt`,
+/// or `None` if `dt` is not a fixed width type
+fn fixed_encoded_len(dt: &DataType) -> Option<usize> {
+    match dt {
+        DataType::Boolean => Some(bool::ENCODED_LEN),
+        DataType::FixedSizeBinary(size) => Some(1 + *size as usize),
+        dt => downcast_native!(dt, native_encoded_len(), None),
+    }
+}
+
+/// Per-column state used while encoding a batch of rows
+enum Encoder {
+    /// The column can be encoded directly from its values
+    Stateless,
+    /// The column is a dictionary, with its values already encoded
+    Dictionary {
+        /// The encoded dictionary values
+        values: Rows,
+        /// The encoding of a null value
+        null: Box<[u8]>,
+        /// The dictionary key of each row
+        keys: Vec<Option<usize>>,
+    },
+}
+
+impl Encoder {
+    fn try_new(column: &ArrayRef, field: &SortField) -> Result<Self> {
+        match &field.data_type {
+            DataType::Dictionary(key_type, value_type) => {
+                let converter =
+                    RowConverter::try_new(vec![SortField::new_with_options(
+                        value_type.as_ref().clone(),
+                        field.options,
+                    )])?;
+                let values = dictionary_values(column, key_type);
+                let values = converter.convert_columns(&[values])?;
+                let null = converter
+                    .convert_columns(&[new_null_array(value_type, 1)])?
+                    .buffer;
+                let keys = dictionary_keys(column, key_type);
+                Ok(Self::Dictionary { values, null, keys })
+            }
+            _ => Ok(Self::Stateless),
+        }
+    }
+}
+
+/// Returns the values of the dictionary `column` with key type `key_type`
+fn dictionary_values(column: &ArrayRef, key_type: &DataType) -> ArrayRef {
+    match key_type {
+        DataType::Int8 => as_dictionary_array::<Int8Type>(column).values().clone(),
+        DataType::Int16 => as_dictionary_array::<Int16Type>(column).values().clone(),
+        DataType::Int32 => as_dictionary_array::<Int32Type>(column).values().clone(),
+        DataType::Int64 => as_dictionary_array::<Int64Type>(column).values().clone(),
+        DataType::UInt8 => as_dictionary_array::<UInt8Type>(column).values().clone(),
+        DataType::UInt16 => as_dictionary_array::<UInt16Type>(column).values().clone(),
+        DataType::UInt32 => as_dictionary_array::<UInt32Type>(column).values().clone(),
+        DataType::UInt64 => as_dictionary_array::<UInt64Type>(column).values().clone(),
+        _ => unreachable!("dictionary key type validated by RowConverter"),
+    }
+}
+
+/// Returns the keys of the dictionary `column` with key type `key_type`
+fn dictionary_keys(column: &ArrayRef, key_type: &DataType) -> Vec<Option<usize>> {
+    match key_type {
+        DataType::Int8 => as_dictionary_array::<Int8Type>(column)
+            .keys_iter()
+            .collect(),
+        DataType::Int16 => as_dictionary_array::<Int16Type>(column)
+            .keys_iter()
+            .collect(),
+        DataType::Int32 => as_dictionary_array::<Int32Type>(column)
+            .keys_iter()
+            .collect(),
+        DataType::Int64 => as_dictionary_array::<Int64Type>(column)
+            .keys_iter()
+            .collect(),
+        DataType::UInt8 => as_dictionary_array::<UInt8Type>(column)
+            .keys_iter()
+            .collect(),
+        DataType::UInt16 => as_dictionary_array::<UInt16Type>(column)
+            .keys_iter()
+            .collect(),
+        DataType::UInt32 => as_dictionary_array::<UInt32Type>(column)
+            .keys_iter()
+            .collect(),
+        DataType::UInt64 => as_dictionary_array::<UInt64Type>(column)
+            .keys_iter()
+            .collect(),
+        _ => unreachable!("dictionary key type validated by RowConverter"),
+    }
+}
+
+/// Computes the length of each encoded [`Rows`] and returns an empty [`Rows`]
+fn new_empty_rows(
+    cols: &[ArrayRef],
+    encoders: &[Encoder],
+    fields: Arc<[SortField]>,
+) -> Rows {
+    let num_rows = cols.first().map(|x| x.len()).unwrap_or(0);
+    let mut lengths = vec![0; num_rows];
+
+    for (array, encoder) in cols.iter().zip(encoders) {
+        match encoder {
+            Encoder::Stateless => match array.data_type() {
+                DataType::Utf8 => as_string_array(array)
+                    .iter()
+                    .zip(lengths.iter_mut())
+                    .for_each(|(slice, length)| {
+                        *length += variable::encoded_len(slice.map(|x| x.as_bytes()))
+                    }),
+                DataType::LargeUtf8 => as_largestring_array(array)
+                    .iter()
+                    .zip(lengths.iter_mut())
+                    .for_each(|(slice, length)| {
+                        *length += variable::encoded_len(slice.map(|x| x.as_bytes()))
+                    }),
+                DataType::Binary => as_generic_binary_array::<i32>(array)
+                    .iter()
+                    .zip(lengths.iter_mut())
+                    .for_each(|(slice, length)| *length += variable::encoded_len(slice)),
+                DataType::LargeBinary => as_generic_binary_array::<i64>(array)
+                    .iter()
+                    .zip(lengths.iter_mut())
+                    .for_each(|(slice, length)| *length += variable::encoded_len(slice)),
+                dt => {
+                    let len = fixed_encoded_len(dt).expect("fixed width type");
+                    lengths.iter_mut().for_each(|x| *x += len)
+                }
+            },
+            Encoder::Dictionary { values, null, keys } => {
+                keys.iter().zip(lengths.iter_mut()).for_each(|(k, length)| {
+                    *length += match k {
+                        Some(k) => values.row(*k).data.len(),
+                        None => null.len(),
+                    }
+                })
+            }
+        }
+    }
+
+    let mut offsets = Vec::with_capacity(num_rows + 1);
+    offsets.push(0);
+
+    // We initialize the offsets shifted down by one row index.
+    //
+    // As the rows are appended to the offsets will be incremented to match
+    //
+    // For example, consider the case of 3 rows of length 3, 4, and 6 respectively.
+    // The offsets would be initialized to `0, 0, 3, 7`
+    //
+    // Writing the first row entirely would yield `0, 3, 3, 7`
+    // The second, `0, 3, 7, 7`
+    // The third, `0, 3, 7, 13`
+    //
+    // This would be the final offsets for reading
+    //
+    // In this way offsets tracks the position during writing whilst eventually serving
+    // as identifying the offsets of the written rows
+    let mut cur_offset = 0_usize;
+    for l in lengths {
+        offsets.push(cur_offset);
+        cur_offset = cur_offset.checked_add(l).expect("overflow");
+    }
+
+    let buffer = vec![0_u8; cur_offset];
+
+    Rows {
+        buffer: buffer.into(),
+        offsets: offsets.into(),
+        fields,
+    }
+}
+
+/// Encodes a column to the provided [`Rows`] incrementing the offsets as it progresses
+fn encode_column(
+    out: &mut Rows,
+    column: &ArrayRef,
+    opts: SortOptions,
+    encoder: &Encoder,
+) {
+    match encoder {
+        Encoder::Stateless => match column.data_type() {
+            DataType::Boolean => fixed::encode(out, as_boolean_array(column), opts),
+            DataType::Utf8 => variable::encode(
+                out,
+                as_string_array(column)
+                    .iter()
+                    .map(|x| x.map(|x| x.as_bytes())),
+                opts,
+            ),
+            DataType::LargeUtf8 => variable::encode(
+                out,
+                as_largestring_array(column)
+                    .iter()
+                    .map(|x| x.map(|x| x.as_bytes())),
+                opts,
+            ),
+            DataType::Binary => {
+                variable::encode(out, as_generic_binary_array::<i32>(column).iter(), opts)
+            }
+            DataType::LargeBinary => {
+                variable::encode(out, as_generic_binary_array::<i64>(column).iter(), opts)
+            }
+            DataType::FixedSizeBinary(_) => {
+                let array = column
+                    .as_any()
+                    .downcast_ref::<FixedSizeBinaryArray>()
+                    .unwrap();
+                fixed::encode_fixed_size_binary(out, array, opts)
+            }
+            dt => downcast_native!(
+                dt,
+                fixed::encode_primitive(out, column.data(), opts),
+                unreachable!("unsupported data type {:?}", dt)
+            ),
+        },
+        Encoder::Dictionary { values, null, keys } => {
+            for (offset, k) in out.offsets.iter_mut().skip(1).zip(keys) {
+                let row = match k {
+                    Some(k) => values.row(*k).data,
+                    None => null.as_ref(),
+                };
+                let end_offset = *offset + row.len();
+                out.buffer[*offset..end_offset].copy_from_slice(row);
+                *offset = end_offset;
+            }
+        }
+    }
+}
+
+/// Decodes a the provided `field` from `rows`
+///
+/// # Safety
+///
+/// Rows must contain valid data for the provided field
+unsafe fn decode_column(field: &SortField, rows: &mut [&[u8]]) -> Result<ArrayRef> {
+    let options = field.options;
+    let data = match &field.data_type {
+        DataType::Boolean => fixed::decode_bool(rows, options),
+        DataType::Utf8 => variable::decode_binary::<i32>(rows, DataType::Utf8, options),
+        DataType::LargeUtf8 => {
+            variable::decode_binary::<i64>(rows, DataType::LargeUtf8, options)
+        }
+        DataType::Binary => {
+            variable::decode_binary::<i32>(rows, DataType::Binary, options)
+        }
+        DataType::LargeBinary => {
+            variable::decode_binary::<i64>(rows, DataType::LargeBinary, options)
+        }
+        DataType::FixedSizeBinary(size) => {
+            fixed::decode_fixed_size_binary(rows, *size, options)
+        }
+        DataType::Dictionary(key_type, value_type) => {
+            return decode_dictionary(key_type, value_type, rows, options)
+        }
+        dt => downcast_native!(
+            dt,
+            fixed::decode_primitive(rows, dt.clone(), options),
+            unreachable!("unsupported data type {:?}", dt)
+        ),
+    };
+    Ok(make_array(data))
+}
+
+/// Decodes a dictionary with key type `key_type` and value type `value_type`
+/// from `rows`, deduplicating the values by their encoded representation
+///
+/// # Safety
+///
+/// Rows must contain valid data for the provided dictionary
+unsafe fn decode_dictionary(
+    key_type: &DataType,
+    value_type: &DataType,
+    rows: &mut [&[u8]],
+    options: SortOptions,
+) -> Result<ArrayRef> {
+    let value_field = SortField::new_with_options(value_type.clone(), options);
+    let starts = rows.to_vec();
+    let values = decode_column(&value_field, rows)?;
+
+    let mut dedup: HashMap<&[u8], usize> = HashMap::new();
+    let mut unique = Vec::new();
+    let keys: Vec<Option<usize>> = starts
+        .iter()
+        .zip(rows.iter())
+        .enumerate()
+        .map(|(idx, (start, end))| {
+            if values.is_null(idx) {
+                return None;
+            }
+            let encoded = &start[..start.len() - end.len()];
+            Some(*dedup.entry(encoded).or_insert_with(|| {
+                unique.push(idx as u32);
+                unique.len() - 1
+            }))
+        })
+        .collect();
+
+    let values = take(values.as_ref(), &UInt32Array::from(unique), None)?;
+
+    match key_type {
+        DataType::Int8 => build_dictionary::<Int8Type>(&keys, values),
+        DataType::Int16 => build_dictionary::<Int16Type>(&keys, values),
+        DataType::Int32 => build_dictionary::<Int32Type>(&keys, values),
+        DataType::Int64 => build_dictionary::<Int64Type>(&keys, values),
+        DataType::UInt8 => build_dictionary::<UInt8Type>(&keys, values),
+        DataType::UInt16 => build_dictionary::<UInt16Type>(&keys, values),
+        DataType::UInt32 => build_dictionary::<UInt32Type>(&keys, values),
+        DataType::UInt64 => build_dictionary::<UInt64Type>(&keys, values),
+        _ => unreachable!("dictionary key type validated by RowConverter"),
+    }
+}
+
+fn build_dictionary<K: ArrowDictionaryKeyType>(
+    keys: &[Option<usize>],
+    values: ArrayRef,
+) -> Result<ArrayRef> {
+    let keys = keys
+        .iter()
+        .map(|k| {
+            k.map(|k| {
+                K::Native::from_usize(k).ok_or(ArrowError::DictionaryKeyOverflowError)
+            })
+            .transpose()
+        })
+        .collect::<Result<PrimitiveArray<K>>>()?;
+    Ok(Arc::new(DictionaryArray::try_new(&keys, values.as_ref())?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compute::SortColumn;
+    use crate::util::display::array_value_to_string;
+    use rand::distributions::uniform::SampleUniform;
+    use rand::distributions::{Distribution, Standard};
+    use rand::{thread_rng, Rng};
+
+    #[test]
+    fn test_fixed_width() {
+        let cols = [
+            Arc::new(Int16Array::from_iter([
+                Some(1),
+                Some(2),
+                None,
+                Some(-5),
+                Some(2),
+                Some(2),
+                Some(0),
+            ])) as ArrayRef,
+            Arc::new(Float32Array::from_iter([
+                Some(1.3),
+                Some(2.5),
+                None,
+                Some(4.),
+                Some(0.1),
+                Some(-4.),
+                Some(-0.),
+            ])) as ArrayRef,
+        ];
+
+        let converter = RowConverter::try_new(vec![
+            SortField::new(DataType::Int16),
+            SortField::new(DataType::Float32),
+        ])
+        .unwrap();
+        let rows = converter.convert_columns(&cols).unwrap();
+
+        assert_eq!(rows.offsets.as_ref(), &[0, 8, 16, 24, 32, 40, 48, 56]);
+        assert_eq!(
+            rows.buffer.as_ref(),
+            &[
+                1, 128, 1, //
+                1, 191, 166, 102, 102, //
+                1, 128, 2, // (1, 2.5)
+                1, 192, 32, 0, 0, //
+                0, 0, 0, // None
+                0, 0, 0, 0, 0, //
+                1, 127, 251, //
+                1, 192, 128, 0, 0, //
+                1, 128, 2, //
+                1, 189, 204, 204, 205, //
+                1, 128, 2, //
+                1, 63, 127, 255, 255, //
+                1, 128, 0, //
+                1, 127, 255, 255, 255 //
+            ]
+        );
+
+        assert!(rows.row(3) < rows.row(6));
+        assert!(rows.row(0) < rows.row(1));
+        assert!(rows.row(3) < rows.row(0));
+        assert!(rows.row(4) < rows.row(1));
+        assert!(rows.row(5) < rows.row(4));
+
+        let back = converter.convert_rows(rows.iter()).unwrap();
+        for (expected, actual) in cols.iter().zip(&back) {
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    fn test_decimal() {
+        let converter =
+            RowConverter::try_new(vec![SortField::new(DataType::Decimal(38, 7))])
+                .unwrap();
+        let col = Arc::new(
+            [
+                None,
+                Some(i128::MIN),
+                Some(-13),
+                Some(46_i128),
+                Some(5456_i128),
+            ]
+            .iter()
+            .collect::<DecimalArray>()
+            .with_precision_and_scale(38, 7)
+            .unwrap(),
+        ) as ArrayRef;
+
+        let rows = converter.convert_columns(&[Arc::clone(&col)]).unwrap();
+        for i in 0..rows.num_rows() - 1 {
+            assert!(rows.row(i) < rows.row(i + 1));
+        }
+
+        let back = converter.convert_rows(rows.iter()).unwrap();
+        assert_eq!(back.len(), 1);
+        assert_eq!(col.as_ref(), back[0].as_ref())
+    }
+
+    #[test]
+    fn test_bool() {
+        let converter =
+            RowConverter::try_new(vec![SortField::new(DataType::Boolean)]).unwrap();
+
+        let col = Arc::new(BooleanArray::from_iter([None, Some(false), Some(true)]))
+            as ArrayRef;
+
+        let rows = converter.convert_columns(&[Arc::clone(&col)]).unwrap();
+        assert!(rows.row(2) > rows.row(1));
+        assert!(rows.row(2) > rows.row(0));
+        assert!(rows.row(1) > rows.row(0));
+
+        let cols = converter.convert_rows(rows.iter()).unwrap();
+        assert_eq!(&cols[0], &col);
+
+        let converter = RowConverter::try_new(vec![SortField::new_with_options(
+            DataType::Boolean,
+            SortOptions {
+                descending: true,
+                nulls_first: false,
+            },
+        )])
+        .unwrap();
+
+        let rows = converter.convert_columns(&[Arc::clone(&col)]).unwrap();
+        assert!(rows.row(2) < rows.row(1));
+        assert!(rows.row(2) < rows.row(0));
+        assert!(rows.row(1) < rows.row(0));
+        let cols = converter.convert_rows(rows.iter()).unwrap();
+        assert_eq!(&cols[0], &col);
+    }
+
+    #[test]
+    fn test_timezone() {
+        let a = TimestampNanosecondArray::from_vec(
+            vec![1, 2, 3, 4, 5],
+            Some("+01:00".to_string()),
+        );
+        let d = a.data_type().clone();
+
+        let converter =
+            RowConverter::try_new(vec![SortField::new(a.data_type().clone())]).unwrap();
+        let rows = converter.convert_columns(&[Arc::new(a) as _]).unwrap();
+        let back = converter.convert_rows(rows.iter()).unwrap();
+        assert_eq!(back.len(), 1);
+        assert_eq!(back[0].data_type(), &d);
+    }
+
+    #[test]
+    fn test_variable_width() {
+        let col = Arc::new(StringArray::from_iter([
+            Some("hello"),
+            Some("he"),
+            None,
+            Some("foo"),
+            Some(""),
+        ])) as ArrayRef;
+
+        let converter =
+            RowConverter::try_new(vec![SortField::new(DataType::Utf8)]).unwrap();
+        let rows = converter.convert_columns(&[Arc::clone(&col)]).unwrap();
+
+        assert!(rows.row(1) < rows.row(0));
+        assert!(rows.row(2) < rows.row(4));
+        assert!(rows.row(3) < rows.row(0));
+        assert!(rows.row(3) < rows.row(1));
+
+        let cols = converter.convert_rows(rows.iter()).unwrap();
+        assert_eq!(&cols[0], &col);
+
+        let col = Arc::new(BinaryArray::from_opt_vec(vec![
+            None,
+            Some(&[0; 0]),
+            Some(&[0]),
+            Some(&[0; 31]),
+            Some(&[0; 32]),
+            Some(&[0; 33]),
+            Some(&[1]),
+            Some(&[0xFF; 64]),
+        ])) as ArrayRef;
+
+        let converter =
+            RowConverter::try_new(vec![SortField::new(DataType::Binary)]).unwrap();
+        let rows = converter.convert_columns(&[Arc::clone(&col)]).unwrap();
+
+        for i in 0..rows.num_rows() {
+            for j in i + 1..rows.num_rows() {
+                assert!(
+                    rows.row(i) < rows.row(j),
+                    "{} < {} - {:?} < {:?}",
+                    i,
+                    j,
+                    rows.row(i),
+                    rows.row(j)
+                );
+            }
+        }
+
+        let cols = converter.convert_rows(rows.iter()).unwrap();
+        assert_eq!(&cols[0], &col);
+
+        let converter = RowConverter::try_new(vec![SortField::new_with_options(
+            DataType::Binary,
+            SortOptions {
+                descending: true,
+                nulls_first: false,
+            },
+        )])
+        .unwrap();
+        let rows = converter.convert_columns(&[Arc::clone(&col)]).unwrap();
+
+        for i in 0..rows.num_rows() {
+            for j in i + 1..rows.num_rows() {
+                assert!(
+                    rows.row(i) > rows.row(j),
+                    "{} > {} - {:?} > {:?}",
+                    i,
+                    j,
+                    rows.row(i),
+                    rows.row(j)
+                );
+            }
+        }
+
+        let cols = converter.convert_rows(rows.iter()).unwrap();
+        assert_eq!(&cols[0], &col);
+    }
+
+    #[test]
+    fn test_fixed_size_binary() {
+        let col = Arc::new(FixedSizeBinaryArray::from(vec![
+            None,
+            Some(&[0, 0][..]),
+            Some(&[0, 1][..]),
+            Some(&[1, 0][..]),
+            Some(&[0xFF, 0xFF][..]),
+        ])) as ArrayRef;
+
+        let converter =
+            RowConverter::try_new(vec![SortField::new(DataType::FixedSizeBinary(2))])
+                .unwrap();
+        let rows = converter.convert_columns(&[Arc::clone(&col)]).unwrap();
+        for i in 0..rows.num_rows() - 1 {
+            assert!(rows.row(i) < rows.row(i + 1));
+        }
+        let cols = converter.convert_rows(rows.iter()).unwrap();
+        assert_eq!(&cols[0], &col);
+
+        let converter = RowConverter::try_new(vec![SortField::new_with_options(
+            DataType::FixedSizeBinary(2),
+            SortOptions {
+                descending: true,
+                nulls_first: false,
+            },
+        )])
+        .unwrap();
+        let rows = converter.convert_columns(&[Arc::clone(&col)]).unwrap();
+        for i in 0..rows.num_rows() - 1 {
+            assert!(rows.row(i) > rows.row(i + 1));
+        }
+        let cols = converter.convert_rows(rows.iter()).unwrap();
+        assert_eq!(&cols[0], &col);
+    }
+
+    #[test]
+    fn test_string_dictionary() {
+        let a = Arc::new(DictionaryArray::<Int32Type>::from_iter([
+            Some("foo"),
+            Some("hello"),
+            Some("he"),
+            None,
+            Some("hello"),
+            Some(""),
+            Some("hello"),
+            Some("hello"),
+        ])) as ArrayRef;
+
+        let converter =
+            RowConverter::try_new(vec![SortField::new(a.data_type().clone())]).unwrap();
+        let rows_a = converter.convert_columns(&[Arc::clone(&a)]).unwrap();
+
+        assert!(rows_a.row(3) < rows_a.row(5));
+        assert!(rows_a.row(2) < rows_a.row(1));
+        assert!(rows_a.row(0) < rows_a.row(1));
+        assert!(rows_a.row(3) < rows_a.row(0));
+
+        assert_eq!(rows_a.row(1), rows_a.row(4));
+        assert_eq!(rows_a.row(1), rows_a.row(6));
+        assert_eq!(rows_a.row(1), rows_a.row(7));
+
+        let cols = converter.convert_rows(rows_a.iter()).unwrap();
+        dictionary_eq(&cols[0], &a);
+
+        // Rows from dictionaries with different values are comparable
+        let b = Arc::new(DictionaryArray::<Int32Type>::from_iter([
+            Some("hello"),
+            None,
+            Some("cupcakes"),
+        ])) as ArrayRef;
+
+        let rows_b = converter.convert_columns(&[Arc::clone(&b)]).unwrap();
+        assert_eq!(rows_a.row(1), rows_b.row(0));
+        assert_eq!(rows_a.row(3), rows_b.row(1));
+        assert!(rows_b.row(2) < rows_a.row(0));
+
+        let cols = converter.convert_rows(rows_b.iter()).unwrap();
+        dictionary_eq(&cols[0], &b);
+
+        let converter = RowConverter::try_new(vec![SortField::new_with_options(
+            a.data_type().clone(),
+            SortOptions {
+                descending: true,
+                nulls_first: false,
+            },
+        )])
+        .unwrap();
+
+        let rows_c = converter.convert_columns(&[Arc::clone(&a)]).unwrap();
+        assert!(rows_c.row(3) > rows_c.row(5));
+        assert!(rows_c.row(2) > rows_c.row(1));
+        assert!(rows_c.row(0) > rows_c.row(1));
+        assert!(rows_c.row(3) > rows_c.row(0));
+
+        let cols = converter.convert_rows(rows_c.iter()).unwrap();
+        dictionary_eq(&cols[0], &a);
+    }
+
+    #[test]
+    fn test_primitive_dictionary() {
+        let mut builder = PrimitiveDictionaryBuilder::<Int32Type, Int32Type>::new(
+            PrimitiveBuilder::new(10),
+            PrimitiveBuilder::new(10),
+        );
+        builder.append(2).unwrap();
+        builder.append(3).unwrap();
+        builder.append(0).unwrap();
+        builder.append_null().unwrap();
+        builder.append(5).unwrap();
+        builder.append(3).unwrap();
+        builder.append(-1).unwrap();
+
+        let a = Arc::new(builder.finish()) as ArrayRef;
+
+        let converter =
+            RowConverter::try_new(vec![SortField::new(a.data_type().clone())]).unwrap();
+        let rows = converter.convert_columns(&[Arc::clone(&a)]).unwrap();
+        assert!(rows.row(0) < rows.row(1));
+        assert!(rows.row(2) < rows.row(0));
+        assert!(rows.row(3) < rows.row(2));
+        assert!(rows.row(6) < rows.row(2));
+        assert!(rows.row(3) < rows.row(6));
+        assert_eq!(rows.row(1), rows.row(5));
+
+        let cols = converter.convert_rows(rows.iter()).unwrap();
+        dictionary_eq(&cols[0], &a);
+    }
+
+    #[test]
+    fn test_dictionary_nulls() {
+        let values = Int32Array::from_iter([Some(1), Some(-1), None, Some(4), None]);
+        let keys =
+            Int32Array::from_iter([Some(0), Some(0), Some(1), Some(2), Some(4), None]);
+        let dictionary = DictionaryArray::<Int32Type>::try_new(&keys, &values).unwrap();
+        let data_type = dictionary.data_type().clone();
+
+        let converter = RowConverter::try_new(vec![SortField::new(data_type)]).unwrap();
+        let rows = converter.convert_columns(&[Arc::new(dictionary)]).unwrap();
+
+        assert_eq!(rows.row(0), rows.row(1));
+        assert_eq!(rows.row(3), rows.row(4));
+        assert_eq!(rows.row(4), rows.row(5));
+        assert!(rows.row(3) < rows.row(0));
+    }
+
+    #[test]
+    fn test_invalid() {
+        let err = RowConverter::try_new(vec![SortField::new(DataType::List(Box::new(
+            Field::new("item", DataType::Int32, true),
+        )))])
+        .unwrap_err();
+        assert!(err.to_string().contains("not yet implemented"), "{}", err);
+
+        let converter =
+            RowConverter::try_new(vec![SortField::new(DataType::Int32)]).unwrap();
+
+        let col = Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef;
+        let err = converter.convert_columns(&[col]).unwrap_err();
+        assert!(err.to_string().contains("schema mismatch"), "{}", err);
+
+        let other = RowConverter::try_new(vec![SortField::new(DataType::Int32)]).unwrap();
+        let col = Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef;
+        let rows = other.convert_columns(&[col]).unwrap();
+        let err = converter.convert_rows(rows.iter()).unwrap_err();
+        assert!(err.to_string().contains("not produced"), "{}", err);
+    }
+
+    #[test]
+    fn test_owned_row() {
+        let col = Arc::new(Int32Array::from(vec![3, 1, 2])) as ArrayRef;
+        let converter =
+            RowConverter::try_new(vec![SortField::new(DataType::Int32)]).unwrap();
+        let rows = converter.convert_columns(&[col]).unwrap();
+
+        let mut owned: Vec<_> = rows.iter().map(|r| r.owned()).collect();
+        owned.sort();
+        drop(rows);
+
+        let back = converter
+            .convert_rows(owned.iter().map(|r| r.row()))
+            .unwrap();
+        assert_eq!(back[0].as_ref(), &Int32Array::from(vec![1, 2, 3]));
+    }
+
+    /// Compares two dictionaries by their logical values
+    fn dictionary_eq(a: &ArrayRef, b: &ArrayRef) {
+        assert_eq!(a.data_type(), b.data_type());
+        assert_eq!(a.len(), b.len());
+        for i in 0..a.len() {
+            assert_eq!(a.is_valid(i), b.is_valid(i));
+            if a.is_valid(i) {
+                assert_eq!(
+                    array_value_to_string(a, i).unwrap(),
+                    array_value_to_string(b, i).unwrap()
+                );
+            }
+        }
+    }
+
+    fn generate_primitive_array<K>(len: usize, valid_percent: f64) -> PrimitiveArray<K>
+    where
+        K: ArrowPrimitiveType,
+        Standard: Distribution<K::Native>,
+    {
+        let mut rng = thread_rng();
+        (0..len)
+            .map(|_| rng.gen_bool(valid_percent).then(|| rng.gen()))
+            .collect()
+    }
+
+    fn generate_strings(len: usize, valid_percent: f64) -> StringArray {
+        let mut rng = thread_rng();
+        (0..len)
+            .map(|_| {
+                rng.gen_bool(valid_percent).then(|| {
+                    let len = rng.gen_range(0..100);
+                    let bytes = (0..len).map(|_| rng.gen_range(0..128)).collect();
+                    String::from_utf8(bytes).unwrap()
+                })
+            })
+            .collect()
+    }
+
+    fn generate_dictionary<K>(
+        values: ArrayRef,
+        len: usize,
+        valid_percent: f64,
+    ) -> DictionaryArray<K>
+    where
+        K: ArrowDictionaryKeyType,
+        K::Native: SampleUniform,
+    {
+        let mut rng = thread_rng();
+        let min_key = K::Native::from_usize(0).unwrap();
+        let max_key = K::Native::from_usize(values.len()).unwrap();
+        let keys: PrimitiveArray<K> = (0..len)
+            .map(|_| {
+                rng.gen_bool(valid_percent)
+                    .then(|| rng.gen_range(min_key..max_key))
+            })
+            .collect();
+
+        DictionaryArray::try_new(&keys, values.as_ref()).unwrap()
+    }
+
+    fn generate_column(len: usize) -> ArrayRef {
+        let mut rng = thread_rng();
+        match rng.gen_range(0..6) {
+            0 => Arc::new(generate_primitive_array::<Int32Type>(len, 0.8)),
+            1 => Arc::new(generate_primitive_array::<UInt32Type>(len, 0.8)),
+            2 => Arc::new(generate_primitive_array::<Int64Type>(len, 0.8)),
+            3 => Arc::new(generate_primitive_array::<Float64Type>(len, 0.8)),
+            4 => Arc::new(generate_strings(len, 0.8)),
+            5 => Arc::new(generate_dictionary::<Int64Type>(
+                // `build_compare` doesn't consider nulls in dictionary values
+                Arc::new(generate_strings(rng.gen_range(1..len), 1.0)),
+                len,
+                0.8,
+            )),
+            _ => unreachable!(),
+        }
+    }
+
+    fn print_row(cols: &[SortColumn], row: usize) -> String {
+        let t: Vec<_> = cols
+            .iter()
+            .map(|x| array_value_to_string(&x.values, row).unwrap())
+            .collect();
+        t.join(",")
+    }
+
+    fn print_col_types(cols: &[SortColumn]) -> String {
+        let t: Vec<_> = cols
+            .iter()
+            .map(|x| x.values.data_type().to_string())
+            .collect();
+        t.join(",")
+    }
+
+    /// Lexicographically compares rows `i` and `j` of `cols` using [`build_compare`]
+    fn lex_cmp(cols: &[SortColumn], i: usize, j: usize) -> Ordering {
+        for col in cols {
+            let options = col.options.unwrap();
+            let values = col.values.as_ref();
+            let ord = match (values.is_valid(i), values.is_valid(j)) {
+                (true, true) => {
+                    let ord = build_compare(values, values).unwrap()(i, j);
+                    match options.descending {
+                        true => ord.reverse(),
+                        false => ord,
+                    }
+                }
+                (false, true) if options.nulls_first => Ordering::Less,
+                (false, true) => Ordering::Greater,
+                (true, false) if options.nulls_first => Ordering::Greater,
+                (true, false) => Ordering::Less,
+                (false, false) => Ordering::Equal,
+            };
+            if ord != Ordering::Equal {
+                return ord;
+            }
+        }
+        Ordering::Equal
+    }
+
+    #[test]
+    fn fuzz_test() {
+        for _ in 0..100 {
+            let mut rng = thread_rng();
+            let num_columns = rng.gen_range(1..5);
+            let len = rng.gen_range(5..100);
+            let arrays: Vec<_> = (0..num_columns).map(|_| generate_column(len)).collect();
+
+            let options: Vec<_> = (0..num_columns)
+                .map(|_| SortOptions {
+                    descending: rng.gen_bool(0.5),
+                    nulls_first: rng.gen_bool(0.5),
+                })
+                .collect();
+
+            let sort_columns: Vec<_> = options
+                .iter()
+                .zip(&arrays)
+                .map(|(o, a)| SortColumn {
+                    values: Arc::clone(a),
+                    options: Some(*o),
+                })
+                .collect();
+
+            let columns = options
+                .into_iter()
+                .zip(&arrays)
+                .map(|(o, a)| SortField::new_with_options(a.data_type().clone(), o))
+                .collect();
+
+            let converter = RowConverter::try_new(columns).unwrap();
+            let rows = converter.convert_columns(&arrays).unwrap();
+
+            for i in 0..len {
+                for j in 0..len {
+                    let row_i = rows.row(i);
+                    let row_j = rows.row(j);
+                    let row_cmp = row_i.cmp(&row_j);
+                    let lex_cmp = lex_cmp(&sort_columns, i, j);
+                    assert_eq!(
+                        row_cmp,
+                        lex_cmp,
+                        "({:?} vs {:?}) vs ({:?} vs {:?}) for types {}",
+                        print_row(&sort_columns, i),
+                        print_row(&sort_columns, j),
+                        row_i,
+                        row_j,
+                        print_col_types(&sort_columns)
+                    );
+                }
+            }
+
+            let back = converter.convert_rows(rows.iter()).unwrap();
+            for (actual, expected) in back.iter().zip(&arrays) {
+                match actual.data_type() {
+                    DataType::Dictionary(_, _) => dictionary_eq(actual, expected),
+                    _ => assert_eq!(actual, expected),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,201 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Encoding of variable length types (strings and binary)
+
+use crate::array::{ArrayData, ArrayDataBuilder, BooleanBufferBuilder, OffsetSizeTrait};
+use crate::buffer::MutableBuffer;
+use crate::compute::SortOptions;
+use crate::datatypes::DataType;
+use crate::row::{null_sentinel, Rows};
+use crate::util::bit_util::ceil;
+
+/// The block size of the variable length encoding
+pub const BLOCK_SIZE: usize = 32;
+
+/// The continuation token
+pub const BLOCK_CONTINUATION: u8 = 0xFF;
+
+/// Indicates an empty string
+pub const EMPTY_SENTINEL: u8 = 1;
+
+/// Indicates a non-empty string
+pub const NON_EMPTY_SENTINEL: u8 = 2;
+
+/// Returns the length of the encoded representation of a byte array, including the null byte
+pub fn encoded_len(a: Option<&[u8]>) -> usize {
+    match a {
+        Some(a) => 1 + ceil(a.len(), BLOCK_SIZE) * (BLOCK_SIZE + 1),
+        None => 1,
+    }
+}
+
+/// Variable length values are encoded as
+///
+/// - single `0_u8` if null
+/// - single `1_u8` if empty array
+/// - `2_u8` if not empty, followed by one or more blocks
+///
+/// where a block is encoded as
+///
+/// - [`BLOCK_SIZE`] bytes of string data, padded with 0s
+/// - `0xFF_u8` if this is not the last block for this string
+/// - otherwise the length of the block as a `u8`
+pub fn encode<'a, I: Iterator<Item = Option<&'a [u8]>>>(
+    out: &mut Rows,
+    i: I,
+    opts: SortOptions,
+) {
+    for (offset, maybe_val) in out.offsets.iter_mut().skip(1).zip(i) {
+        match maybe_val {
+            Some([]) => {
+                out.buffer[*offset] = match opts.descending {
+                    true => !EMPTY_SENTINEL,
+                    false => EMPTY_SENTINEL,
+                };
+                *offset += 1;
+            }
+            Some(val) => {
+                let block_count = ceil(val.len(), BLOCK_SIZE);
+                let end_offset = *offset + 1 + block_count * (BLOCK_SIZE + 1);
+                let to_write = &mut out.buffer[*offset..end_offset];
+
+                // Write `2_u8` to demarcate as non-empty, non-null string
+                to_write[0] = NON_EMPTY_SENTINEL;
+
+                let chunks = val.chunks_exact(BLOCK_SIZE);
+                let remainder = chunks.remainder();
+                for (input, output) in chunks
+                    .clone()
+                    .zip(to_write[1..].chunks_exact_mut(BLOCK_SIZE + 1))
+                {
+                    output[..BLOCK_SIZE].copy_from_slice(input);
+
+                    // Indicate that there are further blocks to follow
+                    output[BLOCK_SIZE] = BLOCK_CONTINUATION;
+                }
+
+                if !remainder.is_empty() {
+                    let start_offset = 1 + (block_count - 1) * (BLOCK_SIZE + 1);
+                    to_write[start_offset..start_offset + remainder.len()]
+                        .copy_from_slice(remainder);
+                    *to_write.last_mut().unwrap() = remainder.len() as u8;
+                } else {
+                    // We must overwrite the continuation marker written by the loop above
+                    *to_write.last_mut().unwrap() = BLOCK_SIZE as u8;
+                }
+
+                *offset = end_offset;
+
+                if opts.descending {
+                    // Invert bits
+                    to_write.iter_mut().for_each(|v| *v = !*v)
+                }
+            }
+            None => {
+                out.buffer[*offset] = null_sentinel(opts);
+                *offset += 1;
+            }
+        }
+    }
+}
+
+/// Decodes a single value from the start of `row` appending its bytes to `values`,
+/// and advancing `row` past it. Returns `false` if the value is null
+fn decode_value(
+    row: &mut &[u8],
+    values: &mut MutableBuffer,
+    options: SortOptions,
+) -> bool {
+    let sentinel = row[0];
+    if sentinel == null_sentinel(options) {
+        *row = &row[1..];
+        return false;
+    }
+
+    let map_byte = |b: u8| match options.descending {
+        true => !b,
+        false => b,
+    };
+
+    if map_byte(sentinel) == EMPTY_SENTINEL {
+        *row = &row[1..];
+        return true;
+    }
+
+    let mut idx = 1;
+    loop {
+        let block = &row[idx..idx + BLOCK_SIZE];
+        let marker = map_byte(row[idx + BLOCK_SIZE]);
+        idx += BLOCK_SIZE + 1;
+
+        let block_len = match marker {
+            BLOCK_CONTINUATION => BLOCK_SIZE,
+            len => len as usize,
+        };
+
+        let start = values.len();
+        values.extend_from_slice(&block[..block_len]);
+        if options.descending {
+            values.as_slice_mut()[start..]
+                .iter_mut()
+                .for_each(|v| *v = !*v);
+        }
+
+        if marker != BLOCK_CONTINUATION {
+            break;
+        }
+    }
+    *row = &row[idx..];
+    true
+}
+
+/// Decodes a binary or string array of `data_type` with offsets of type `O` from rows
+///
+/// # Safety
+///
+/// If `data_type` is a string type, the decoded values must be valid UTF-8
+pub unsafe fn decode_binary<O: OffsetSizeTrait>(
+    rows: &mut [&[u8]],
+    data_type: DataType,
+    options: SortOptions,
+) -> ArrayData {
+    let len = rows.len();
+    let mut nulls = BooleanBufferBuilder::new(len);
+    let mut offsets = MutableBuffer::new((len + 1) * std::mem::size_of::<O>());
+    let mut values = MutableBuffer::new(0);
+    let mut null_count = 0;
+
+    offsets.push(O::zero());
+    for row in rows {
+        let valid = decode_value(row, &mut values, options);
+        if !valid {
+            null_count += 1;
+        }
+        nulls.append(valid);
+        offsets.push(O::from_usize(values.len()).expect("offset overflow"));
+    }
+
+    let builder = ArrayDataBuilder::new(data_type)
+        .len(len)
+        .null_count(null_count)
+        .add_buffer(offsets.into())
+        .add_buffer(values.into())
+        .null_bit_buffer(Some(nulls.finish()));
+
+    builder.build_unchecked()
+}
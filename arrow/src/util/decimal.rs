@@ -23,7 +23,6 @@ use std::cmp::Ordering;
 /// The decimal value is represented by a signed 128-bit integer.
 #[derive(Debug)]
 pub struct Decimal128 {
-    #[allow(dead_code)]
     precision: usize,
     scale: usize,
     value: i128,
@@ -91,6 +90,16 @@ impl Decimal128 {
         self.value
     }
 
+    /// Returns the precision of this decimal
+    pub fn precision(&self) -> usize {
+        self.precision
+    }
+
+    /// Returns the scale of this decimal
+    pub fn scale(&self) -> usize {
+        self.scale
+    }
+
     pub fn as_string(&self) -> String {
         let value_str = self.value.to_string();
 
@@ -117,9 +126,197 @@ impl From<Decimal128> for i128 {
     }
 }
 
+/// Divides `n` by `d` rounding half away from zero, returns `None` on overflow
+pub(crate) fn decimal_div_round(n: i128, d: i128) -> Option<i128> {
+    let q = n.checked_div(d)?;
+    let r = n % d;
+    // `|r| < |d| <= 2^127`, and so this cannot overflow
+    if r.unsigned_abs() * 2 >= d.unsigned_abs() {
+        q.checked_add(n.signum() * d.signum())
+    } else {
+        Some(q)
+    }
+}
+
+/// Rescales `value` from scale `from` to scale `to`, rounding half away from zero
+/// if the scale is reduced. Returns `None` on overflow
+pub(crate) fn decimal_rescale(value: i128, from: usize, to: usize) -> Option<i128> {
+    if to >= from {
+        value.checked_mul(10_i128.checked_pow((to - from) as u32)?)
+    } else {
+        match 10_i128.checked_pow((from - to) as u32) {
+            Some(d) => decimal_div_round(value, d),
+            // The divisor exceeds any possible value
+            None => Some(0),
+        }
+    }
+}
+
+/// Returns `a * b` rescaled from scale `from` to scale `to`, rounding half away from
+/// zero if the scale is reduced. The product is computed without overflowing, so that
+/// only a result that does not fit in an `i128` returns `None`
+pub(crate) fn decimal_mul_rescale(
+    a: i128,
+    b: i128,
+    from: usize,
+    to: usize,
+) -> Option<i128> {
+    if to >= from {
+        return a.checked_mul(b).and_then(|v| decimal_rescale(v, from, to));
+    }
+    let product = U256::mul(a.unsigned_abs(), b.unsigned_abs());
+    let value = product.div_pow10_round(from - to).to_u128()?;
+    with_sign(value, (a < 0) != (b < 0))
+}
+
+/// Returns `a / b` rescaled from scale `from` to scale `to`, rounding half away from
+/// zero. The dividend is upscaled without overflowing, so that only a result that
+/// does not fit in an `i128` returns `None`, as does a zero divisor
+pub(crate) fn decimal_div_rescale(
+    a: i128,
+    b: i128,
+    from: usize,
+    to: usize,
+) -> Option<i128> {
+    if b == 0 {
+        return None;
+    }
+    let (n, d) = (a.unsigned_abs(), b.unsigned_abs());
+    let value = if to >= from {
+        let mut n = U256::from(n);
+        let mut digits = to - from;
+        while digits > 0 {
+            let step = digits.min(MAX_POW10_DIGITS);
+            n = n.checked_mul(10_u128.pow(step as u32))?;
+            digits -= step;
+        }
+        let (q, r) = n.div_rem(d);
+        // `r < d`, and so `d - r` cannot overflow
+        let q = q.to_u128()?;
+        if r >= d - r {
+            q.checked_add(1)?
+        } else {
+            q
+        }
+    } else {
+        // `floor(floor(n / d) / 10^k) == floor(n / (d * 10^k))`, and so the remainder
+        // of the division by `d` does not affect the rounding by `10^k`
+        U256::from(n / d).div_pow10_round(from - to).to_u128()?
+    };
+    with_sign(value, (a < 0) != (b < 0))
+}
+
+/// Returns `value` with the sign given by `negative`, or `None` if it overflows
+fn with_sign(value: u128, negative: bool) -> Option<i128> {
+    let value = i128::try_from(value).ok()?;
+    Some(if negative { -value } else { value })
+}
+
+/// The largest number of digits `k` for which `10^k` fits in a `u128`
+const MAX_POW10_DIGITS: usize = 38;
+
+/// An unsigned 256-bit integer, used as the intermediate result of decimal arithmetic
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct U256 {
+    hi: u128,
+    lo: u128,
+}
+
+impl U256 {
+    /// Returns the full product of `a` and `b`
+    fn mul(a: u128, b: u128) -> Self {
+        const MASK: u128 = u64::MAX as u128;
+        let (a1, a0) = (a >> 64, a & MASK);
+        let (b1, b0) = (b >> 64, b & MASK);
+        let p00 = a0 * b0;
+        let p01 = a0 * b1;
+        let p10 = a1 * b0;
+        // At most `3 * (2^64 - 1)`, and so this cannot overflow
+        let mid = (p00 >> 64) + (p01 & MASK) + (p10 & MASK);
+        Self {
+            hi: a1 * b1 + (p01 >> 64) + (p10 >> 64) + (mid >> 64),
+            lo: (mid << 64) | (p00 & MASK),
+        }
+    }
+
+    /// Returns `self * rhs`, or `None` on overflow
+    fn checked_mul(self, rhs: u128) -> Option<Self> {
+        let lo = Self::mul(self.lo, rhs);
+        let hi = self.hi.checked_mul(rhs)?.checked_add(lo.hi)?;
+        Some(Self { hi, lo: lo.lo })
+    }
+
+    /// Returns the quotient and remainder of `self / d`, `d` must not be zero
+    fn div_rem(self, d: u128) -> (Self, u128) {
+        let hi = self.hi / d;
+        let mut rem = self.hi % d;
+        if rem == 0 {
+            return (
+                Self {
+                    hi,
+                    lo: self.lo / d,
+                },
+                self.lo % d,
+            );
+        }
+
+        // Long division of `rem * 2^128 + self.lo`, where `rem < d`
+        let mut lo = 0;
+        for i in (0..128).rev() {
+            let carry = rem >> 127;
+            rem = (rem << 1) | ((self.lo >> i) & 1);
+            lo <<= 1;
+            if carry == 1 || rem >= d {
+                // The shifted remainder is less than `2 * d`, and so this
+                // is less than `d` even if the shift overflowed
+                rem = rem.wrapping_sub(d);
+                lo |= 1;
+            }
+        }
+        (Self { hi, lo }, rem)
+    }
+
+    /// Returns `self / 10^k` rounded half away from zero
+    fn div_pow10_round(self, k: usize) -> Self {
+        if k == 0 {
+            return self;
+        }
+        // Truncate all but the last digit, as `round(x / 10^k)` is
+        // `floor((floor(x / 10^(k - 1)) + 5) / 10)`
+        let mut value = self;
+        let mut digits = k - 1;
+        while digits > 0 {
+            let step = digits.min(MAX_POW10_DIGITS);
+            value = value.div_rem(10_u128.pow(step as u32)).0;
+            digits -= step;
+        }
+        let (q, r) = value.div_rem(10);
+        if r >= 5 {
+            // `q < 2^256 / 10`, and so this cannot overflow
+            let (lo, carry) = q.lo.overflowing_add(1);
+            Self {
+                hi: q.hi + carry as u128,
+                lo,
+            }
+        } else {
+            q
+        }
+    }
+
+    fn to_u128(self) -> Option<u128> {
+        (self.hi == 0).then(|| self.lo)
+    }
+}
+
+impl From<u128> for U256 {
+    fn from(lo: u128) -> Self {
+        Self { hi: 0, lo }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::util::decimal::Decimal128;
+    use crate::util::decimal::{decimal_div_rescale, decimal_mul_rescale, Decimal128};
 
     #[test]
     fn decimal_128_to_string() {
@@ -141,6 +338,31 @@ mod tests {
         value.into()
     }
 
+    #[test]
+    fn decimal_mul_div_rescale() {
+        let max = i128::MAX;
+        assert_eq!(decimal_mul_rescale(max, max, 0, 0), None);
+        assert_eq!(decimal_mul_rescale(max, 10, 1, 0), Some(max));
+        assert_eq!(decimal_mul_rescale(max, -2, 1, 0), Some(-(max / 5)));
+        assert_eq!(decimal_mul_rescale(15, 1, 1, 0), Some(2));
+        assert_eq!(decimal_mul_rescale(-15, 1, 1, 0), Some(-2));
+        assert_eq!(decimal_mul_rescale(max, max, 76, 0), Some(3));
+        assert_eq!(decimal_mul_rescale(3, 5, 0, 2), Some(1500));
+
+        assert_eq!(decimal_div_rescale(1, 0, 0, 0), None);
+        assert_eq!(decimal_div_rescale(2, 3, 0, 0), Some(1));
+        assert_eq!(decimal_div_rescale(-2, 3, 0, 1), Some(-7));
+        assert_eq!(decimal_div_rescale(max, max, 0, 38), Some(10_i128.pow(38)));
+        assert_eq!(decimal_div_rescale(max, max, 0, 39), None);
+        assert_eq!(decimal_div_rescale(1, 3, 0, 76), None);
+        assert_eq!(
+            decimal_div_rescale(1, 2 * 10_i128.pow(37), 0, 75),
+            Some(5 * 10_i128.pow(37))
+        );
+        assert_eq!(decimal_div_rescale(-2500, 1, 3, 0), Some(-3));
+        assert_eq!(decimal_div_rescale(max, 1, 39, 0), Some(0));
+    }
+
     #[test]
     fn decimal_128_to_i128() {
         let value = Decimal128::new_from_i128(5, 2, 100);